@@ -0,0 +1,92 @@
+// --- Insert-Mode Abbreviations ---
+// :iabbrev 로 정의한 낱말은 입력 모드에서 뒤에 낱말이 아닌 글자(공백, 문장 부호, Enter, Esc)를
+// 치는 순간 대체 글로 바뀜 (viirc에 써 두면 시작할 때마다 정의됨)
+//   iabbrev teh the                   모든 파일에서
+//   iabbrev -ft=rust sopl println!()  rust 파일에서만 (같은 낱말이면 전체보다 먼저)
+//   iunabbrev [-ft=rust] teh          지움, iabbrev 만 쓰면 목록
+use crate::EditorConfig;
+
+struct Abbreviation {
+    filetype: Option<String>,
+    lhs: String,
+    rhs: String,
+}
+
+pub struct Abbreviations {
+    list: Vec<Abbreviation>,
+}
+
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// 앞에 붙은 -ft=종류 를 떼어냄
+fn split_filetype(args: &str) -> (Option<String>, &str) {
+    match args.strip_prefix("-ft=").and_then(|rest| rest.split_once(' ')) {
+        Some((filetype, rest)) => (Some(filetype.to_string()), rest.trim_start()),
+        None => (None, args),
+    }
+}
+
+impl Abbreviations {
+    pub fn new() -> Self {
+        Abbreviations { list: Vec::new() }
+    }
+
+    // "[-ft=종류] lhs rhs", 같은 종류와 lhs가 있으면 바꿈
+    pub fn define(&mut self, args: &str) -> Result<(), String> {
+        let (filetype, args) = split_filetype(args);
+        let Some((lhs, rhs)) = args.split_once(' ').filter(|(_, rhs)| !rhs.trim().is_empty()) else {
+            return Err("Usage: :iabbrev [-ft=filetype] <lhs> <rhs>".into());
+        };
+        if !lhs.chars().all(is_word_char) {
+            return Err(format!("Invalid abbreviation: {} (letters, digits and _ only)", lhs));
+        }
+        let rhs = rhs.trim().to_string();
+        match self.list.iter_mut().find(|a| a.filetype == filetype && a.lhs == lhs) {
+            Some(a) => a.rhs = rhs,
+            None => self.list.push(Abbreviation { filetype, lhs: lhs.to_string(), rhs }),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, args: &str) -> bool {
+        let (filetype, lhs) = split_filetype(args.trim());
+        let len = self.list.len();
+        self.list.retain(|a| !(a.filetype == filetype && a.lhs == lhs));
+        self.list.len() != len
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.list
+            .iter()
+            .map(|a| match &a.filetype {
+                Some(ft) => format!("[{}] {} {}", ft, a.lhs, a.rhs),
+                None => format!("{} {}", a.lhs, a.rhs),
+            })
+            .collect()
+    }
+
+    // 파일 종류에 맞는 것을 먼저 찾음
+    fn get(&self, filetype: Option<&str>, word: &str) -> Option<&str> {
+        let find = |ft: Option<&str>| self.list.iter().find(|a| a.filetype.as_deref() == ft && a.lhs == word);
+        filetype.and_then(|ft| find(Some(ft))).or_else(|| find(None)).map(|a| a.rhs.as_str())
+    }
+}
+
+impl EditorConfig {
+    // 커서 앞의 낱말이 약어면 대체 글로 바꿈 (낱말이 아닌 글자를 넣기 직전에 부름)
+    pub(crate) fn expand_abbreviation(&mut self) {
+        let content = &self.buffer.rows[self.cy].content;
+        let before = &content[..self.cx];
+        let start = before.char_indices().rev().take_while(|&(_, c)| is_word_char(c)).last().map_or(self.cx, |(i, _)| i);
+        if start == self.cx {
+            return;
+        }
+        let Some(rhs) = self.abbreviations.get(self.buffer_options.filetype.as_deref(), &before[start..]) else { return };
+        let rhs = rhs.to_string();
+        self.buffer.rows[self.cy].content.replace_range(start..self.cx, &rhs);
+        self.cx = start + rhs.len();
+        self.buffer.touch();
+    }
+}
@@ -0,0 +1,65 @@
+// --- Argument List ---
+// 명령 줄에서 받은 파일들을 순서대로 두고 하나씩 버퍼로 열어 오감
+//   :args              목록, 지금 파일은 [ ] 로 감쌈
+//   :args a.rs b.rs    목록을 바꾸고 첫 파일을 엶
+//   :next / :prev      (:n / :N / :previous) 다음 / 이전 파일
+//   :first / :last     (:rewind) 처음 / 마지막 파일
+use crate::EditorConfig;
+
+pub struct ArgList {
+    pub files: Vec<String>,
+    pub index: usize,
+}
+
+impl ArgList {
+    pub fn new() -> Self {
+        ArgList { files: Vec::new(), index: 0 }
+    }
+
+    pub fn set(&mut self, files: Vec<String>) {
+        self.files = files;
+        self.index = 0;
+    }
+
+    // "a.rs [b.rs] c.rs"
+    fn display(&self) -> String {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(i, f)| if i == self.index { format!("[{}]", f) } else { f.clone() })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl EditorConfig {
+    pub(crate) fn arglist_command(&mut self, cmd: &str, args: &str) {
+        if cmd == "args" && !args.is_empty() {
+            self.arglist.set(args.split_whitespace().map(String::from).collect());
+            self.edit_arg(0);
+            return;
+        }
+        if self.arglist.files.is_empty() {
+            self.status_msg = "Argument list is empty".into();
+            return;
+        }
+        let last = self.arglist.files.len() - 1;
+        let index = self.arglist.index;
+        match cmd {
+            "next" if index == last => self.error("Cannot go beyond last file"),
+            "next" => self.edit_arg(index + 1),
+            "Next" | "previous" if index == 0 => self.error("Cannot go before first file"),
+            "Next" | "previous" => self.edit_arg(index - 1),
+            "first" | "rewind" => self.edit_arg(0),
+            "last" => self.edit_arg(last),
+            _ => self.status_msg = self.arglist.display(),
+        }
+    }
+
+    fn edit_arg(&mut self, index: usize) {
+        self.arglist.index = index;
+        let file = self.arglist.files[index].clone();
+        self.edit_file(&file);
+        self.status_msg = format!("\"{}\" ({} of {})", file, index + 1, self.arglist.files.len());
+    }
+}
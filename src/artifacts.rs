@@ -0,0 +1,54 @@
+// --- Artifact Directories ---
+// 복구 파일, 백업, 실행 취소 기록을 둘 디렉터리 (없으면 쓸 때 만듦)
+//   set swapdir=~/.cache/vii     복구 파일 (recovery.rs) 과 잠금 파일 (lock.rs)
+//   set backupdir=...            set backup 일 때 저장하기 전의 파일 (name~)
+//   set undodir=...              set undofile 일 때 실행 취소 기록 (undo.rs)
+// 기본은 $XDG_STATE_HOME/vii/swap, backup, undo ($XDG_STATE_HOME 이 없으면 ~/.local/state)
+// "." 이면 편집하는 파일 옆에 숨김 파일로 (.name.vii.swp), 아니면 전체 경로의 / 를 % 로 바꾼 이름으로 (%home%me%a.txt.vii.swp)
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 기본 디렉터리 ($XDG_STATE_HOME/vii/<kind>)
+pub fn default_dir(kind: &str) -> String {
+    let state = match env::var("XDG_STATE_HOME") {
+        Ok(dir) if Path::new(&dir).is_absolute() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var("HOME").unwrap_or_default()).join(".local/state"),
+    };
+    state.join("vii").join(kind).to_string_lossy().into()
+}
+
+// 앞의 ~ 를 홈 디렉터리로
+fn expand(dir: &str) -> PathBuf {
+    match dir.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            PathBuf::from(format!("{}{}", env::var("HOME").unwrap_or_default(), rest))
+        }
+        _ => PathBuf::from(dir),
+    }
+}
+
+/// filename 에 딸린 파일 경로 (dir 이 "." 이 아니면 전체 경로로 이름을 지음)
+pub fn path_in(dir: &str, filename: &str, suffix: &str) -> PathBuf {
+    let path = Path::new(filename);
+    if dir == "." {
+        let base = path.file_name().map_or_else(|| filename.into(), |n| n.to_string_lossy());
+        return path.with_file_name(format!(".{}{}", base, suffix));
+    }
+    let absolute = env::current_dir().map(|d| d.join(path)).unwrap_or_else(|_| path.to_path_buf());
+    expand(dir).join(format!("{}{}", absolute.to_string_lossy().replace('/', "%"), suffix))
+}
+
+/// 이름 없는 버퍼처럼 원래 파일이 없는 것의 경로 ("." 이면 임시 디렉터리)
+pub fn unnamed_in(dir: &str, name: &str) -> PathBuf {
+    if dir == "." { env::temp_dir().join(name) } else { expand(dir).join(name) }
+}
+
+/// path 의 디렉터리가 없으면 만들고 path 에 씀
+pub fn write(path: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
@@ -0,0 +1,48 @@
+// --- Keeping File Attributes ---
+// 저장은 임시 파일에 쓴 뒤 바꿔치우므로, 원래 파일의 권한(실행 비트 등), 소유자, 확장 속성을 임시 파일에 옮겨 둠
+// 소유자와 확장 속성은 옮길 수 있는 만큼만 (다른 사람의 파일을 root 가 아닌 채로 고치면 소유자는 바뀜)
+use std::fs::{File, Metadata};
+use std::io;
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// original 의 권한, 소유자, 확장 속성을 file 에 줌
+pub fn copy(original: &Path, metadata: &Metadata, file: &File) -> io::Result<()> {
+    // chown 이 setuid 비트를 지우므로 권한은 나중에
+    unsafe { libc::fchown(file.as_raw_fd(), metadata.uid(), metadata.gid()) };
+    copy_xattrs(original, file);
+    file.set_permissions(metadata.permissions())
+}
+
+#[cfg(target_os = "linux")]
+fn copy_xattrs(original: &Path, file: &File) {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(path) = CString::new(original.as_os_str().as_bytes()) else { return };
+    // 이름들은 \0 으로 나뉘어 옴
+    let size = unsafe { libc::listxattr(path.as_ptr(), std::ptr::null_mut(), 0) };
+    if size <= 0 {
+        return;
+    }
+    let mut names = vec![0u8; size as usize];
+    let size = unsafe { libc::listxattr(path.as_ptr(), names.as_mut_ptr() as *mut libc::c_char, names.len()) };
+    if size <= 0 {
+        return;
+    }
+    for name in names[..size as usize].split(|&b| b == 0).filter(|n| !n.is_empty()) {
+        let Ok(name) = CString::new(name) else { continue };
+        let len = unsafe { libc::getxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0) };
+        if len < 0 {
+            continue;
+        }
+        let mut value = vec![0u8; len as usize];
+        let len = unsafe { libc::getxattr(path.as_ptr(), name.as_ptr(), value.as_mut_ptr() as *mut libc::c_void, value.len()) };
+        if len >= 0 {
+            unsafe { libc::fsetxattr(file.as_raw_fd(), name.as_ptr(), value.as_ptr() as *const libc::c_void, len as usize, 0) };
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn copy_xattrs(_original: &Path, _file: &File) {}
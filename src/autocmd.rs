@@ -0,0 +1,121 @@
+// --- Autocommands ---
+// 이벤트가 일어나면 거기에 등록한 ex 명령과 스크립트의 vii.autocmd 함수를 등록 순서대로 실행
+//   autocmd BufWritePre *.rs %trim          저장하기 전에 줄 끝 공백을 지움
+//   autocmd BufReadPost,BufNewFile *.md set sw=2
+//   autocmd FileType python set sw=4        패턴을 파일 종류와 맞춤
+//   autocmd ModeChanged *:i set cul         패턴을 "이전 모드:새 모드" (n, i, v, c)와 맞춤
+//   autocmd                                  목록     autocmd! [이벤트]  지움 (이벤트가 없으면 모두)
+// 이벤트:
+//   BufReadPost  파일을 읽은 뒤    BufNewFile  없는 파일을 열었을 때    BufEnter  버퍼로 들어갔을 때
+//   BufWritePre  저장하기 직전     BufWritePost  저장한 뒤              FileType  파일 종류가 정해졌을 때
+//   InsertEnter / InsertLeave      입력 모드에 들어갈 때 / 나올 때      ModeChanged  모드가 바뀌었을 때
+// Buf* 이벤트의 패턴은 파일 이름과 맞춤 (/ 가 없으면 파일 이름만, * 와 ? 를 씀)
+// 이벤트를 처리하는 동안 생긴 이벤트는 다시 처리하지 않음 (BufWritePre 에서 :w 를 해도 돌지 않게)
+use crate::{EditorConfig, filetype};
+
+const EVENTS: [&str; 9] = [
+    "BufReadPost",
+    "BufNewFile",
+    "BufEnter",
+    "BufWritePre",
+    "BufWritePost",
+    "FileType",
+    "InsertEnter",
+    "InsertLeave",
+    "ModeChanged",
+];
+
+struct Autocmd {
+    event: &'static str,
+    pattern: String,
+    command: String,
+}
+
+pub struct Autocmds {
+    list: Vec<Autocmd>,
+    running: bool,
+}
+
+impl Autocmds {
+    pub fn new() -> Self {
+        Autocmds { list: Vec::new(), running: false }
+    }
+
+    // "이벤트[,이벤트] 패턴 명령"
+    pub fn add(&mut self, args: &str) -> Result<(), String> {
+        let mut parts = args.splitn(3, ' ');
+        let (Some(events), Some(pattern), Some(command)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err("Usage: :autocmd <event>[,<event>] <pattern> <command>".into());
+        };
+        let events: Vec<&'static str> = events.split(',').map(find_event).collect::<Result<_, _>>()?;
+        for event in events {
+            self.list.push(Autocmd { event, pattern: pattern.to_string(), command: command.trim().to_string() });
+        }
+        Ok(())
+    }
+
+    // 이벤트의 autocmd를 지움, 이벤트가 없으면 모두
+    pub fn remove(&mut self, event: &str) -> Result<(), String> {
+        if event.is_empty() {
+            self.list.clear();
+            return Ok(());
+        }
+        let event = find_event(event)?;
+        self.list.retain(|a| a.event != event);
+        Ok(())
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.list.iter().map(|a| format!("{} {} {}", a.event, a.pattern, a.command)).collect()
+    }
+
+    fn commands(&self, event: &str, target: &str) -> Vec<String> {
+        self.list
+            .iter()
+            .filter(|a| a.event == event && filetype::matches_pattern(&a.pattern, target))
+            .map(|a| a.command.clone())
+            .collect()
+    }
+}
+
+fn find_event(name: &str) -> Result<&'static str, String> {
+    EVENTS.into_iter().find(|e| e.eq_ignore_ascii_case(name)).ok_or_else(|| format!("No such event: {}", name))
+}
+
+impl EditorConfig {
+    // 이벤트를 알림, 패턴은 지금 파일 이름과 맞춤
+    pub(crate) fn emit(&mut self, event: &str) {
+        let target = self.filename.clone().unwrap_or_default();
+        self.emit_for(event, &target);
+    }
+
+    // 패턴과 맞출 대상(파일 종류, 모드 등)을 따로 주고 이벤트를 알림
+    pub(crate) fn emit_for(&mut self, event: &str, target: &str) {
+        if self.autocmds.running {
+            return;
+        }
+        self.autocmds.running = true;
+        for command in self.autocmds.commands(event, target) {
+            self.run_command(&command);
+        }
+        self.with_script(|script, ed| script.emit(ed, event, target));
+        self.autocmds.running = false;
+    }
+
+    // :[범위]trim 줄 끝의 공백을 지움 (범위가 없으면 버퍼 전체)
+    pub(crate) fn trim_trailing(&mut self, start: usize, end: usize) {
+        let mut changed = false;
+        let end = end.min(self.buffer.rows.len() - 1);
+        for row in &mut self.buffer.rows[start..=end] {
+            let len = row.content.trim_end().len();
+            if len < row.content.len() {
+                row.content.truncate(len);
+                changed = true;
+            }
+        }
+        if changed {
+            self.buffer.touch();
+            self.clamp_cursor();
+        }
+    }
+}
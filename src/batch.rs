@@ -0,0 +1,57 @@
+// --- Batch Mode ---
+// vii -es [-c 명령]... [+명령]... [-S 스크립트] [파일...]: 화면 없이 ex 명령을 차례로 실행하고 끝냄 (셸 스크립트용)
+//   vii -es -c '%s/foo/bar/g' -c wq a.txt
+//   vii -es +'%s/foo/bar/g' +wq a.txt
+//   vii -es -S fix.vii a.txt                  스크립트 파일의 한 줄이 명령 하나 (빈 줄과 " 로 시작하는 줄은 건너뜀)
+//   printf '%%sort\nwq\n' | vii -es a.txt     -c, +, -S 가 없으면 명령을 표준 입력에서 읽음
+// 파일마다 버퍼를 만들고 첫 파일에서 시작 (:bn, :next, :bufdo 로 오감). 결과를 쓰려면 :w 나 :wq (명령이 끝나면 저장하지 않고 나감)
+// 오류 메시지는 호출한 쪽이 표준 오류로 내고 0 이 아닌 값으로 끝남
+use crate::EditorConfig;
+use std::fs;
+use std::io::{self, Read};
+
+/// 인자를 풀어 파일을 열고 명령을 실행, 실패한 명령들의 오류 메시지를 돌려줌
+pub fn run(args: &[String], editor: &mut EditorConfig) -> Result<Vec<String>, String> {
+    let mut commands = Vec::new();
+    let mut script = None;
+    let mut files = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-c" => commands.push(args.next().ok_or("-c needs a command")?.clone()),
+            "-S" => script = Some(args.next().ok_or("-S needs a file")?.clone()),
+            _ => match arg.strip_prefix('+') {
+                Some(command) => commands.push(command.to_string()),
+                None => files.push(arg.clone()),
+            },
+        }
+    }
+    let text = match &script {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?,
+        None if commands.is_empty() => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|e| format!("stdin: {}", e))?;
+            text
+        }
+        None => String::new(),
+    };
+    let lines = text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('"'));
+    commands.extend(lines.map(String::from));
+
+    let mut errors = Vec::new();
+    editor.set_arglist(&files);
+    if editor.is_error_message() {
+        errors.push(editor.status_msg.clone());
+    }
+    for command in commands {
+        editor.status_msg.clear();
+        let running = editor.run_command(command.strip_prefix(':').unwrap_or(&command));
+        if editor.is_error_message() {
+            errors.push(format!("{}: {}", command, editor.status_msg));
+        }
+        if !running {
+            break;
+        }
+    }
+    Ok(errors)
+}
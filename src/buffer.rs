@@ -0,0 +1,83 @@
+// --- Text Buffer ---
+// 파일 내용을 줄 단위로 들고 있는 버퍼
+use crate::options::FileFormat;
+use std::fs::read_to_string;
+use std::io;
+
+/// 버퍼의 한 줄
+pub struct Row {
+    pub content: String,
+}
+
+impl Row {
+    pub fn new(s: String) -> Self {
+        Row { content: s }
+    }
+    pub fn insert_char(&mut self, at: usize, c: char) {
+        if at >= self.content.len() {
+            self.content.push(c);
+        } else {
+            self.content.insert(at, c);
+        }
+    }
+    pub fn delete_char(&mut self, at: usize) {
+        if at < self.content.len() {
+            self.content.remove(at);
+        }
+    }
+}
+
+/// 열려 있는 파일의 내용 (항상 최소 한 줄)
+pub struct EditorBuffer {
+    pub rows: Vec<Row>,
+    pub dirty: bool, // 마지막 저장 이후 변경 여부
+    pub version: u64, // 내용이 바뀔 때마다 증가 (LSP 동기화용)
+}
+
+impl Default for EditorBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditorBuffer {
+    pub fn new() -> Self {
+        EditorBuffer {
+            rows: vec![Row::new(String::new())],
+            dirty: false,
+            version: 0,
+        }
+    }
+    /// 내용이 바뀌었음을 표시 (dirty와 version 갱신)
+    pub fn touch(&mut self) {
+        self.dirty = true;
+        self.version += 1;
+    }
+    /// 줄들을 개행으로 이은 전체 내용
+    pub fn rows_to_string(&self) -> String {
+        self.rows.iter()
+            .map(|r| r.content.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+    /// 파일을 읽어 버퍼를 채움, 첫 줄이 \r\n 으로 끝나면 Dos
+    pub fn open(&mut self, filename: &str) -> io::Result<FileFormat> {
+        let content = read_to_string(filename)?; // 파일을 읽어옴
+        Ok(self.load(&content))
+    }
+    /// 글을 줄로 나눠 버퍼를 채움 (표준 입력에서 읽은 내용 등)
+    pub fn load(&mut self, content: &str) -> FileFormat {
+        self.rows.clear(); // 기본 빈 줄 제거
+
+        for line in content.lines() {
+            self.rows.push(Row::new(line.to_string())); // 한 줄씩 버퍼에 추가
+        }
+
+        // 파일이 비어있을 경우를 대비해 최소 한 줄은 유지
+        if self.rows.is_empty() {
+            self.rows.push(Row::new(String::new()));
+        }
+        let dos = content.find('\n').is_some_and(|i| content[..i].ends_with('\r'));
+        if dos { FileFormat::Dos } else { FileFormat::Unix }
+    }
+}
@@ -0,0 +1,237 @@
+// --- Buffer List ---
+// :e 로 연 파일마다 버퍼를 하나씩 두고 :bn / :bp / :b N / :ls 와 버퍼 선택 창(:Buffers)으로 오간다.
+// 지금 보고 있는 버퍼의 내용과 커서는 EditorConfig 필드에 있고, 나머지 버퍼만 여기에 보관
+// :bufdo 명령 / :argdo 명령 은 모든 버퍼 / 인자 목록의 파일마다 명령을 실행하고 (예: :bufdo %s/old/new/g)
+// 바뀐 줄 수를 모아서 보여준 뒤 원래 버퍼로 돌아옴
+use crate::changelist::ChangeList;
+use crate::fold::Folds;
+use crate::options::BufferOptions;
+use crate::undo::UndoTree;
+use crate::{EditorBuffer, EditorConfig, git};
+use std::collections::BTreeMap;
+use std::fs;
+use std::mem;
+
+// 화면에 없는 버퍼의 내용과 보던 위치
+pub struct HiddenBuffer {
+    buffer: EditorBuffer,
+    filename: Option<String>,
+    cursor: (usize, usize),
+    offset: (usize, usize),
+    marks: BTreeMap<char, (usize, usize)>,
+    folds: Folds,
+    annotations: BTreeMap<usize, String>,
+    changes: ChangeList,
+    undo: UndoTree,
+    options: BufferOptions,
+}
+
+pub struct BufferList {
+    slots: Vec<Option<HiddenBuffer>>, // 지금 버퍼의 자리는 None
+    pub current: usize,
+}
+
+impl BufferList {
+    pub fn new() -> Self {
+        BufferList { slots: vec![None], current: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+/// 버퍼 목록의 한 줄
+pub struct BufferInfo {
+    pub number: usize, // 1부터
+    pub name: String,
+    pub modified: bool,
+    pub lines: usize,
+    pub current: bool,
+}
+
+impl BufferInfo {
+    pub fn display(&self) -> String {
+        let flag = if self.current { '%' } else { ' ' };
+        let modified = if self.modified { " [+]" } else { "" };
+        format!("{:>3}{} {}{}  {} lines", self.number, flag, self.name, modified, self.lines)
+    }
+}
+
+impl EditorConfig {
+    // :e 파일 — 이미 열려 있으면 그 버퍼로, 아니면 새 버퍼에서 엶
+    pub(crate) fn edit_file(&mut self, filename: &str) {
+        if let Some(i) = self.buffer_infos().iter().position(|b| b.name == filename) {
+            self.switch_buffer(i);
+            return;
+        }
+        self.new_buffer();
+        self.open_file(filename);
+    }
+
+    // 지금 버퍼를 뒤로 보내고 빈 버퍼를 지금 버퍼로 (:e, :Tutor)
+    pub(crate) fn new_buffer(&mut self) {
+        // 백그라운드 찾기의 결과가 다른 버퍼의 커서를 옮기지 않게
+        self.cancel_search();
+        // 처음 빈 버퍼는 그대로 재사용
+        let unused = self.filename.is_none() && !self.buffer.dirty;
+        if !unused {
+            let hidden = self.hide_current();
+            let current = self.buffers.current;
+            self.buffers.slots[current] = Some(hidden);
+            self.buffers.slots.push(None);
+            self.buffers.current = self.buffers.slots.len() - 1;
+        }
+        self.marks.clear();
+    }
+
+    pub(crate) fn switch_buffer(&mut self, index: usize) {
+        if index == self.buffers.current || index >= self.buffers.len() {
+            return;
+        }
+        let Some(next) = self.buffers.slots[index].take() else { return };
+        self.cancel_search();
+        let current = self.buffers.current;
+        self.buffers.slots[current] = Some(self.hide_current());
+        self.buffers.current = index;
+        self.buffer = next.buffer;
+        self.filename = next.filename;
+        self.refresh_canonical();
+        (self.cy, self.cx) = next.cursor;
+        (self.row_offset, self.col_offset) = next.offset;
+        self.marks = next.marks;
+        self.folds = next.folds;
+        self.annotations = next.annotations;
+        self.cursors.clear();
+        self.changes = next.changes;
+        self.undo = next.undo;
+        self.buffer_options = next.options;
+        // 버퍼마다 다른 부가 정보는 새로 계산
+        self.git.reset();
+        self.syntax.reset();
+        self.branch = git::branch(self.filename.as_deref());
+        self.blame = None;
+        self.diff = None;
+        self.update_conflicts();
+        self.status_msg = format!("\"{}\" {} lines", self.filename.as_deref().unwrap_or("[No Name]"), self.buffer.rows.len());
+        self.emit("BufEnter");
+    }
+
+    // :bn / :bp, 끝에서는 반대쪽 끝으로 돎
+    pub(crate) fn cycle_buffer(&mut self, forward: bool) {
+        let len = self.buffers.len();
+        let current = self.buffers.current;
+        self.switch_buffer(if forward { (current + 1) % len } else { (current + len - 1) % len });
+    }
+
+    fn hide_current(&mut self) -> HiddenBuffer {
+        self.canonical = None;
+        HiddenBuffer {
+            buffer: mem::take(&mut self.buffer),
+            filename: self.filename.take(),
+            cursor: (self.cy, self.cx),
+            offset: (self.row_offset, self.col_offset),
+            marks: mem::take(&mut self.marks),
+            folds: mem::replace(&mut self.folds, Folds::new()),
+            annotations: mem::take(&mut self.annotations),
+            changes: mem::replace(&mut self.changes, ChangeList::new()),
+            undo: mem::replace(&mut self.undo, UndoTree::new()),
+            options: mem::replace(&mut self.buffer_options, self.buffer_defaults.clone()),
+        }
+    }
+
+    // :bufdo / :argdo, argdo 면 인자 목록의 파일들을 (안 열렸으면 열어서) 차례로
+    pub(crate) fn run_in_each(&mut self, name: &str, command: &str, args: bool) -> bool {
+        if command.is_empty() {
+            self.status_msg = format!("Usage: :{} <command>", name);
+            return true;
+        }
+        let original = self.buffers.current;
+        let count = if args { self.arglist.files.len() } else { self.buffers.len() };
+        let (mut lines, mut changed) = (0, 0);
+        for i in 0..count {
+            if args {
+                let file = self.arglist.files[i].clone();
+                self.edit_file(&file);
+            } else {
+                self.switch_buffer(i);
+            }
+            let before: Vec<String> = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+            if !self.run_command(command) {
+                return false;
+            }
+            let rows = &self.buffer.rows;
+            let differ = (0..before.len().max(rows.len())).filter(|&i| before.get(i) != rows.get(i).map(|r| &r.content)).count();
+            if differ > 0 {
+                lines += differ;
+                changed += 1;
+            }
+        }
+        self.switch_buffer(original);
+        let what = if args { "files" } else { "buffers" };
+        self.status_msg = format!("{}: {} lines changed in {} of {} {}", name, lines, changed, count, what);
+        true
+    }
+
+    // Ctrl-G: "이름" [Modified] 30 lines --50%--
+    pub(crate) fn show_file_info(&mut self) {
+        let name = self.filename.as_deref().unwrap_or("[No Name]");
+        let mut info = format!("\"{}\"", name);
+        if self.buffer.dirty {
+            info.push_str(" [Modified]");
+        }
+        if self.buffer_options.readonly {
+            info.push_str(" [readonly]");
+        }
+        let lines = self.buffer.rows.len();
+        let percent = (self.cy + 1) * 100 / lines;
+        self.status_msg = format!("{} {} line{} --{}%--", info, lines, if lines == 1 { "" } else { "s" }, percent);
+    }
+
+    // 파일을 담은 버퍼 (지금 버퍼나 숨은 버퍼), 같은 파일을 다른 경로로 열었어도 찾음
+    pub(crate) fn buffer_for_mut(&mut self, filename: &str) -> Option<&mut EditorBuffer> {
+        self.buffer_and_undo_for_mut(filename).map(|(buffer, _)| buffer)
+    }
+
+    // 파일을 담은 버퍼와 그 실행 취소 기록
+    pub(crate) fn buffer_and_undo_for_mut(&mut self, filename: &str) -> Option<(&mut EditorBuffer, &mut UndoTree)> {
+        let target = fs::canonicalize(filename).unwrap_or_else(|_| filename.into());
+        let same = |name: Option<&String>| name.is_some_and(|n| fs::canonicalize(n).unwrap_or_else(|_| n.into()) == target);
+        if same(self.filename.as_ref()) {
+            return Some((&mut self.buffer, &mut self.undo));
+        }
+        self.buffers.slots.iter_mut().flatten().find(|b| same(b.filename.as_ref())).map(|b| (&mut b.buffer, &mut b.undo))
+    }
+
+    // 저장하지 않은 버퍼들의 (번호, 파일 이름, 내용)
+    pub(crate) fn unsaved_buffers(&self) -> Vec<(usize, Option<String>, String)> {
+        (0..self.buffers.len())
+            .filter_map(|i| match &self.buffers.slots[i] {
+                Some(b) => b.buffer.dirty.then(|| (i + 1, b.filename.clone(), b.buffer.rows_to_string())),
+                None => self.buffer.dirty.then(|| (i + 1, self.filename.clone(), self.buffer.rows_to_string())),
+            })
+            .collect()
+    }
+
+    pub(crate) fn buffer_infos(&self) -> Vec<BufferInfo> {
+        let name = |n: Option<&String>| n.cloned().unwrap_or_else(|| "[No Name]".into());
+        (0..self.buffers.len())
+            .map(|i| match &self.buffers.slots[i] {
+                Some(b) => BufferInfo {
+                    number: i + 1,
+                    name: name(b.filename.as_ref()),
+                    modified: b.buffer.dirty,
+                    lines: b.buffer.rows.len(),
+                    current: false,
+                },
+                None => BufferInfo {
+                    number: i + 1,
+                    name: name(self.filename.as_ref()),
+                    modified: self.buffer.dirty,
+                    lines: self.buffer.rows.len(),
+                    current: true,
+                },
+            })
+            .collect()
+    }
+}
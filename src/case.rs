@@ -0,0 +1,109 @@
+// --- Case Operators ---
+// 대소문자를 바꾸는 명령 (. 으로 되풀이 가능)
+//   ~                        커서 아래 글자의 대소문자를 뒤집고 오른쪽으로
+//   g~{움직임} / gu / gU     뒤집기 / 소문자로 / 대문자로
+// 움직임은 h l (글자 하나), $ (줄 끝까지), j k (커서 줄과 아래 / 위 줄 전체),
+// 같은 글자를 한 번 더 치면 (g~~, guu, gUU, g~g~, gugu, gUgU) 커서 줄 전체
+use crate::EditorConfig;
+
+#[derive(Clone, Copy)]
+pub enum CaseOp {
+    Lower,
+    Upper,
+    Toggle,
+}
+
+impl CaseOp {
+    pub fn from_key(c: char) -> Option<CaseOp> {
+        match c {
+            'u' => Some(CaseOp::Lower),
+            'U' => Some(CaseOp::Upper),
+            '~' => Some(CaseOp::Toggle),
+            _ => None,
+        }
+    }
+
+    fn apply(self, s: &str) -> String {
+        match self {
+            CaseOp::Lower => s.to_lowercase(),
+            CaseOp::Upper => s.to_uppercase(),
+            CaseOp::Toggle => s
+                .chars()
+                .flat_map(|c| if c.is_uppercase() { c.to_lowercase().collect::<Vec<_>>() } else { c.to_uppercase().collect() })
+                .collect(),
+        }
+    }
+}
+
+impl EditorConfig {
+    // 줄의 start..end 바이트 범위를 바꾸고 바뀐 뒤의 길이를 돌려줌
+    pub(crate) fn change_case(&mut self, op: CaseOp, line: usize, start: usize, end: usize) -> usize {
+        let content = &self.buffer.rows[line].content;
+        let changed = op.apply(&content[start..end]);
+        if changed != content[start..end] {
+            self.buffer.rows[line].content.replace_range(start..end, &changed);
+            self.buffer.touch();
+        }
+        changed.len()
+    }
+
+    // ~: 커서 아래 글자를 뒤집고 다음 글자로 (줄 끝이면 그대로)
+    pub(crate) fn toggle_case_under(&mut self) {
+        let content = &self.buffer.rows[self.cy].content;
+        let Some(c) = content[self.cx.min(content.len())..].chars().next() else { return };
+        let len = self.change_case(CaseOp::Toggle, self.cy, self.cx, self.cx + c.len_utf8());
+        if self.cx + len < self.buffer.rows[self.cy].content.len() {
+            self.cx += len;
+        }
+    }
+
+    // g 다음에 친 글자들 (u, ug 등)과 그 뒤의 움직임
+    pub(crate) fn case_operator(&mut self, keys: &str, motion: char) {
+        let Some(op) = keys.chars().next().and_then(CaseOp::from_key) else { return };
+        if keys.starts_with(motion) {
+            self.case_line(op);
+        } else if keys.len() == 1 {
+            self.case_motion(op, motion);
+        }
+    }
+
+    fn case_motion(&mut self, op: CaseOp, motion: char) {
+        let content = &self.buffer.rows[self.cy].content;
+        let cx = self.cx.min(content.len());
+        match motion {
+            'h' => {
+                let Some((start, _)) = content[..cx].char_indices().next_back() else { return };
+                self.change_case(op, self.cy, start, cx);
+                self.cx = start;
+            }
+            'l' => {
+                let Some(c) = content[cx..].chars().next() else { return };
+                self.change_case(op, self.cy, cx, cx + c.len_utf8());
+            }
+            '$' => {
+                let end = content.len();
+                self.change_case(op, self.cy, cx, end);
+            }
+            'j' | 'k' => {
+                let (first, last) = if motion == 'j' { (self.cy, self.cy + 1) } else { (self.cy.saturating_sub(1), self.cy) };
+                if last >= self.buffer.rows.len() || first == last {
+                    return;
+                }
+                for line in first..=last {
+                    let end = self.buffer.rows[line].content.len();
+                    self.change_case(op, line, 0, end);
+                }
+                self.cy = first;
+            }
+            _ => return,
+        }
+        self.clamp_cursor();
+    }
+
+    // g~~, guu, gUU: 커서 줄 전체
+    fn case_line(&mut self, op: CaseOp) {
+        let end = self.buffer.rows[self.cy].content.len();
+        self.change_case(op, self.cy, 0, end);
+        self.clamp_cursor();
+    }
+}
@@ -0,0 +1,65 @@
+// --- Change List ---
+// 버퍼를 바꾼 위치들을 버퍼마다 기억 (같은 줄에서 이어진 변경은 한 항목, 최대 100개)
+//   g; / g,   더 오래된 / 더 새로운 변경 위치로
+//   gi        마지막으로 입력 모드를 끝낸 위치에서 다시 입력 모드
+use crate::EditorConfig;
+
+const MAX_CHANGES: usize = 100;
+
+pub struct ChangeList {
+    positions: Vec<(usize, usize)>, // (줄, 열), 오래된 것부터
+    index: usize,                   // g; / g, 로 보고 있는 항목, len이면 가장 새 변경 다음
+    pub last_insert: Option<(usize, usize)>,
+}
+
+impl ChangeList {
+    pub fn new() -> Self {
+        ChangeList { positions: Vec::new(), index: 0, last_insert: None }
+    }
+
+    pub fn record(&mut self, pos: (usize, usize)) {
+        if self.positions.last().is_some_and(|last| last.0 == pos.0) {
+            self.positions.pop();
+        }
+        self.positions.push(pos);
+        if self.positions.len() > MAX_CHANGES {
+            self.positions.remove(0);
+        }
+        self.index = self.positions.len();
+    }
+
+    // 한 칸 옮긴 항목의 위치, 끝이면 None
+    fn step(&mut self, older: bool) -> Option<(usize, usize)> {
+        let index = if older { self.index.checked_sub(1)? } else { self.index + 1 };
+        let pos = *self.positions.get(index)?;
+        self.index = index;
+        Some(pos)
+    }
+}
+
+impl EditorConfig {
+    // g; / g,
+    pub(crate) fn jump_change(&mut self, older: bool) {
+        if self.changes.positions.is_empty() {
+            self.status_msg = "Change list is empty".into();
+            return;
+        }
+        match self.changes.step(older) {
+            Some((line, col)) => {
+                (self.cy, self.cx) = (line, col);
+                self.clamp_cursor();
+            }
+            None if older => self.status_msg = "At start of change list".into(),
+            None => self.status_msg = "At end of change list".into(),
+        }
+    }
+
+    // gi: 마지막 입력 위치가 있으면 그리로 가서 입력 모드
+    pub(crate) fn resume_insert(&mut self) {
+        if let Some((line, col)) = self.changes.last_insert {
+            (self.cy, self.cx) = (line, col);
+            self.clamp_cursor();
+        }
+        self.start_insert();
+    }
+}
@@ -0,0 +1,102 @@
+// --- Command Line ---
+// main 이 받은 인자를 풀어 할 일을 정함 (옵션은 파일 이름보다 앞뒤 어디에 와도 됨, -- 뒤는 모두 파일 이름)
+//   vii [파일...]             파일마다 버퍼를 만들고 첫 파일에서 시작
+//   vii -                     표준 입력을 버퍼로 (키는 /dev/tty 에서)
+//   -R, --readonly            버퍼를 readonly 로 열어 :w 를 거절함 (:w! 로 씀)
+//   --clean                   ~/.viirc 와 스크립트, 플러그인을 읽지 않음
+//   --tutor                   튜토리얼 버퍼로 시작 (:Tutor, tutor.rs)
+//   --timing                  :profile on 으로 시작해 끝날 때 잰 결과를 표준 오류에 냄 (profile.rs)
+//   -c <명령>, +<명령>        파일을 연 뒤 차례로 실행 (+42, +/글 은 startup.rs)
+//   -d <파일1> <파일2>        두 파일을 좌우로 비교
+//   -es ...                   화면 없이 ex 명령만 실행 (뒤의 인자는 batch.rs 가 풂)
+//   --server <소켓>           소켓으로 명령을 받음 (server.rs)
+//   -h, --help / --version    사용법 / 버전을 찍고 끝냄
+
+/// 인자를 푼 결과
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Edit(EditArgs),
+    Batch(Vec<String>),
+    Server(String),
+    Help,
+    Version,
+}
+
+/// 화면을 띄워 편집할 때의 인자
+#[derive(Debug, Default, PartialEq)]
+pub struct EditArgs {
+    pub files: Vec<String>,
+    pub commands: Vec<String>, // -c 와 + 로 준 명령 (+ 는 뗌)
+    pub readonly: bool,
+    pub clean: bool,
+    pub timing: bool,
+    pub tutor: bool,
+    pub stdin: bool,
+    pub diff: Option<(String, String)>,
+}
+
+pub const USAGE: &str = "\
+Usage: vii [options] [file ...]
+       vii -d <file1> <file2>
+       vii -es [-c <cmd>]... [+<cmd>]... [-S <script>] [file ...]
+       vii --server <socket>
+
+Options:
+  -                  read the text from stdin
+  +<N>               start at line N (+ alone: last line)
+  +/<text>           start at the first occurrence of text
+  +<cmd>, -c <cmd>   run an ex command after opening the files
+  -R, --readonly     refuse :w unless forced with :w!
+  --clean            do not read ~/.viirc, scripts, or plugins
+  --tutor            start with the interactive tutorial
+  --timing           measure render and input timings, report them on exit
+  -d                 compare two files side by side
+  -es                run ex commands without a screen
+  --server <socket>  take JSON-RPC commands on a unix socket
+  -h, --help         print this help
+  --version          print the version";
+
+pub fn version() -> String {
+    format!("vii {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// 프로그램 이름을 뺀 인자들을 풂
+pub fn parse(args: &[String]) -> Result<Action, String> {
+    match args.first().map(String::as_str) {
+        Some("-es") => return Ok(Action::Batch(args[1..].to_vec())),
+        Some("--server") => return args.get(1).cloned().map(Action::Server).ok_or("--server needs a socket path".into()),
+        _ => {}
+    }
+    let mut edit = EditArgs::default();
+    let mut diff = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Action::Help),
+            "--version" => return Ok(Action::Version),
+            "-R" | "--readonly" => edit.readonly = true,
+            "--clean" => edit.clean = true,
+            "--timing" => edit.timing = true,
+            "--tutor" => edit.tutor = true,
+            "-d" => diff = true,
+            "-" => edit.stdin = true,
+            "-c" => edit.commands.push(args.next().ok_or("-c needs a command")?.clone()),
+            "--" => edit.files.extend(args.by_ref().cloned()),
+            "-es" | "--server" => return Err(format!("{} must come first", arg)),
+            _ if arg.starts_with('+') => edit.commands.push(arg[1..].to_string()),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ => edit.files.push(arg.clone()),
+        }
+    }
+    if diff {
+        let [a, b] = <[String; 2]>::try_from(std::mem::take(&mut edit.files)).map_err(|_| "-d needs two files")?;
+        edit.diff = Some((a, b));
+    }
+    if edit.stdin && (!edit.files.is_empty() || edit.diff.is_some()) {
+        return Err("- cannot be combined with files".into());
+    }
+    if edit.tutor && (edit.stdin || !edit.files.is_empty() || edit.diff.is_some()) {
+        return Err("--tutor cannot be combined with files".into());
+    }
+    Ok(Action::Edit(edit))
+}
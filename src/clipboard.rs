@@ -0,0 +1,33 @@
+// --- System Clipboard ---
+// "+ 와 "* 레지스터가 쓰는 시스템 클립보드, 있는 도구를 차례로 시도함
+//   pbcopy/pbpaste (macOS), wl-copy/wl-paste (Wayland), xclip, xsel (X11)
+// 도구가 없거나 실패하면 편집기 안의 + 레지스터로만 동작
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const TOOLS: &[(&[&str], &[&str])] = &[
+    (&["pbcopy"], &["pbpaste"]),
+    (&["wl-copy"], &["wl-paste", "--no-newline"]),
+    (&["xclip", "-selection", "clipboard"], &["xclip", "-selection", "clipboard", "-o"]),
+    (&["xsel", "--clipboard", "--input"], &["xsel", "--clipboard", "--output"]),
+];
+
+/// 클립보드에 넣음, 넣은 도구가 있으면 true
+pub fn copy(text: &str) -> bool {
+    TOOLS.iter().any(|(copy, _)| {
+        let Ok(mut child) = Command::new(copy[0]).args(&copy[1..]).stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()
+        else {
+            return false;
+        };
+        let written = child.stdin.take().is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|s| s.success()) && written
+    })
+}
+
+/// 클립보드의 내용, 읽을 수 없으면 None
+pub fn paste() -> Option<String> {
+    TOOLS.iter().find_map(|(_, paste)| {
+        let output = Command::new(paste[0]).args(&paste[1..]).stdin(Stdio::null()).stderr(Stdio::null()).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    })
+}
@@ -0,0 +1,93 @@
+// --- Command Names ---
+// 내장 ex 명령의 이름표, run_command 는 입력한 이름을 여기서 온전한 이름으로 바꾼 뒤 실행함
+//   "q[uit]"   q, qu, qui, quit 모두 받음 ([ 앞까지가 가장 짧은 줄임말)
+//   표의 줄임말보다 짧아도 한 명령에만 맞는 앞부분이면 받음 (:Diag → :Diagnostics)
+//   여러 명령에 맞으면 오류 (:c → Ambiguous command)
+// 사용자 별칭: 명령 이름을 다른 명령 줄로 바꿈, 명령 뒤의 인자는 그대로 붙음
+//   :cabbrev W w       :W 를 :w 로 (:W foo.txt 는 :w foo.txt)
+//   :cabbrev Q q!      별칭에 인자나 ! 를 넣어도 됨
+//   :cabbrev           별칭 목록      :cunabbrev W   별칭 지우기
+use std::collections::BTreeMap;
+
+// 사용자 명령, 스크립트 명령, wasm 플러그인 명령은 이 표보다 먼저 찾음
+const COMMANDS: &[&str] = &[
+    "w[rite]", "q[uit]", "wq", "gr[ep]", "greplace", "ta[g]", "ts[elect]", "mak[e]",
+    "cn[ext]", "cp[revious]", "cN[ext]", "cc", "cfir[st]", "cla[st]", "cope[n]", "ccl[ose]",
+    "Blame", "Zen", "ConflictOurs", "ConflictTheirs", "ConflictBoth", "diffs[plit]", "diffo[ff]",
+    "lsp", "Rename", "Format", "Diagnostics", "Symbols", "com[mand]", "delc[ommand]",
+    "e[dit]", "bufdo", "argdo", "ar[gs]", "n[ext]", "N[ext]", "prev[ious]", "fir[st]", "rew[ind]", "la[st]",
+    "bn[ext]", "bp[revious]", "bN[ext]", "b[uffer]", "ls", "buffers", "files", "Buffers",
+    "reg[isters]", "di[splay]", "mes[sages]", "prof[ile]", "marks",
+    "u[ndo]", "red[o]", "undotree", "ea[rlier]", "lat[er]", "lua", "leader", "se[t]", "au[tocmd]",
+    "filet[ype]", "ia[bbrev]", "iuna[bbrev]", "nno[remap]", "ino[remap]", "nun[map]", "iu[nmap]",
+    "ca[bbrev]", "cuna[bbrev]", "h[elp]", "Tutor", "fo[ld]", "sor[t]", "ret[ab]", "trim", "MoveDown", "MoveUp", "Duplicate",
+];
+
+// "q[uit]" → (가장 짧은 줄임말의 길이, "quit")
+fn parse(entry: &str) -> (usize, String) {
+    match entry.split_once('[') {
+        Some((short, rest)) => (short.len(), format!("{}{}", short, rest.trim_end_matches(']'))),
+        None => (entry.len(), entry.to_string()),
+    }
+}
+
+/// 입력한 명령 이름 (끝의 ! 포함) 을 온전한 이름으로, 내장 명령이 아니면 None
+pub fn resolve(typed: &str) -> Result<Option<String>, String> {
+    let (name, bang) = match typed.strip_suffix('!') {
+        Some(name) => (name, "!"),
+        None => (typed, ""),
+    };
+    if name.is_empty() {
+        return Ok(None);
+    }
+    let candidates: Vec<(usize, String)> = COMMANDS.iter().map(|e| parse(e)).filter(|(_, full)| full.starts_with(name)).collect();
+    // 표에 적은 줄임말이 먼저 (:n 은 :nnoremap 이 아니라 :next)
+    let full = match candidates.iter().find(|(short, _)| name.len() >= *short) {
+        Some((_, full)) => full,
+        None => match candidates.as_slice() {
+            [] => return Ok(None),
+            [(_, full)] => full,
+            _ => {
+                let names: Vec<_> = candidates.iter().map(|(_, full)| full.as_str()).collect();
+                return Err(format!("Ambiguous command: {} ({})", name, names.join(", ")));
+            }
+        },
+    };
+    Ok(Some(format!("{}{}", full, bang)))
+}
+
+pub struct CommandAliases {
+    aliases: BTreeMap<String, String>,
+}
+
+impl CommandAliases {
+    pub fn new() -> Self {
+        CommandAliases { aliases: BTreeMap::new() }
+    }
+
+    // "W w" 처럼 별칭과 바꿀 명령 줄
+    pub fn define(&mut self, args: &str) -> Result<(), String> {
+        match args.split_once(' ') {
+            Some((name, command)) if !command.trim().is_empty() => {
+                self.aliases.insert(name.to_string(), command.trim().to_string());
+                Ok(())
+            }
+            _ => Err("Usage: :cabbrev <name> <command>".into()),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.aliases.remove(name).is_some()
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.aliases.iter().map(|(name, command)| format!("{} {}", name, command)).collect()
+    }
+
+    /// 첫 낱말이 별칭이면 바꾼 명령 줄 (한 번만 바꿈)
+    pub fn expand(&self, line: &str) -> Option<String> {
+        let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let command = self.aliases.get(name)?;
+        Some(format!("{} {}", command, rest).trim_end().to_string())
+    }
+}
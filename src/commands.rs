@@ -0,0 +1,429 @@
+// --- Ex Commands ---
+// 명령 모드에서 입력한 한 줄을 해석해서 실행 (설정 파일, 스크립트, 서버에서도 사용)
+use crate::options::{self, OptionScope};
+use crate::{EditorConfig, Mode, PanelKind, cmdnames, conflict, diagnostics, filetype, grep, keys, make, range};
+
+impl EditorConfig {
+    // :cnext, :cprev, :cc 공통 처리
+    fn quickfix_command(&mut self, cmd: &str, args: &str) {
+        if self.quickfix.entries.is_empty() {
+            self.status_msg = "No quickfix entries".into();
+            return;
+        }
+        let moved = match cmd {
+            "cnext" => self.quickfix.next(),
+            "cprevious" | "cNext" => self.quickfix.prev(),
+            "cfirst" => self.quickfix.select(1),
+            "clast" => self.quickfix.select(self.quickfix.entries.len()),
+            _ => match args.parse() {
+                Ok(nr) => self.quickfix.select(nr),
+                Err(_) => args.is_empty(), // 인자 없는 :cc는 현재 항목
+            },
+        };
+        if !moved {
+            self.status_msg = match cmd {
+                "cnext" => "No more items".into(),
+                "cprevious" | "cNext" => "Already at first item".into(),
+                _ => format!("Invalid item: {}", args),
+            };
+            return;
+        }
+        self.jump_to_quickfix();
+    }
+
+    fn grep(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            self.status_msg = "Usage: :grep <pattern>".into();
+            return;
+        }
+        match grep::grep(pattern, ".") {
+            Ok((entries, Some(warning))) if entries.is_empty() => self.error(format!("Error: {}", warning)),
+            Ok((entries, _)) if entries.is_empty() => self.status_msg = format!("No matches: {}", pattern),
+            Ok((entries, warning)) => {
+                self.status_msg = format!("{} matches for {}", entries.len(), pattern);
+                if let Some(warning) = warning {
+                    self.status_msg += &format!(" (warning: {})", warning);
+                }
+                self.quickfix.set(format!("grep {}", pattern), entries);
+                self.open_quickfix_panel();
+            }
+            Err(e) => self.error(format!("Error: {}", e)),
+        }
+    }
+
+    // :make [인자]: makeprg를 실행하고 오류 위치를 quickfix 목록에 넣은 뒤 첫 오류로 이동
+    fn make(&mut self, args: &str) {
+        let command = format!("{} {}", self.options.makeprg, args).trim().to_string();
+        match make::run(&command) {
+            Ok((success, output)) => {
+                let entries = make::parse(&output, &self.options.errorformat);
+                self.make_diagnostics = diagnostics::from_make(&entries);
+                if entries.is_empty() {
+                    self.status_msg = if success { format!("{}: no errors", command) } else { format!("{}: failed", command) };
+                }
+                self.quickfix.set(format!("make {}", args).trim().to_string(), entries);
+                if let Some(panel) = self.panel.as_ref() && panel.kind == PanelKind::Quickfix {
+                    self.open_quickfix_panel();
+                }
+                self.jump_to_quickfix();
+            }
+            Err(e) => self.error(format!("Error: {}", e)),
+        }
+    }
+
+    pub(crate) fn execute_command(&mut self) -> bool {
+        let line = self.command_buffer.clone();
+        if self.prompt != ':' {
+            let forward = self.prompt == '/';
+            (self.mode, self.prompt) = (Mode::Normal, ':');
+            self.command_buffer.clear();
+            self.search_prompt(&line, forward);
+            return true;
+        }
+        if !line.trim().is_empty() {
+            self.last_command = Some(line.clone());
+        }
+        let should_continue = self.run_command(&line);
+        self.mode = Mode::Normal;
+        self.command_buffer.clear();
+        should_continue
+    }
+
+    // @: 마지막 명령 줄을 횟수만큼 다시 실행 (.,+2 같은 범위는 지금 커서에서 다시 해석), @@ 는 마지막 @ 를 되풀이
+    pub(crate) fn execute_register(&mut self, name: char) -> bool {
+        let name = if name == '@' { self.last_executed.unwrap_or('@') } else { name };
+        if name != ':' {
+            self.error(format!("Invalid register name: {}", name));
+            return true;
+        }
+        let Some(line) = self.last_command.clone() else {
+            self.status_msg = "No previous command line".into();
+            return true;
+        };
+        self.last_executed = Some(name);
+        (0..self.take_count()).all(|_| self.run_command(&line))
+    }
+
+    /// 명령어 한 줄 실행 (명령 모드와 설정 파일에서 공용), :q 등으로 종료해야 하면 false
+    pub fn run_command(&mut self, line: &str) -> bool {
+        let (range, line) = range::parse(line.trim_start(), self.cy + 1, self.buffer.rows.len());
+        // :cabbrev 별칭은 명령 이름만 바꿈 (범위는 그대로)
+        let aliased = self.command_aliases.expand(line);
+        let line = aliased.as_deref().unwrap_or(line);
+        let (cmd, args) = match line.split_once(' ') {
+            Some((cmd, args)) => (cmd, args.trim()),
+            None => (line, ""),
+        };
+        let cursor_line = range::Range { start: self.cy + 1, end: self.cy + 1 };
+        // 범위만 있으면 (:42, :$, :.+3) 범위의 끝 줄로
+        if cmd.is_empty() && let Some(range) = range {
+            self.goto_line(range.end);
+            return true;
+        }
+        if let Some(user) = self.user_commands.get(cmd) {
+            let commands = user.expand(args, range.unwrap_or(cursor_line));
+            return self.run_user_command(cmd, commands);
+        }
+        if let Some(script) = &self.script && script.has_command(cmd) {
+            let range = range.unwrap_or(cursor_line);
+            self.with_script(|script, ed| script.call_command(ed, cmd, args, range));
+            return true;
+        }
+        #[cfg(feature = "wasm")]
+        if self.run_wasm_command(cmd, args) {
+            return true;
+        }
+        // 내장 명령은 줄임말을 온전한 이름으로 (:wri → :write)
+        let resolved = match cmdnames::resolve(cmd) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                self.error(e);
+                return true;
+            }
+        };
+        let cmd = resolved.as_deref().unwrap_or(cmd);
+        // 범위를 받는 내장 명령
+        if cmd == "fold" {
+            let range = range.unwrap_or(cursor_line);
+            self.create_fold(range.start - 1, range.end - 1);
+            return true;
+        }
+        // 범위가 없으면 버퍼 전체
+        if matches!(cmd, "sort" | "sort!") {
+            let range = range.unwrap_or(range::Range { start: 1, end: self.buffer.rows.len() });
+            self.sort(range.start - 1, range.end - 1, cmd.ends_with('!'), args);
+            return true;
+        }
+        if cmd == "retab" {
+            let range = range.unwrap_or(range::Range { start: 1, end: self.buffer.rows.len() });
+            self.retab(range.start - 1, range.end - 1, args);
+            return true;
+        }
+        if cmd == "trim" {
+            let range = range.unwrap_or(range::Range { start: 1, end: self.buffer.rows.len() });
+            self.trim_trailing(range.start - 1, range.end - 1);
+            return true;
+        }
+        // :s/찾을 글/바꿀 글/ 은 명령 이름 뒤에 공백 없이 구분자가 옴
+        if let Some(args) = line.strip_prefix('s').filter(|rest| rest.starts_with(|c: char| c.is_ascii_punctuation() && c != '\\')) {
+            self.substitute(range.unwrap_or(cursor_line), args);
+            return true;
+        }
+        if let Some(args) = line.strip_prefix("greplace").filter(|rest| rest.starts_with(|c: char| c.is_ascii_punctuation() && c != '\\')) {
+            self.project_replace(args);
+            return true;
+        }
+        if matches!(cmd, "MoveDown" | "MoveUp" | "Duplicate") {
+            let range = range.unwrap_or(cursor_line);
+            self.move_command(cmd, range.start - 1, range.end - 1);
+            return true;
+        }
+        if range.is_some() {
+            self.error(format!("No range allowed: {}", cmd));
+            return true;
+        }
+        let mut should_continue = true;
+        match cmd {
+            // ! 는 readonly 여도 씀
+            "write" | "write!" => {
+                let force = cmd == "write!";
+                let result = if args.is_empty() { self.write_file(force) } else { self.save_as(args, force) };
+                if let Err(e) = result {
+                    self.error(e.to_string());
+                }
+            }
+            "quit" | "quit!" => should_continue = false,
+            // 저장에 실패하면 나가지 않음
+            "wq" | "wq!" => {
+                match self.write_file(cmd == "wq!") {
+                    Ok(()) => should_continue = false,
+                    Err(e) => self.error(e.to_string()),
+                }
+            }
+            "grep" => self.grep(args),
+            "greplace" => self.project_replace(args),
+            "tag" => self.tag(args),
+            "tselect" => self.tselect(args),
+            "make" => self.make(args),
+            "cnext" | "cprevious" | "cNext" | "cc" | "cfirst" | "clast" => self.quickfix_command(cmd, args),
+            "copen" => {
+                if self.quickfix.entries.is_empty() {
+                    self.status_msg = "No quickfix entries".into();
+                } else {
+                    self.open_quickfix_panel();
+                }
+            }
+            "cclose" => {
+                if self.panel.as_ref().is_some_and(|p| p.kind == PanelKind::Quickfix) {
+                    self.close_panel();
+                }
+            }
+            "Blame" => self.toggle_blame(),
+            "Zen" => self.toggle_zen(),
+            "ConflictOurs" => self.resolve_conflict(conflict::Choice::Ours),
+            "ConflictTheirs" => self.resolve_conflict(conflict::Choice::Theirs),
+            "ConflictBoth" => self.resolve_conflict(conflict::Choice::Both),
+            "diffsplit" => self.diff_split(args),
+            "diffoff" => {
+                self.diff = None;
+                self.row_offset = 0;
+            }
+            // :lsp <filetype> <command...> 파일 종류별 언어 서버 등록
+            "lsp" => match args.split_once(' ') {
+                Some((filetype, command)) => self.lsp.register(filetype, command.trim()),
+                None => self.status_msg = "Usage: :lsp <filetype> <command>".into(),
+            },
+            "Rename" => self.lsp_rename(args),
+            "Format" => self.lsp_format(),
+            "Diagnostics" => self.toggle_diagnostics_panel(),
+            "Symbols" => self.document_symbols(),
+            "command" | "command!" => self.define_command(cmd.ends_with('!'), args),
+            "delcommand" => {
+                if !self.user_commands.remove(args) {
+                    self.error(format!("No such user-defined command: {}", args));
+                }
+            }
+            "edit" if !args.is_empty() => self.edit_file(args),
+            "bufdo" => should_continue = self.run_in_each(cmd, args, false),
+            "argdo" => should_continue = self.run_in_each(cmd, args, true),
+            "args" | "next" | "Next" | "previous" | "first" | "rewind" | "last" => self.arglist_command(cmd, args),
+            "bnext" => self.cycle_buffer(true),
+            "bprevious" | "bNext" => self.cycle_buffer(false),
+            "buffer" => match args.parse::<usize>() {
+                Ok(n) if (1..=self.buffers.len()).contains(&n) => self.switch_buffer(n - 1),
+                _ => self.error(format!("No such buffer: {}", args)),
+            },
+            "ls" | "buffers" | "files" => {
+                self.status_msg = self.buffer_infos().iter().map(|b| b.display().trim().to_string()).collect::<Vec<_>>().join(" | ");
+            }
+            "Buffers" => self.open_buffer_picker(),
+            "registers" | "display" => self.show_registers(),
+            "messages" => self.show_messages(args),
+            "profile" => self.profile_command(args),
+            "marks" => self.show_marks(),
+            "undo" => self.undo(),
+            "redo" => self.redo(),
+            "undotree" => self.open_undo_panel(),
+            "earlier" => self.undo_by_amount(args, false),
+            "later" => self.undo_by_amount(args, true),
+            "lua" => self.with_script(|script, ed| script.run_string(ed, args)),
+            "leader" => match keys::parse(args).as_slice() {
+                [] => self.status_msg = format!("leader {}", self.keymaps.leader),
+                [key] => self.keymaps.leader = *key,
+                _ => self.status_msg = "Usage: :leader <key>".into(),
+            },
+            "set" => self.set_options(args),
+            "autocmd" if args.is_empty() => {
+                let list = self.autocmds.list();
+                self.status_msg = if list.is_empty() { "No autocommands".into() } else { list.join(" | ") };
+            }
+            "autocmd" => {
+                if let Err(e) = self.autocmds.add(args) {
+                    self.error(e);
+                }
+            }
+            "autocmd!" => {
+                if let Err(e) = self.autocmds.remove(args) {
+                    self.error(e);
+                }
+            }
+            "filetype" => match args.split_once(' ') {
+                Some((pattern, filetype)) => {
+                    self.filetype_rules.add(pattern, filetype.trim());
+                    if let Some(name) = self.filename.clone() {
+                        self.buffer_options.filetype =
+                            filetype::detect_buffer(&self.filetype_rules, &name, &self.buffer.rows[0].content);
+                    }
+                }
+                None if args.is_empty() => {
+                    self.status_msg = match &self.buffer_options.filetype {
+                        Some(filetype) => format!("filetype={}", filetype),
+                        None => "No filetype".into(),
+                    }
+                }
+                None => self.status_msg = "Usage: :filetype [<pattern> <filetype>]".into(),
+            },
+            "iabbrev" if args.is_empty() => {
+                let list = self.abbreviations.list();
+                self.status_msg = if list.is_empty() { "No abbreviations".into() } else { list.join(" | ") };
+            }
+            "iabbrev" => {
+                if let Err(e) = self.abbreviations.define(args) {
+                    self.error(e);
+                }
+            }
+            "iunabbrev" => {
+                if !self.abbreviations.remove(args) {
+                    self.error(format!("No such abbreviation: {}", args));
+                }
+            }
+            "nnoremap" => self.map_command("n", args),
+            "inoremap" => self.map_command("i", args),
+            "nunmap" | "iunmap" => {
+                if !self.keymaps.remove(&cmd[..1], args) {
+                    self.error(format!("No such mapping: {}", args));
+                }
+            }
+            "cabbrev" if args.is_empty() => {
+                let list = self.command_aliases.list();
+                self.status_msg = if list.is_empty() { "No command aliases".into() } else { list.join(" | ") };
+            }
+            "cabbrev" => {
+                if let Err(e) = self.command_aliases.define(args) {
+                    self.error(e);
+                }
+            }
+            "cunabbrev" => {
+                if !self.command_aliases.remove(args) {
+                    self.error(format!("No such command alias: {}", args));
+                }
+            }
+            "help" => self.help(args),
+            "Tutor" => self.open_tutor(),
+            _ => self.error(format!("Unknown: {}", cmd)),
+        }
+        should_continue
+    }
+
+    // :nnoremap lhs rhs, 인자가 없으면 정의된 매핑을 보여줌
+    fn map_command(&mut self, mode: &str, args: &str) {
+        match args.split_once(' ') {
+            Some((lhs, rhs)) if !rhs.trim().is_empty() => self.keymaps.define(mode, lhs, rhs.trim()),
+            _ if args.is_empty() => {
+                let maps = self.keymaps.list(mode);
+                self.status_msg = if maps.is_empty() { "No mappings".into() } else { maps.join(" | ") };
+            }
+            _ => self.status_msg = format!("Usage: :{}noremap <lhs> <rhs>", mode),
+        }
+    }
+
+    // :set 인자들을 차례로 적용, 물어본 값들은 모아서 보여줌
+    fn set_options(&mut self, args: &str) {
+        if args.is_empty() {
+            self.status_msg = "Usage: :set <option>[!|?] | no<option>".into();
+            return;
+        }
+        let mut shown = Vec::new();
+        // "\ "는 값 안의 공백
+        let args: Vec<String> = args.replace("\\ ", "\0").split_whitespace().map(|a| a.replace('\0', " ")).collect();
+        for arg in &args {
+            let mut scope =
+                OptionScope { global: &mut self.options, window: &mut self.window.options, buffer: &mut self.buffer_options };
+            match scope.set(arg) {
+                Ok(Some(value)) => shown.push(value),
+                Ok(None) => {}
+                Err(e) => {
+                    self.error(e);
+                    return;
+                }
+            }
+            // 버퍼별 설정은 앞으로 열 버퍼의 값도 바꿈
+            if options::is_buffer_local(arg) {
+                let mut defaults =
+                    OptionScope { global: &mut self.options, window: &mut self.window.options, buffer: &mut self.buffer_defaults };
+                let _ = defaults.set(arg);
+            }
+        }
+        if !shown.is_empty() {
+            self.status_msg = shown.join("  ");
+        }
+    }
+
+    // :command Name 대체명령, 인자가 없으면 정의된 명령을 보여줌
+    fn define_command(&mut self, bang: bool, args: &str) {
+        // -range 같은 속성은 받아들이되 무시 (범위와 인자는 항상 넘어감)
+        let args = args.split_whitespace().skip_while(|a| a.starts_with('-')).collect::<Vec<_>>().join(" ");
+        let Some((name, replacement)) = args.split_once(' ') else {
+            self.status_msg = if args.is_empty() {
+                let names: Vec<_> = self.user_commands.iter().map(|c| c.name.as_str()).collect();
+                if names.is_empty() { "No user-defined commands".into() } else { names.join(" ") }
+            } else {
+                match self.user_commands.get(&args) {
+                    Some(c) => format!("{}  {}", c.name, c.replacement),
+                    None => format!("No such user-defined command: {}", args),
+                }
+            };
+            return;
+        };
+        if let Err(e) = self.user_commands.define(name, replacement.trim(), bang) {
+            self.error(e);
+        }
+    }
+
+    fn run_user_command(&mut self, name: &str, commands: Vec<String>) -> bool {
+        if self.command_depth >= 20 {
+            self.error(format!("Recursive user command: {}", name));
+            return true;
+        }
+        self.command_depth += 1;
+        let mut should_continue = true;
+        for line in commands {
+            if !self.run_command(&line) {
+                should_continue = false;
+                break;
+            }
+        }
+        self.command_depth -= 1;
+        should_continue
+    }
+}
@@ -0,0 +1,235 @@
+// --- Word Completion ---
+// 입력 모드의 Ctrl-N/Ctrl-P: 버퍼에 있는 단어로 자동 완성
+// 언어 서버가 있는 파일이면 textDocument/completion 도 물어서, 답이 오면 서버의 후보를 앞에 두고 버퍼 단어와 합침
+// 메뉴에는 후보의 종류(f 함수, m 메서드, v 변수 등)와 설명을 함께 보여줌
+// 서버의 후보를 확정하면 completionItem/resolve 로 자동 import 같은 추가 편집을 받아 적용
+use crate::json::Json;
+use crate::lsp::{self, Request};
+use crate::{EditorConfig, Row};
+use std::collections::HashMap;
+
+pub struct Item {
+    pub word: String,
+    pub kind: char,     // 메뉴에 보여줄 종류 글자 (버퍼 단어는 빈칸)
+    pub detail: String, // 타입 등 짧은 설명
+    lsp: Option<Json>,  // 서버가 준 원래 항목 (resolve 에 씀)
+}
+
+impl Item {
+    fn word(word: String) -> Self {
+        Item { word, kind: ' ', detail: String::new(), lsp: None }
+    }
+}
+
+pub struct Completion {
+    pub start: usize,     // 완성 중인 단어가 시작하는 위치
+    line: usize,          // 완성 중인 줄
+    pub original: String, // 완성을 시작할 때 입력되어 있던 글자
+    pub items: Vec<Item>,
+    pub selected: usize,
+    forward: bool, // Ctrl-N 으로 시작했으면 true
+    moved: bool,   // 후보를 옮겨 다녔으면 true (서버의 답이 와도 고른 것을 그대로 둠)
+}
+
+impl Completion {
+    pub fn current(&self) -> Option<&Item> {
+        self.items.get(self.selected)
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len().max(1);
+        self.moved = true;
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = (self.selected + self.items.len().max(1) - 1) % self.items.len().max(1);
+        self.moved = true;
+    }
+}
+
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// 후보 단어 목록: 앞부분이 정확히 일치하는 단어, 덜 흩어진 일치, 커서와 가까운 줄 순서
+pub fn candidates<'a>(lines: impl Iterator<Item = &'a str>, cursor_line: usize, prefix: &str) -> Vec<String> {
+    let mut best: HashMap<&str, (bool, usize, usize)> = HashMap::new();
+    for (i, line) in lines.enumerate() {
+        for word in line.split(|c: char| !is_word_char(c)) {
+            if word.chars().count() < 2 || word == prefix {
+                continue;
+            }
+            let Some(gap) = fuzzy_gap(prefix, word) else { continue };
+            let key = (!word.starts_with(prefix), gap, i.abs_diff(cursor_line));
+            best.entry(word).and_modify(|k| *k = (*k).min(key)).or_insert(key);
+        }
+    }
+    let mut words: Vec<_> = best.into_iter().collect();
+    words.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+    words.into_iter().map(|(w, _)| w.to_string()).collect()
+}
+
+// prefix의 글자가 (첫 글자는 맨 앞에서) 순서대로 나타나면 사이에 낀 글자 수를 반환
+fn fuzzy_gap(prefix: &str, word: &str) -> Option<usize> {
+    let mut chars = word.chars();
+    let mut gap = 0;
+    for (i, p) in prefix.chars().enumerate() {
+        loop {
+            let c = chars.next()?;
+            if c.to_lowercase().eq(p.to_lowercase()) {
+                break;
+            }
+            if i == 0 {
+                return None;
+            }
+            gap += 1;
+        }
+    }
+    Some(gap)
+}
+
+// CompletionItemKind 를 한 글자로
+fn kind_char(kind: i64) -> char {
+    match kind {
+        2 => 'm',       // Method
+        3 | 4 => 'f',   // Function, Constructor
+        5 | 10 => 'F',  // Field, Property
+        6 | 12 => 'v',  // Variable, Value
+        7 | 22 => 's',  // Class, Struct
+        8 => 'i',       // Interface
+        9 => 'M',       // Module
+        13 | 20 => 'e', // Enum, EnumMember
+        14 => 'k',      // Keyword
+        15 => 'S',      // Snippet
+        21 => 'c',      // Constant
+        25 => 't',      // TypeParameter
+        _ => '*',
+    }
+}
+
+/// 서버의 CompletionItem[] (또는 CompletionList) 에서 prefix 에 맞는 후보들 (버퍼 단어와 같은 기준, 그다음 sortText 순서)
+fn lsp_items(result: &Json, prefix: &str) -> Vec<Item> {
+    let list = result.get("items").unwrap_or(result).as_array().unwrap_or(&[]);
+    let mut items: Vec<((bool, usize, &str), Item)> = list
+        .iter()
+        .filter_map(|item| {
+            let label = item.get("label")?.as_str()?;
+            let word = item
+                .get("textEdit")
+                .and_then(|e| e.get("newText"))
+                .or(item.get("insertText"))
+                .and_then(|t| t.as_str())
+                .unwrap_or(label)
+                .to_string();
+            let filter = item.get("filterText").and_then(|t| t.as_str()).unwrap_or(label);
+            let gap = fuzzy_gap(prefix, filter)?;
+            let kind = item.get("kind").and_then(|k| k.as_i64()).map_or(' ', kind_char);
+            let detail = item.get("detail").and_then(|d| d.as_str()).unwrap_or("").lines().next().unwrap_or("").to_string();
+            let sort = item.get("sortText").and_then(|t| t.as_str()).unwrap_or(label);
+            Some(((!filter.starts_with(prefix), gap, sort), Item { word, kind, detail, lsp: Some(item.clone()) }))
+        })
+        .collect();
+    items.sort_by_key(|(key, _)| *key);
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+impl EditorConfig {
+    // Ctrl-N / Ctrl-P
+    pub(crate) fn start_completion(&mut self, forward: bool) {
+        let line = &self.buffer.rows[self.cy].content;
+        let start = line[..self.cx].char_indices().rev().take_while(|(_, c)| is_word_char(*c)).last().map_or(self.cx, |(i, _)| i);
+        let prefix = line[start..self.cx].to_string();
+        let lines = self.buffer.rows.iter().map(|r| r.content.as_str());
+        let items: Vec<Item> = candidates(lines, self.cy, &prefix).into_iter().map(Item::word).collect();
+        // 버퍼를 바꾸기 전의 내용으로 서버에 물음
+        let asked = self.filename.as_ref().is_some_and(|name| self.lsp.has_server(name)) && {
+            let params = vec![self.lsp_cursor()];
+            self.lsp_request(Request::Completion, params)
+        };
+        if items.is_empty() && !asked {
+            self.status_msg = "No completions".into();
+            return;
+        }
+        let selected = if forward { 0 } else { items.len().saturating_sub(1) };
+        self.completion = Some(Completion { start, line: self.cy, original: prefix, items, selected, forward, moved: false });
+        self.apply_completion();
+    }
+
+    // 선택된 후보로 단어를 바꿔 넣음
+    pub(crate) fn apply_completion(&mut self) {
+        let Some(comp) = &self.completion else { return };
+        let Some(item) = comp.current() else {
+            self.status_msg = "Asking the language server...".into();
+            return;
+        };
+        let word = item.word.clone();
+        self.buffer.rows[self.cy].content.replace_range(comp.start..self.cx, &word);
+        self.cx = comp.start + word.len();
+        self.buffer.touch();
+        self.status_msg = format!("match {} of {}", comp.selected + 1, comp.items.len());
+    }
+
+    // 서버의 후보들이 오면 버퍼 단어와 합침 (그 사이 완성을 끝냈으면 버림)
+    pub(crate) fn merge_lsp_completion(&mut self, result: &Json) {
+        let cy = self.cy;
+        let Some(comp) = self.completion.as_mut().filter(|c| c.line == cy) else { return };
+        let mut items = lsp_items(result, &comp.original);
+        if items.is_empty() {
+            if comp.items.is_empty() {
+                self.completion = None;
+                self.status_msg = "No completions".into();
+            }
+            return;
+        }
+        let current = comp.current().map(|i| i.word.clone());
+        let words: Vec<Item> = std::mem::take(&mut comp.items).into_iter().filter(|w| !items.iter().any(|i| i.word == w.word)).collect();
+        items.extend(words);
+        comp.items = items;
+        comp.selected = match (comp.moved, current) {
+            (true, Some(word)) => comp.items.iter().position(|i| i.word == word).unwrap_or(0),
+            _ if comp.forward => 0,
+            _ => comp.items.len() - 1,
+        };
+        self.apply_completion();
+    }
+
+    // 완성을 확정: 서버의 후보면 추가 편집을 (없으면 resolve 로 물어서) 적용
+    pub(crate) fn accept_completion(&mut self) {
+        let Some(comp) = self.completion.take() else { return };
+        let Some(item) = comp.current().and_then(|i| i.lsp.as_ref()) else { return };
+        if item.get("additionalTextEdits").is_some() {
+            self.apply_additional_edits(item);
+            return;
+        }
+        let Json::Object(fields) = item else { return };
+        let params = fields.iter().map(|(k, v)| (k.as_str(), v.clone())).collect();
+        self.lsp_request(Request::ResolveCompletion, params);
+    }
+
+    // resolve 응답, 확정한 뒤 버퍼를 더 고쳤으면 편집 위치가 어긋날 수 있으니 버림
+    pub(crate) fn apply_resolved_completion(&mut self, version: u64, item: &Json) {
+        if version != self.buffer.version {
+            self.status_msg = "Buffer changed, extra edits skipped".into();
+            return;
+        }
+        self.apply_additional_edits(item);
+    }
+
+    // 확정한 후보의 additionalTextEdits, 커서보다 위의 줄 수가 바뀌면 커서도 옮김
+    fn apply_additional_edits(&mut self, item: &Json) {
+        let edits = item.get("additionalTextEdits").map(lsp::text_edits).unwrap_or_default();
+        if edits.is_empty() {
+            return;
+        }
+        let lines: Vec<String> = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+        let shift: isize = edits
+            .iter()
+            .filter(|e| e.end.0 < self.cy)
+            .map(|e| e.text.matches('\n').count() as isize - (e.end.0 - e.start.0) as isize)
+            .sum();
+        self.buffer.rows = lsp::apply_edits(&lines, &edits).into_iter().map(Row::new).collect();
+        self.buffer.touch();
+        self.cy = self.cy.saturating_add_signed(shift);
+        self.clamp_cursor();
+    }
+}
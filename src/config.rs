@@ -0,0 +1,31 @@
+// --- Config File (viirc) ---
+// 설정 파일의 각 줄은 ':' 없이 쓴 명령어 한 줄 (예: lsp rust rust-analyzer)
+use std::env;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+// $XDG_CONFIG_HOME/vii 또는 ~/.config/vii
+pub fn config_dir() -> Option<PathBuf> {
+    if let Ok(dir) = env::var("XDG_CONFIG_HOME") && !dir.is_empty() {
+        return Some(PathBuf::from(dir).join("vii"));
+    }
+    let home = env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config").join("vii"))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("viirc"))
+}
+
+// 빈 줄과 주석(" 또는 #)을 제외한 명령어 목록
+pub fn load() -> Vec<String> {
+    let Some(content) = config_path().and_then(|p| read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('"') && !l.starts_with('#'))
+        .map(|l| l.trim_start_matches(':').to_string())
+        .collect()
+}
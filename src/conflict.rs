@@ -0,0 +1,77 @@
+// --- Merge Conflicts ---
+// <<<<<<< / ======= / >>>>>>> 표시로 둘러싸인 충돌 구간을 찾고 해결
+
+pub struct Conflict {
+    pub start: usize,        // <<<<<<< 줄
+    pub base: Option<usize>, // ||||||| 줄 (diff3 형식일 때)
+    pub sep: usize,          // ======= 줄
+    pub end: usize,          // >>>>>>> 줄
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Region {
+    Marker,
+    Ours,
+    Base,
+    Theirs,
+}
+
+#[derive(Clone, Copy)]
+pub enum Choice {
+    Ours,
+    Theirs,
+    Both,
+}
+
+impl Conflict {
+    pub fn contains(&self, line: usize) -> bool {
+        (self.start..=self.end).contains(&line)
+    }
+
+    pub fn region(&self, line: usize) -> Region {
+        let ours_end = self.base.unwrap_or(self.sep);
+        if line == self.start || line == self.sep || line == self.end || Some(line) == self.base {
+            Region::Marker
+        } else if line < ours_end {
+            Region::Ours
+        } else if line < self.sep {
+            Region::Base
+        } else {
+            Region::Theirs
+        }
+    }
+
+    // 선택한 쪽의 줄 목록
+    pub fn resolve(&self, lines: &[&str], choice: Choice) -> Vec<String> {
+        let ours = &lines[self.start + 1..self.base.unwrap_or(self.sep)];
+        let theirs = &lines[self.sep + 1..self.end];
+        let chosen: Vec<&str> = match choice {
+            Choice::Ours => ours.to_vec(),
+            Choice::Theirs => theirs.to_vec(),
+            Choice::Both => ours.iter().chain(theirs).copied().collect(),
+        };
+        chosen.into_iter().map(|l| l.to_string()).collect()
+    }
+}
+
+pub fn find(lines: &[&str]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    let mut start = None;
+    let mut base = None;
+    let mut sep = None;
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            (start, base, sep) = (Some(i), None, None);
+        } else if line.starts_with("|||||||") && start.is_some() && sep.is_none() {
+            base = Some(i);
+        } else if *line == "=======" && start.is_some() {
+            sep = Some(i);
+        } else if line.starts_with(">>>>>>>") {
+            if let (Some(start), Some(sep)) = (start, sep) {
+                conflicts.push(Conflict { start, base, sep, end: i });
+            }
+            (start, base, sep) = (None, None, None);
+        }
+    }
+    conflicts
+}
@@ -0,0 +1,123 @@
+// --- Diagnostics List ---
+// 지금 버퍼의 진단들: 언어 서버가 보낸 것과 마지막 :make 출력에서 이 파일을 가리키는 것을 줄 순서로 모음
+//   ]d / [d          다음 / 이전 진단으로 (끝에서는 반대쪽 끝으로), 메시지는 상태 줄에
+//   :Diagnostics     진단 목록 창을 열거나 닫음 (Enter 로 그 자리로, 진단이 바뀌면 목록도 바뀜)
+// :make 의 메시지가 warning / note 로 시작하면 경고 / 정보, 나머지는 오류로 침
+// :make 의 열 번호는 바이트라서 파일의 그 줄을 읽어 LSP 와 같은 UTF-16 글자 번호로 바꿔 둠
+use crate::lsp::{self, Diagnostic, Severity};
+use crate::quickfix::QuickfixEntry;
+use crate::{EditorConfig, PanelKind};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// :make 결과를 파일(절대 경로)마다의 진단으로
+pub fn from_make(entries: &[QuickfixEntry]) -> BTreeMap<PathBuf, Vec<Diagnostic>> {
+    let mut files: BTreeMap<PathBuf, Vec<Diagnostic>> = BTreeMap::new();
+    let mut contents: BTreeMap<PathBuf, String> = BTreeMap::new();
+    for entry in entries {
+        let message = entry.text.trim().to_string();
+        let lower = message.to_lowercase();
+        let severity = if lower.starts_with("warning") {
+            Severity::Warning
+        } else if lower.starts_with("note") || lower.starts_with("info") {
+            Severity::Info
+        } else {
+            Severity::Error
+        };
+        let path = fs::canonicalize(&entry.filename).unwrap_or_else(|_| entry.filename.clone().into());
+        let content = contents.entry(path.clone()).or_insert_with(|| fs::read_to_string(&path).unwrap_or_default());
+        let line = content.lines().nth(entry.line.saturating_sub(1)).unwrap_or("");
+        let col = lsp::to_character(line, entry.col.saturating_sub(1));
+        files.entry(path).or_default().push(Diagnostic { line: entry.line - 1, col, severity, message });
+    }
+    files
+}
+
+impl EditorConfig {
+    pub(crate) fn refresh_canonical(&mut self) {
+        self.canonical = self.filename.as_ref().and_then(|name| fs::canonicalize(name).ok());
+    }
+
+    // 지금 버퍼의 진단들 (정렬하지 않음, 줄마다 그릴 때처럼 자주 부르는 곳에서)
+    pub(crate) fn diagnostics_iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        let from_lsp = self.filename.as_deref().map_or(&[][..], |name| self.lsp.diagnostics(name));
+        let made = self.canonical.as_ref().and_then(|path| self.make_diagnostics.get(path));
+        from_lsp.iter().chain(made.into_iter().flatten())
+    }
+
+    /// 지금 버퍼의 진단들 (줄, 열 순서)
+    pub(crate) fn diagnostics(&self) -> Vec<&Diagnostic> {
+        let mut list: Vec<&Diagnostic> = self.diagnostics_iter().collect();
+        list.sort_by_key(|d| (d.line, d.col));
+        list
+    }
+
+    // 진단의 버퍼 안 위치 (줄, 바이트 열)
+    fn diagnostic_position(&self, diagnostic: &Diagnostic) -> (usize, usize) {
+        let line = diagnostic.line.min(self.buffer.rows.len() - 1);
+        (line, lsp::to_byte(&self.buffer.rows[line].content, diagnostic.col))
+    }
+
+    // ]d / [d
+    pub(crate) fn jump_diagnostic(&mut self, forward: bool) {
+        let positions: Vec<(usize, usize)> = self.diagnostics().into_iter().map(|d| self.diagnostic_position(d)).collect();
+        if positions.is_empty() {
+            self.error("No diagnostics");
+            return;
+        }
+        let cursor = (self.cy, self.cx);
+        let target = match forward {
+            true => positions.iter().find(|&&p| p > cursor).or(positions.first()),
+            false => positions.iter().rev().find(|&&p| p < cursor).or(positions.last()),
+        };
+        if let Some(&(line, col)) = target {
+            (self.cy, self.cx) = (line, col);
+            self.clamp_cursor();
+        }
+        // 상태 줄은 메시지가 없을 때만 진단을 보여주므로 지난 메시지를 지움
+        self.status_msg.clear();
+    }
+
+    fn diagnostic_lines(&self) -> Vec<String> {
+        self.diagnostics()
+            .iter()
+            .map(|d| format!("{:>4}:{:<3} {}  {}", d.line + 1, d.col + 1, d.severity.sign(), d.message.lines().next().unwrap_or("")))
+            .collect()
+    }
+
+    // :Diagnostics
+    pub(crate) fn toggle_diagnostics_panel(&mut self) {
+        if self.panel.as_ref().is_some_and(|p| p.kind == PanelKind::Diagnostics) {
+            self.close_panel();
+            return;
+        }
+        let title = self.filename.clone().unwrap_or_else(|| "[No Name]".into());
+        let lines = self.diagnostic_lines();
+        self.open_panel(PanelKind::Diagnostics, title, lines, 0);
+    }
+
+    // 열려 있는 진단 목록 창을 지금 진단들로 바꿈, 바뀌었으면 true
+    pub(crate) fn refresh_diagnostics_panel(&mut self) -> bool {
+        if self.panel.as_ref().is_none_or(|p| p.kind != PanelKind::Diagnostics) {
+            return false;
+        }
+        let lines = self.diagnostic_lines();
+        let title = self.filename.clone().unwrap_or_else(|| "[No Name]".into());
+        let Some(panel) = self.panel.as_mut() else { return false };
+        if panel.lines == lines && panel.title == title {
+            return false;
+        }
+        panel.selected = panel.selected.min(lines.len().saturating_sub(1));
+        (panel.lines, panel.title) = (lines, title);
+        true
+    }
+
+    // 목록 창의 Enter
+    pub(crate) fn jump_to_listed_diagnostic(&mut self, selected: usize) {
+        let Some(&diagnostic) = self.diagnostics().get(selected) else { return };
+        (self.cy, self.cx) = self.diagnostic_position(diagnostic);
+        self.clamp_cursor();
+        self.panel_focused = false;
+    }
+}
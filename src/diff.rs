@@ -0,0 +1,126 @@
+// --- Line Diff ---
+// Myers 알고리즘으로 두 줄 목록 사이의 편집 과정을 구함
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Edit {
+    Equal,
+    Delete, // 이전 쪽에만 있는 줄
+    Insert, // 새 쪽에만 있는 줄
+}
+
+// 연속된 변경 구간 (0부터 시작하는 줄 번호)
+pub struct Hunk {
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+}
+
+// 편집 거리가 이보다 크면 정밀한 비교를 포기하고 통째로 바뀐 것으로 본다
+const MAX_EDIT_DISTANCE: usize = 4000;
+
+pub fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    // 앞뒤의 공통 부분은 미리 잘라내서 비교 범위를 줄임
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut edits = vec![Edit::Equal; prefix];
+    edits.extend(myers(mid_a, mid_b));
+    edits.extend(vec![Edit::Equal; suffix]);
+    edits
+}
+
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m) as usize;
+    let off = max as isize + 1;
+    let mut v = vec![0isize; 2 * max + 3];
+    // 각 단계 시작 시점의 v[-d..=d]
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max.min(MAX_EDIT_DISTANCE) as isize {
+        trace.push(v[(off - d) as usize..=(off + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (off + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) { v[i + 1] } else { v[i - 1] + 1 };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                return backtrack(&trace, n, m);
+            }
+        }
+    }
+
+    // 너무 다르면 전부 지우고 새로 넣은 것으로 처리
+    let mut edits = vec![Edit::Delete; a.len()];
+    edits.extend(vec![Edit::Insert; b.len()]);
+    edits
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        if d == 0 {
+            while x > 0 && y > 0 {
+                edits.push(Edit::Equal);
+                x -= 1;
+                y -= 1;
+            }
+            break;
+        }
+        let k = x - y;
+        let at = |k: isize| v[(k + d) as usize];
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) { k + 1 } else { k - 1 };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        edits.push(if x == prev_x { Edit::Insert } else { Edit::Delete });
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    edits
+}
+
+pub fn hunks(edits: &[Edit]) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut new = 0;
+    let mut in_hunk = false;
+    for edit in edits {
+        match edit {
+            Edit::Equal => {
+                new += 1;
+                in_hunk = false;
+            }
+            Edit::Delete | Edit::Insert => {
+                if !in_hunk {
+                    hunks.push(Hunk { old_len: 0, new_start: new, new_len: 0 });
+                    in_hunk = true;
+                }
+                let hunk = hunks.last_mut().unwrap();
+                if *edit == Edit::Delete {
+                    hunk.old_len += 1;
+                } else {
+                    hunk.new_len += 1;
+                    new += 1;
+                }
+            }
+        }
+    }
+    hunks
+}
@@ -0,0 +1,104 @@
+// --- Side-by-side Diff ---
+// 현재 버퍼(왼쪽)와 다른 파일(오른쪽)을 줄 단위로 맞춰서 보여주기 위한 상태
+use crate::EditorBuffer;
+use crate::diff::{self, Edit};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Same,
+    Changed,
+    Removed, // 왼쪽에만 있는 줄
+    Added,   // 오른쪽에만 있는 줄
+}
+
+// 화면의 한 줄: 양쪽 줄 번호 (None이면 빈 채움 줄)
+pub struct DiffLine {
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+    pub kind: DiffKind,
+}
+
+pub struct DiffView {
+    pub other: EditorBuffer,
+    pub other_name: String,
+    pub lines: Vec<DiffLine>,
+    left_rows: Vec<usize>, // 왼쪽 줄 번호 → 화면 줄 번호
+    version: Option<u64>,  // 계산할 때의 왼쪽 버퍼 버전
+}
+
+impl DiffView {
+    pub fn new(other: EditorBuffer, other_name: String) -> Self {
+        DiffView { other, other_name, lines: Vec::new(), left_rows: Vec::new(), version: None }
+    }
+
+    // 왼쪽 버퍼가 바뀌었으면 정렬을 다시 계산
+    pub fn update(&mut self, left: &EditorBuffer) {
+        if self.version == Some(left.version) {
+            return;
+        }
+        self.version = Some(left.version);
+        let a: Vec<&str> = left.rows.iter().map(|r| r.content.as_str()).collect();
+        let b: Vec<&str> = self.other.rows.iter().map(|r| r.content.as_str()).collect();
+        self.lines = align(&diff::diff(&a, &b));
+        self.left_rows = vec![0; a.len()];
+        for (i, line) in self.lines.iter().enumerate() {
+            if let Some(l) = line.left {
+                self.left_rows[l] = i;
+            }
+        }
+    }
+
+    pub fn display_row(&self, left_line: usize) -> usize {
+        self.left_rows.get(left_line).copied().unwrap_or(left_line)
+    }
+
+    pub fn change_count(&self) -> usize {
+        // 연속된 변경 줄은 하나로 셈
+        let mut count = 0;
+        let mut prev = DiffKind::Same;
+        for line in &self.lines {
+            if line.kind != DiffKind::Same && prev == DiffKind::Same {
+                count += 1;
+            }
+            prev = line.kind;
+        }
+        count
+    }
+}
+
+fn align(edits: &[Edit]) -> Vec<DiffLine> {
+    let mut lines = Vec::new();
+    let (mut a, mut b) = (0, 0);
+    let mut i = 0;
+    while i < edits.len() {
+        if edits[i] == Edit::Equal {
+            lines.push(DiffLine { left: Some(a), right: Some(b), kind: DiffKind::Same });
+            a += 1;
+            b += 1;
+            i += 1;
+            continue;
+        }
+        // 변경 구간: 지운 줄과 넣은 줄을 앞에서부터 짝지어 "바뀐 줄"로 표시
+        let (mut removed, mut added) = (Vec::new(), Vec::new());
+        while i < edits.len() && edits[i] != Edit::Equal {
+            if edits[i] == Edit::Delete {
+                removed.push(a);
+                a += 1;
+            } else {
+                added.push(b);
+                b += 1;
+            }
+            i += 1;
+        }
+        for j in 0..removed.len().max(added.len()) {
+            let (left, right) = (removed.get(j).copied(), added.get(j).copied());
+            let kind = match (left, right) {
+                (Some(_), Some(_)) => DiffKind::Changed,
+                (Some(_), None) => DiffKind::Removed,
+                _ => DiffKind::Added,
+            };
+            lines.push(DiffLine { left, right, kind });
+        }
+    }
+    lines
+}
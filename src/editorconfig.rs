@@ -0,0 +1,152 @@
+// --- EditorConfig ---
+// 파일을 열 때 그 디렉터리부터 위로 올라가며 .editorconfig 를 읽어 파일과 맞는 구역의 설정을 버퍼에 적용
+//   indent_style = space | tab          expandtab / noexpandtab
+//   indent_size = 4 | tab               shiftwidth (tab 이면 tabstop 과 같게)
+//   tab_width = 8                       tabstop (없으면 indent_size 를 씀)
+//   end_of_line = lf | crlf             fileformat=unix / dos
+//   trim_trailing_whitespace = true     trimtrailing (저장할 때 줄 끝 공백을 지움)
+//   insert_final_newline = true         fixendofline (저장할 때 마지막 줄 뒤에 줄바꿈)
+// root = true 인 파일에서 멈춤, 가까운 파일과 뒤에 있는 구역이 이김
+// 구역 패턴: * (/ 는 빼고), **, ?, [abc], [!abc], {a,b}; / 가 없는 패턴은 어느 디렉터리든 파일 이름과 맞춤
+// BufReadPost autocmd, 모드 줄, :set 이 그 뒤에 적용됨    set noeditorconfig 로 끔
+use crate::EditorConfig;
+use crate::options::FileFormat;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 한 .editorconfig 파일: root 여부와 (패턴, 설정들) 구역들
+struct File {
+    root: bool,
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+fn parse(text: &str) -> File {
+    let mut file = File { root: false, sections: Vec::new() };
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(pattern) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            file.sections.push((pattern.to_string(), Vec::new()));
+        } else if let Some((key, value)) = line.split_once('=') {
+            let (key, value) = (key.trim().to_ascii_lowercase(), value.trim().to_ascii_lowercase());
+            match file.sections.last_mut() {
+                Some((_, properties)) => properties.push((key, value)),
+                None if key == "root" => file.root = value == "true",
+                None => {}
+            }
+        }
+    }
+    file
+}
+
+// {a,b} 를 풀어 패턴 여러 개로
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else { return vec![pattern.to_string()] };
+    let Some(close) = pattern[open..].find('}').map(|i| open + i) else { return vec![pattern.to_string()] };
+    let (head, tail) = (&pattern[..open], &pattern[close + 1..]);
+    pattern[open + 1..close]
+        .split(',')
+        .flat_map(|alt| expand_braces(&format!("{}{}{}", head, alt, tail)))
+        .collect()
+}
+
+// ** 는 / 를 넘고, * 와 ? 는 / 를 넘지 않음
+fn glob_match(p: &[char], t: &[char]) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some('*') if p.get(1) == Some(&'*') => (0..=t.len()).any(|i| glob_match(&p[2..], &t[i..])),
+        Some('*') => (0..=t.len()).take_while(|&i| i == 0 || t[i - 1] != '/').any(|i| glob_match(&p[1..], &t[i..])),
+        Some('?') => t.first().is_some_and(|&c| c != '/') && glob_match(&p[1..], &t[1..]),
+        Some('[') if p.contains(&']') => {
+            let end = p.iter().position(|&c| c == ']').unwrap_or(0);
+            let (negate, set) = match p[1..end].split_first() {
+                Some(('!', rest)) => (true, rest),
+                _ => (false, &p[1..end]),
+            };
+            t.first().is_some_and(|c| set.contains(c) != negate) && glob_match(&p[end + 1..], &t[1..])
+        }
+        Some(&c) => t.first() == Some(&c) && glob_match(&p[1..], &t[1..]),
+    }
+}
+
+// dir 의 .editorconfig 에 있는 구역 패턴이 path 와 맞는지
+fn section_matches(pattern: &str, dir: &Path, path: &Path) -> bool {
+    let target = if pattern.contains('/') {
+        path.strip_prefix(dir).ok().and_then(|p| p.to_str())
+    } else {
+        path.file_name().and_then(|n| n.to_str())
+    };
+    let Some(target) = target else { return false };
+    let target: Vec<char> = target.chars().collect();
+    let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+    expand_braces(pattern).iter().any(|p| glob_match(&p.chars().collect::<Vec<_>>(), &target))
+}
+
+/// 파일에 적용되는 설정들 (이름과 값은 소문자)
+pub fn properties(filename: &str) -> HashMap<String, String> {
+    let path = Path::new(filename);
+    let path: PathBuf = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map(|d| d.join(path)).unwrap_or_else(|_| path.to_path_buf())
+    };
+    // 가까운 것부터 모은 뒤 먼 것부터 적용
+    let mut files = Vec::new();
+    for dir in path.ancestors().skip(1) {
+        if let Ok(text) = fs::read_to_string(dir.join(".editorconfig")) {
+            let file = parse(&text);
+            let root = file.root;
+            files.push((dir, file));
+            if root {
+                break;
+            }
+        }
+    }
+    let mut properties = HashMap::new();
+    for (dir, file) in files.into_iter().rev() {
+        for (pattern, section) in file.sections {
+            if section_matches(&pattern, dir, &path) {
+                properties.extend(section);
+            }
+        }
+    }
+    properties
+}
+
+impl EditorConfig {
+    pub(crate) fn apply_editorconfig(&mut self, filename: &str) {
+        if !self.options.editorconfig {
+            return;
+        }
+        let properties = properties(filename);
+        let get = |name: &str| properties.get(name).map(String::as_str);
+        let size = |name: &str| get(name).and_then(|v| v.parse::<usize>().ok()).filter(|&n| n > 0);
+        let options = &mut self.buffer_options;
+        match get("indent_style") {
+            Some("space") => options.expandtab = true,
+            Some("tab") => options.expandtab = false,
+            _ => {}
+        }
+        if let Some(width) = size("tab_width").or(size("indent_size")) {
+            options.tabstop = width;
+        }
+        if get("indent_size") == Some("tab") {
+            options.shiftwidth = options.tabstop;
+        } else if let Some(width) = size("indent_size") {
+            options.shiftwidth = width;
+        }
+        match get("end_of_line") {
+            Some("lf") => options.fileformat = FileFormat::Unix,
+            Some("crlf") => options.fileformat = FileFormat::Dos,
+            _ => {}
+        }
+        if let Some(value) = get("trim_trailing_whitespace") {
+            options.trimtrailing = value == "true";
+        }
+        if let Some(value) = get("insert_final_newline") {
+            options.fixendofline = value == "true";
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// --- Errors ---
+// 편집기 작업이 실패한 이유, 호출한 쪽이 self.error 로 상태 줄에 빨갛게 보여줌
+//   저장: 이름이 없음, readonly, 쓰기 실패 (디스크가 가득 참, 권한 없음 등) — 임시 파일에 다 쓴 뒤 바꾸므로 원래 파일은 그대로
+//   열기: 읽을 수 없는 파일 (권한 없음, 디렉터리 등) — 새 파일로 여기지 않고 오류를 보여줌
+use std::{fmt, io};
+
+/// 편집기 작업의 실패
+#[derive(Debug)]
+pub enum Error {
+    /// 저장할 파일 이름이 없음
+    NoFileName,
+    /// readonly 버퍼를 ! 없이 저장하려 함
+    ReadOnly,
+    /// 파일을 읽거나 쓰지 못함 (파일 이름, 원인)
+    Io(String, io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NoFileName => write!(f, "No file name (use :w <file>)"),
+            Error::ReadOnly => write!(f, "readonly option is set (add ! to override)"),
+            Error::Io(path, e) => write!(f, "{}: {}", path, e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,146 @@
+// --- Filetype Detection ---
+// 버퍼를 열 때 파일 종류를 정해 버퍼별 설정 filetype 에 둠 (상태 줄, gc 주석, 약어, LSP, 줄 옮기기의 들여쓰기가 씀)
+// 먼저 맞는 것을 씀:
+//   1. :filetype 패턴 종류 로 더한 규칙 (나중에 더한 것부터)
+//   2. 파일 이름 (Makefile, Dockerfile 등)과 확장자
+//   3. 첫 줄의 #! (#!/usr/bin/env python3 → python)
+//   4. 내용 (<?xml, <!DOCTYPE html 로 시작하는 파일)
+//   :filetype                 지금 버퍼의 종류
+//   :filetype *.tpl html      규칙을 더하고 지금 버퍼를 다시 판별 (/ 가 없는 패턴은 파일 이름만 맞춤)
+//   :set filetype=lua         지금 버퍼의 종류를 직접 바꿈
+use std::path::Path;
+
+pub struct FiletypeRules {
+    rules: Vec<(String, String)>, // (패턴, 종류)
+}
+
+impl FiletypeRules {
+    pub fn new() -> Self {
+        FiletypeRules { rules: Vec::new() }
+    }
+
+    pub fn add(&mut self, pattern: &str, filetype: &str) {
+        self.rules.push((pattern.to_string(), filetype.to_string()));
+    }
+
+    fn find(&self, filename: &str) -> Option<&str> {
+        self.rules.iter().rev().find(|(pattern, _)| matches_pattern(pattern, filename)).map(|(_, filetype)| filetype.as_str())
+    }
+}
+
+/// 파일 패턴과 맞는지, / 가 없는 패턴은 파일 이름만 맞춤 (autocmd 패턴도 사용)
+pub fn matches_pattern(pattern: &str, filename: &str) -> bool {
+    let name = Path::new(filename).file_name().and_then(|n| n.to_str()).unwrap_or(filename);
+    glob_match(pattern, if pattern.contains('/') { filename } else { name })
+}
+
+// * 는 아무 글자들, ? 는 글자 하나
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    // 마지막 * 의 위치와 그때 맞춘 글 위치로 되돌아가며 맞춤
+    let (mut i, mut j, mut star) = (0, 0, None);
+    while j < t.len() {
+        match p.get(i) {
+            Some('*') => {
+                star = Some((i, j));
+                i += 1;
+            }
+            Some(&c) if c == '?' || c == t[j] => {
+                i += 1;
+                j += 1;
+            }
+            _ => match star {
+                Some((si, sj)) => {
+                    (i, j) = (si + 1, sj + 1);
+                    star = Some((si, sj + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[i..].iter().all(|&c| c == '*')
+}
+
+// 파일 이름과 확장자로 파일 종류를 판별 (LSP languageId와 같은 이름 사용)
+fn detect(filename: &str) -> Option<&'static str> {
+    let name = Path::new(filename).file_name()?.to_str()?;
+    let filetype = match name {
+        "Makefile" | "makefile" | "GNUmakefile" => return Some("make"),
+        "Dockerfile" => return Some("dockerfile"),
+        ".bashrc" | ".bash_profile" | ".profile" | ".zshrc" => return Some("sh"),
+        _ => Path::new(name).extension()?.to_str()?,
+    };
+    let filetype = match filetype {
+        "rs" => "rust",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "py" => "python",
+        "go" => "go",
+        "js" | "mjs" => "javascript",
+        "ts" => "typescript",
+        "lua" => "lua",
+        "sh" | "bash" | "zsh" => "sh",
+        "toml" => "toml",
+        "json" => "json",
+        "md" => "markdown",
+        "yaml" | "yml" => "yaml",
+        "html" | "htm" => "html",
+        "xml" => "xml",
+        "mk" => "make",
+        _ => return None,
+    };
+    Some(filetype)
+}
+
+// #! 줄의 실행 파일 (env 뒤의 이름도)
+fn detect_shebang(line: &str) -> Option<&'static str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = Path::new(words.next()?).file_name()?.to_str()?;
+    if program == "env" {
+        program = words.find(|w| !w.starts_with('-'))?;
+    }
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    Some(match program {
+        "python" => "python",
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => "sh",
+        "node" => "javascript",
+        "lua" | "luajit" => "lua",
+        _ => return None,
+    })
+}
+
+fn detect_content(first_line: &str) -> Option<&'static str> {
+    let line = first_line.trim_start();
+    if line.starts_with("<?xml") {
+        Some("xml")
+    } else if line.to_ascii_lowercase().starts_with("<!doctype html") || line.starts_with("<html") {
+        Some("html")
+    } else {
+        None
+    }
+}
+
+/// 규칙, 파일 이름, #! 줄, 내용 순서로 파일 종류를 정함
+pub fn detect_buffer(rules: &FiletypeRules, filename: &str, first_line: &str) -> Option<String> {
+    if let Some(filetype) = rules.find(filename) {
+        return Some(filetype.to_string());
+    }
+    detect(filename).or_else(|| detect_shebang(first_line)).or_else(|| detect_content(first_line)).map(String::from)
+}
+
+/// 줄 주석 기호 (gc), 모르는 종류는 #
+pub fn comment_prefix(filetype: Option<&str>) -> &'static str {
+    match filetype {
+        Some("rust" | "c" | "cpp" | "go" | "javascript" | "typescript") => "//",
+        Some("lua") => "--",
+        _ => "#",
+    }
+}
+
+/// 줄 끝에 오면 블록을 여는 글자들 (줄 옮길 때 들여쓰기), : 는 모르는 종류와 python, yaml 만
+pub fn block_openers(filetype: Option<&str>) -> &'static [char] {
+    match filetype {
+        None | Some("python" | "yaml") => &['{', '(', '[', ':'],
+        Some(_) => &['{', '(', '['],
+    }
+}
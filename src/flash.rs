@@ -0,0 +1,38 @@
+// --- Yank Flash ---
+// yy, yw 로 복사한 부분을 잠깐 (150ms) 반전해서 무엇이 복사됐는지 보여줌
+// 그동안은 이벤트 루프가 키를 기다리지 않고 프레임을 그려서, 시간이 지나면 키를 치지 않아도 사라짐
+use crate::EditorConfig;
+use std::time::{Duration, Instant};
+
+const DURATION: Duration = Duration::from_millis(150);
+
+pub struct Flash {
+    lines: (usize, usize),            // 처음과 마지막 줄
+    columns: Option<(usize, usize)>,  // 한 줄 안의 바이트 범위, None 이면 줄 전체
+    since: Instant,
+}
+
+impl EditorConfig {
+    // start..=end 줄, columns 가 있으면 그 줄의 그 범위만
+    pub(crate) fn flash(&mut self, start: usize, end: usize, columns: Option<(usize, usize)>) {
+        self.flash = Some(Flash { lines: (start, end), columns, since: Instant::now() });
+    }
+
+    fn flash_visible(&self) -> Option<&Flash> {
+        self.flash.as_ref().filter(|f| f.since.elapsed() < DURATION)
+    }
+
+    /// line 줄에서 반전할 바이트 범위 (화면 그릴 때)
+    pub(crate) fn flashed_columns(&self, line: usize) -> Option<(usize, usize)> {
+        let flash = self.flash_visible()?;
+        if line < flash.lines.0 || line > flash.lines.1 {
+            return None;
+        }
+        Some(flash.columns.unwrap_or((0, self.buffer.rows.get(line)?.content.len() + 1)))
+    }
+
+    /// 시간이 지나면 화면이 바뀌는 중인지 (smoothscroll, 복사 표시), 그동안 이벤트 루프는 입력 없이도 다음 프레임을 그림
+    pub fn is_animating(&self) -> bool {
+        self.is_scrolling() || self.flash_visible().is_some()
+    }
+}
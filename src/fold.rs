@@ -0,0 +1,265 @@
+// --- Folds ---
+// 여러 줄을 접어 화면에 요약 줄 하나로 보여줌 (왼쪽 접기 열에 + 닫힘, - 열림, │ 안쪽)
+//   zfj / zfk / zf'a   커서 줄부터 아래 줄 / 위 줄 / 마크 a의 줄까지 접기를 만듦
+//   zF                 커서 줄만 접음        :{범위}fold  범위의 줄들을 접음
+//   비주얼 모드의 zf   선택한 줄들을 접음
+//   zo / zc / za       열기 / 닫기 / 뒤집기   zR / zM  모두 열기 / 모두 닫기
+//   zd / zE            커서의 접기 / 모든 접기를 지움
+// 접기는 겹치지 않거나 완전히 안에 들어가야 함 (vim과 같이 안쪽 접기를 따로 열고 닫음)
+// :set foldmethod=indent 이면 들여쓰기가 shiftwidth만큼 깊어지는 줄들이 한 단계의 접기가 되고
+// 버퍼가 바뀔 때마다 다시 계산함 (처음엔 모두 닫힘, 이후 새로 생긴 접기는 열림)
+use crate::options::FoldMethod;
+use crate::{EditorConfig, Row};
+
+#[derive(Clone, Copy)]
+struct Fold {
+    start: usize, // 0부터, end 포함
+    end: usize,
+    closed: bool,
+}
+
+impl Fold {
+    fn contains(&self, line: usize) -> bool {
+        self.start <= line && line <= self.end
+    }
+}
+
+pub struct Folds {
+    folds: Vec<Fold>,             // start 순, 같으면 바깥 것이 먼저
+    indent_version: Option<u64>, // 들여쓰기 접기를 계산한 버퍼 버전
+    indent_lines: usize,         // 그때의 줄 수
+}
+
+impl Folds {
+    pub fn new() -> Self {
+        Folds { folds: Vec::new(), indent_version: None, indent_lines: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.folds.is_empty()
+    }
+
+    // 새 접기는 닫힌 채로 만듦, 걸쳐 있는 접기가 있으면 만들지 않음
+    fn create(&mut self, start: usize, end: usize) -> bool {
+        let crosses = |f: &Fold| (f.start < start && start <= f.end && f.end < end) || (start < f.start && f.start <= end && end < f.end);
+        if self.folds.iter().any(crosses) {
+            return false;
+        }
+        match self.folds.iter_mut().find(|f| f.start == start && f.end == end) {
+            Some(fold) => fold.closed = true,
+            None => {
+                self.folds.push(Fold { start, end, closed: true });
+                self.folds.sort_by_key(|f| (f.start, usize::MAX - f.end));
+            }
+        }
+        true
+    }
+
+    // 보이는 (가장 바깥의) 닫힌 접기를 엶
+    fn open(&mut self, line: usize) -> bool {
+        self.folds.iter_mut().find(|f| f.closed && f.contains(line)).map(|f| f.closed = false).is_some()
+    }
+
+    // 열린 접기 중 가장 안쪽을 닫음
+    fn close(&mut self, line: usize) -> bool {
+        self.folds.iter_mut().rev().find(|f| !f.closed && f.contains(line)).map(|f| f.closed = true).is_some()
+    }
+
+    // 보이는 접기를 지움: 닫혀 있으면 그 접기, 아니면 가장 안쪽
+    fn delete(&mut self, line: usize) -> bool {
+        let index = match self.folds.iter().position(|f| f.closed && f.contains(line)) {
+            Some(i) => Some(i),
+            None => self.folds.iter().rposition(|f| f.contains(line)),
+        };
+        index.map(|i| self.folds.remove(i)).is_some()
+    }
+
+    // 들여쓰기 단계가 n 이상인 연속된 줄들이 n단계 접기, 빈 줄은 위아래 중 얕은 쪽을 따름
+    fn compute_indent(&mut self, rows: &[Row], cursor: usize, shiftwidth: usize) {
+        let indent = |row: &Row| {
+            let width: usize = row.content.chars().take_while(|c| c.is_whitespace()).map(|c| if c == '\t' { shiftwidth } else { 1 }).sum();
+            (!row.content.trim().is_empty()).then_some(width / shiftwidth)
+        };
+        let levels: Vec<Option<usize>> = rows.iter().map(indent).collect();
+        let mut starts: Vec<usize> = Vec::new(); // 단계마다 접기가 시작된 줄
+        let mut folds = Vec::new();
+        for i in 0..=levels.len() {
+            let level = match levels.get(i) {
+                Some(Some(level)) => *level,
+                Some(None) => {
+                    let above = levels[..i].iter().rev().find_map(|l| *l).unwrap_or(0);
+                    let below = levels[i..].iter().find_map(|l| *l).unwrap_or(0);
+                    above.min(below)
+                }
+                None => 0, // 끝에서 남은 접기를 모두 닫음
+            };
+            while starts.len() > level {
+                let start = starts.pop().unwrap();
+                folds.push(Fold { start, end: i - 1, closed: true });
+            }
+            while starts.len() < level {
+                starts.push(i);
+            }
+        }
+        // 다시 계산할 때는 같은 줄에서 시작하는 접기의 열림 상태를 이어받음,
+        // 편집한 줄 (커서 줄, 줄이 늘었으면 그만큼 위) 아래의 접기는 늘거나 준 줄 수만큼 옮겨서 맞춤
+        if self.indent_version.is_some() {
+            let delta = rows.len() as isize - self.indent_lines as isize;
+            let edited = cursor.saturating_sub(delta.max(0) as usize);
+            let moved = |f: &Fold| if f.start > edited { f.start.saturating_add_signed(delta) } else { f.start };
+            for fold in folds.iter_mut() {
+                fold.closed = self.folds.iter().any(|f| moved(f) == fold.start && f.closed);
+            }
+        }
+        folds.sort_by_key(|f| (f.start, usize::MAX - f.end));
+        self.folds = folds;
+        self.indent_lines = rows.len();
+    }
+
+    /// 줄이 지워져 버퍼가 짧아지면 끝을 넘는 접기를 맞춤
+    pub fn clamp(&mut self, line_count: usize) {
+        self.folds.retain(|f| f.start < line_count);
+        self.folds.iter_mut().for_each(|f| f.end = f.end.min(line_count - 1));
+    }
+
+    /// 줄을 가리는 가장 바깥의 닫힌 접기 (start, end)
+    pub fn closed_at(&self, line: usize) -> Option<(usize, usize)> {
+        self.folds.iter().find(|f| f.closed && f.contains(line)).map(|f| (f.start, f.end))
+    }
+
+    // 화면에서 접혀 있는 구간들 (가장 바깥 것만, start 순)
+    fn closed_ranges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut covered = None;
+        self.folds.iter().filter(|f| f.closed).filter_map(move |f| {
+            if covered.is_some_and(|end| f.start <= end) {
+                return None;
+            }
+            covered = Some(f.end);
+            Some((f.start, f.end))
+        })
+    }
+
+    /// 버퍼 줄이 화면에서 몇 번째 줄인지 (닫힌 접기 안이면 요약 줄의 위치)
+    pub fn display_row(&self, line: usize) -> usize {
+        let mut row = line;
+        for (start, end) in self.closed_ranges() {
+            if end < line {
+                row -= end - start;
+            } else if start <= line {
+                row -= line - start;
+                break;
+            }
+        }
+        row
+    }
+
+    /// 화면의 줄이 보여주는 버퍼 줄 (접혀 있으면 첫 줄), 버퍼 끝 너머도 이어서 셈
+    pub fn line_at(&self, row: usize) -> usize {
+        let mut line = row;
+        for (start, end) in self.closed_ranges() {
+            if start < line {
+                line += end - start;
+            } else {
+                break;
+            }
+        }
+        line
+    }
+
+    /// 왼쪽 접기 열에 보일 표시
+    pub fn sign(&self, line: usize) -> char {
+        if self.closed_at(line).is_some() {
+            return '+';
+        }
+        match self.folds.iter().rev().find(|f| f.contains(line)) {
+            Some(f) if f.start == line => '-',
+            Some(_) => '│',
+            None => ' ',
+        }
+    }
+}
+
+impl EditorConfig {
+    // 화면을 그리기 전에 접기를 버퍼에 맞춤
+    pub(crate) fn update_folds(&mut self) {
+        match self.window.options.foldmethod {
+            FoldMethod::Indent if self.folds.indent_version != Some(self.buffer.version) => {
+                self.folds.compute_indent(&self.buffer.rows, self.cy, self.buffer_options.shiftwidth);
+                self.folds.indent_version = Some(self.buffer.version);
+            }
+            FoldMethod::Indent => {}
+            FoldMethod::Manual => {
+                self.folds.indent_version = None;
+                self.folds.clamp(self.buffer.rows.len());
+            }
+        }
+    }
+
+    // 두 줄(0부터) 사이를 접음, 순서는 상관없음
+    pub(crate) fn create_fold(&mut self, a: usize, b: usize) {
+        if self.window.options.foldmethod != FoldMethod::Manual {
+            self.error("Cannot create a fold with foldmethod=indent");
+            return;
+        }
+        let last = self.buffer.rows.len() - 1;
+        let (start, end) = (a.min(b).min(last), a.max(b).min(last));
+        if !self.folds.create(start, end) {
+            self.error("Cannot create a fold that crosses another one");
+            return;
+        }
+        self.cy = start;
+        self.clamp_cursor();
+    }
+
+    // 비주얼 모드의 zf
+    pub(crate) fn fold_selection(&mut self) {
+        let Some(((start, _), (end, _))) = self.selection_bounds() else { return };
+        self.end_visual();
+        self.create_fold(start, end);
+    }
+
+    // zf 다음의 움직임으로 접을 범위를 정함 (j, k)
+    pub(crate) fn fold_motion(&mut self, motion: char) {
+        match motion {
+            'j' => self.create_fold(self.cy, self.cy + 1),
+            'k' => self.create_fold(self.cy.saturating_sub(1), self.cy),
+            _ => {}
+        }
+    }
+
+    // zf'a / zf`a: 커서 줄부터 마크의 줄까지
+    pub(crate) fn fold_to_mark(&mut self, name: char) {
+        match self.marks.get(&name) {
+            Some(&(line, _)) => self.create_fold(self.cy, line),
+            None => self.error(format!("Mark not set: {}", name)),
+        }
+    }
+
+    // zo, zc, za, zR, zM, zd, zE
+    pub(crate) fn fold_command(&mut self, key: char) {
+        let line = self.cy;
+        let folds = &mut self.folds;
+        let found = match key {
+            'o' => folds.open(line),
+            'c' => folds.close(line),
+            'a' => folds.open(line) || folds.close(line),
+            'R' | 'M' => {
+                folds.folds.iter_mut().for_each(|f| f.closed = key == 'M');
+                true
+            }
+            'd' | 'E' if self.window.options.foldmethod != FoldMethod::Manual => {
+                self.status_msg = "Cannot delete a fold with foldmethod=indent".into();
+                return;
+            }
+            'd' => folds.delete(line),
+            'E' => {
+                folds.folds.clear();
+                true
+            }
+            _ => true,
+        };
+        if !found {
+            self.status_msg = "No fold found".into();
+        }
+    }
+}
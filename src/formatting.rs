@@ -0,0 +1,58 @@
+// --- LSP Formatting ---
+// 언어 서버에 맡겨 코드 모양을 맞춤 (:lsp 로 서버를 등록한 파일 종류에서)
+//   :Format      버퍼 전체 (textDocument/formatting)
+//   ==  3==      커서 줄부터 횟수만큼의 줄 (textDocument/rangeFormatting)
+//   비주얼 모드 =  고른 줄들
+// 들여쓰기는 shiftwidth 칸, expandtab 이면 공백으로 해 달라고 함
+// 요청한 뒤 버퍼를 고쳤으면 돌아온 편집은 버림 (u 로 한 번에 되돌릴 수 있음)
+use crate::json::Json;
+use crate::lsp::{self, Request};
+use crate::{EditorConfig, Row};
+
+impl EditorConfig {
+    fn formatting_options(&self) -> (&'static str, Json) {
+        let options = Json::obj(vec![
+            ("tabSize", (self.buffer_options.shiftwidth as i64).into()),
+            ("insertSpaces", Json::Bool(self.buffer_options.expandtab)),
+        ]);
+        ("options", options)
+    }
+
+    // :Format
+    pub(crate) fn lsp_format(&mut self) {
+        let params = vec![self.formatting_options()];
+        if self.lsp_request(Request::Formatting, params) {
+            self.status_msg = "Formatting...".into();
+        }
+    }
+
+    // = : start..end 줄 (끝은 제외)
+    pub(crate) fn lsp_format_lines(&mut self, start: usize, end: usize) {
+        let last = &self.buffer.rows[end - 1].content;
+        let range = Json::obj(vec![("start", lsp::position(start, 0)), ("end", lsp::position(end - 1, lsp::to_character(last, last.len())))]);
+        let params = vec![("range", range), self.formatting_options()];
+        if self.lsp_request(Request::RangeFormatting, params) {
+            self.status_msg = "Formatting...".into();
+        }
+    }
+
+    // 서버가 돌려준 TextEdit[] 를 지금 버퍼에 적용
+    pub(crate) fn apply_formatting(&mut self, version: u64, result: &Json) {
+        if version != self.buffer.version {
+            self.error("Buffer changed while formatting, try again");
+            return;
+        }
+        let edits = lsp::text_edits(result);
+        let lines: Vec<String> = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+        let formatted = lsp::apply_edits(&lines, &edits);
+        if formatted == lines {
+            self.status_msg = "Already formatted".into();
+            return;
+        }
+        let changed = (0..lines.len().max(formatted.len())).filter(|&i| lines.get(i) != formatted.get(i)).count();
+        self.buffer.rows = formatted.into_iter().map(Row::new).collect();
+        self.buffer.touch();
+        self.clamp_cursor();
+        self.status_msg = format!("Formatted, {} line{} changed", changed, if changed == 1 { "" } else { "s" });
+    }
+}
@@ -0,0 +1,270 @@
+// --- Git Integration ---
+use crate::diff;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+// 파일이 있는 디렉터리에서 git 명령을 실행하고 표준 출력을 돌려줌
+fn git_in(filename: &str, args: &[&str]) -> Option<String> {
+    let path = Path::new(filename);
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let output = Command::new("git").arg("-C").arg(dir).args(args).stderr(Stdio::null()).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// 파일이 속한 저장소의 현재 브랜치 (.git/HEAD를 직접 읽음), 분리된 HEAD면 커밋 앞 7글자
+pub fn branch(filename: Option<&str>) -> Option<String> {
+    let start = match filename.map(Path::new).and_then(|p| p.parent()) {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => std::env::current_dir().ok()?,
+    };
+    let start = start.canonicalize().unwrap_or(start);
+    for dir in start.ancestors() {
+        let dot_git = dir.join(".git");
+        // 워크트리나 서브모듈에서는 .git이 "gitdir: 경로"를 담은 파일
+        let git_dir = if dot_git.is_file() {
+            let content = fs::read_to_string(&dot_git).ok()?;
+            dir.join(content.strip_prefix("gitdir:")?.trim())
+        } else if dot_git.is_dir() {
+            dot_git
+        } else {
+            continue;
+        };
+        let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+        let head = head.trim();
+        return Some(match head.strip_prefix("ref: ") {
+            Some(r) => r.strip_prefix("refs/heads/").unwrap_or(r).to_string(),
+            None => head.chars().take(7).collect(),
+        });
+    }
+    None
+}
+
+fn file_name(filename: &str) -> Option<String> {
+    Some(Path::new(filename).file_name()?.to_string_lossy().to_string())
+}
+
+// 인덱스(스테이징 영역)에 있는 파일 내용
+fn index_contents(filename: &str) -> Option<Vec<String>> {
+    let spec = format!(":./{}", file_name(filename)?);
+    let content = git_in(filename, &["show", &spec])?;
+    Some(content.lines().map(|l| l.to_string()).collect())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum GitSign {
+    Added,
+    Modified,
+    Removed, // 이 줄 아래에서 줄이 지워짐
+}
+
+impl GitSign {
+    pub fn sign(self) -> char {
+        match self {
+            GitSign::Added => '+',
+            GitSign::Modified => '~',
+            GitSign::Removed => '_',
+        }
+    }
+}
+
+fn compute_signs(index: &[String], lines: &[String]) -> Vec<Option<GitSign>> {
+    let mut signs = vec![None; lines.len()];
+    for hunk in diff::hunks(&diff::diff(index, lines)) {
+        let changed = hunk.old_len.min(hunk.new_len);
+        for i in 0..hunk.new_len {
+            signs[hunk.new_start + i] = Some(if i < changed { GitSign::Modified } else { GitSign::Added });
+        }
+        if hunk.new_len == 0 && !lines.is_empty() {
+            let line = hunk.new_start.saturating_sub(1).min(lines.len() - 1);
+            signs[line] = Some(GitSign::Removed);
+        }
+    }
+    signs
+}
+
+struct Job {
+    ticket: u64,
+    filename: String,
+    reload: bool, // 인덱스 내용을 다시 읽을지 여부
+    lines: Vec<String>,
+}
+
+// 버퍼와 git 인덱스를 백그라운드 스레드에서 비교해 줄마다 표시를 만든다
+pub struct GitGutter {
+    tx: Sender<Job>,
+    rx: Receiver<(u64, Vec<Option<GitSign>>)>,
+    signs: Vec<Option<GitSign>>,
+    ticket: u64,
+    version: Option<u64>, // 마지막으로 요청한 버퍼 버전
+    reload: bool,
+}
+
+impl GitGutter {
+    pub fn new() -> Self {
+        let (tx, job_rx) = mpsc::channel::<Job>();
+        let (result_tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut cached: Option<(String, Option<Vec<String>>)> = None;
+            while let Ok(mut job) = job_rx.recv() {
+                // 밀린 요청은 가장 최근 것만 처리
+                while let Ok(newer) = job_rx.try_recv() {
+                    let reload = job.reload || newer.reload;
+                    job = newer;
+                    job.reload = reload;
+                }
+                if job.reload || cached.as_ref().is_none_or(|(name, _)| *name != job.filename) {
+                    cached = Some((job.filename.clone(), index_contents(&job.filename)));
+                }
+                let signs = match cached.as_ref().and_then(|(_, index)| index.as_ref()) {
+                    Some(index) => compute_signs(index, &job.lines),
+                    None => Vec::new(),
+                };
+                if result_tx.send((job.ticket, signs)).is_err() {
+                    return;
+                }
+            }
+        });
+        GitGutter { tx, rx, signs: Vec::new(), ticket: 0, version: None, reload: true }
+    }
+
+    // 다른 파일을 열었을 때
+    pub fn reset(&mut self) {
+        self.signs.clear();
+        self.reload();
+    }
+
+    // 저장 등으로 인덱스를 다시 읽어야 할 때
+    pub fn reload(&mut self) {
+        self.version = None;
+        self.reload = true;
+    }
+
+    pub fn needs_update(&self, version: u64) -> bool {
+        self.version != Some(version)
+    }
+
+    pub fn request(&mut self, filename: &str, version: u64, lines: Vec<String>) {
+        self.ticket += 1;
+        self.version = Some(version);
+        let job = Job { ticket: self.ticket, filename: filename.to_string(), reload: self.reload, lines };
+        self.reload = false;
+        let _ = self.tx.send(job);
+    }
+
+    // 결과가 도착했으면 반영하고 true
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((ticket, signs)) = self.rx.try_recv() {
+            if ticket == self.ticket {
+                self.signs = signs;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn sign(&self, line: usize) -> Option<GitSign> {
+        self.signs.get(line).copied().flatten()
+    }
+
+    pub fn has_signs(&self) -> bool {
+        self.signs.iter().any(|s| s.is_some())
+    }
+}
+
+pub struct BlameLine {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+}
+
+impl BlameLine {
+    pub const WIDTH: usize = 33;
+
+    // "abcd1234 Author       2024-01-31 " 형태의 고정 폭 문자열
+    pub fn display(&self) -> String {
+        let author: String = self.author.chars().take(12).collect();
+        format!("{:8} {:12} {:10} ", self.hash, author, self.date)
+    }
+}
+
+// 현재 버퍼 내용을 기준으로 git blame (저장하지 않은 줄은 "Not Committed Yet")
+pub fn blame(filename: &str, contents: &str) -> Result<Vec<BlameLine>, String> {
+    let path = Path::new(filename);
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let name = file_name(filename).ok_or("no file name")?;
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["blame", "--porcelain", "--contents", "-", "--", &name])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // 내용을 다 쓰기 전에 출력 파이프가 가득 차지 않도록 별도 스레드에서 씀
+    let mut stdin = child.stdin.take().ok_or("git: no stdin")?;
+    let contents = contents.to_string();
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(contents.as_bytes());
+        let _ = stdin.write_all(b"\n");
+    });
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    let _ = writer.join();
+    if !output.status.success() {
+        let msg = String::from_utf8_lossy(&output.stderr);
+        return Err(msg.lines().next().unwrap_or("git blame failed").to_string());
+    }
+    Ok(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut commits: HashMap<String, (String, String)> = HashMap::new();
+    let mut lines = Vec::new();
+    let (mut hash, mut author, mut date) = (String::new(), String::new(), String::new());
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // 한 줄에 대한 정보가 끝남: 처음 나온 커밋이면 기록해 둠
+            let info = commits.entry(hash.clone()).or_insert_with(|| (author.clone(), date.clone()));
+            let short: String = if hash.bytes().all(|b| b == b'0') { "00000000".into() } else { hash.chars().take(8).collect() };
+            lines.push(BlameLine { hash: short, author: info.0.clone(), date: info.1.clone() });
+        } else if let Some(name) = line.strip_prefix("author ") {
+            author = name.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            date = time.parse().map(format_date).unwrap_or_default();
+        } else if line.len() > 40 && line.as_bytes()[..40].iter().all(|b| b.is_ascii_hexdigit()) {
+            hash = line[..40].to_string();
+        }
+    }
+    lines
+}
+
+// 유닉스 시간을 YYYY-MM-DD로 변환 (UTC)
+fn format_date(epoch: i64) -> String {
+    let days = epoch.div_euclid(86400);
+    // Howard Hinnant의 civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,124 @@
+// --- LSP Navigation ---
+// 언어 서버에 물어 커서 아래 이름의 정의나 쓰인 곳으로 감 (:lsp 로 서버를 등록한 파일 종류에서)
+//   gd   정의로    gD   선언으로    (여러 곳이면 quickfix 목록에도 넣고 첫 번째로)
+//   gr   쓰인 곳들을 quickfix 목록에 넣고 목록 창을 엶 (:cn / :cp 로 오감)
+// 다른 파일이면 :e 처럼 새 버퍼로 엶
+// 응답은 백그라운드로 받고, 그 사이 다른 파일로 옮겨 갔으면 버림
+use crate::EditorConfig;
+use crate::error::Error;
+use crate::json::Json;
+use crate::lsp::{self, Request, Response};
+use crate::quickfix::QuickfixEntry;
+use std::collections::HashMap;
+use std::fs;
+
+impl EditorConfig {
+    // 지금 파일의 서버에 요청을 보냄 (서버가 가진 내용이 옛것이면 먼저 보냄), 보냈으면 true
+    pub(crate) fn lsp_request(&mut self, kind: Request, params: Vec<(&str, Json)>) -> bool {
+        let Some(name) = self.filename.clone() else {
+            self.error(Error::NoFileName.to_string());
+            return false;
+        };
+        if self.lsp.is_stale(&name, self.buffer.version) {
+            let text = self.buffer.rows_to_string();
+            let _ = self.lsp.change(&name, self.buffer.version, &text);
+        }
+        match self.lsp.request(&name, kind, params) {
+            Ok(true) => true,
+            Ok(false) => {
+                self.error("No language server for this file (see :lsp)");
+                false
+            }
+            Err(e) => {
+                self.error(format!("LSP: {}", e));
+                false
+            }
+        }
+    }
+
+    // 커서 위치 ({textDocument, position} 의 position)
+    pub(crate) fn lsp_cursor(&self) -> (&'static str, Json) {
+        let character = lsp::to_character(&self.buffer.rows[self.cy].content, self.cx);
+        ("position", lsp::position(self.cy, character))
+    }
+
+    // gd / gD / gr
+    pub(crate) fn lsp_goto(&mut self, kind: Request) {
+        let mut params = vec![self.lsp_cursor()];
+        if kind == Request::References {
+            params.push(("context", Json::obj(vec![("includeDeclaration", Json::Bool(true))])));
+        }
+        if self.lsp_request(kind, params) {
+            self.status_msg = "Asking the language server...".into();
+        }
+    }
+
+    // 서버가 보낸 응답 처리
+    pub(crate) fn handle_lsp_response(&mut self, response: Response) {
+        if self.filename.as_deref() != Some(response.filename.as_str()) {
+            return;
+        }
+        let result = match response.result {
+            Ok(result) => result,
+            Err(e) => {
+                self.error(format!("LSP: {}", e));
+                return;
+            }
+        };
+        match response.kind {
+            Request::Definition | Request::Declaration | Request::References => self.show_locations(response.kind, &result),
+            Request::Hover => self.show_hover(&result),
+            Request::Rename => self.apply_rename(response.version, &result),
+            Request::Formatting | Request::RangeFormatting => self.apply_formatting(response.version, &result),
+            Request::Completion => self.merge_lsp_completion(&result),
+            Request::ResolveCompletion => self.apply_resolved_completion(response.version, &result),
+            Request::SignatureHelp => self.show_signature(response.version, &result),
+            Request::DocumentSymbol => self.show_lsp_symbols(&result),
+        }
+    }
+
+    fn show_locations(&mut self, kind: Request, result: &Json) {
+        let what = match kind {
+            Request::Definition => "definition",
+            Request::Declaration => "declaration",
+            _ => "references",
+        };
+        let locations = lsp::locations(result);
+        if locations.is_empty() {
+            self.error(format!("No {} found", what));
+            return;
+        }
+        // 줄 내용은 열린 버퍼나 파일에서
+        let mut files: HashMap<String, Vec<String>> = HashMap::new();
+        let mut entries = Vec::new();
+        for (filename, line, character) in &locations {
+            let lines = files.entry(filename.clone()).or_insert_with(|| match self.is_current_file(filename) {
+                true => self.buffer.rows.iter().map(|r| r.content.clone()).collect(),
+                false => fs::read_to_string(filename).map(|t| t.lines().map(String::from).collect()).unwrap_or_default(),
+            });
+            let text = lines.get(*line).cloned().unwrap_or_default();
+            let col = lsp::to_byte(&text, *character);
+            entries.push(QuickfixEntry { filename: filename.clone(), line: line + 1, col: col + 1, text });
+        }
+        let count = entries.len();
+        let first = (entries[0].filename.clone(), entries[0].line, entries[0].col);
+        if kind == Request::References || count > 1 {
+            self.quickfix.set(format!("lsp {}", what), entries);
+        }
+        if kind == Request::References {
+            self.status_msg = format!("{} references", count);
+            self.open_quickfix_panel();
+            return;
+        }
+        let (filename, line, col) = first;
+        if !self.is_current_file(&filename) {
+            self.edit_file(&filename);
+        }
+        self.cy = (line - 1).min(self.buffer.rows.len() - 1);
+        self.cx = (col - 1).min(self.buffer.rows[self.cy].content.len());
+        self.status_msg = match count {
+            1 => format!("{}:{}", filename, line),
+            n => format!("{} 1 of {} (:cn for the next)", what, n),
+        };
+    }
+}
@@ -0,0 +1,69 @@
+// --- Project-wide Grep ---
+// :grep 패턴   지금 디렉터리 아래의 파일들에서 패턴을 찾아 quickfix 목록에 넣음
+// 패턴은 정규식이 아닌 글자 그대로 찾음 (rg 는 --fixed-strings 로, 없으면 내장 검색이 같은 규칙으로)
+// rg 가 일부 파일을 읽지 못해도 (종료 코드 2) 찾은 줄은 그대로 두고 오류 출력은 경고로 돌려줌
+use crate::quickfix::QuickfixEntry;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+// dir 아래에서 글자 그대로 찾은 줄들과 rg 의 경고, ripgrep이 설치되어 있으면 사용하고 없으면 내장 검색으로 대체
+pub fn grep(pattern: &str, dir: &str) -> io::Result<(Vec<QuickfixEntry>, Option<String>)> {
+    match ripgrep(pattern, dir) {
+        Ok(found) => Ok(found),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let mut entries = Vec::new();
+            search_dir(Path::new(dir), pattern, &mut entries);
+            Ok((entries, None))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn ripgrep(pattern: &str, dir: &str) -> io::Result<(Vec<QuickfixEntry>, Option<String>)> {
+    let mut command = Command::new("rg");
+    command.args(["--vimgrep", "--no-heading", "--color", "never", "--fixed-strings", "--", pattern]);
+    // 지금 디렉터리면 경로를 주지 않아야 rg 가 "./" 없이 보여줌
+    if dir != "." {
+        command.arg(dir);
+    }
+    let output = command.output()?;
+    // rg는 매치가 없으면 1, 오류가 나면 2를 반환 (오류가 나도 읽은 파일에서 찾은 줄은 출력함)
+    let warning = match output.status.code() {
+        Some(2) => String::from_utf8_lossy(&output.stderr).lines().next().map(str::to_string),
+        _ => None,
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok((stdout.lines().filter_map(QuickfixEntry::parse).collect(), warning))
+}
+
+// 내장 검색: 숨김 디렉터리와 target은 건너뛰고, 고정 문자열로 비교
+fn search_dir(dir: &Path, pattern: &str, entries: &mut Vec<QuickfixEntry>) {
+    let Ok(read_dir) = fs::read_dir(dir) else { return };
+    let mut paths: Vec<_> = read_dir.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.starts_with('.') || name == "target" {
+            continue;
+        }
+        if path.is_dir() {
+            search_dir(&path, pattern, entries);
+        } else if let Ok(content) = fs::read_to_string(&path) {
+            // 실행 인자처럼 보이도록 앞의 "./" 제거
+            let display = path.strip_prefix(".").unwrap_or(&path).to_string_lossy().to_string();
+            for (i, line) in content.lines().enumerate() {
+                if let Some(col) = line.find(pattern) {
+                    entries.push(QuickfixEntry {
+                        filename: display.clone(),
+                        line: i + 1,
+                        col: col + 1,
+                        text: line.to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// --- Scripted-Keystroke Test Harness ---
+// 정해진 키 입력을 MemoryTerminal을 통해 이벤트 루프에 흘려 넣고,
+// 버퍼 내용과 가상 화면을 함께 검사한다. (tests/ 의 통합 테스트에서 사용)
+use crate::terminal::MemoryTerminal;
+use crate::{EditorConfig, Row, keys, run};
+
+pub struct Harness {
+    pub editor: EditorConfig,
+    pub screen: MemoryTerminal,
+}
+
+impl Harness {
+    pub fn new(cols: u16, rows: u16) -> Self {
+        let mut editor = EditorConfig::new();
+        // 테스트가 실제 시스템 클립보드를 읽고 쓰지 않게 함
+        editor.registers.system_clipboard = false;
+        Harness { editor, screen: MemoryTerminal::new(cols, rows) }
+    }
+
+    pub fn with_lines(cols: u16, rows: u16, lines: &[&str]) -> Self {
+        let mut h = Harness::new(cols, rows);
+        h.editor.buffer.rows = lines.iter().map(|l| Row::new(l.to_string())).collect();
+        h
+    }
+
+    // 키 입력을 이벤트 루프에 넣고 화면을 갱신 ("<Esc>", "<CR>", "<C-n>" 같은 표기 사용 가능)
+    pub fn keys(&mut self, notation: &str) -> &mut Self {
+        self.screen.push_keys(&keys::parse(notation));
+        run(&mut self.editor, &mut self.screen).unwrap();
+        self
+    }
+
+    // 시스템 클립보드에 text 가 있는 것처럼 함 ("+p, "*p 가 읽음)
+    pub fn set_clipboard(&mut self, text: &str) {
+        self.editor.registers.set_clipboard(text);
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.editor.buffer.rows.iter().map(|r| r.content.as_str()).collect()
+    }
+
+    // (줄, 열), 0부터 시작
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.editor.cy, self.editor.cx)
+    }
+}
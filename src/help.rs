@@ -0,0 +1,78 @@
+// --- Help ---
+// :help [주제]   편집기에 넣어 둔 도움말 (help.txt) 을 아래 창에 열고 주제가 있는 줄로
+//   주제는 *이름* 으로 표시된 태그: 키 (K, Ctrl-]), 명령 (:write, :w), 설정 ('number', 'nu')
+//   그대로 맞는 태그가 없으면 :주제, 줄임말을 푼 :명령, '주제', 주제로 시작하는 태그 순서로 찾음
+// 도움말 창에서 (j / k, Ctrl-D / Ctrl-U, q 는 다른 창과 같음)
+//   Ctrl-] / Enter   선택한 줄의 첫 |링크| 로
+//   Ctrl-T           링크를 따라오기 전 자리로
+use crate::{EditorConfig, PanelKind, cmdnames};
+
+const TEXT: &str = include_str!("help.txt");
+
+// 줄에 있는 *태그* 들의 이름
+fn tags(line: &str) -> impl Iterator<Item = &str> {
+    line.split_whitespace().filter(|w| w.len() > 2 && w.starts_with('*') && w.ends_with('*')).map(|w| &w[1..w.len() - 1])
+}
+
+// 주제의 태그가 있는 줄 (0부터)
+fn find(topic: &str) -> Option<usize> {
+    let line_of = |tag: &str| TEXT.lines().position(|l| tags(l).any(|t| t == tag));
+    let command = cmdnames::resolve(topic.trim_start_matches(':')).ok().flatten();
+    line_of(topic)
+        .or_else(|| line_of(&format!(":{}", topic)))
+        .or_else(|| command.and_then(|c| line_of(&format!(":{}", c))))
+        .or_else(|| line_of(&format!("'{}'", topic)))
+        .or_else(|| TEXT.lines().position(|l| tags(l).any(|t| t.starts_with(topic))))
+}
+
+// 줄의 첫 |링크|
+fn link(line: &str) -> Option<&str> {
+    let start = line.find('|')? + 1;
+    let len = line[start..].find('|')?;
+    Some(&line[start..start + len]).filter(|l| !l.is_empty() && !l.contains(' '))
+}
+
+impl EditorConfig {
+    // :help
+    pub(crate) fn help(&mut self, topic: &str) {
+        match if topic.is_empty() { Some(0) } else { find(topic) } {
+            Some(line) => self.show_help(line),
+            None => self.error(format!("Sorry, no help for {}", topic)),
+        }
+    }
+
+    // 도움말 창을 열고 line 줄을 창의 맨 위에
+    fn show_help(&mut self, line: usize) {
+        let lines: Vec<String> = TEXT.lines().map(String::from).collect();
+        let last = lines.len();
+        self.open_panel(PanelKind::Help, "help.txt".into(), lines, line);
+        let rows = self.panel_height() - 1;
+        if let Some(panel) = self.panel.as_mut() {
+            panel.offset = line.min(last.saturating_sub(rows));
+        }
+    }
+
+    // 도움말 창의 Ctrl-] / Enter
+    pub(crate) fn follow_help_link(&mut self) {
+        let Some(panel) = &self.panel else { return };
+        let from = panel.selected;
+        let Some(topic) = panel.lines.get(from).and_then(|l| link(l)).map(String::from) else {
+            return self.error("No link on this line");
+        };
+        match find(&topic) {
+            Some(line) => {
+                self.help_stack.push(from);
+                self.show_help(line);
+            }
+            None => self.error(format!("Sorry, no help for {}", topic)),
+        }
+    }
+
+    // 도움말 창의 Ctrl-T
+    pub(crate) fn help_back(&mut self) {
+        match self.help_stack.pop() {
+            Some(line) => self.show_help(line),
+            None => self.error("At bottom of help stack"),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// --- LSP Hover ---
+// K 로 커서 아래 이름의 설명(타입, 문서)을 언어 서버에 물어 커서 위(자리가 없으면 아래)의 작은 창에 보여줌
+// 다음 키를 누르면 닫힘 (그 키는 그대로 처리됨)
+// 마크다운은 글자만 남김: ``` 줄은 빼고, # 머리, **굵게**, `코드`, \ 이스케이프 표시를 뗌
+use crate::EditorConfig;
+use crate::json::Json;
+use crate::lsp::Request;
+
+// 창에 보여줄 최대 줄 수
+pub const HOVER_HEIGHT: usize = 12;
+
+// MarkupContent, MarkedString, MarkedString[] 의 글
+fn contents_text(contents: &Json) -> String {
+    match contents {
+        Json::String(s) => s.clone(),
+        Json::Array(items) => items.iter().map(contents_text).filter(|t| !t.is_empty()).collect::<Vec<_>>().join("\n\n"),
+        other => other.get("value").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+    }
+}
+
+// 마크다운 한 줄의 꾸밈을 뗌
+fn strip_inline(line: &str) -> String {
+    let line = line.trim_start_matches('#').trim_start_matches(' ');
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|c| c.is_ascii_punctuation()) => text.extend(chars.next()),
+            '`' => {}
+            '*' | '_' if chars.peek() == Some(&c) => {
+                chars.next();
+            }
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+/// 마크다운을 보여줄 줄들로 (코드 블록 안은 그대로, 빈 줄이 이어지면 하나만)
+pub fn markdown_lines(markdown: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        let line = if in_code { line.trim_end().to_string() } else { strip_inline(line.trim_end()) };
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+impl EditorConfig {
+    // K
+    pub(crate) fn lsp_hover(&mut self) {
+        let params = vec![self.lsp_cursor()];
+        if self.lsp_request(Request::Hover, params) {
+            self.status_msg = "Asking the language server...".into();
+        }
+    }
+
+    pub(crate) fn show_hover(&mut self, result: &Json) {
+        let lines = result.get("contents").map(|c| markdown_lines(&contents_text(c))).unwrap_or_default();
+        if lines.is_empty() {
+            self.error("No hover information");
+            return;
+        }
+        self.status_msg.clear();
+        self.hover = Some(lines);
+    }
+}
@@ -0,0 +1,578 @@
+// --- Key Handling ---
+// 모드별 키 처리: 키 매핑, 일반/입력/명령 모드와 완성 메뉴, 목록 창, 접두 키
+use crate::keys::{KeyCode, KeyEvent, Modifiers};
+use crate::lsp::Request;
+use crate::{EditorConfig, Mode, PanelKind, abbrev, keymap};
+use std::mem;
+use std::time::Instant;
+use KeyCode::*;
+
+const NONE: Modifiers = Modifiers::NONE;
+const CTRL: Modifiers = Modifiers::CTRL;
+const ALT: Modifiers = Modifiers::ALT;
+const CTRL_W: KeyEvent = KeyEvent::ctrl('w');
+
+impl EditorConfig {
+    /// 키 하나를 현재 모드에 맞게 처리, :q 등으로 종료해야 하면 false
+    pub fn handle_keypress(&mut self, key: KeyEvent) -> bool {
+        if self.picker.is_some() {
+            self.handle_picker_key(key);
+            return true;
+        }
+        if self.mode == Mode::Insert && self.pending_keys.is_empty() && self.handle_completion_key(key) {
+            return true;
+        }
+        let mode = match self.mode {
+            Mode::Normal if self.pending.is_empty() && !self.panel_focused => "n",
+            Mode::Insert if self.pending.is_empty() => "i",
+            _ => return self.handle_key(key),
+        };
+        self.pending_keys.push(key);
+        let typed: String = self.pending_keys.iter().map(|k| k.notation()).collect();
+        if self.keymap_has_prefix(mode, &typed) {
+            // 더 긴 매핑이 있으면 다음 키나 시간 초과를 기다림
+            self.pending_keys_since = Some(Instant::now());
+            return true;
+        }
+        self.flush_pending_keys()
+    }
+
+    /// 기다리던 키들을 처리: 가장 길게 맞는 매핑을 실행하고 남은 키는 다시 매핑을 찾음,
+    /// 맞는 매핑이 없으면 첫 키를 그대로 처리
+    pub(crate) fn flush_pending_keys(&mut self) -> bool {
+        let keys = mem::take(&mut self.pending_keys);
+        self.pending_keys_since = None;
+        let mode = if self.mode == Mode::Insert { "i" } else { "n" };
+        let mut used = 1;
+        let mut running = None;
+        for n in (1..=keys.len()).rev() {
+            let typed: String = keys[..n].iter().map(|k| k.notation()).collect();
+            running = self.run_keymap(mode, &typed);
+            if running.is_some() {
+                used = n;
+                break;
+            }
+        }
+        let running = match (running, keys.first()) {
+            (Some(running), _) => running,
+            (None, Some(&first)) => self.handle_key(first),
+            (None, None) => return true,
+        };
+        running && keys[used..].iter().all(|&k| self.handle_keypress(k))
+    }
+
+    fn keymap_has_prefix(&self, mode: &str, typed: &str) -> bool {
+        self.keymaps.has_prefix(mode, typed)
+            || self.script.as_ref().is_some_and(|s| {
+                s.keymaps(mode).iter().any(|lhs| keymap::is_prefix(typed, &self.keymaps.expand(lhs)))
+            })
+    }
+
+    // 입력한 키들에 정확히 맞는 매핑을 실행, 없으면 None (있으면 계속 실행할지)
+    fn run_keymap(&mut self, mode: &str, typed: &str) -> Option<bool> {
+        // :nnoremap 의 오른쪽 키들은 다시 매핑하지 않고 처리
+        if let Some(rhs) = self.keymaps.get(mode, typed) {
+            return Some(rhs.into_iter().all(|k| self.handle_key(k)));
+        }
+        let lhs = self.script.as_ref()?.keymaps(mode).into_iter().find(|lhs| self.keymaps.expand(lhs) == typed)?;
+        self.with_script(|script, ed| script.call_keymap(ed, mode, &lhs));
+        Some(true)
+    }
+
+    /// 입력 중인 매핑이나 접두 키 (상태 줄에 표시)
+    pub fn pending_keys(&self) -> String {
+        self.pending.iter().chain(&self.pending_keys).map(|k| k.notation()).collect()
+    }
+
+    // 매핑을 거치지 않고 키 하나를 처리, 일반 모드에서 시작해 버퍼를 바꾸고 다시 일반 모드로
+    // 돌아오기까지의 키들(ihello<Esc>, "add, D 등)은 . 으로 되풀이할 수 있게 기록
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.hover = None;
+        let idle = self.mode == Mode::Normal && self.pending.is_empty() && !self.panel_focused;
+        if idle {
+            self.commit_undo(); // 키 밖에서 (스크립트 등) 바뀐 내용도 상태로
+        }
+        if idle && key == KeyEvent::char('.') {
+            let keys = self.last_change.clone();
+            return keys.into_iter().all(|k| self.handle_key(k));
+        }
+        if idle {
+            self.change = Some((self.buffer.version, Vec::new()));
+        } else if self.mode == Mode::Command {
+            self.change = None; // 명령 줄의 명령은 되풀이하지 않음
+        }
+        if let Some((_, keys)) = self.change.as_mut() {
+            keys.push(key);
+        }
+        let (version, mode) = (self.buffer.version, self.mode);
+        let running = self.dispatch_multi(key);
+        if self.mode != mode {
+            self.emit_for("ModeChanged", &format!("{}:{}", mode.short_name(), self.mode.short_name()));
+        }
+        if self.buffer.version != version {
+            self.changes.record((self.cy, self.cx));
+        }
+        if self.mode == Mode::Normal && self.pending.is_empty() {
+            self.commit_undo();
+        }
+        // 횟수는 접두 키("a 등)를 거쳐 명령이 끝날 때까지 유지
+        if self.mode != Mode::Normal || (self.pending.is_empty() && self.register.is_none()) {
+            self.count = None;
+        }
+        if self.mode == Mode::Normal
+            && self.pending.is_empty()
+            && let Some((version, keys)) = self.change.take()
+            && version != self.buffer.version
+        {
+            self.last_change = keys;
+        }
+        running
+    }
+
+    pub(crate) fn dispatch_key(&mut self, key: KeyEvent) -> bool {
+        if self.mode == Mode::Insert && !self.pending.is_empty() {
+            return self.handle_literal(key);
+        }
+        // 비주얼 모드의 접두 키는 gc, gj, gk, g Ctrl-A, g Ctrl-X 의 g 와 zf 의 z
+        if self.mode == Mode::Visual && !self.pending.is_empty() {
+            let prefix = mem::take(&mut self.pending)[0].as_char();
+            match (prefix, key.code, key.modifiers) {
+                (Some('g'), Char('c'), NONE) => self.visual_operator('c'),
+                (Some('g'), Char(c @ ('j' | 'k')), NONE) => self.display_move(c == 'j'),
+                (Some('g'), Char(c @ ('a' | 'x')), CTRL) => self.increment_selection(if c == 'a' { 1 } else { -1 }, true),
+                (Some('z'), Char('f'), NONE) => self.fold_selection(),
+                _ => {}
+            }
+            return true;
+        }
+        if self.mode == Mode::Normal {
+            if !self.pending.is_empty() {
+                let mut prefix = mem::take(&mut self.pending);
+                // 숫자로 된 접두는 다음 명령의 횟수, 연산자 뒤의 숫자(d3w)는 앞의 횟수(2d3w)와 곱함
+                let start = usize::from(is_operator(prefix[0]));
+                if let Some(count) = count_of(&prefix[start..]) {
+                    if key.as_char().is_some_and(|c| c.is_ascii_digit()) {
+                        self.pending = prefix;
+                        self.pending.push(key);
+                        return true;
+                    }
+                    self.count = Some(self.count.map_or(count, |c| c * count));
+                    prefix.truncate(start);
+                    if prefix.is_empty() {
+                        return self.dispatch_key(key);
+                    }
+                }
+                if prefix == [KeyEvent::char('@')] {
+                    return key.as_char().is_none_or(|c| self.execute_register(c));
+                }
+                self.handle_pending(&prefix, key);
+                return true;
+            }
+            if key == CTRL_W {
+                self.pending.push(key);
+                return true;
+            }
+            if self.panel_focused {
+                self.handle_panel_key(key);
+                return true;
+            }
+        }
+        if self.mode == Mode::Insert && self.handle_completion_key(key) {
+            return true;
+        }
+        match self.mode {
+            Mode::Normal => match (key.code, key.modifiers) {
+                (Char('i') | Insert, NONE) => self.start_insert(),
+                (Char(':'), NONE) => {
+                    self.mode = Mode::Command;
+                    self.command_buffer.clear();
+                }
+                (Char(c @ ('/' | '?')), NONE) => {
+                    (self.mode, self.prompt) = (Mode::Command, c);
+                    self.command_buffer.clear();
+                }
+                (Char('n'), NONE) => self.search_next(false),
+                (Char('N'), NONE) => self.search_next(true),
+                // 백그라운드에서 찾는 중이면 그만둠
+                (Esc, _) | (Char('c'), CTRL) if self.is_searching() => self.cancel_search(),
+                (Char(c @ ('j' | 'k')), NONE) if self.window.options.displaylines => {
+                    (0..self.take_count()).for_each(|_| self.display_move(c == 'j'))
+                }
+                (Char(c @ ('h' | 'j' | 'k' | 'l')), NONE) => (0..self.take_count()).for_each(|_| self.move_cursor(c)),
+                (Left | Down | Up | Right, NONE) => (0..self.take_count()).for_each(|_| self.move_arrow(key.code)),
+                (Char('w'), NONE) => self.word_forward(),
+                (Char('0') | Home, NONE) => self.smart_home(key.code == Home),
+                (Char('^'), NONE) => self.first_non_blank(),
+                // 횟수가 있으면 그 줄로, 없으면 마지막 줄로
+                (Char('G'), NONE) => {
+                    let line = self.count.take().unwrap_or(self.buffer.rows.len());
+                    self.goto_line(line);
+                }
+                (Char(']' | '[' | 'y' | 'd' | 'c' | '>' | '<' | '=' | 'g' | 'm' | '\'' | '`' | '"' | '@' | 'z'), NONE) => {
+                    self.pending.push(key)
+                }
+                (Char('~'), NONE) => self.toggle_case_under(),
+                (Char('v'), NONE) => self.start_visual(false),
+                (Char('V'), NONE) => self.start_visual(true),
+                (Char('1'..='9'), NONE) => self.pending.push(key),
+                (Char('a'), CTRL) => self.increment(1),
+                (Char('x'), CTRL) => self.increment(-1),
+                (Char('x') | Delete, NONE) => self.delete_char_under(),
+                (Char('D'), NONE) => self.delete_to_line_end(),
+                (Char('C'), NONE) => {
+                    self.delete_to_line_end();
+                    self.start_insert();
+                }
+                (Char('S'), NONE) => {
+                    self.clear_line();
+                    self.start_insert();
+                }
+                (Char('s'), NONE) => {
+                    self.delete_char_under();
+                    self.start_insert();
+                }
+                (Char('d'), ALT) => self.duplicate_current(),
+                (Char('u'), NONE) => self.undo(),
+                (Char('n'), CTRL) => self.add_cursor_at_next_word(),
+                (Char('r'), CTRL) => self.redo(),
+                (Char('g'), CTRL) => self.show_file_info(),
+                (Char(c @ ('d' | 'u')), CTRL) => self.scroll_half_page(c == 'd'),
+                (Char(c @ ('f' | 'b')), CTRL) => self.scroll_pages(c == 'f'),
+                (PageDown | PageUp, NONE) => self.scroll_pages(key.code == PageDown),
+                (Char('K'), NONE) => self.lookup_keyword(),
+                (Char(']'), CTRL) => self.tag_under_cursor(),
+                (Char('t'), CTRL) => self.pop_tag(),
+                (Char('p'), NONE) => self.put(true),
+                (Char('P'), NONE) => self.put(false),
+                (Char('j') | Down, ALT) => self.move_current(true),
+                (Char('k') | Up, ALT) => self.move_current(false),
+                _ => {}
+            },
+            Mode::Insert => match (key.code, key.modifiers) {
+                (Esc, _) => {
+                    self.expand_abbreviation();
+                    self.signature = None;
+                    self.changes.last_insert = Some((self.cy, self.cx));
+                    self.mode = Mode::Normal;
+                    self.emit("InsertLeave");
+                }
+                (Char('n'), CTRL) => self.start_completion(true),
+                (Char('p'), CTRL) => self.start_completion(false),
+                (Enter, _) => {
+                    self.expand_abbreviation();
+                    self.insert_newline();
+                }
+                (Backspace, _) => self.backspace(),
+                (Char('v'), CTRL) => self.pending.push(key),
+                (Char('w'), CTRL) => self.delete_word_before(),
+                (Char('u'), CTRL) => self.delete_to_line_start(),
+                (Char('t'), CTRL) => self.shift_line(true),
+                (Char('d'), CTRL) => self.shift_line(false),
+                (Char('j') | Down, ALT) => self.move_current(true),
+                (Char('k') | Up, ALT) => self.move_current(false),
+                (Tab, NONE) => {
+                    self.expand_abbreviation();
+                    self.insert_tab();
+                }
+                (Left | Down | Up | Right, NONE) => self.move_arrow(key.code),
+                (Home, NONE) => self.smart_home(true),
+                (Char(c), NONE) if !c.is_control() => {
+                    // 낱말이 끝나면 약어를 펼침
+                    if !abbrev::is_word_char(c) {
+                        self.expand_abbreviation();
+                    }
+                    self.insert_char(c);
+                    if matches!(c, '(' | ',' | ')') {
+                        self.signature_trigger(c);
+                    }
+                }
+                _ => {}
+            },
+            Mode::Visual => match (key.code, key.modifiers) {
+                (Esc, _) => self.end_visual(),
+                (Char('v'), NONE) => self.switch_visual(false),
+                (Char('V'), NONE) => self.switch_visual(true),
+                (Char(c @ ('j' | 'k')), NONE) if self.window.options.displaylines => self.display_move(c == 'j'),
+                (Char(c @ ('h' | 'j' | 'k' | 'l')), NONE) => self.move_cursor(c),
+                (Left | Down | Up | Right, NONE) => self.move_arrow(key.code),
+                (Char('0') | Home, NONE) => self.smart_home(key.code == Home),
+                (Char('^'), NONE) => self.first_non_blank(),
+                (Char(c @ ('>' | '<' | '=' | 'u' | 'U' | '~')), NONE) => self.visual_operator(c),
+                (Char('j') | Down, ALT) => self.move_current(true),
+                (Char('k') | Up, ALT) => self.move_current(false),
+                (Char('d'), ALT) => self.duplicate_current(),
+                (Char(c @ ('a' | 'x')), CTRL) => self.increment_selection(if c == 'a' { 1 } else { -1 }, false),
+                (Char('g' | 'z'), NONE) => self.pending.push(key),
+                _ => {}
+            },
+            Mode::Command => match (key.code, key.modifiers) {
+                (Esc, _) => (self.mode, self.prompt) = (Mode::Normal, ':'),
+                (Enter, _) => return self.execute_command(),
+                (Backspace, _) => { self.command_buffer.pop(); }
+                (Char(c), NONE) if !c.is_control() => self.command_buffer.push(c),
+                _ => {}
+            },
+        }
+        true
+    }
+
+    // 입력 모드에서 Ctrl-V 다음 키: 그 키의 글자를 그대로 넣음 (Ctrl-A, Esc, Enter도 제어 문자로),
+    // u/U/x 나 숫자로 시작하면 코드 값을 모아서 그 글자를 넣음
+    //   Ctrl-V u00e9  16진수 4자리   Ctrl-V U0001F600  8자리   Ctrl-V x41  2자리   Ctrl-V 065  10진수 3자리
+    // 자릿수를 다 채우기 전에 다른 키를 치면 모은 값까지만 넣고 그 키는 평소대로 처리
+    fn handle_literal(&mut self, key: KeyEvent) -> bool {
+        let typed: String = self.pending[1..].iter().filter_map(|k| k.as_char()).collect();
+        let Some(first) = typed.chars().next() else {
+            match key.as_char() {
+                Some('u' | 'U' | 'x' | 'X' | '0'..='9') => self.pending.push(key),
+                _ => {
+                    self.pending.clear();
+                    if let Some(c) = literal_char(key) {
+                        self.insert_char(c);
+                    }
+                }
+            }
+            return true;
+        };
+        let (radix, digits, max) = match first {
+            'u' => (16, &typed[1..], 4),
+            'U' => (16, &typed[1..], 8),
+            'x' | 'X' => (16, &typed[1..], 2),
+            _ => (10, typed.as_str(), 3),
+        };
+        let next = key.as_char().filter(|c| c.is_digit(radix));
+        if let Some(c) = next {
+            if digits.len() + 1 < max {
+                self.pending.push(key);
+                return true;
+            }
+            let code = format!("{}{}", digits, c);
+            self.pending.clear();
+            self.insert_code(&code, radix);
+            return true;
+        }
+        // 자릿수가 모자란 채로 끝남: 숫자가 없으면 u/x 글자를 그대로
+        self.pending.clear();
+        if digits.is_empty() {
+            self.insert_char(first);
+        } else {
+            self.insert_code(digits, radix);
+        }
+        self.dispatch_key(key)
+    }
+
+    fn insert_code(&mut self, digits: &str, radix: u32) {
+        match u32::from_str_radix(digits, radix).ok().and_then(char::from_u32) {
+            Some(c) => self.insert_char(c),
+            None => self.error(format!("Invalid character code: {}", digits)),
+        }
+    }
+
+    pub(crate) fn start_insert(&mut self) {
+        self.mode = Mode::Insert;
+        self.emit("InsertEnter");
+    }
+
+    // 명령 앞에 친 횟수를 가져감 (없으면 1), 횟수를 쓰는 명령은 모두 이것으로 읽음
+    pub(crate) fn take_count(&mut self) -> usize {
+        self.count.take().unwrap_or(1)
+    }
+
+    // 커서 줄부터 횟수만큼의 줄 (끝은 제외, 버퍼 끝을 넘지 않음)
+    pub(crate) fn counted_lines(&mut self) -> (usize, usize) {
+        let end = (self.cy + self.take_count()).min(self.buffer.rows.len());
+        (self.cy, end)
+    }
+
+    // 화살표 키는 hjkl과 같게 움직임
+    fn move_arrow(&mut self, code: KeyCode) {
+        match code {
+            Left => self.move_cursor('h'),
+            Down => self.move_cursor('j'),
+            Up => self.move_cursor('k'),
+            Right => self.move_cursor('l'),
+            _ => {}
+        }
+    }
+
+    // 완성 메뉴가 떠 있을 때의 키 처리, 키를 소비했으면 true
+    fn handle_completion_key(&mut self, key: KeyEvent) -> bool {
+        let Some(comp) = self.completion.as_mut() else { return false };
+        // 서버의 답을 기다리는 빈 메뉴는 키를 가로채지 않음
+        if comp.items.is_empty() && !matches!((key.code, key.modifiers), (Char('n' | 'p'), CTRL)) {
+            self.completion = None;
+            return false;
+        }
+        match (key.code, key.modifiers) {
+            (Char('n'), CTRL) | (Down, NONE) => comp.next(),
+            (Char('p'), CTRL) | (Up, NONE) => comp.prev(),
+            (Char('y'), CTRL) | (Enter, _) => {
+                // Ctrl-Y/Enter: 선택 확정
+                self.accept_completion();
+                return true;
+            }
+            (Char('e'), CTRL) => {
+                // Ctrl-E: 원래 입력으로 되돌림
+                let (start, original) = (comp.start, comp.original.clone());
+                self.buffer.rows[self.cy].content.replace_range(start..self.cx, &original);
+                self.cx = start + original.len();
+                self.completion = None;
+                return true;
+            }
+            _ => {
+                // 다른 키는 선택을 확정하고 평소대로 처리
+                self.accept_completion();
+                return false;
+            }
+        }
+        self.apply_completion();
+        true
+    }
+
+    fn handle_pending(&mut self, prefix: &[KeyEvent], key: KeyEvent) {
+        if prefix == [CTRL_W] {
+            // Ctrl-W w/j/k/p (Ctrl-W Ctrl-W 포함): 편집 창과 목록 창 사이를 오감
+            if matches!(key.code, Char('w' | 'j' | 'k' | 'p') | Down | Up) && self.panel.is_some() {
+                self.panel_focused = !self.panel_focused;
+            }
+            return;
+        }
+        let (Some(typed), Some(key)) = (prefix.iter().map(|k| k.as_char()).collect::<Option<String>>(), key.as_char()) else {
+            self.register = None;
+            return;
+        };
+        match (typed.as_str(), key) {
+            // ]x / [x: 다음/이전 충돌 구간
+            ("]", 'x') => self.jump_conflict(true),
+            ("[", 'x') => self.jump_conflict(false),
+            ("]", 'd') => self.jump_diagnostic(true),
+            ("[", 'd') => self.jump_diagnostic(false),
+            ("y", 'y') => self.yank_line(),
+            ("d", 'd') => self.delete_line(),
+            ("c", 'c') => {
+                self.clear_line();
+                self.start_insert();
+            }
+            (">", '>') => self.shift_lines(true),
+            ("<", '<') => self.shift_lines(false),
+            ("=", '=') => {
+                let (start, end) = self.counted_lines();
+                self.lsp_format_lines(start, end);
+            }
+            ("d" | "y" | "c", 'w') => self.word_operator(&typed),
+            // 연산자 뒤의 횟수 (d3w), 다음 숫자들은 dispatch_key 에서 모음
+            ("d" | "y" | "c" | ">" | "<" | "=", '1'..='9') => {
+                self.pending = prefix.to_vec();
+                self.pending.push(KeyEvent::char(key));
+            }
+            // g~ / gu / gU 뒤에는 움직임이 오고, 같은 글자를 한 번 더 치면 줄 전체 (g~g~ 처럼 g를 붙여도 됨)
+            ("g", 'u' | 'U' | '~') | ("gu" | "gU" | "g~", 'g') => {
+                self.pending = prefix.to_vec();
+                self.pending.push(KeyEvent::char(key));
+            }
+            ("gu" | "gU" | "g~" | "gug" | "gUg" | "g~g", c) => self.case_operator(&typed[1..], c),
+            ("g", ';') => self.jump_change(true),
+            ("g", ',') => self.jump_change(false),
+            ("g", 'i') => self.resume_insert(),
+            ("g", 'j' | 'k') => (0..self.take_count()).for_each(|_| self.display_move(key == 'j')),
+            ("g", 'v') => self.reselect(),
+            ("g", 'a') => self.show_char_info(),
+            ("g", 'd') => self.lsp_goto(Request::Definition),
+            ("g", 'D') => self.lsp_goto(Request::Declaration),
+            ("g", 'r') => self.lsp_goto(Request::References),
+            ("g", 'g') => {
+                let line = self.take_count();
+                self.goto_line(line);
+            }
+            ("g", '-') => self.undo_chronological(false),
+            ("g", '+') => self.undo_chronological(true),
+            ("m", c) => self.set_mark(c),
+            ("'", c) => self.jump_mark(c, false),
+            ("`", c) => self.jump_mark(c, true),
+            ("\"", c) => self.register = Some(c),
+            // zf 뒤에는 움직임이나 마크가 옴
+            ("z", 'f') | ("zf", '\'' | '`') => {
+                self.pending = prefix.to_vec();
+                self.pending.push(KeyEvent::char(key));
+            }
+            ("z", 'F') => self.create_fold(self.cy, self.cy),
+            ("z", c) => self.fold_command(c),
+            ("zf", c) => self.fold_motion(c),
+            ("zf'" | "zf`", c) => self.fold_to_mark(c),
+            _ => self.register = None,
+        }
+    }
+
+    fn handle_panel_key(&mut self, key: KeyEvent) {
+        let recovering = self.panel.as_ref().is_some_and(|p| p.kind == PanelKind::Recovery);
+        if recovering && key.modifiers == NONE && let Some(c) = key.as_char() && self.recovery_key(c) {
+            return;
+        }
+        let replacing = self.panel.as_ref().is_some_and(|p| p.kind == PanelKind::Replace);
+        if replacing && key.modifiers == NONE && let Some(c) = key.as_char() && self.replace_key(c) {
+            return;
+        }
+        let half = self.panel_height() / 2;
+        let Some(panel) = self.panel.as_mut() else {
+            self.panel_focused = false;
+            return;
+        };
+        match (key.code, key.modifiers) {
+            (Char('j') | Down, NONE) if panel.selected + 1 < panel.lines.len() => panel.selected += 1,
+            (Char('k') | Up, NONE) if panel.selected > 0 => panel.selected -= 1,
+            // 반 창씩
+            (Char('d'), CTRL) => panel.selected = (panel.selected + half).min(panel.lines.len().saturating_sub(1)),
+            (Char('u'), CTRL) => panel.selected = panel.selected.saturating_sub(half),
+            (Char(']'), CTRL) if panel.kind == PanelKind::Help => self.follow_help_link(),
+            (Char('t'), CTRL) if panel.kind == PanelKind::Help => self.help_back(),
+            (Char('q') | Esc, NONE) => self.close_panel(),
+            (Enter, _) => {
+                let (kind, selected) = (panel.kind, panel.selected);
+                match kind {
+                    PanelKind::Quickfix => {
+                        self.quickfix.current = selected;
+                        if self.jump_to_quickfix() {
+                            self.panel_focused = false;
+                        }
+                    }
+                    PanelKind::Marks => self.jump_to_listed_mark(selected),
+                    PanelKind::UndoTree => self.jump_to_listed_undo(selected),
+                    PanelKind::Replace => self.show_replace_line(selected),
+                    PanelKind::Tags => self.jump_to_listed_tag(selected),
+                    PanelKind::Diagnostics => self.jump_to_listed_diagnostic(selected),
+                    PanelKind::Help => self.follow_help_link(),
+                    PanelKind::Registers | PanelKind::Messages | PanelKind::Profile | PanelKind::Recovery | PanelKind::Lookup => {}
+                }
+            }
+            (Char(':'), NONE) => {
+                self.mode = Mode::Command;
+                self.command_buffer.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+// 뒤에 횟수를 받을 수 있는 연산자 키
+fn is_operator(key: KeyEvent) -> bool {
+    matches!(key.as_char(), Some('d' | 'y' | 'c' | '>' | '<' | '='))
+}
+
+// 숫자 키들로 된 횟수
+fn count_of(keys: &[KeyEvent]) -> Option<usize> {
+    let digits: String = keys.iter().map(|k| k.as_char().filter(|c| c.is_ascii_digit())).collect::<Option<_>>()?;
+    digits.parse().ok()
+}
+
+// Ctrl-V 다음에 친 키가 뜻하는 글자
+fn literal_char(key: KeyEvent) -> Option<char> {
+    match (key.code, key.modifiers) {
+        (Char(c), CTRL) if c.is_ascii_alphabetic() || "@[\\]^_".contains(c) => Some((c.to_ascii_uppercase() as u8 & 0x1f) as char),
+        (Char(c), _) => Some(c),
+        (Enter, _) => Some('\r'),
+        (Tab, _) => Some('\t'),
+        (Esc, _) => Some('\x1b'),
+        (Backspace, _) => Some('\x7f'),
+        _ => None,
+    }
+}
@@ -0,0 +1,72 @@
+// --- Key Mappings ---
+// :nnoremap / :inoremap 으로 정의한 매핑과 리더 키
+//   leader <Space>             리더 키 설정 (기본 \)
+//   nnoremap <leader>w :w<CR>  일반 모드에서 \w 를 누르면 ":w<CR>"을 입력한 것처럼 처리
+// 여러 키로 된 매핑은 앞부분을 누른 채로 TIMEOUT 동안 기다리고, 그래도 끝나지 않으면
+// 맞는 매핑을 실행하거나 눌렀던 키를 그대로 처리한다.
+use crate::keys::{self, KeyEvent};
+use std::time::Duration;
+
+pub const TIMEOUT: Duration = Duration::from_millis(1000);
+
+struct Mapping {
+    mode: String, // "n" 또는 "i"
+    lhs: String,  // 정규화된 표기, <leader>는 그대로 둠
+    rhs: String,
+}
+
+pub struct Keymaps {
+    pub leader: KeyEvent,
+    maps: Vec<Mapping>,
+}
+
+impl Keymaps {
+    pub fn new() -> Self {
+        // 기본 매핑 (nnoremap으로 바꾸거나 nunmap으로 지울 수 있음)
+        let defaults = [("n", "<leader>b", ":Buffers<CR>")];
+        let maps = defaults
+            .iter()
+            .map(|(mode, lhs, rhs)| Mapping { mode: mode.to_string(), lhs: lhs.to_string(), rhs: rhs.to_string() })
+            .collect();
+        Keymaps { leader: KeyEvent::char('\\'), maps }
+    }
+
+    // 같은 모드와 lhs가 있으면 바꿈
+    pub fn define(&mut self, mode: &str, lhs: &str, rhs: &str) {
+        let lhs = keys::normalize(lhs);
+        match self.maps.iter_mut().find(|m| m.mode == mode && m.lhs == lhs) {
+            Some(m) => m.rhs = rhs.to_string(),
+            None => self.maps.push(Mapping { mode: mode.to_string(), lhs, rhs: rhs.to_string() }),
+        }
+    }
+
+    pub fn remove(&mut self, mode: &str, lhs: &str) -> bool {
+        let lhs = keys::normalize(lhs);
+        let len = self.maps.len();
+        self.maps.retain(|m| !(m.mode == mode && m.lhs == lhs));
+        self.maps.len() != len
+    }
+
+    pub fn get(&self, mode: &str, typed: &str) -> Option<Vec<KeyEvent>> {
+        let m = self.maps.iter().find(|m| m.mode == mode && self.expand(&m.lhs) == typed)?;
+        Some(keys::parse(&m.rhs))
+    }
+
+    // 입력한 키들로 시작하는 (더 긴) 매핑이 있는지
+    pub fn has_prefix(&self, mode: &str, typed: &str) -> bool {
+        self.maps.iter().any(|m| m.mode == mode && is_prefix(typed, &self.expand(&m.lhs)))
+    }
+
+    pub fn list(&self, mode: &str) -> Vec<String> {
+        self.maps.iter().filter(|m| m.mode == mode).map(|m| format!("{} {}", m.lhs, m.rhs)).collect()
+    }
+
+    /// <leader>를 지금의 리더 키 표기로 바꿈
+    pub fn expand(&self, lhs: &str) -> String {
+        lhs.replace(keys::LEADER, &self.leader.notation())
+    }
+}
+
+pub fn is_prefix(typed: &str, lhs: &str) -> bool {
+    lhs.len() > typed.len() && lhs.starts_with(typed)
+}
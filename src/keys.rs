@@ -0,0 +1,343 @@
+// --- Key Events ---
+// 터미널에서 읽은 바이트를 KeyEvent로 해석하고, "<C-x>", "<CR>" 같은 표기와 서로 변환
+use std::fmt;
+use std::ops::BitOr;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum KeyCode {
+    Char(char),
+    Enter,
+    Esc,
+    Backspace,
+    Tab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    F(u8),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const SHIFT: Modifiers = Modifiers(1);
+    pub const ALT: Modifiers = Modifiers(2);
+    pub const CTRL: Modifiers = Modifiers(4);
+
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // xterm 방식의 수정자 번호 (CSI 1;5A 의 5 = 1 + Ctrl)
+    fn from_xterm(n: u32) -> Self {
+        Modifiers((n.saturating_sub(1) & 0x7) as u8)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+    fn bitor(self, other: Modifiers) -> Modifiers {
+        Modifiers(self.0 | other.0)
+    }
+}
+
+/// 키 하나와 함께 눌린 수정자. 글자는 Shift가 이미 반영된 모양으로 들어온다 ('A'는 Shift 없이 Char('A')).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct KeyEvent {
+    pub code: KeyCode,
+    pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+    pub const fn new(code: KeyCode, modifiers: Modifiers) -> Self {
+        KeyEvent { code, modifiers }
+    }
+
+    pub const fn plain(code: KeyCode) -> Self {
+        KeyEvent { code, modifiers: Modifiers::NONE }
+    }
+
+    pub const fn char(c: char) -> Self {
+        KeyEvent::plain(KeyCode::Char(c))
+    }
+
+    pub const fn ctrl(c: char) -> Self {
+        KeyEvent { code: KeyCode::Char(c), modifiers: Modifiers::CTRL }
+    }
+
+    pub const fn alt(c: char) -> Self {
+        KeyEvent { code: KeyCode::Char(c), modifiers: Modifiers::ALT }
+    }
+
+    /// 수정자 없이 입력된 글자
+    pub fn as_char(self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.is_empty() => Some(c),
+            _ => None,
+        }
+    }
+
+    /// "<C-x>", "a", "<lt>" 같은 표기 (스크립트 키 매핑의 이름으로도 씀)
+    pub fn notation(self) -> String {
+        let name = match self.code {
+            KeyCode::Char('<') => "lt".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.modifiers.is_empty() => return c.to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "CR".into(),
+            KeyCode::Esc => "Esc".into(),
+            KeyCode::Backspace => "BS".into(),
+            KeyCode::Tab => "Tab".into(),
+            KeyCode::Up => "Up".into(),
+            KeyCode::Down => "Down".into(),
+            KeyCode::Left => "Left".into(),
+            KeyCode::Right => "Right".into(),
+            KeyCode::Home => "Home".into(),
+            KeyCode::End => "End".into(),
+            KeyCode::PageUp => "PageUp".into(),
+            KeyCode::PageDown => "PageDown".into(),
+            KeyCode::Insert => "Insert".into(),
+            KeyCode::Delete => "Del".into(),
+            KeyCode::F(n) => format!("F{}", n),
+        };
+        let mut out = String::from("<");
+        for (m, prefix) in [(Modifiers::CTRL, "C-"), (Modifiers::ALT, "M-"), (Modifiers::SHIFT, "S-")] {
+            if self.modifiers.contains(m) {
+                out.push_str(prefix);
+            }
+        }
+        out.push_str(&name);
+        out.push('>');
+        out
+    }
+}
+
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.notation())
+    }
+}
+
+/// "ihello<Esc>", "<C-w>j" 같은 표기를 키 목록으로 변환
+pub fn parse(s: &str) -> Vec<KeyEvent> {
+    let mut out = Vec::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' && let Some(end) = rest.find('>') && let Some(key) = special_key(&rest[1..end]) {
+            out.push(key);
+            rest = &rest[end + 1..];
+            continue;
+        }
+        out.push(KeyEvent::char(c));
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// 매핑에서 리더 키 자리를 나타내는 표기 (매핑을 찾을 때 실제 리더 키로 바뀜)
+pub const LEADER: &str = "<leader>";
+
+/// 같은 키를 같은 문자열로 ("<c-W>" → "<C-w>", "<Leader>" → "<leader>")
+pub fn normalize(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.to_ascii_lowercase().find(LEADER) {
+        out.extend(parse(&rest[..i]).into_iter().map(KeyEvent::notation));
+        out.push_str(LEADER);
+        rest = &rest[i + LEADER.len()..];
+    }
+    out.extend(parse(rest).into_iter().map(KeyEvent::notation));
+    out
+}
+
+fn special_key(name: &str) -> Option<KeyEvent> {
+    let mut modifiers = Modifiers::NONE;
+    let mut rest = name;
+    // C-, M-/A-, S- 접두어 (순서 상관없음), 마지막 글자 하나는 키 이름으로 남김
+    while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+        modifiers = modifiers
+            | match rest.as_bytes()[0].to_ascii_lowercase() {
+                b'c' => Modifiers::CTRL,
+                b'm' | b'a' => Modifiers::ALT,
+                b's' => Modifiers::SHIFT,
+                _ => return None,
+            };
+        rest = &rest[2..];
+    }
+    let lower = rest.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "cr" | "enter" | "return" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "bs" => KeyCode::Backspace,
+        "space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "bar" => KeyCode::Char('|'),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "insert" => KeyCode::Insert,
+        "del" | "delete" => KeyCode::Delete,
+        _ => {
+            if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) && (1..=24).contains(&n) {
+                KeyCode::F(n)
+            } else {
+                let mut chars = rest.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() || modifiers.is_empty() {
+                    return None;
+                }
+                // <C-X>와 <C-x>는 같은 키 (터미널이 구분하지 못함)
+                KeyCode::Char(if modifiers.contains(Modifiers::CTRL) { c.to_ascii_lowercase() } else { c })
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// 바이트열 앞부분에서 키 하나를 해석해 (키, 사용한 바이트 수) 반환.
+/// 키를 뗀 이벤트나 터미널의 응답처럼 키 입력이 아닌 시퀀스는 키 자리가 None.
+/// 이스케이프 시퀀스나 UTF-8 글자가 아직 다 도착하지 않았으면 None.
+/// ESC 하나만 있으면 Alt 조합의 앞부분일 수 있으므로 역시 None (기다려도 안 오면 decode_partial).
+pub fn decode(bytes: &[u8]) -> Option<(Option<KeyEvent>, usize)> {
+    let b = *bytes.first()?;
+    let key = match b {
+        0x1b => return decode_escape(bytes),
+        b'\r' | b'\n' => KeyEvent::plain(KeyCode::Enter),
+        b'\t' => KeyEvent::plain(KeyCode::Tab),
+        0x7f | 0x08 => KeyEvent::plain(KeyCode::Backspace),
+        0x00 => KeyEvent::ctrl(' '),
+        0x01..=0x1a => KeyEvent::ctrl((b'a' + b - 1) as char),
+        0x1c..=0x1f => KeyEvent::ctrl((b'\\' + b - 0x1c) as char),
+        0x20..=0x7e => KeyEvent::char(b as char),
+        _ => {
+            // UTF-8 여러 바이트 글자
+            let len = match b {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Some((Some(KeyEvent::char('\u{FFFD}')), 1)),
+            };
+            let text = bytes.get(..len)?;
+            let c = std::str::from_utf8(text).ok().and_then(|s| s.chars().next()).unwrap_or('\u{FFFD}');
+            return Some((Some(KeyEvent::char(c)), len));
+        }
+    };
+    Some((Some(key), 1))
+}
+
+/// 더 기다려도 바이트가 오지 않을 때 decode 대신 씀: 잘린 시퀀스는 앞부분만 해석
+pub fn decode_partial(bytes: &[u8]) -> (KeyEvent, usize) {
+    match bytes {
+        // "ESC [" 나 "ESC O" 에서 멈췄으면 Alt-[ / Alt-O
+        [0x1b, b @ (b'[' | b'O'), ..] => (KeyEvent::alt(*b as char), 2),
+        [0x1b, ..] => (KeyEvent::plain(KeyCode::Esc), 1),
+        _ => (KeyEvent::char('\u{FFFD}'), 1),
+    }
+}
+
+fn decode_escape(bytes: &[u8]) -> Option<(Option<KeyEvent>, usize)> {
+    let esc = KeyEvent::plain(KeyCode::Esc);
+    match bytes.get(1) {
+        None => None,
+        Some(0x1b) => Some((Some(esc), 1)),
+        Some(b'[') => {
+            // CSI: ESC [ 숫자;숫자 ... 끝 글자
+            let end = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b))? + 2;
+            Some((decode_csi(&bytes[2..end], bytes[end]), end + 1))
+        }
+        Some(b'O') => {
+            // SS3: ESC O P 등 (F1~F4, 애플리케이션 모드의 화살표)
+            let code = match *bytes.get(2)? {
+                b'P' => KeyCode::F(1),
+                b'Q' => KeyCode::F(2),
+                b'R' => KeyCode::F(3),
+                b'S' => KeyCode::F(4),
+                b'A' => KeyCode::Up,
+                b'B' => KeyCode::Down,
+                b'C' => KeyCode::Right,
+                b'D' => KeyCode::Left,
+                b'H' => KeyCode::Home,
+                b'F' => KeyCode::End,
+                _ => return Some((Some(esc), 1)),
+            };
+            Some((Some(KeyEvent::plain(code)), 3))
+        }
+        // ESC 뒤에 바로 붙어 온 키는 Alt 조합 (터미널이 Alt-j를 "ESC j"로 보냄)
+        Some(_) => {
+            let (key, len) = decode(&bytes[1..])?;
+            Some((key.map(|k| KeyEvent::new(k.code, k.modifiers | Modifiers::ALT)), len + 1))
+        }
+    }
+}
+
+// CSI 시퀀스 하나 (ESC [ 뒤의 인자와 끝 글자). 인자는 "코드:대체코드;수정자:이벤트" 꼴
+fn decode_csi(params: &[u8], last: u8) -> Option<KeyEvent> {
+    let params = std::str::from_utf8(params).ok()?;
+    // ? > < = 로 시작하면 키가 아니라 터미널의 응답
+    if params.starts_with(['?', '>', '<', '=']) {
+        return None;
+    }
+    let field = |i: usize, j: usize| -> u32 {
+        params.split(';').nth(i).and_then(|p| p.split(':').nth(j)).and_then(|n| n.parse().ok()).unwrap_or(1)
+    };
+    // 이벤트 종류: 1 누름, 2 반복, 3 뗌 (kitty 프로토콜에서 뗀 이벤트를 요청했을 때)
+    if field(1, 1) == 3 {
+        return None;
+    }
+    let modifiers = Modifiers::from_xterm(field(1, 0));
+    let code = match last {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'P' => KeyCode::F(1),
+        b'Q' => KeyCode::F(2),
+        b'R' => KeyCode::F(3),
+        b'S' => KeyCode::F(4),
+        b'Z' => return Some(KeyEvent::new(KeyCode::Tab, Modifiers::SHIFT)),
+        b'~' => match field(0, 0) {
+            1 | 7 => KeyCode::Home,
+            2 => KeyCode::Insert,
+            3 => KeyCode::Delete,
+            4 | 8 => KeyCode::End,
+            5 => KeyCode::PageUp,
+            6 => KeyCode::PageDown,
+            n @ 11..=15 => KeyCode::F((n - 10) as u8),
+            n @ 17..=21 => KeyCode::F((n - 11) as u8),
+            n @ 23..=24 => KeyCode::F((n - 12) as u8),
+            _ => return None,
+        },
+        // kitty 키보드 프로토콜: CSI 유니코드 ; 수정자 u
+        b'u' => match field(0, 0) {
+            9 => KeyCode::Tab,
+            13 => KeyCode::Enter,
+            27 => KeyCode::Esc,
+            127 => KeyCode::Backspace,
+            // 57344부터는 키패드, 미디어 키, 수정자 키 단독 입력 (쓰지 않음)
+            n if n >= 57344 => return None,
+            n => KeyCode::Char(char::from_u32(n)?),
+        },
+        _ => return None,
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
@@ -0,0 +1,1189 @@
+// --- vii Editor Core ---
+// 에디터 상태(EditorConfig)와 편집 로직, 화면 그리기를 담은 라이브러리.
+// main.rs는 터미널을 로우 모드로 바꾸고 인자를 처리하는 연결 코드만 가진다.
+//
+//   buffer    줄 단위 텍스트 버퍼 (Row, EditorBuffer)
+//   input     모드별 키 처리 (EditorConfig::handle_keypress)
+//   keys      키 이벤트와 "<C-x>" 표기, 터미널 입력 해석
+//   commands  ex 명령 실행 (EditorConfig::run_command)
+//   render    화면 그리기 (refresh_screen)
+//   terminal  터미널 백엔드 트레잇과 구현 (실제 터미널, 테스트용 메모리 터미널)
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+mod abbrev;
+mod arglist;
+mod artifacts;
+mod attributes;
+mod autocmd;
+pub mod batch;
+pub mod buffer;
+mod buffers;
+mod case;
+mod changelist;
+pub mod cli;
+mod clipboard;
+mod cmdnames;
+pub mod commands;
+mod completion;
+mod config;
+mod conflict;
+mod diagnostics;
+mod diff;
+mod diffview;
+mod editorconfig;
+pub mod error;
+mod filetype;
+mod flash;
+mod fold;
+mod formatting;
+mod git;
+mod goto;
+mod grep;
+mod help;
+mod hover;
+pub mod harness;
+pub mod input;
+mod json;
+mod keymap;
+mod lock;
+mod lookup;
+pub mod keys;
+mod lsp;
+mod make;
+mod message;
+mod modeline;
+mod movelines;
+mod multicursor;
+mod number;
+mod options;
+mod paste;
+mod picker;
+mod profile;
+mod quickfix;
+mod range;
+mod recovery;
+mod registers;
+pub mod render;
+mod rename;
+mod replace;
+mod script;
+mod scrolling;
+mod search;
+mod signature;
+pub mod server;
+mod sort;
+mod startup;
+mod substitute;
+mod symbols;
+pub mod syntax;
+mod tabs;
+mod tags;
+pub mod terminal;
+mod tutor;
+mod undo;
+mod usercmd;
+mod virtualtext;
+mod visual;
+mod window;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod word;
+mod wrap;
+mod zen;
+
+pub use buffer::{EditorBuffer, Row};
+use abbrev::Abbreviations;
+use arglist::ArgList;
+use autocmd::Autocmds;
+use buffers::BufferList;
+use changelist::ChangeList;
+use cmdnames::CommandAliases;
+use completion::Completion;
+use diffview::DiffView;
+use error::Error;
+use filetype::FiletypeRules;
+use fold::Folds;
+use git::GitGutter;
+use keymap::Keymaps;
+use keys::KeyEvent;
+use lock::Locks;
+use lsp::Lsp;
+use message::MessageLine;
+use options::{BufferOptions, Options};
+use picker::Picker;
+use profile::Profiler;
+use quickfix::QuickfixList;
+pub use render::refresh_screen;
+use registers::Registers;
+use replace::ProjectReplace;
+use script::Scripting;
+use search::Search;
+use syntax::Highlighter;
+use tags::TagStack;
+use terminal::{Input, Terminal};
+use undo::UndoTree;
+use usercmd::UserCommands;
+use visual::Selection;
+use window::Window;
+
+/// 편집 모드
+#[derive(PartialEq, Clone, Copy)]
+pub enum Mode {
+    Normal,
+    Insert,
+    Command,
+    Visual,
+}
+
+impl Mode {
+    /// 스크립트, 서버, ModeChanged 가 쓰는 한 글자 이름
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Mode::Normal => "n",
+            Mode::Insert => "i",
+            Mode::Command => "c",
+            Mode::Visual => "v",
+        }
+    }
+}
+
+// 화면 아래쪽에 열리는 목록 창 (quickfix 결과 등)
+#[derive(PartialEq, Clone, Copy)]
+pub(crate) enum PanelKind {
+    Quickfix,
+    Registers,
+    Marks,
+    UndoTree,
+    Messages,
+    Profile,
+    Recovery,
+    Replace,
+    Tags,
+    Diagnostics,
+    Lookup,
+    Help,
+}
+
+pub(crate) struct Panel {
+    kind: PanelKind,
+    title: String,
+    lines: Vec<String>,
+    selected: usize,
+    offset: usize,
+}
+
+const PANEL_HEIGHT: usize = 10;
+
+// 화면을 다시 그리는 최소 간격 (초당 60번 정도)
+const FRAME: Duration = Duration::from_millis(16);
+
+/// 에디터 전체 상태: 버퍼, 커서, 화면 크기와 스크롤, 부가 기능들
+pub struct EditorConfig {
+    pub cx: usize, // 커서 열 (0부터)
+    pub cy: usize, // 커서 줄 (0부터)
+    pub screen_cols: u16,
+    pub screen_rows: u16,
+    pub row_offset: usize,
+    pub col_offset: usize, // 화면 왼쪽에 표시될 열의 인덱스 (바이트가 아닌 글자 수)
+    pub mode: Mode,
+    pub buffer: EditorBuffer,
+    pub command_buffer: String,
+    prompt: char, // 명령 줄 앞의 글자 (: 는 ex 명령, / 와 ? 는 찾기)
+    last_command: Option<String>, // @: 로 되풀이할 마지막 명령 줄
+    last_executed: Option<char>,  // @@ 가 되풀이할 마지막 @ 의 레지스터
+    pub status_msg: String,
+    pub filename: Option<String>,
+    quickfix: QuickfixList,
+    panel: Option<Panel>,
+    panel_focused: bool,
+    pending: Vec<KeyEvent>, // Ctrl-W, zf처럼 다음 키를 기다리는 접두 키들
+    keymaps: Keymaps,
+    abbreviations: Abbreviations,
+    pending_keys: Vec<KeyEvent>, // 더 긴 매핑의 앞부분으로 입력된 키들
+    pending_keys_since: Option<Instant>,
+    change: Option<(u64, Vec<KeyEvent>)>, // 기록 중인 변경 (시작할 때의 버퍼 버전, 키들)
+    last_change: Vec<KeyEvent>,           // . 으로 되풀이할 마지막 변경
+    registers: Registers,
+    register: Option<char>, // "x 로 고른, 다음 복사/삭제/붙여넣기가 쓸 레지스터
+    count: Option<usize>,   // 명령 앞에 친 횟수 (5<C-a> 등)
+    marks: BTreeMap<char, (usize, usize)>, // 마크 이름 → (줄, 열)
+    folds: Folds,
+    annotations: BTreeMap<usize, String>, // 줄 뒤에 붙여 보여줄 주석 (가상 글)
+    changes: ChangeList,
+    undo: UndoTree,
+    cursors: Vec<(usize, usize)>, // 주 커서 말고 더 둔 커서들 (Ctrl-N)
+    arglist: ArgList,             // 명령 줄에서 받은 파일들 (:args, :argdo)
+    selection: Option<Selection>,                      // 비주얼 모드의 선택
+    last_selection: Option<(Selection, (usize, usize))>, // gv 로 다시 고를 선택과 그때의 커서
+    buffers: BufferList,
+    branch: Option<String>, // 상태 줄에 보여줄 git 브랜치 (열 때, 저장할 때, 버퍼를 바꿀 때 갱신)
+    picker: Option<Picker>, // 화면 가운데의 선택 창
+    title: String,          // 마지막으로 터미널에 보낸 창 제목 (바뀔 때만 다시 보냄)
+    lsp: Lsp,
+    make_diagnostics: BTreeMap<PathBuf, Vec<lsp::Diagnostic>>, // 마지막 :make 가 찾은 진단들 (파일의 절대 경로마다)
+    canonical: Option<PathBuf>, // 지금 버퍼 파일의 절대 경로 (열 때, 저장할 때, 버퍼를 바꿀 때 다시 구함)
+    completion: Option<Completion>,
+    hover: Option<Vec<String>>, // K 로 받은 설명, 커서 옆의 창에 보여주고 다음 키에 닫음
+    signature: Option<signature::Signature>, // 입력 중인 함수 호출의 시그니처
+    signature_at: (usize, usize, u64),       // 시그니처를 물은 커서 위치와 버퍼 버전
+    smooth: scrolling::SmoothScroll,          // smoothscroll 로 움직이는 중인 화면
+    flash: Option<flash::Flash>,              // 방금 복사한 부분
+    git: GitGutter,
+    search: Search,
+    tags: TagStack, // Ctrl-] 로 뛴 자리들과 :tselect 의 항목들
+    help_stack: Vec<usize>, // 도움말 창에서 링크를 따라오기 전의 줄들 (Ctrl-T)
+    syntax: Highlighter,
+    blame: Option<Vec<git::BlameLine>>, // :Blame으로 켠 blame 열
+    zen: bool,                          // :Zen 으로 켠 글쓰기 화면
+    blame_version: u64,
+    diff: Option<DiffView>, // 좌우 비교 모드
+    conflicts: Vec<conflict::Conflict>,
+    conflicts_version: Option<u64>,
+    script: Option<Scripting>, // 스크립트 실행 중에는 잠시 꺼내 둠
+    user_commands: UserCommands,
+    command_aliases: CommandAliases,
+    options: Options,
+    window: Window,
+    buffer_options: BufferOptions,  // 지금 버퍼의 설정 (tabstop, shiftwidth)
+    buffer_defaults: BufferOptions, // 새로 여는 버퍼가 물려받는 값
+    filetype_rules: FiletypeRules,  // :filetype 으로 더한 판별 규칙
+    autocmds: Autocmds,
+    messages: MessageLine, // 상태 줄 메시지가 보인 시각과 오류 여부
+    profiler: Profiler,    // :profile 로 켜는 시간 재기
+    recovery: Option<PathBuf>, // 복구 창에서 고르기를 기다리는 복구 파일
+    project_replace: Option<ProjectReplace>, // :greplace 로 찾은, 바꾸기를 기다리는 줄들
+    locks: Locks,              // 연 파일들의 잠금 파일 (에디터를 버릴 때 지움)
+    command_depth: usize, // 사용자 명령이 자기 자신을 부르며 끝없이 도는 것을 막음
+    #[cfg(feature = "wasm")]
+    wasm: Option<wasm::WasmPlugins>,
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditorConfig {
+    /// 빈 버퍼로 시작하는 에디터 (화면 크기는 run이 터미널에 맞춰 바꿈)
+    pub fn new() -> Self {
+        EditorConfig {
+            cx: 0,
+            cy: 0,
+            screen_cols: 80,
+            screen_rows: 24,
+            row_offset: 0, // 0번 줄부터 시작
+            col_offset: 0,
+            mode: Mode::Normal,
+            buffer: EditorBuffer::new(),
+            command_buffer: String::new(),
+            prompt: ':',
+            last_command: None,
+            last_executed: None,
+            status_msg: String::from("WELCOME! :q to quit"),
+            filename: None,
+            quickfix: QuickfixList::new(),
+            panel: None,
+            panel_focused: false,
+            pending: Vec::new(),
+            keymaps: Keymaps::new(),
+            abbreviations: Abbreviations::new(),
+            pending_keys: Vec::new(),
+            pending_keys_since: None,
+            change: None,
+            last_change: Vec::new(),
+            registers: Registers::new(),
+            register: None,
+            count: None,
+            marks: BTreeMap::new(),
+            folds: Folds::new(),
+            annotations: BTreeMap::new(),
+            changes: ChangeList::new(),
+            undo: UndoTree::new(),
+            cursors: Vec::new(),
+            arglist: ArgList::new(),
+            selection: None,
+            last_selection: None,
+            buffers: BufferList::new(),
+            branch: None,
+            picker: None,
+            title: String::new(),
+            lsp: Lsp::new(),
+            make_diagnostics: BTreeMap::new(),
+            canonical: None,
+            completion: None,
+            hover: None,
+            signature: None,
+            signature_at: (0, 0, 0),
+            smooth: scrolling::SmoothScroll::default(),
+            flash: None,
+            git: GitGutter::new(),
+            search: Search::new(),
+            tags: TagStack::new(),
+            help_stack: Vec::new(),
+            syntax: Highlighter::new(),
+            blame: None,
+            zen: false,
+            blame_version: 0,
+            diff: None,
+            conflicts: Vec::new(),
+            conflicts_version: None,
+            script: Scripting::new().ok(),
+            user_commands: UserCommands::new(),
+            command_aliases: CommandAliases::new(),
+            options: Options::new(),
+            window: Window::new(),
+            buffer_options: BufferOptions::new(),
+            buffer_defaults: BufferOptions::new(),
+            filetype_rules: FiletypeRules::new(),
+            autocmds: Autocmds::new(),
+            messages: MessageLine::new(),
+            profiler: Profiler::new(),
+            recovery: None,
+            project_replace: None,
+            locks: Locks::new(),
+            command_depth: 0,
+            #[cfg(feature = "wasm")]
+            wasm: None,
+        }
+    }
+
+    /// 명령 줄의 파일들을 인자 목록으로 두고 파일마다 버퍼를 만든 뒤 첫 파일에서 시작
+    pub fn set_arglist(&mut self, files: &[String]) {
+        self.arglist.set(files.to_vec());
+        for file in files {
+            self.edit_file(file);
+        }
+        if files.len() > 1 {
+            self.edit_file(&files[0]);
+            self.status_msg = format!("{} files to edit", files.len());
+        }
+    }
+
+    // 새로 채운 버퍼에 맞게 커서, 접기, 실행 취소 기록, git 정보 같은 버퍼마다의 상태를 처음으로 (open_file, :Tutor)
+    pub(crate) fn reset_buffer_state(&mut self) {
+        self.cx = 0;
+        self.cy = 0;
+        self.row_offset = 0;
+        self.col_offset = 0;
+        self.folds = Folds::new();
+        self.buffer_options = self.buffer_defaults.clone();
+        self.annotations.clear();
+        self.changes = ChangeList::new();
+        self.undo = UndoTree::new();
+        self.cursors.clear();
+        self.last_selection = None;
+        self.git.reset();
+        self.syntax.reset();
+        self.branch = git::branch(self.filename.as_deref());
+        self.blame = None;
+        self.diff = None;
+        self.refresh_canonical();
+        self.update_conflicts();
+    }
+
+    /// 파일을 열어 버퍼를 바꿈, 없는 파일이면 새 파일로 시작
+    pub fn open_file(&mut self, filename: &str) {
+        let mut buffer = EditorBuffer::new();
+        let (format, exists, read_failed) = match buffer.open(filename) {
+            Ok(format) => {
+                self.status_msg = format!("Opened: {}", filename);
+                (Some(format), true, false)
+            }
+            // 파일이 없으면 새 파일로 간주
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.status_msg = format!("New file: {}", filename);
+                (None, false, false)
+            }
+            Err(e) => {
+                self.error(Error::Io(filename.to_string(), e).to_string());
+                (None, false, true)
+            }
+        };
+        self.buffer = buffer;
+        self.filename = Some(filename.to_string());
+        self.reset_buffer_state();
+        self.buffer_options.filetype = filetype::detect_buffer(&self.filetype_rules, filename, &self.buffer.rows[0].content);
+        if let Some(format) = format {
+            self.buffer_options.fileformat = format;
+        }
+        self.apply_editorconfig(filename);
+        // 읽지 못한 파일을 빈 버퍼로 덮어쓰지 않도록 :w 를 막음
+        self.buffer_options.readonly |= read_failed;
+        if !self.conflicts.is_empty() {
+            self.status_msg = format!("{} ({} merge conflicts)", self.status_msg, self.conflicts.len());
+        }
+        self.emit(if exists { "BufReadPost" } else { "BufNewFile" });
+        self.apply_modelines();
+        if let Some(filetype) = self.buffer_options.filetype.clone() {
+            self.emit_for("FileType", &filetype);
+        }
+        self.emit("BufEnter");
+
+        if let Some(filetype) = self.buffer_options.filetype.clone() {
+            let filetype = filetype.as_str();
+            let text = self.buffer.rows_to_string();
+            if let Err(e) = self.lsp.open(filename, filetype, &text, self.buffer.version) {
+                self.error(format!("LSP: failed to start {} server: {}", filetype, e));
+            }
+        }
+        self.read_undo_file(filename);
+        self.check_recovery(filename);
+        self.lock_file(filename);
+    }
+
+    /// 표준 입력에서 읽은 글로 이름 없는 버퍼를 채움 (vii -), 저장하려면 :w 파일 이름
+    pub fn load_text(&mut self, text: &str) {
+        self.buffer_options.fileformat = self.buffer.load(text);
+        self.buffer_options.filetype = filetype::detect_buffer(&self.filetype_rules, "", &self.buffer.rows[0].content);
+        self.status_msg = format!("Read {} lines from stdin", self.buffer.rows.len());
+        if let Some(filetype) = self.buffer_options.filetype.clone() {
+            self.emit_for("FileType", &filetype);
+        }
+    }
+
+    /// 키 입력이 없을 때 호출: LSP와 동기화하고 응답을 처리, 다시 그려야 하면 true
+    pub fn poll_background(&mut self) -> bool {
+        let start = self.profiler.start();
+        if let Some(name) = &self.filename && self.lsp.is_stale(name, self.buffer.version) {
+            let text = self.buffer.rows_to_string();
+            let _ = self.lsp.change(name, self.buffer.version, &text);
+        }
+        let lsp_changed = self.lsp.poll();
+        for response in self.lsp.take_responses() {
+            self.handle_lsp_response(response);
+        }
+        let lsp_changed = self.refresh_diagnostics_panel() || lsp_changed;
+        self.profiler.stop("lsp", start);
+        let start = self.profiler.start();
+        if let Some(name) = &self.filename && self.git.needs_update(self.buffer.version) {
+            let lines = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+            self.git.request(name, self.buffer.version, lines);
+        }
+        let git_changed = self.git.poll();
+        self.profiler.stop("git", start);
+        // blame 열이 켜져 있으면 바뀐 내용으로 다시 계산
+        let mut blame_changed = false;
+        if self.blame.is_some() && self.blame_version != self.buffer.version {
+            self.refresh_blame();
+            blame_changed = true;
+        }
+        let start = self.profiler.start();
+        let syntax_changed = self.update_highlight();
+        self.profiler.stop("syntax", start);
+        let start = self.profiler.start();
+        let search_changed = self.poll_search();
+        self.profiler.stop("search", start);
+        let message_expired = self.expire_message();
+        lsp_changed || git_changed || syntax_changed || search_changed || blame_changed || message_expired
+    }
+
+    fn toggle_blame(&mut self) {
+        if self.blame.is_some() {
+            self.blame = None;
+            return;
+        }
+        if self.filename.is_none() {
+            self.status_msg = "No file name".into();
+            return;
+        }
+        self.blame = Some(Vec::new());
+        self.refresh_blame();
+    }
+
+    fn refresh_blame(&mut self) {
+        let Some(name) = &self.filename else { return };
+        self.blame_version = self.buffer.version;
+        match git::blame(name, &self.buffer.rows_to_string()) {
+            Ok(lines) => self.blame = Some(lines),
+            Err(e) => {
+                self.blame = None;
+                self.error(format!("Blame: {}", e));
+            }
+        }
+    }
+
+    /// :diffsplit 파일을 오른쪽에 열고 비교 모드로 전환
+    pub fn diff_split(&mut self, filename: &str) {
+        if filename.is_empty() {
+            self.status_msg = "Usage: :diffsplit <file>".into();
+            return;
+        }
+        let mut other = EditorBuffer::new();
+        if let Err(e) = other.open(filename) {
+            self.error(format!("Error: {}: {}", filename, e));
+            return;
+        }
+        let mut view = DiffView::new(other, filename.to_string());
+        view.update(&self.buffer);
+        self.status_msg = format!("Diff with {}: {} changes", filename, view.change_count());
+        self.diff = Some(view);
+        self.row_offset = 0;
+    }
+
+    // 비교 모드에서 한쪽 창의 폭
+    fn pane_width(&self) -> usize {
+        ((self.screen_cols as usize).saturating_sub(1) / 2).max(1)
+    }
+
+    // 편집 영역의 가로 폭
+    fn text_cols(&self) -> usize {
+        if self.diff.is_some() {
+            return self.pane_width();
+        }
+        if self.zen {
+            return self.options.zenwidth.min(self.screen_cols as usize).max(1);
+        }
+        (self.screen_cols as usize).saturating_sub(self.left_margin() + self.right_margin()).max(1)
+    }
+
+    // 커서가 있는 줄이 화면 기준으로 몇 번째 줄인지 (비교 모드에서는 채움 줄 포함, 닫힌 접기는 한 줄)
+    fn cursor_display_row(&self) -> usize {
+        match &self.diff {
+            Some(view) => view.display_row(self.cy),
+            None => self.folds.display_row(self.cy),
+        }
+    }
+
+    // 화면 줄로 센 버퍼의 길이 (비교 모드에서는 채움 줄 포함, 닫힌 접기는 한 줄)
+    fn display_rows(&self) -> usize {
+        match &self.diff {
+            Some(view) => view.lines.len(),
+            None => self.folds.display_row(self.buffer.rows.len()),
+        }
+    }
+
+    // 텍스트 오른쪽의 스크롤 막대 열 (비교 모드에서는 없음)
+    fn right_margin(&self) -> usize {
+        usize::from(self.window.options.scrollbar && self.diff.is_none() && !self.zen)
+    }
+
+    // 텍스트 왼쪽에 붙는 열들(blame, 표시 열, 접기 열)의 전체 폭, :Zen 이면 가운데로 미는 빈 칸
+    fn left_margin(&self) -> usize {
+        if self.diff.is_some() {
+            return 0;
+        }
+        if self.zen {
+            return self.zen_padding();
+        }
+        let blame = if self.blame.is_some() { git::BlameLine::WIDTH } else { 0 };
+        blame + self.gutter_width() + self.fold_column_width() + self.number_width()
+    }
+
+    // set number 이면 가장 큰 줄 번호의 자릿수(최소 3)에 한 칸을 더한 줄 번호 열
+    fn number_width(&self) -> usize {
+        if self.window.options.number && !self.zen { self.buffer.rows.len().to_string().len().max(3) + 1 } else { 0 }
+    }
+
+    // 접기가 있으면 두 칸짜리 접기 열을 둔다
+    fn fold_column_width(&self) -> usize {
+        if self.folds.is_empty() || self.zen { 0 } else { 2 }
+    }
+
+    // 진단이나 git 변경 표시가 있으면 왼쪽에 두 칸짜리 표시 열을 둔다
+    fn gutter_width(&self) -> usize {
+        if self.zen || self.diagnostics_iter().next().is_none() && !self.git.has_signs() { 0 } else { 2 }
+    }
+
+    fn is_current_file(&self, filename: &str) -> bool {
+        match &self.filename {
+            Some(name) if name == filename => true,
+            Some(name) => match (std::fs::canonicalize(name), std::fs::canonicalize(filename)) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    // :42, 42G, gg — 줄(1부터, 버퍼 안으로 맞춤)의 첫 공백 아닌 글자로
+    pub(crate) fn goto_line(&mut self, line: usize) {
+        self.cy = line.clamp(1, self.buffer.rows.len()) - 1;
+        self.first_non_blank();
+    }
+
+    // ga: 커서 글자의 코드 값 "<é> 233, Hex 00e9, Oct 351, UTF-8 c3 a9"
+    fn show_char_info(&mut self) {
+        let Some(c) = self.buffer.rows[self.cy].content[self.cx..].chars().next() else {
+            self.status_msg = "NUL".into();
+            return;
+        };
+        let shown = if c.is_control() { format!("^{}", char::from_u32(c as u32 ^ 0x40).unwrap_or('?')) } else { c.to_string() };
+        let mut bytes = [0; 4];
+        let utf8: Vec<String> = c.encode_utf8(&mut bytes).bytes().map(|b| format!("{:02x}", b)).collect();
+        let code = c as u32;
+        self.status_msg = format!("<{}> {}, Hex {:04x}, Oct {:o}, UTF-8 {}", shown, code, code, code, utf8.join(" "));
+    }
+
+    // 파일의 특정 위치(1부터 시작하는 줄/열)로 이동, 필요하면 파일을 연다
+    fn jump_to(&mut self, filename: &str, line: usize, col: usize) -> bool {
+        if !self.is_current_file(filename) {
+            if self.buffer.dirty {
+                self.error("No write since last change (:w first)");
+                return false;
+            }
+            self.open_file(filename);
+        }
+        self.cy = line.saturating_sub(1).min(self.buffer.rows.len() - 1);
+        self.cx = col.saturating_sub(1).min(self.buffer.rows[self.cy].content.len());
+        true
+    }
+
+    fn move_cursor(&mut self, key: char) {
+        let row_count = self.buffer.rows.len();
+        let content = &self.buffer.rows[self.cy].content;
+        match key {
+            // 여러 바이트 글자도 한 글자씩
+            'h' if self.cx > 0 => self.cx = content[..self.cx].char_indices().next_back().map_or(0, |(i, _)| i),
+            // 닫힌 접기는 한 줄처럼 건너뜀
+            'j' => {
+                let last = self.folds.closed_at(self.cy).map_or(self.cy, |(_, end)| end);
+                if last < row_count - 1 {
+                    self.cy = last + 1;
+                }
+            }
+            'k' if self.cy > 0 => self.cy = self.folds.closed_at(self.cy - 1).map_or(self.cy - 1, |(start, _)| start),
+            'l' if self.cx < content.len() => self.cx += content[self.cx..].chars().next().map_or(1, char::len_utf8),
+            _ => {}
+        }
+        // 다른 줄로 옮겼으면 줄 끝과 글자 경계 안으로
+        let content = &self.buffer.rows[self.cy].content;
+        self.cx = self.cx.min(content.len());
+        while !content.is_char_boundary(self.cx) {
+            self.cx -= 1;
+        }
+    }
+
+    fn insert_char(&mut self, c: char) {
+        self.buffer.rows[self.cy].insert_char(self.cx, c);
+        self.buffer.touch();
+        self.cx += c.len_utf8();
+    }
+
+    fn insert_newline(&mut self) {
+        let remaining = self.buffer.rows[self.cy].content.split_off(self.cx);
+        self.buffer.rows.insert(self.cy + 1, Row::new(remaining));
+        self.buffer.touch();
+        self.cy += 1;
+        self.cx = 0;
+    }
+
+    // 줄바꿈을 포함한 글자 입력 (스크립트용)
+    fn insert_text_char(&mut self, c: char) {
+        if c == '\n' {
+            self.insert_newline();
+        } else {
+            self.insert_char(c);
+        }
+    }
+
+    // 버퍼가 바뀐 뒤 커서가 범위를 벗어나지 않도록 맞춤
+    fn clamp_cursor(&mut self) {
+        self.cy = self.cy.min(self.buffer.rows.len() - 1);
+        self.cx = self.cx.min(self.buffer.rows[self.cy].content.len());
+    }
+
+    // 스크립트를 잠시 꺼내서 에디터와 함께 실행, 오류는 상태 줄에 표시
+    fn with_script(&mut self, f: impl FnOnce(&Scripting, &mut EditorConfig) -> mlua::Result<()>) {
+        let Some(script) = self.script.take() else { return };
+        if let Err(e) = f(&script, self) {
+            let msg = e.to_string();
+            self.error(format!("Lua: {}", msg.lines().next().unwrap_or("")));
+        }
+        self.script = Some(script);
+    }
+
+    // WebAssembly 플러그인은 버퍼 사본을 받아서 실행하고, 끝나면 바뀐 내용을 반영
+    #[cfg(feature = "wasm")]
+    fn wasm_snapshot(&self) -> wasm::Snapshot {
+        wasm::Snapshot {
+            lines: self.buffer.rows.iter().map(|r| r.content.clone()).collect(),
+            cy: self.cy,
+            cx: self.cx,
+            message: None,
+            modified: false,
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    fn apply_wasm_snapshot(&mut self, view: wasm::Snapshot) {
+        if view.modified {
+            self.buffer.rows = view.lines.into_iter().map(Row::new).collect();
+            if self.buffer.rows.is_empty() {
+                self.buffer.rows.push(Row::new(String::new()));
+            }
+            self.buffer.touch();
+        }
+        self.cy = view.cy;
+        self.cx = view.cx;
+        self.clamp_cursor();
+        if let Some(msg) = view.message {
+            self.status_msg = msg;
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    fn load_wasm_plugins(&mut self) {
+        let Some(dir) = config::config_dir() else { return };
+        let mut view = self.wasm_snapshot();
+        let (plugins, errors) = wasm::WasmPlugins::load(&dir.join("plugins"), &mut view);
+        self.apply_wasm_snapshot(view);
+        if let Some(e) = errors.first() {
+            self.error(format!("Wasm: {}", e));
+        }
+        self.wasm = Some(plugins);
+    }
+
+    #[cfg(feature = "wasm")]
+    fn run_wasm_command(&mut self, cmd: &str, args: &str) -> bool {
+        let Some(mut plugins) = self.wasm.take() else { return false };
+        let found = plugins.has_command(cmd);
+        if found {
+            let mut view = self.wasm_snapshot();
+            let result = plugins.run_command(cmd, args, &mut view);
+            self.apply_wasm_snapshot(view);
+            if let Err(e) = result {
+                self.error(format!("Wasm: {}", e));
+            }
+        }
+        self.wasm = Some(plugins);
+        found
+    }
+
+    fn delete_char(&mut self) {
+        if self.cx == 0 && self.cy == 0 { return; }
+        self.buffer.touch();
+        if self.cx > 0 {
+            self.buffer.rows[self.cy].delete_char(self.cx - 1);
+            self.cx -= 1;
+        } else {
+            let current_row_content = self.buffer.rows.remove(self.cy).content;
+            self.cy -= 1;
+            let prev_row = &mut self.buffer.rows[self.cy];
+            self.cx = prev_row.content.len();
+            prev_row.content.push_str(&current_row_content);
+        }
+    }
+
+    // Ctrl-W: 커서 앞의 단어 하나(와 그 뒤 공백)를 지움, 줄 맨 앞이면 윗줄과 합침
+    fn delete_word_before(&mut self) {
+        if self.cx == 0 {
+            return self.delete_char();
+        }
+        let line = &self.buffer.rows[self.cy].content[..self.cx];
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let mut chars = line.char_indices().rev().skip_while(|(_, c)| c.is_whitespace()).peekable();
+        let mut start = 0;
+        if let Some(&(_, last)) = chars.peek() {
+            for (i, c) in chars {
+                if c.is_whitespace() || is_word(c) != is_word(last) {
+                    start = i + c.len_utf8();
+                    break;
+                }
+            }
+        }
+        self.delete_before(start);
+    }
+
+    // Ctrl-U: 들여쓰기 뒤부터 커서까지 지움, 이미 들여쓰기 안이면 줄 맨 앞까지
+    fn delete_to_line_start(&mut self) {
+        if self.cx == 0 {
+            return self.delete_char();
+        }
+        let content = &self.buffer.rows[self.cy].content;
+        let indent = content.len() - content.trim_start().len();
+        self.delete_before(if self.cx > indent { indent } else { 0 });
+    }
+
+    fn delete_before(&mut self, start: usize) {
+        self.buffer.rows[self.cy].content.replace_range(start..self.cx, "");
+        self.cx = start;
+        self.buffer.touch();
+    }
+
+    // >> / <<: 횟수만큼의 줄을 옮기고 커서는 첫 글자로
+    fn shift_lines(&mut self, indent: bool) {
+        let (start, end) = self.counted_lines();
+        for line in start..end {
+            self.shift_row(line, indent);
+        }
+        let content = &self.buffer.rows[self.cy].content;
+        self.cx = content.len() - content.trim_start().len();
+    }
+
+    // Ctrl-T/Ctrl-D: 현재 줄을 shiftwidth만큼 들여쓰거나 내어씀 (커서는 글자를 따라감)
+    fn shift_line(&mut self, indent: bool) {
+        let before = self.buffer.rows[self.cy].content.len();
+        self.shift_row(self.cy, indent);
+        self.cx = (self.cx + self.buffer.rows[self.cy].content.len()).saturating_sub(before);
+    }
+
+    // 줄 하나를 shiftwidth만큼 들여쓰거나 내어씀 (비주얼 모드의 > / < 도 사용)
+    fn shift_row(&mut self, line: usize, indent: bool) {
+        let BufferOptions { shiftwidth: width, tabstop, expandtab, .. } = self.buffer_options;
+        let row = &mut self.buffer.rows[line].content;
+        if !expandtab {
+            // 들여쓰기 폭을 바꾸고 탭과 남는 공백으로 다시 씀
+            let len = row.len() - row.trim_start_matches([' ', '\t']).len();
+            let old = row[..len].chars().fold(0, |w, c| if c == '\t' { (w / tabstop + 1) * tabstop } else { w + 1 });
+            let new = if indent { old + width } else { old.saturating_sub(width) };
+            let prefix = "\t".repeat(new / tabstop) + &" ".repeat(new % tabstop);
+            if prefix == row[..len] {
+                return;
+            }
+            row.replace_range(..len, &prefix);
+        } else if indent {
+            row.insert_str(0, &" ".repeat(width));
+        } else {
+            let spaces = row.len() - row.trim_start_matches(' ').len();
+            let removed = if spaces == 0 && row.starts_with('\t') { 1 } else { spaces.min(width) };
+            if removed == 0 {
+                return;
+            }
+            row.replace_range(..removed, "");
+        }
+        self.buffer.touch();
+    }
+
+    /// 버퍼를 현재 파일 이름으로 저장
+    pub fn save(&mut self) -> error::Result<()> {
+        self.write_file(false)
+    }
+
+    // force (:w!) 면 readonly 여도 이번 한 번만 씀 (readonly 는 그대로)
+    pub(crate) fn write_file(&mut self, force: bool) -> error::Result<()> {
+        if self.filename.is_none() {
+            return Err(Error::NoFileName);
+        }
+        if self.buffer_options.readonly && !force {
+            return Err(Error::ReadOnly);
+        }
+        self.emit("BufWritePre");
+        if self.buffer_options.trimtrailing {
+            self.trim_trailing(0, self.buffer.rows.len() - 1);
+        }
+        let Some(path) = self.filename.clone() else { return Err(Error::NoFileName) };
+        if self.options.backup {
+            self.write_backup(&path)?;
+        }
+        write_replacing(Path::new(&path), self.file_content().as_bytes()).map_err(|e| Error::Io(path.clone(), e))?;
+        self.status_msg = format!("Saved to {}", path);
+        self.buffer.dirty = false;
+        self.refresh_canonical();
+        self.write_undo_file(&path);
+        let _ = self.lsp.save(&path);
+        self.git.reload();
+        self.branch = git::branch(self.filename.as_deref());
+        self.emit("BufWritePost");
+        Ok(())
+    }
+
+    // set backup: 덮어쓰기 전의 파일을 backupdir 에 (name~) 남김, 아직 없는 파일이면 할 일 없음
+    fn write_backup(&self, path: &str) -> error::Result<()> {
+        let Ok(original) = fs::read(path) else { return Ok(()) };
+        let backup = artifacts::path_in(&self.options.backupdir, path, "~");
+        artifacts::write(&backup, &original).map_err(|e| Error::Io(backup.display().to_string(), e))
+    }
+
+    /// :w 파일 — 이름 없는 버퍼는 그 이름을 갖고 저장, 이름이 있으면 버퍼는 그대로 두고 사본을 씀
+    pub fn save_as(&mut self, path: &str, force: bool) -> error::Result<()> {
+        if self.filename.is_none() || self.filename.as_deref() == Some(path) {
+            self.filename = Some(path.to_string());
+            return self.write_file(force);
+        }
+        write_replacing(Path::new(path), self.file_content().as_bytes()).map_err(|e| Error::Io(path.to_string(), e))?;
+        self.status_msg = format!("Written to {}", path);
+        Ok(())
+    }
+
+    // 저장할 내용: fileformat 의 줄바꿈으로 잇고 fixendofline 이면 끝에도 붙임
+    fn file_content(&self) -> String {
+        let eol = self.buffer_options.fileformat.eol();
+        let mut content = self.buffer.rows.iter().map(|r| r.content.as_str()).collect::<Vec<_>>().join(eol);
+        if self.buffer_options.fixendofline {
+            content.push_str(eol);
+        }
+        content
+    }
+
+    fn update_conflicts(&mut self) {
+        if self.conflicts_version == Some(self.buffer.version) {
+            return;
+        }
+        let lines: Vec<&str> = self.buffer.rows.iter().map(|r| r.content.as_str()).collect();
+        self.conflicts = conflict::find(&lines);
+        self.conflicts_version = Some(self.buffer.version);
+    }
+
+    fn jump_conflict(&mut self, forward: bool) {
+        self.update_conflicts();
+        let cy = self.cy;
+        let target = if forward {
+            self.conflicts.iter().find(|c| c.start > cy)
+        } else {
+            self.conflicts.iter().rev().find(|c| c.start < cy && !c.contains(cy))
+                .or_else(|| self.conflicts.iter().rev().find(|c| c.start < cy))
+        };
+        match target {
+            Some(c) => {
+                self.cy = c.start;
+                self.cx = 0;
+                let nr = self.conflicts.iter().position(|x| x.start == c.start).unwrap_or(0) + 1;
+                self.status_msg = format!("Conflict {} of {}", nr, self.conflicts.len());
+            }
+            None if self.conflicts.is_empty() => self.status_msg = "No merge conflicts".into(),
+            None => self.status_msg = "No more conflicts".into(),
+        }
+    }
+
+    // 커서가 있는 충돌 구간을 선택한 쪽으로 해결
+    fn resolve_conflict(&mut self, choice: conflict::Choice) {
+        self.update_conflicts();
+        let Some(c) = self.conflicts.iter().find(|c| c.contains(self.cy)) else {
+            self.status_msg = "Cursor is not in a merge conflict".into();
+            return;
+        };
+        let lines: Vec<&str> = self.buffer.rows.iter().map(|r| r.content.as_str()).collect();
+        let mut resolved: Vec<Row> = c.resolve(&lines, choice).into_iter().map(Row::new).collect();
+        let (start, end) = (c.start, c.end);
+        if resolved.is_empty() && self.buffer.rows.len() == end - start + 1 {
+            resolved.push(Row::new(String::new())); // 버퍼에는 최소 한 줄
+        }
+        self.buffer.rows.splice(start..=end, resolved);
+        self.buffer.touch();
+        self.cy = start.min(self.buffer.rows.len() - 1);
+        self.cx = 0;
+        self.update_conflicts();
+        self.status_msg = format!("Resolved ({} conflicts left)", self.conflicts.len());
+    }
+
+    fn open_panel(&mut self, kind: PanelKind, title: String, lines: Vec<String>, selected: usize) {
+        self.panel = Some(Panel { kind, title, lines, selected, offset: 0 });
+        self.panel_focused = true;
+    }
+
+    fn close_panel(&mut self) {
+        self.panel = None;
+        self.panel_focused = false;
+    }
+
+    fn open_quickfix_panel(&mut self) {
+        let lines = self.quickfix.entries.iter().map(|e| e.display()).collect();
+        let title = self.quickfix.title.clone();
+        self.open_panel(PanelKind::Quickfix, title, lines, self.quickfix.current);
+    }
+
+    fn jump_to_quickfix(&mut self) -> bool {
+        let Some(entry) = self.quickfix.entries.get(self.quickfix.current) else { return false };
+        let (filename, line, col) = (entry.filename.clone(), entry.line, entry.col);
+        let text = entry.text.trim().to_string();
+        if !self.jump_to(&filename, line, col) {
+            return false;
+        }
+        // 열려 있는 quickfix 창의 선택도 맞춰줌
+        if let Some(panel) = self.panel.as_mut() && panel.kind == PanelKind::Quickfix {
+            panel.selected = self.quickfix.current;
+        }
+        self.status_msg = format!("({} of {}) {}", self.quickfix.current + 1, self.quickfix.entries.len(), text);
+        true
+    }
+
+    // 목록 창이 차지하는 줄 수 (제목 줄 포함)
+    fn panel_height(&self) -> usize {
+        match &self.panel {
+            Some(panel) => {
+                let max = (self.screen_rows as usize).saturating_sub(3);
+                // K 의 결과와 도움말은 화면의 반까지
+                let height = if matches!(panel.kind, PanelKind::Lookup | PanelKind::Help) { PANEL_HEIGHT.max(max / 2) } else { PANEL_HEIGHT };
+                (panel.lines.len().clamp(1, height) + 1).min(max)
+            }
+            None => 0,
+        }
+    }
+
+    // 편집 영역에 보이는 줄 수
+    fn text_rows(&self) -> usize {
+        (self.screen_rows as usize - 1).saturating_sub(self.panel_height()).max(1)
+    }
+
+    fn scroll(&mut self) {
+        let visible_rows = self.text_rows();
+        let visible_cols = self.text_cols(); // 가로 폭
+        if let Some(view) = self.diff.as_mut() {
+            view.update(&self.buffer);
+        }
+        self.update_conflicts();
+
+        // 커서가 닫힌 접기 안에 있으면 접기의 첫 줄로
+        self.update_folds();
+        if let Some((start, _)) = self.folds.closed_at(self.cy) {
+            self.cy = start;
+            self.clamp_cursor();
+        }
+
+        // 세로 스크롤 (비교 모드에서는 양쪽 창이 같은 오프셋을 씀)
+        // 커서 위아래로 scrolloff 줄씩 남김 (파일 끝을 넘어서는 남기지 않음)
+        let row = self.cursor_display_row();
+        let context = self.window.options.scrolloff.min(visible_rows.saturating_sub(1) / 2);
+        let below = context.min(self.display_rows().saturating_sub(row + 1));
+        if row < self.row_offset + context {
+            self.row_offset = row.saturating_sub(context);
+        }
+        if row + below >= self.row_offset + visible_rows {
+            self.row_offset = row + below + 1 - visible_rows;
+        }
+
+        // 가로 스크롤 추가 (wrap 이면 대신 나뉜 화면 줄들이 보이도록)
+        // 커서 왼쪽과 오른쪽에 sidescrolloff 칸씩 남김 (줄 끝을 넘어서는 남기지 않음)
+        if self.wraps() {
+            self.scroll_wrapped(visible_rows, below);
+        } else {
+            let context = self.window.options.sidescrolloff.min(visible_cols.saturating_sub(1) / 2);
+            let col = self.cursor_col();
+            let right = context.min(self.buffer.rows[self.cy].content.chars().count().saturating_sub(col));
+            if col < self.col_offset + context {
+                self.col_offset = col.saturating_sub(context);
+            }
+            if col + right >= self.col_offset + visible_cols {
+                self.col_offset = col + right + 1 - visible_cols;
+            }
+        }
+
+        // 목록 창의 선택 줄이 보이도록 조정
+        let list_rows = self.panel_height().saturating_sub(1).max(1);
+        if let Some(panel) = self.panel.as_mut() {
+            if panel.selected < panel.offset {
+                panel.offset = panel.selected;
+            }
+            if panel.selected >= panel.offset + list_rows {
+                panel.offset = panel.selected - list_rows + 1;
+            }
+        }
+    }
+}
+
+// 같은 디렉터리의 임시 파일에 다 쓰고 디스크로 보낸 뒤 바꿔치움, 쓰다가 실패해도 원래 파일은 그대로
+fn write_replacing(path: &Path, content: &[u8]) -> io::Result<()> {
+    let (temp, path) = write_staged(path, content)?;
+    let result = fs::rename(&temp, &path);
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+// 바꿔치울 임시 파일만 다 써 두고 (임시 파일, 바꿔치울 파일) 을 돌려줌, 실패하면 임시 파일은 지움
+// 심볼릭 링크면 링크를 파일로 바꾸지 않고 가리키는 파일에 쓰며, 원래 파일의 권한 등은 그대로 둠 (attributes.rs)
+fn write_staged(path: &Path, content: &[u8]) -> io::Result<(PathBuf, PathBuf)> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let original = fs::metadata(&path).ok();
+    let name = path.file_name().ok_or_else(|| io::Error::other("not a file name"))?;
+    let temp = path.with_file_name(format!(".{}.vii-tmp", name.to_string_lossy()));
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            if let Some(metadata) = &original {
+                attributes::copy(&path, metadata, &file)?;
+            }
+            file.sync_all()
+        });
+    match result {
+        Ok(()) => Ok((temp, path)),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+// 화면을 그리고, 재는 중이면 그리기 시간과 입력 지연을 남김
+fn draw_frame(config: &mut EditorConfig, term: &mut impl Terminal) -> io::Result<()> {
+    let start = config.profiler.start();
+    refresh_screen(config, term)?;
+    config.profiler.stop("render", start);
+    config.profiler.frame_drawn();
+    Ok(())
+}
+
+/// 메인 이벤트 루프: 입력이 끝나거나 :q 로 종료할 때까지 키를 처리하고 화면을 갱신
+pub fn run(config: &mut EditorConfig, term: &mut impl Terminal) -> io::Result<()> {
+    (config.screen_cols, config.screen_rows) = term.size();
+    draw_frame(config, term)?;
+    let mut last_frame = Instant::now();
+    let mut redraw = false;
+    loop {
+        // 다시 그려야 하면 쌓인 입력을 먼저 처리하고, 입력이 끊긴 채 한 프레임이 지났을 때 한 번만 그림
+        // (키 반복, 붙여넣기, 매크로처럼 몰려온 키마다 화면 전체를 그리지 않음)
+        let wait = if redraw { FRAME.saturating_sub(last_frame.elapsed()).as_micros().div_ceil(1000) as i32 } else { 100 };
+        match term.read_event(wait)? {
+            Input::Timeout if redraw => {
+                draw_frame(config, term)?; // 화면 갱신 (스크롤 및 커서 위치 계산 포함)
+                last_frame = Instant::now();
+                // 화면이 움직이거나 복사 표시가 남아 있으면 입력이 없어도 다음 프레임을 그림
+                redraw = config.is_animating();
+            }
+            // 입력이 없는 동안에는 LSP 같은 백그라운드 작업을 처리
+            Input::Timeout => {
+                // 매핑의 앞부분만 누른 채 시간이 지나면 지금까지의 키로 처리
+                if config.pending_keys_since.is_some_and(|t| t.elapsed() >= keymap::TIMEOUT) {
+                    redraw = true;
+                    if !config.flush_pending_keys() {
+                        return term.write(b"\x1b[2J\x1b[H");
+                    }
+                } else {
+                    redraw = config.poll_background();
+                }
+            }
+            Input::Eof if redraw => return draw_frame(config, term),
+            Input::Eof => return Ok(()),
+            // 종료 신호: 저장하지 않은 내용을 복구 파일에 남기고 나감 (터미널은 호출한 쪽이 되돌림)
+            Input::Signal(signal) => {
+                let written = config.write_recovery();
+                let _ = term.write(b"\x1b[2J\x1b[H");
+                let mut msg = format!("caught {}", signal);
+                if !written.is_empty() {
+                    let paths: Vec<String> = written.iter().map(|p| p.display().to_string()).collect();
+                    msg = format!("{}, unsaved changes written to {}", msg, paths.join(", "));
+                }
+                return Err(io::Error::new(io::ErrorKind::Interrupted, msg));
+            }
+            Input::Paste(text) => {
+                redraw = true;
+                config.profiler.input_arrived();
+                let start = config.profiler.start();
+                config.paste_text(&text);
+                config.profiler.stop("keys", start);
+            }
+            Input::Key(c) => {
+                // 입력이 끊이지 않아도 한 프레임마다는 그림 (j 를 누르고 있는 동안에도 화면이 따라옴)
+                if redraw && last_frame.elapsed() >= FRAME {
+                    draw_frame(config, term)?;
+                    last_frame = Instant::now();
+                }
+                redraw = true;
+                config.profiler.input_arrived();
+                let start = config.profiler.start();
+                let running = config.handle_keypress(c);
+                config.profiler.stop("keys", start);
+                // handle_keypress가 false를 반환하면 (:q 등) 루프 종료
+                if !running {
+                    return term.write(b"\x1b[2J\x1b[H"); // 종료 전 화면 정리
+                }
+            }
+        }
+    }
+}
+
+/// viirc, Lua 스크립트, WebAssembly 플러그인을 차례로 불러옴
+pub fn load_user_config(config: &mut EditorConfig) {
+    for line in config::load() {
+        config.run_command(&line);
+    }
+    config.with_script(|script, ed| script.load_config(ed));
+    #[cfg(feature = "wasm")]
+    config.load_wasm_plugins();
+}
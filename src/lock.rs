@@ -0,0 +1,85 @@
+// --- File Locks ---
+// 파일을 열면 복구 파일과 같은 곳(swapdir, artifacts.rs)에 잠금 파일을 만들어 pid 를 적어 둠 (권고일 뿐, 쓰기를 막지는 않음)
+//   set dir=.  이면 dir/name → dir/.name.vii-lock, 아니면 swapdir/%dir%name.vii-lock
+// 심볼릭 링크로 열어도 같은 파일이면 같은 잠금 파일이 되도록 실제 경로로 바꿔서 이름을 지음
+// 다른 vii 가 만든 잠금 파일이 있고 그 프로세스가 살아 있으면 다른 곳에서 고치는 중이라고 경고
+// 죽은 프로세스가 남긴 것은 새로 씀, 에디터가 끝나면 만든 잠금 파일들을 지움
+use crate::{EditorConfig, artifacts};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+/// swapdir 에 둘 파일의 잠금 파일 경로
+pub fn lock_path(swapdir: &str, filename: &str) -> PathBuf {
+    let path = fs::canonicalize(filename).unwrap_or_else(|_| PathBuf::from(filename));
+    artifacts::path_in(swapdir, &path.to_string_lossy(), ".vii-lock")
+}
+
+// 살아 있는 프로세스인지 (다른 사용자의 것이면 EPERM)
+fn alive(pid: i32) -> bool {
+    pid > 0 && (unsafe { libc::kill(pid, 0) } == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM))
+}
+
+pub struct Locks {
+    held: Vec<PathBuf>, // 이 에디터가 만든 잠금 파일들
+}
+
+impl Locks {
+    pub fn new() -> Self {
+        Locks { held: Vec::new() }
+    }
+}
+
+impl Drop for Locks {
+    fn drop(&mut self) {
+        for path in &self.held {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl EditorConfig {
+    // 파일을 열 때: 다른 vii 가 잠가 두었으면 경고하고, 아니면 잠금 파일을 만듦
+    pub(crate) fn lock_file(&mut self, filename: &str) {
+        let path = lock_path(&self.options.swapdir, filename);
+        if self.locks.held.contains(&path) {
+            return;
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            let _ = fs::create_dir_all(parent);
+        }
+        let me = std::process::id() as i32;
+        // 없을 때만 만들어서, 동시에 연 두 vii 가 서로의 잠금 파일을 덮어쓰지 않게 함
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    if writeln!(file, "{}", me).is_ok() {
+                        self.locks.held.push(path);
+                    }
+                    return;
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let owner = fs::read_to_string(&path).ok().and_then(|text| text.trim().parse::<i32>().ok());
+                    match owner {
+                        Some(pid) if pid == me => {
+                            self.locks.held.push(path);
+                            return;
+                        }
+                        Some(pid) if alive(pid) => {
+                            self.error(format!("{} is being edited by another vii (pid {})", filename, pid));
+                            return;
+                        }
+                        // 죽은 프로세스의 잠금만 지우고 다시 만듦
+                        Some(_) => {
+                            let _ = fs::remove_file(&path);
+                        }
+                        // 아직 pid 를 적는 중이거나 알 수 없는 파일이면 건드리지 않음
+                        None => return,
+                    }
+                }
+                // 디렉터리에 쓸 수 없으면 잠그지 않고 넘어감
+                Err(_) => return,
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// --- Keyword Lookup ---
+// K   커서 아래 낱말을 keywordprg 로 찾아 결과를 아래 창에 보여줌 (j / k, Ctrl-D / Ctrl-U 로 넘기고 q 로 닫음)
+//   set keywordprg=man            기본값, 낱말은 명령 끝에 붙음 (3K 처럼 횟수를 주면 "man 3 낱말")
+//   set kp=rustup\ doc\ --path    값 안의 공백은 \로
+//   autocmd FileType python set kp=pydoc3   파일 종류마다
+// 언어 서버가 있는 파일에서는 K 가 서버에 hover 를 물음 (hover.rs)
+// man 이 쓰는 굵은 글씨 / 밑줄 (글자 \b 글자) 과 ANSI 색은 걷어내고, 폭은 MANWIDTH 로 화면에 맞춤
+use crate::{EditorConfig, PanelKind};
+use std::process::{Command, Stdio};
+
+pub const DEFAULT_KEYWORDPRG: &str = "man";
+
+// 겹쳐 찍기, 색, 탭을 걷어낸 한 줄
+fn plain(line: &str) -> String {
+    let mut text = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x08' => {
+                text.pop();
+            }
+            // ESC [ ... 글자
+            '\x1b' => {
+                let _ = chars.by_ref().skip(1).find(|c| c.is_ascii_alphabetic());
+            }
+            '\t' => text.push_str(&" ".repeat(8 - text.chars().count() % 8)),
+            c if c.is_control() => {}
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+impl EditorConfig {
+    // K
+    pub(crate) fn lookup_keyword(&mut self) {
+        if self.filename.as_ref().is_some_and(|name| self.lsp.has_server(name)) {
+            return self.lsp_hover();
+        }
+        let count = self.count.take();
+        let Some(word) = self.identifier_under_cursor() else {
+            return self.error("No identifier under cursor");
+        };
+        let command = match count {
+            Some(n) => format!("{} {} {}", self.options.keywordprg, n, word),
+            None => format!("{} {}", self.options.keywordprg, word),
+        };
+        let output = Command::new("sh")
+            .args(["-c", &command])
+            .env("MANWIDTH", self.screen_cols.to_string())
+            .env("MANPAGER", "cat")
+            .stdin(Stdio::null())
+            .output();
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => return self.error(format!("Error: {}", e)),
+        };
+        let text = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<String> = text.lines().map(plain).collect();
+        // 실패하면 (No manual entry for ...) 오류 출력의 첫 줄을 보여줌
+        if !output.status.success() || lines.iter().all(|l| l.trim().is_empty()) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().map(plain).find(|l| !l.trim().is_empty());
+            return self.error(reason.unwrap_or(format!("Nothing found: {}", command)));
+        }
+        self.status_msg.clear();
+        self.open_panel(PanelKind::Lookup, command, lines, 0);
+    }
+}
@@ -0,0 +1,547 @@
+// --- LSP Client ---
+// 파일 종류마다 설정된 언어 서버를 띄우고 stdio 위의 JSON-RPC로 통신
+// 요청(정의 찾기 등)은 보내 두고 응답이 오면 poll 이 모아 두며, 에디터가 take_responses 로 가져가 처리 (goto.rs 등)
+// 위치의 글자 번호는 LSP 대로 UTF-16 단위 (to_character / to_byte 로 줄의 바이트 열과 바꿈)
+use crate::json::Json;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl Severity {
+    fn from_lsp(n: i64) -> Self {
+        match n {
+            1 => Severity::Error,
+            2 => Severity::Warning,
+            3 => Severity::Info,
+            _ => Severity::Hint,
+        }
+    }
+
+    pub fn sign(self) -> char {
+        match self {
+            Severity::Error => 'E',
+            Severity::Warning => 'W',
+            Severity::Info => 'I',
+            Severity::Hint => 'H',
+        }
+    }
+}
+
+pub struct Diagnostic {
+    pub line: usize, // 0부터 시작
+    pub col: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// 서버에 보낸 요청의 종류 (응답을 받아 무엇을 할지)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Request {
+    Definition,
+    Declaration,
+    References,
+    Hover,
+    Rename,
+    Formatting,
+    RangeFormatting,
+    Completion,
+    ResolveCompletion,
+    SignatureHelp,
+    DocumentSymbol,
+}
+
+impl Request {
+    fn method(self) -> &'static str {
+        match self {
+            Request::Definition => "textDocument/definition",
+            Request::Declaration => "textDocument/declaration",
+            Request::References => "textDocument/references",
+            Request::Hover => "textDocument/hover",
+            Request::Rename => "textDocument/rename",
+            Request::Formatting => "textDocument/formatting",
+            Request::RangeFormatting => "textDocument/rangeFormatting",
+            Request::Completion => "textDocument/completion",
+            Request::ResolveCompletion => "completionItem/resolve",
+            Request::SignatureHelp => "textDocument/signatureHelp",
+            Request::DocumentSymbol => "textDocument/documentSymbol",
+        }
+    }
+}
+
+/// 요청에 대한 응답 (서버가 오류를 보냈으면 Err 에 메시지)
+pub struct Response {
+    pub kind: Request,
+    pub filename: String, // 요청을 보낸 파일
+    pub version: u64,     // 요청을 보낼 때 서버가 가진 문서의 버전
+    pub result: Result<Json, String>,
+}
+
+struct Pending {
+    client: usize,
+    id: i64,
+    kind: Request,
+    filename: String,
+    version: u64,
+}
+
+struct Document {
+    uri: String,
+    client: usize,
+    version: u64,
+}
+
+pub struct Lsp {
+    servers: HashMap<String, String>, // filetype → 실행 명령
+    clients: Vec<(String, Client)>,
+    documents: HashMap<String, Document>, // 에디터 파일 이름 → 문서 정보
+    diagnostics: HashMap<String, Vec<Diagnostic>>, // uri → 진단 목록
+    pending: Vec<Pending>,                          // 응답을 기다리는 요청들
+    responses: Vec<Response>,                       // 받았지만 아직 처리하지 않은 응답들
+}
+
+impl Lsp {
+    pub fn new() -> Self {
+        Lsp {
+            servers: HashMap::new(),
+            clients: Vec::new(),
+            documents: HashMap::new(),
+            diagnostics: HashMap::new(),
+            pending: Vec::new(),
+            responses: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, filetype: &str, command: &str) {
+        self.servers.insert(filetype.to_string(), command.to_string());
+    }
+
+    // 서버가 설정된 파일 종류면 (필요 시 서버를 띄우고) didOpen 전송
+    pub fn open(&mut self, filename: &str, filetype: &str, text: &str, version: u64) -> io::Result<()> {
+        let Some(command) = self.servers.get(filetype) else { return Ok(()) };
+        let client = match self.clients.iter().position(|(ft, _)| ft == filetype) {
+            Some(i) => i,
+            None => {
+                let client = Client::start(command)?;
+                self.clients.push((filetype.to_string(), client));
+                self.clients.len() - 1
+            }
+        };
+        let uri = path_to_uri(filename);
+        let params = Json::obj(vec![(
+            "textDocument",
+            Json::obj(vec![
+                ("uri", uri.as_str().into()),
+                ("languageId", filetype.into()),
+                ("version", (version as i64).into()),
+                ("text", text.into()),
+            ]),
+        )]);
+        self.clients[client].1.notify("textDocument/didOpen", params)?;
+        self.documents.insert(filename.to_string(), Document { uri, client, version });
+        Ok(())
+    }
+
+    // 서버가 마지막으로 받은 버전과 다르면 true
+    pub fn is_stale(&self, filename: &str, version: u64) -> bool {
+        self.documents.get(filename).is_some_and(|d| d.version != version)
+    }
+
+    // 전체 내용을 보내는 방식의 didChange
+    pub fn change(&mut self, filename: &str, version: u64, text: &str) -> io::Result<()> {
+        let Some(doc) = self.documents.get_mut(filename) else { return Ok(()) };
+        doc.version = version;
+        let params = Json::obj(vec![
+            ("textDocument", Json::obj(vec![("uri", doc.uri.as_str().into()), ("version", (version as i64).into())])),
+            ("contentChanges", Json::Array(vec![Json::obj(vec![("text", text.into())])])),
+        ]);
+        self.clients[doc.client].1.notify("textDocument/didChange", params)
+    }
+
+    pub fn save(&mut self, filename: &str) -> io::Result<()> {
+        let Some(doc) = self.documents.get(filename) else { return Ok(()) };
+        let params = Json::obj(vec![("textDocument", Json::obj(vec![("uri", doc.uri.as_str().into())]))]);
+        self.clients[doc.client].1.notify("textDocument/didSave", params)
+    }
+
+    // 파일을 맡은 서버가 있으면 true
+    pub fn has_server(&self, filename: &str) -> bool {
+        self.documents.contains_key(filename)
+    }
+
+    /// 파일의 서버에 요청을 보냄, textDocument/ 요청이면 params 에 textDocument 가 더해짐 (서버가 없는 파일이면 false)
+    pub fn request(&mut self, filename: &str, kind: Request, params: Vec<(&str, Json)>) -> io::Result<bool> {
+        let Some(doc) = self.documents.get(filename) else { return Ok(false) };
+        let mut fields = Vec::new();
+        if kind.method().starts_with("textDocument/") {
+            fields.push(("textDocument", Json::obj(vec![("uri", doc.uri.as_str().into())])));
+        }
+        fields.extend(params);
+        let (client, version) = (doc.client, doc.version);
+        // 같은 종류의 이전 요청은 응답이 와도 버림
+        self.pending.retain(|p| p.kind != kind);
+        let id = self.clients[client].1.call(kind.method(), Json::obj(fields))?;
+        self.pending.push(Pending { client, id, kind, filename: filename.to_string(), version });
+        Ok(true)
+    }
+
+    /// 받은 응답들을 가져감
+    pub fn take_responses(&mut self) -> Vec<Response> {
+        std::mem::take(&mut self.responses)
+    }
+
+    // 서버가 보낸 메시지 처리, 화면을 다시 그려야 하면 true
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        for (index, (_, client)) in self.clients.iter_mut().enumerate() {
+            while let Ok(msg) = client.rx.try_recv() {
+                match (msg.get("id"), msg.get("method").and_then(|m| m.as_str())) {
+                    // 서버 → 클라이언트 요청: 지원하지 않으므로 빈 결과로 응답
+                    (Some(id), Some(method)) => {
+                        let result = match method {
+                            "workspace/configuration" => {
+                                let n = msg.get("params").and_then(|p| p.get("items")).and_then(|i| i.as_array()).map_or(0, |i| i.len());
+                                Json::Array(vec![Json::Null; n])
+                            }
+                            _ => Json::Null,
+                        };
+                        let _ = client.send(&Json::obj(vec![("jsonrpc", "2.0".into()), ("id", id.clone()), ("result", result)]));
+                    }
+                    (Some(id), None) if id.as_i64() == Some(client.init_id) => {
+                        let _ = client.finish_init();
+                    }
+                    (Some(id), None) => {
+                        let id = id.as_i64();
+                        let Some(i) = self.pending.iter().position(|p| p.client == index && Some(p.id) == id) else { continue };
+                        let pending = self.pending.remove(i);
+                        let result = match msg.get("error") {
+                            Some(error) => Err(error.get("message").and_then(|m| m.as_str()).unwrap_or("request failed").to_string()),
+                            None => Ok(msg.get("result").cloned().unwrap_or(Json::Null)),
+                        };
+                        self.responses.push(Response { kind: pending.kind, filename: pending.filename, version: pending.version, result });
+                        changed = true;
+                    }
+                    (None, Some("textDocument/publishDiagnostics")) => {
+                        if let Some(params) = msg.get("params") {
+                            store_diagnostics(&mut self.diagnostics, params);
+                            changed = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        changed
+    }
+
+    pub fn diagnostics(&self, filename: &str) -> &[Diagnostic] {
+        self.documents
+            .get(filename)
+            .and_then(|doc| self.diagnostics.get(&doc.uri))
+            .map_or(&[], |d| d.as_slice())
+    }
+}
+
+fn store_diagnostics(store: &mut HashMap<String, Vec<Diagnostic>>, params: &Json) {
+    let Some(uri) = params.get("uri").and_then(|u| u.as_str()) else { return };
+    let list = params.get("diagnostics").and_then(|d| d.as_array()).unwrap_or(&[]);
+    let mut diagnostics: Vec<Diagnostic> = list
+        .iter()
+        .filter_map(|d| {
+            let start = d.get("range")?.get("start")?;
+            Some(Diagnostic {
+                line: start.get("line")?.as_i64()? as usize,
+                col: start.get("character")?.as_i64()? as usize,
+                severity: Severity::from_lsp(d.get("severity").and_then(|s| s.as_i64()).unwrap_or(1)),
+                message: d.get("message")?.as_str()?.to_string(),
+            })
+        })
+        .collect();
+    diagnostics.sort_by_key(|d| (d.line, d.col));
+    store.insert(uri.to_string(), diagnostics);
+}
+
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    rx: Receiver<Json>,
+    next_id: i64,
+    init_id: i64,
+    initialized: bool,
+    queue: Vec<Json>, // initialize 응답 전에 보내려던 알림
+}
+
+impl Client {
+    fn start(command: &str) -> io::Result<Client> {
+        let mut parts = command.split_whitespace();
+        let program = parts.next().ok_or_else(|| io::Error::other("empty server command"))?;
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or_else(|| io::Error::other("no server stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| io::Error::other("no server stdout"))?;
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || read_messages(stdout, tx));
+
+        let mut client = Client { child, stdin, rx, next_id: 1, init_id: 0, initialized: false, queue: Vec::new() };
+        let root = std::env::current_dir().map(|d| path_to_uri(&d.to_string_lossy())).unwrap_or_default();
+        // 스니펫은 받지 않고, 자동 import 같은 추가 편집은 고를 때 resolve 로 받음
+        let properties = Json::Array(vec!["additionalTextEdits".into(), "detail".into()]);
+        let completion_item = Json::obj(vec![
+            ("snippetSupport", Json::Bool(false)),
+            ("resolveSupport", Json::obj(vec![("properties", properties)])),
+        ]);
+        let capabilities = Json::obj(vec![(
+            "textDocument",
+            Json::obj(vec![
+                ("publishDiagnostics", Json::obj(vec![])),
+                ("synchronization", Json::obj(vec![("didSave", Json::Bool(true))])),
+                ("completion", Json::obj(vec![("completionItem", completion_item)])),
+            ]),
+        )]);
+        let params = Json::obj(vec![
+            ("processId", (std::process::id() as i64).into()),
+            ("rootUri", root.into()),
+            ("clientInfo", Json::obj(vec![("name", "vii".into())])),
+            ("capabilities", capabilities),
+        ]);
+        client.init_id = client.request("initialize", params)?;
+        Ok(client)
+    }
+
+    fn send(&mut self, msg: &Json) -> io::Result<()> {
+        let body = msg.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+        self.stdin.flush()
+    }
+
+    fn request(&mut self, method: &str, params: Json) -> io::Result<i64> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&Json::obj(vec![("jsonrpc", "2.0".into()), ("id", id.into()), ("method", method.into()), ("params", params)]))?;
+        Ok(id)
+    }
+
+    // initialize 응답 전이면 알림처럼 모아 두었다 보냄
+    fn call(&mut self, method: &str, params: Json) -> io::Result<i64> {
+        if self.initialized {
+            return self.request(method, params);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queue.push(Json::obj(vec![("jsonrpc", "2.0".into()), ("id", id.into()), ("method", method.into()), ("params", params)]));
+        Ok(id)
+    }
+
+    fn notify(&mut self, method: &str, params: Json) -> io::Result<()> {
+        let msg = Json::obj(vec![("jsonrpc", "2.0".into()), ("method", method.into()), ("params", params)]);
+        if self.initialized {
+            self.send(&msg)
+        } else {
+            self.queue.push(msg);
+            Ok(())
+        }
+    }
+
+    fn finish_init(&mut self) -> io::Result<()> {
+        self.initialized = true;
+        self.notify("initialized", Json::obj(vec![]))?;
+        for msg in std::mem::take(&mut self.queue) {
+            self.send(&msg)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        let _ = self.send(&Json::obj(vec![("jsonrpc", "2.0".into()), ("method", "exit".into())]));
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// Content-Length 헤더로 구분된 메시지를 읽어 채널로 넘김
+fn read_messages(stdout: ChildStdout, tx: Sender<Json>) {
+    let mut reader = BufReader::new(stdout);
+    loop {
+        let mut len = None;
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => return,
+                Ok(_) => {}
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(v) = line.strip_prefix("Content-Length:") {
+                len = v.trim().parse().ok();
+            }
+        }
+        let Some(len) = len else { continue };
+        let mut body = vec![0; len];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+        if let Some(msg) = Json::parse(&String::from_utf8_lossy(&body)) && tx.send(msg).is_err() {
+            return;
+        }
+    }
+}
+
+/// LSP 위치 ({line, character})
+pub fn position(line: usize, character: usize) -> Json {
+    Json::obj(vec![("line", (line as i64).into()), ("character", (character as i64).into())])
+}
+
+/// 줄의 바이트 열을 LSP 의 글자 번호로 (UTF-16 단위)
+pub fn to_character(line: &str, col: usize) -> usize {
+    line.get(..col).map_or(line.len(), |l| l.encode_utf16().count())
+}
+
+/// LSP 의 글자 번호를 줄의 바이트 열로
+pub fn to_byte(line: &str, character: usize) -> usize {
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= character {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// 결과의 Location, Location[], LocationLink[] 에서 (파일 이름, 줄, 글자 번호) 들
+pub fn locations(result: &Json) -> Vec<(String, usize, usize)> {
+    let items = match result {
+        Json::Array(items) => items.as_slice(),
+        Json::Null => &[],
+        single => std::slice::from_ref(single),
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let (uri, range) = match item.get("targetUri") {
+                Some(uri) => (uri, item.get("targetSelectionRange").or(item.get("targetRange"))?),
+                None => (item.get("uri")?, item.get("range")?),
+            };
+            let start = range.get("start")?;
+            let filename = uri_to_path(uri.as_str()?)?;
+            Some((filename, start.get("line")?.as_i64()? as usize, start.get("character")?.as_i64()? as usize))
+        })
+        .collect()
+}
+
+/// 문서 한 곳을 바꾸는 편집 (줄과 글자 번호는 0부터, 글자 번호는 UTF-16 단위)
+pub struct TextEdit {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub text: String,
+}
+
+/// TextEdit[]
+pub fn text_edits(edits: &Json) -> Vec<TextEdit> {
+    let point = |p: &Json| Some((p.get("line")?.as_i64()? as usize, p.get("character")?.as_i64()? as usize));
+    let edit = |e: &Json| {
+        let range = e.get("range")?;
+        Some(TextEdit { start: point(range.get("start")?)?, end: point(range.get("end")?)?, text: e.get("newText")?.as_str()?.to_string() })
+    };
+    edits.as_array().unwrap_or(&[]).iter().filter_map(edit).collect()
+}
+
+/// WorkspaceEdit 의 파일마다의 편집들 (changes 나 documentChanges, 파일 만들기 등은 뺌)
+pub fn workspace_edits(edit: &Json) -> Vec<(String, Vec<TextEdit>)> {
+    let mut files = Vec::new();
+    if let Some(changes) = edit.get("documentChanges").and_then(|c| c.as_array()) {
+        for change in changes {
+            let Some(uri) = change.get("textDocument").and_then(|d| d.get("uri")).and_then(|u| u.as_str()) else { continue };
+            files.extend(uri_to_path(uri).map(|f| (f, change.get("edits").map(text_edits).unwrap_or_default())));
+        }
+    } else if let Some(Json::Object(changes)) = edit.get("changes") {
+        files.extend(changes.iter().filter_map(|(uri, edits)| Some((uri_to_path(uri)?, text_edits(edits)))));
+    }
+    files.retain(|(_, edits)| !edits.is_empty());
+    files
+}
+
+/// 줄들에 편집들을 적용한 새 줄들 (편집들은 서로 겹치지 않아야 함)
+pub fn apply_edits(lines: &[String], edits: &[TextEdit]) -> Vec<String> {
+    let mut starts = Vec::with_capacity(lines.len());
+    let mut text = String::new();
+    for line in lines {
+        starts.push(text.len());
+        text.push_str(line);
+        text.push('\n');
+    }
+    let offset = |(line, character): (usize, usize)| match lines.get(line) {
+        Some(content) => starts[line] + to_byte(content, character),
+        None => text.len(),
+    };
+    // 뒤에서부터 바꿔야 앞의 위치가 그대로
+    let mut ranges: Vec<(usize, usize, &str)> = edits.iter().map(|e| (offset(e.start), offset(e.end), e.text.as_str())).collect();
+    ranges.sort_by_key(|&(start, end, _)| (start, end));
+    for &(start, end, new) in ranges.iter().rev() {
+        text.replace_range(start..end.max(start), new);
+    }
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    text.split('\n').map(String::from).collect()
+}
+
+/// file:// URI 를 파일 이름으로 (지금 디렉터리 아래면 상대 경로)
+pub fn uri_to_path(uri: &str) -> Option<String> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok())) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8_lossy(&decoded).into_owned();
+    let cwd = std::env::current_dir().ok().and_then(|d| std::fs::canonicalize(d).ok());
+    match cwd.as_ref().and_then(|d| Path::new(&path).strip_prefix(d).ok()) {
+        Some(relative) => Some(relative.to_string_lossy().into_owned()),
+        None => Some(path),
+    }
+}
+
+pub fn path_to_uri(filename: &str) -> String {
+    // 아직 없는 파일은 절대 경로로 만들 수 없으므로 지금 디렉터리 뒤에 붙임 (uri_to_path 가 다시 떼어 냄)
+    let path = std::fs::canonicalize(filename).unwrap_or_else(|_| {
+        let cwd = std::env::current_dir().and_then(std::fs::canonicalize);
+        cwd.map_or_else(|_| Path::new(filename).to_path_buf(), |d| d.join(filename))
+    });
+    let mut uri = String::from("file://");
+    for b in path.to_string_lossy().bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => uri.push(b as char),
+            _ => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
@@ -1,362 +1,107 @@
-use libc::{ioctl, winsize, ECHO, ICANON, STDIN_FILENO, STDOUT_FILENO, TCSAFLUSH, TIOCGWINSZ, tcgetattr, tcsetattr, termios};
-use std::io::{self, Read, Write};
-use std::mem;
-use std::fs::File;
-use std::env; // 실행 인자를 가져오기 위해 추가
-use std::fs::read_to_string; // 파일 내용을 읽기 위해 추가
-// --- Terminal Raw Mode Handling ---
-struct RawMode {
-    orig_termios: termios,
-}
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use vii::terminal::{self, AnsiTerminal, Terminal};
+use vii::cli::{self, Action};
+use vii::{EditorConfig, batch, load_user_config, run, server};
 
-impl RawMode {
-    fn enable() -> Self {
-        unsafe {
-            let mut raw: termios = mem::zeroed();
-            if tcgetattr(STDIN_FILENO, &mut raw) == -1 {
-                panic!("tcgetattr 실패");
-            }
-            let orig_termios = raw;
-            raw.c_lflag &= !(ECHO | ICANON); 
-            if tcsetattr(STDIN_FILENO, TCSAFLUSH, &raw) == -1 {
-                panic!("tcsetattr 실패");
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match cli::parse(&args) {
+        Ok(Action::Edit(args)) => args,
+        Ok(Action::Help) => return println!("{}", cli::USAGE),
+        Ok(Action::Version) => return println!("{}", cli::version()),
+        Ok(Action::Server(socket)) => {
+            // vii --server <socket>: 터미널 없이 소켓으로 명령을 받음
+            let mut config = EditorConfig::new();
+            load_user_config(&mut config);
+            if let Err(e) = server::run(&socket, &mut config) {
+                eprintln!("vii: {}: {}", socket, e);
+                std::process::exit(1);
             }
-            RawMode { orig_termios }
-        }
-    }
-}
-
-impl Drop for RawMode {
-    fn drop(&mut self) {
-        unsafe {
-            tcsetattr(STDIN_FILENO, TCSAFLUSH, &self.orig_termios);
-        }
-    }
-}
-
-// --- Data Structures ---
-#[derive(PartialEq)]
-enum Mode {
-    Normal,
-    Insert,
-    Command,
-}
-
-struct Row {
-    content: String,
-}
-
-impl Row {
-    fn new(s: String) -> Self {
-        Row { content: s }
-    }
-    fn insert_char(&mut self, at: usize, c: char) {
-        if at >= self.content.len() {
-            self.content.push(c);
-        } else {
-            self.content.insert(at, c);
-        }
-    }
-    fn delete_char(&mut self, at: usize) {
-        if at < self.content.len() {
-            self.content.remove(at);
-        }
-    }
-}
-
-struct EditorBuffer {
-    rows: Vec<Row>,
-}
-
-impl EditorBuffer {
-    fn new() -> Self {
-        EditorBuffer {
-            rows: vec![Row::new(String::new())],
-        }
-    }
-    fn rows_to_string(&self) -> String {
-        self.rows.iter()
-            .map(|r| r.content.as_str())
-            .collect::<Vec<&str>>()
-            .join("\n")
-    }
-    fn open(&mut self, filename: &str) -> io::Result<()> {
-        let content = read_to_string(filename)?; // 파일을 읽어옴
-        self.rows.clear(); // 기본 빈 줄 제거
-
-        for line in content.lines() {
-            self.rows.push(Row::new(line.to_string())); // 한 줄씩 버퍼에 추가
-        }
-
-        // 파일이 비어있을 경우를 대비해 최소 한 줄은 유지
-        if self.rows.is_empty() {
-            self.rows.push(Row::new(String::new()));
-        }
-        Ok(())
-    }
-}
-
-struct EditorConfig {
-    cx: u16,
-    cy: u16,
-    screen_cols: u16,
-    screen_rows: u16,
-    row_offset: usize,
-    col_offset: usize, // 추가: 화면 왼쪽에 표시될 열의 인덱스
-    mode: Mode,
-    buffer: EditorBuffer,
-    command_buffer: String,
-    status_msg: String,
-    filename: Option<String>,
-}
-
-impl EditorConfig {
-  fn new() -> Self {
-        let (cols, rows) = get_terminal_size();
-        EditorConfig {
-            cx: 0,
-            cy: 0,
-            screen_cols: cols,
-            screen_rows: rows,
-            row_offset: 0, // 0번 줄부터 시작
-            col_offset: 0,
-            mode: Mode::Normal,
-            buffer: EditorBuffer::new(),
-            command_buffer: String::new(),
-            status_msg: String::from("WELCOME! :q to quit"),
-            filename: None,
-        }
-    }
-
-    fn move_cursor(&mut self, key: char) {
-        let row_count = self.buffer.rows.len();
-        match key {
-            'h' => if self.cx > 0 { self.cx -= 1 },
-            'j' => if (self.cy as usize) < row_count - 1 { self.cy += 1 },
-            'k' => if self.cy > 0 { self.cy -= 1 },
-            'l' => {
-                let cur_row_len = self.buffer.rows[self.cy as usize].content.len() as u16;
-                if self.cx < cur_row_len { self.cx += 1; }
+            return;
+        }
+        Ok(Action::Batch(args)) => {
+            // vii -es ...: 화면 없이 ex 명령만 실행 (사용자 설정은 읽지 않음)
+            let mut config = EditorConfig::new();
+            match batch::run(&args, &mut config) {
+                Ok(errors) if errors.is_empty() => return,
+                Ok(errors) => errors.iter().for_each(|e| eprintln!("vii: {}", e)),
+                Err(e) => eprintln!("vii: {}", e),
             }
-            _ => {}
+            std::process::exit(1);
         }
-        let cur_row_len = self.buffer.rows[self.cy as usize].content.len() as u16;
-        if self.cx > cur_row_len { self.cx = cur_row_len; }
-    }
-
-    fn insert_char(&mut self, c: char) {
-        self.buffer.rows[self.cy as usize].insert_char(self.cx as usize, c);
-        self.cx += 1;
-    }
-
-    fn delete_char(&mut self) {
-        if self.cx == 0 && self.cy == 0 { return; }
-        if self.cx > 0 {
-            self.buffer.rows[self.cy as usize].delete_char(self.cx as usize - 1);
-            self.cx -= 1;
-        } else {
-            let current_row_content = self.buffer.rows.remove(self.cy as usize).content;
-            self.cy -= 1;
-            let prev_row = &mut self.buffer.rows[self.cy as usize];
-            self.cx = prev_row.content.len() as u16;
-            prev_row.content.push_str(&current_row_content);
+        Err(e) => {
+            eprintln!("vii: {}\nTry 'vii --help' for more information.", e);
+            std::process::exit(2);
         }
-    }
-
-   fn save(&mut self) -> io::Result<()> {
-        // filename이 있으면 사용, 없으면 에러 처리
-        let path = match &self.filename {
-            Some(name) => name,
-            None => {
-                self.status_msg = "No file name! Use :w <filename> (TBD)".into();
-                return Ok(());
-            }
-        };
-
-        let content = self.buffer.rows_to_string();
-        let mut file = File::create(path)?;
-        file.write_all(content.as_bytes())?;
-        self.status_msg = format!("Saved to {}", path);
-        Ok(())
-    } 
+    };
 
-    fn handle_keypress(&mut self, key: char) -> bool {
-        match self.mode {
-            Mode::Normal => match key {
-                'i' => self.mode = Mode::Insert,
-                ':' => {
-                    self.mode = Mode::Command;
-                    self.command_buffer.clear();
-                }
-                'h' | 'j' | 'k' | 'l' => self.move_cursor(key),
-                _ => {}
-            },
-            Mode::Insert => match key {
-                '\x1b' => self.mode = Mode::Normal,
-                '\r' | '\n' => {
-                    let remaining = self.buffer.rows[self.cy as usize].content.split_off(self.cx as usize);
-                    self.buffer.rows.insert(self.cy as usize + 1, Row::new(remaining));
-                    self.cy += 1;
-                    self.cx = 0;
-                }
-                '\x7f' | '\x08' => self.delete_char(),
-                c if !c.is_control() => self.insert_char(c),
-                _ => {}
-            },
-            Mode::Command => match key {
-                '\x1b' => self.mode = Mode::Normal,
-                '\r' | '\n' => return self.execute_command(),
-                '\x7f' | '\x08' => { self.command_buffer.pop(); }
-                c if !c.is_control() => self.command_buffer.push(c),
-                _ => {}
-            },
-        }
-        true
+    // vii -: 표준 입력을 끝까지 읽어 버퍼로 쓰고, 키는 /dev/tty 에서 읽음
+    let stdin = args.stdin;
+    let mut text = String::new();
+    if stdin && let Err(e) = io::stdin().read_to_string(&mut text) {
+        eprintln!("vii: cannot read stdin: {}", e);
+        std::process::exit(1);
     }
 
-    fn execute_command(&mut self) -> bool {
-        let cmd = self.command_buffer.as_str();
-        let mut should_continue = true;
-        match cmd {
-            "w" => match self.save() {
-                Ok(_) => self.status_msg = "Saved to output.txt".into(),
-                Err(e) => self.status_msg = format!("Error: {}", e),
-            },
-            "q" => should_continue = false,
-            "wq" => {
-                let _ = self.save();
-                should_continue = false;
-            },
-            _ => self.status_msg = format!("Unknown: {}", cmd),
-        }
-        self.mode = Mode::Normal;
-        self.command_buffer.clear();
-        should_continue
+    // 터미널을 로우 모드로 전환, 패닉하면 훅이 먼저 되돌림
+    terminal::install_panic_hook();
+    let mut term = if stdin {
+        AnsiTerminal::tty().unwrap_or_else(|e| {
+            eprintln!("vii: cannot open /dev/tty: {}", e);
+            std::process::exit(1);
+        })
+    } else {
+        AnsiTerminal::new()
+    };
+    if let Err(e) = term.enable_raw_mode() {
+        eprintln!("vii: cannot set up the terminal: {}", e);
+        std::process::exit(1);
     }
-    fn scroll(&mut self) {
-        let visible_rows = (self.screen_rows - 1) as usize;
-        let visible_cols = self.screen_cols as usize; // 가로 폭
-
-        // 세로 스크롤 (기존 로직 유지)
-        if (self.cy as usize) < self.row_offset {
-            self.row_offset = self.cy as usize;
-        }
-        if (self.cy as usize) >= self.row_offset + visible_rows {
-            self.row_offset = (self.cy as usize) - visible_rows + 1;
-        }
-
-        // 가로 스크롤 추가
-        if (self.cx as usize) < self.col_offset {
-            self.col_offset = self.cx as usize;
-        }
-        if (self.cx as usize) >= self.col_offset + visible_cols {
-            self.col_offset = (self.cx as usize) - visible_cols + 1;
-        }
+    let mut config = EditorConfig::new(); // 에디터 설정 초기화
+    if !args.clean {
+        load_user_config(&mut config);
     }
-}
-
-// --- Helper Functions ---
-fn get_terminal_size() -> (u16, u16) {
-    unsafe {
-        let mut ws: winsize = std::mem::zeroed();
-        if ioctl(STDOUT_FILENO, TIOCGWINSZ, &mut ws) == -1 {
-            return (80, 24);
-        }
-        (ws.ws_col, ws.ws_row)
+    if args.readonly {
+        config.run_command("set readonly");
     }
-}
-
-fn draw_screen(config: &EditorConfig) {
-    let visible_rows = (config.screen_rows - 1) as usize;
-    let visible_cols = config.screen_cols as usize;
-    
-    for y in 0..visible_rows {
-        let file_row_idx = y + config.row_offset;
-        print!("\x1b[K"); 
-
-        if file_row_idx < config.buffer.rows.len() {
-            let row_content = &config.buffer.rows[file_row_idx].content;
-            
-            // col_offset 이후의 문자열만 추출
-            if row_content.len() > config.col_offset {
-                let mut line = row_content[config.col_offset..].to_string();
-                line.truncate(visible_cols); // 화면 폭만큼 자르기
-                print!("{}\r\n", line);
-            } else {
-                print!("\r\n"); // 오프셋이 내용보다 길면 빈 줄
-            }
-        } else {
-            print!("~\r\n");
-        }
+    if args.timing {
+        config.run_command("profile on");
     }
-}
 
-fn draw_status_bar(config: &EditorConfig) {
-    print!("\x1b[{};1H\x1b[K", config.screen_rows);
-    if config.mode == Mode::Command {
-        print!(":{}", config.command_buffer);
+    // 1. 실행 인자 처리 (파일 열기)
+    if stdin {
+        config.load_text(&text);
+    } else if let Some((a, b)) = &args.diff {
+        // vii -d file1 file2: 두 파일을 좌우로 비교
+        config.open_file(a);
+        config.diff_split(b);
+    } else if args.tutor {
+        // vii --tutor: 파일 대신 튜토리얼 버퍼로
+        config.run_command("Tutor");
     } else {
-        let mode_str = match config.mode {
-            Mode::Normal => "-- NORMAL --",
-            Mode::Insert => "-- INSERT --",
-            _ => "",
-        };
-        let status = format!("{} | Pos: {},{} | {}", mode_str, config.cx, config.cy, config.status_msg);
-        print!("\x1b[7m{:width$}\x1b[m", status, width = config.screen_cols as usize);
+        config.set_arglist(&args.files);
     }
-}
-fn refresh_screen(config: &mut EditorConfig) {
-    config.scroll();
-
-    print!("\x1b[?25l\x1b[H"); 
-    draw_screen(config);
-    draw_status_bar(config);
-
-    // 상대 좌표 계산
-    let screen_y = config.cy - config.row_offset as u16;
-    let screen_x = config.cx - config.col_offset as u16; // 가로 보정 추가
-    
-    print!("\x1b[{};{}H\x1b[?25h", screen_y + 1, screen_x + 1);
-    io::stdout().flush().unwrap();
-}
-fn main() {
-    let _raw_mode = RawMode::enable(); // 터미널을 로우 모드로 전환
-    let mut config = EditorConfig::new(); // 에디터 설정 초기화
-
-    // 1. 실행 인자 처리 (파일 열기)
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() > 1 {
-        let filename = args[1].clone();
-        // 파일 열기 시도
-        if config.buffer.open(&filename).is_ok() {
-            config.filename = Some(filename.clone());
-            config.status_msg = format!("Opened: {}", filename);
-        } else {
-            // 파일이 없으면 새 파일로 간주
-            config.filename = Some(filename.clone());
-            config.status_msg = format!("New file: {}", filename);
-        }
+    // -c 와 +인자는 파일을 모두 연 뒤 첫 파일에 적용 (vii +42 a.rs, vii +/TODO a.rs)
+    for command in &args.commands {
+        config.startup_command(command);
     }
 
-    // 2. 초기 화면 청소
-    print!("\x1b[2J");
-
-    // 3. 메인 이벤트 루프
-    loop {
-        refresh_screen(&mut config); // 화면 갱신 (스크롤 및 커서 위치 계산 포함)
-
-        let mut buf = [0; 1];
-        // 표준 입력으로부터 한 바이트씩 읽음
-        if io::stdin().read(&mut buf).is_ok() {
-            let c = buf[0] as char;
-            
-            // 키 입력 처리 핸들러 호출
-            // handle_keypress가 false를 반환하면 (:q 등) 루프 종료
-            if !config.handle_keypress(c) {
-                print!("\x1b[2J\x1b[H"); // 종료 전 화면 정리
-                io::stdout().flush().unwrap();
-                break;
-            }
+    // 2. 초기 화면 청소 후 메인 이벤트 루프
+    let result = panic::catch_unwind(AssertUnwindSafe(|| term.write(b"\x1b[2J").and_then(|_| run(&mut config, &mut term))));
+    drop(term); // 로우 모드를 풀고 나서 오류를 출력
+    if args.timing {
+        config.profile_report().iter().for_each(|line| eprintln!("{}", line));
+    }
+    // 패닉 메시지는 훅이 찍었고, 저장하지 않은 내용을 복구 파일에 남기고 끝냄
+    let Ok(result) = result else {
+        for path in config.write_recovery() {
+            eprintln!("vii: unsaved changes written to {}", path.display());
         }
+        std::process::exit(101);
+    };
+    if let Err(e) = result {
+        eprintln!("vii: {}", e);
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,88 @@
+// --- :make ---
+// makeprg를 셸로 실행하고 출력(표준 출력과 오류)에서 errorformat에 맞는 줄을 quickfix 목록으로 모음
+// errorformat은 쉼표로 나눈 형식들이고 앞의 것부터 맞춰봄
+//   %f 파일 이름   %l 줄   %c 열   %m 메시지   %% 글자 %   나머지는 그대로 같아야 함
+use crate::quickfix::QuickfixEntry;
+use std::io;
+use std::process::{Command, Stdio};
+
+pub const DEFAULT_MAKEPRG: &str = "make";
+pub const DEFAULT_ERRORFORMAT: &str = "%f:%l:%c: %m,%f:%l: %m";
+
+enum Token {
+    Text(String),
+    Field(char), // f, l, c, m
+}
+
+fn tokenize(format: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('%', Some(f @ ('f' | 'l' | 'c' | 'm'))) => {
+                chars.next();
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                tokens.push(Token::Field(f));
+            }
+            ('%', Some('%')) => {
+                chars.next();
+                text.push('%');
+            }
+            (c, _) => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    tokens
+}
+
+// 형식의 나머지가 s 전체에 맞으면 true, 맞춘 값은 entry에 채움
+fn matches(tokens: &[Token], s: &str, entry: &mut QuickfixEntry) -> bool {
+    let Some((token, rest)) = tokens.split_first() else { return s.is_empty() };
+    match token {
+        Token::Text(text) => s.strip_prefix(text.as_str()).is_some_and(|s| matches(rest, s, entry)),
+        Token::Field('l' | 'c') => {
+            let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let Ok(n) = s[..end].parse() else { return false };
+            if matches!(token, Token::Field('l')) { entry.line = n } else { entry.col = n }
+            matches(rest, &s[end..], entry)
+        }
+        Token::Field(field) => {
+            // 파일 이름과 메시지는 나머지가 맞는 가장 짧은 길이 (비어 있으면 안 되는 파일 이름)
+            let start = if *field == 'f' { 1 } else { 0 };
+            s.char_indices().map(|(i, _)| i).chain(Some(s.len())).filter(|&i| i >= start).any(|i| {
+                if !matches(rest, &s[i..], entry) {
+                    return false;
+                }
+                if *field == 'f' { entry.filename = s[..i].to_string() } else { entry.text = s[..i].to_string() }
+                true
+            })
+        }
+    }
+}
+
+/// 출력의 각 줄을 errorformat의 형식들과 맞춰서 위치가 있는 줄만 모음
+pub fn parse(output: &str, errorformat: &str) -> Vec<QuickfixEntry> {
+    let formats: Vec<Vec<Token>> = errorformat.split(',').map(tokenize).collect();
+    output
+        .lines()
+        .filter_map(|line| {
+            formats.iter().find_map(|tokens| {
+                let mut entry = QuickfixEntry { filename: String::new(), line: 0, col: 1, text: String::new() };
+                (matches(tokens, line, &mut entry) && !entry.filename.is_empty() && entry.line > 0).then_some(entry)
+            })
+        })
+        .collect()
+}
+
+/// 셸에서 명령을 실행, (성공했는지, 표준 출력과 오류를 이은 출력)
+pub fn run(command: &str) -> io::Result<(bool, String)> {
+    let output = Command::new("sh").args(["-c", command]).stdin(Stdio::null()).output()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok((output.status.success(), text))
+}
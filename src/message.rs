@@ -0,0 +1,88 @@
+// --- Messages ---
+// 상태 줄의 메시지: status_msg 에 넣은 알림은 화면에 나온 지 messagetime 밀리초가 지나면 사라지고,
+// self.error(..) 로 넣은 오류는 빨간 바탕으로 보이며 다른 메시지로 바뀔 때까지 남음
+//   set messagetime=4000   알림이 보이는 시간 (0 이면 사라지지 않음)
+// 나온 메시지는 최근 HISTORY 개까지 기록에 남음 (오류는 넣을 때, 알림은 화면을 그릴 때)
+//   :messages          기록을 목록 창에 보여줌 (맨 아래가 가장 최근)
+//   :messages clear    기록을 지움
+use crate::{EditorConfig, PanelKind};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const HISTORY: usize = 200;
+
+pub(crate) struct MessageLine {
+    shown: String,             // 마지막으로 화면에 낸 status_msg
+    since: Instant,            // 그 메시지가 처음 보인 때
+    error: Option<String>,     // 오류로 넣은 메시지, status_msg 가 바뀌면 더는 맞지 않음
+    history: VecDeque<String>, // (오류면 "E " 를 붙인) 지난 메시지들
+}
+
+impl MessageLine {
+    pub(crate) fn new() -> Self {
+        MessageLine { shown: String::new(), since: Instant::now(), error: None, history: VecDeque::new() }
+    }
+
+    // 바로 앞에 남긴 것과 같은 메시지는 한 번만
+    fn log(&mut self, entry: String) {
+        if self.history.back() == Some(&entry) {
+            return;
+        }
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(entry);
+    }
+}
+
+impl EditorConfig {
+    /// 오류 메시지를 상태 줄에 냄 (사라지지 않고 다른 색으로 보임)
+    pub(crate) fn error(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        self.messages.log(format!("E {}", msg));
+        self.messages.error = Some(msg.clone());
+        self.status_msg = msg;
+    }
+
+    pub(crate) fn is_error_message(&self) -> bool {
+        !self.status_msg.is_empty() && self.messages.error.as_deref() == Some(self.status_msg.as_str())
+    }
+
+    // 화면을 그릴 때 부름: 메시지가 바뀌었으면 보인 시각을 새로 잼
+    pub(crate) fn track_message(&mut self) {
+        if self.messages.shown != self.status_msg {
+            self.messages.shown = self.status_msg.clone();
+            self.messages.since = Instant::now();
+            if !self.status_msg.is_empty() && !self.is_error_message() {
+                self.messages.log(self.status_msg.clone());
+            }
+        }
+    }
+
+    // :messages [clear]
+    pub(crate) fn show_messages(&mut self, args: &str) {
+        match args {
+            "" if self.messages.history.is_empty() => self.status_msg = "No messages".into(),
+            "" => {
+                let lines: Vec<String> = self.messages.history.iter().cloned().collect();
+                let last = lines.len() - 1;
+                self.open_panel(PanelKind::Messages, "E = error, newest last".into(), lines, last);
+            }
+            "clear" => self.messages.history.clear(),
+            _ => self.error(format!("Invalid argument: {}", args)),
+        }
+    }
+
+    // 시간이 지난 알림을 지움, 지웠으면 true
+    pub(crate) fn expire_message(&mut self) -> bool {
+        let timeout = self.options.messagetime;
+        if self.status_msg.is_empty() || timeout == 0 || self.is_error_message() {
+            return false;
+        }
+        if self.messages.shown != self.status_msg || self.messages.since.elapsed() < Duration::from_millis(timeout) {
+            return false;
+        }
+        self.status_msg.clear();
+        true
+    }
+}
@@ -0,0 +1,51 @@
+// --- Modelines ---
+// set modeline 이 켜져 있으면 파일을 열 때 처음과 마지막 5줄에서 vim 형식의 모드 줄을 찾아 설정을 적용
+//   // vim: set ts=4 sw=4:    set 형식, 다음 : 까지가 설정 (앞뒤의 글은 무시, \: 는 글자 :)
+//   # vi:ts=8:sw=2            set 없이 설정을 공백이나 : 로 나눔
+// vi: / vim: / ex: 앞에는 공백이 있어야 함 (줄 처음의 vi:, vim: 은 됨)
+// 파일이 편집기를 마음대로 바꾸지 못하게 버퍼별 설정(tabstop, shiftwidth)만 받고 나머지는 거절
+use crate::EditorConfig;
+use crate::options::{self, OptionScope};
+
+const MODELINES: usize = 5;
+
+/// 줄이 모드 줄이면 그 안의 설정들
+pub fn parse(line: &str) -> Option<Vec<String>> {
+    let rest = ["vi:", "vim:", "ex:"].iter().find_map(|marker| {
+        let (i, _) = line.match_indices(marker).find(|&(i, _)| {
+            line[..i].ends_with(char::is_whitespace) || (i == 0 && *marker != "ex:")
+        })?;
+        Some(line[i + marker.len()..].trim_start())
+    })?;
+    let options: Vec<String> = match rest.strip_prefix("set ").or_else(|| rest.strip_prefix("se ")) {
+        Some(set) => {
+            let (end, _) = set.char_indices().find(|&(i, c)| c == ':' && !set[..i].ends_with('\\'))?;
+            set[..end].replace("\\:", ":").split_whitespace().map(String::from).collect()
+        }
+        None => rest.split(|c: char| c == ':' || c.is_whitespace()).filter(|s| !s.is_empty()).map(String::from).collect(),
+    };
+    (!options.is_empty()).then_some(options)
+}
+
+impl EditorConfig {
+    pub(crate) fn apply_modelines(&mut self) {
+        if !self.options.modeline {
+            return;
+        }
+        let rows = &self.buffer.rows;
+        let len = rows.len();
+        let lines = (0..len.min(MODELINES)).chain(len.saturating_sub(MODELINES).max(MODELINES)..len);
+        let args: Vec<String> = lines.filter_map(|i| parse(&rows[i].content)).flatten().collect();
+        for arg in args {
+            if !options::is_buffer_local(&arg) {
+                self.error(format!("Modeline: option not allowed: {}", arg));
+                continue;
+            }
+            let mut scope =
+                OptionScope { global: &mut self.options, window: &mut self.window.options, buffer: &mut self.buffer_options };
+            if let Err(e) = scope.set(&arg) {
+                self.error(format!("Modeline: {}", e));
+            }
+        }
+    }
+}
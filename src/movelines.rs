@@ -0,0 +1,132 @@
+// --- Move and Duplicate Lines ---
+// 커서 줄 (비주얼 모드면 선택한 줄들)을 한 줄씩 위아래로 옮기거나 바로 아래에 복제함
+//   Alt-j / Alt-k   아래 / 위로 옮김 (일반, 입력, 비주얼 모드)    Alt-d  복제 (일반, 비주얼 모드)
+//   :[범위]MoveDown / :[범위]MoveUp / :[범위]Duplicate   같은 일을 하는 명령 (매핑으로 다른 키에 붙일 때)
+// 블록을 여는 줄 ({ ( [ 로 끝남, python 등은 : 도)이나 닫는 줄 (} ) ] 로 시작)을 넘어가면
+// 옮긴 줄들의 들여쓰기를 새 자리에 맞춤: 위 줄의 들여쓰기, 위 줄이 블록을 열면 한 단계 더, 첫 줄이 닫는 줄이면 한 단계 덜
+use crate::options::BufferOptions;
+use crate::{EditorConfig, Mode, Row, filetype};
+
+// 줄 앞 공백의 바이트 길이
+fn indent_len(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+// 줄 앞 공백의 화면 폭 (탭은 다음 tabstop 칸까지)
+fn indent_width(line: &str, tabstop: usize) -> usize {
+    line[..indent_len(line)].chars().fold(0, |w, c| if c == '\t' { (w / tabstop + 1) * tabstop } else { w + 1 })
+}
+
+fn opens_block(line: &str, openers: &[char]) -> bool {
+    line.trim_end().ends_with(openers)
+}
+
+fn closes_block(line: &str) -> bool {
+    line.trim_start().starts_with(['}', ')', ']'])
+}
+
+impl EditorConfig {
+    // start..=end 줄들을 한 줄 옮기고 첫 줄 들여쓰기의 변화량을 돌려줌, 옮길 수 없으면 None
+    fn move_lines(&mut self, start: usize, end: usize, down: bool) -> Option<isize> {
+        let rows = &mut self.buffer.rows;
+        let (crossed, start, end) = if down {
+            let row = rows.get(end + 1)?;
+            let crossed = row.content.clone();
+            let row = rows.remove(end + 1);
+            rows.insert(start, row);
+            (crossed, start + 1, end + 1)
+        } else {
+            let crossed = rows.get(start.checked_sub(1)?)?.content.clone();
+            let row = rows.remove(start - 1);
+            rows.insert(end, row);
+            (crossed, start - 1, end - 1)
+        };
+        let openers = filetype::block_openers(self.buffer_options.filetype.as_deref());
+        let delta = if opens_block(&crossed, openers) || closes_block(&crossed) { self.reindent_lines(start, end) } else { 0 };
+        self.buffer.touch();
+        Some(delta)
+    }
+
+    // 위의 (빈 줄이 아닌) 줄을 기준으로 start..=end 줄들을 한꺼번에 들여씀, 첫 줄의 바이트 변화량을 돌려줌
+    // 폭은 화면 칸으로 재고, 들여쓰기는 >> 처럼 expandtab 이면 공백, 아니면 탭과 남는 공백으로 다시 씀
+    fn reindent_lines(&mut self, start: usize, end: usize) -> isize {
+        let BufferOptions { shiftwidth: width, tabstop, expandtab, .. } = self.buffer_options;
+        let openers = filetype::block_openers(self.buffer_options.filetype.as_deref());
+        let rows = &mut self.buffer.rows;
+        let above = rows[..start].iter().rev().map(|r| r.content.as_str()).find(|l| !l.trim().is_empty());
+        let mut want = above.map_or(0, |l| indent_width(l, tabstop) + if opens_block(l, openers) { width } else { 0 });
+        if closes_block(&rows[start].content) {
+            want = want.saturating_sub(width);
+        }
+        let delta = want as isize - indent_width(&rows[start].content, tabstop) as isize;
+        let first = rows[start].content.len() as isize;
+        for row in rows[start..=end].iter_mut().filter(|r| !r.content.trim().is_empty()) {
+            let new = indent_width(&row.content, tabstop).saturating_add_signed(delta);
+            let prefix = match expandtab {
+                true => " ".repeat(new),
+                false => "\t".repeat(new / tabstop) + &" ".repeat(new % tabstop),
+            };
+            row.content.replace_range(..indent_len(&row.content), &prefix);
+        }
+        rows[start].content.len() as isize - first
+    }
+
+    // Alt-j / Alt-k: 커서 줄이나 선택한 줄들을 옮기고 커서와 선택도 따라감
+    pub(crate) fn move_current(&mut self, down: bool) {
+        let (start, end) = self.current_lines();
+        let Some(delta) = self.move_lines(start, end, down) else { return };
+        let step = if down { 1 } else { -1 };
+        self.cy = self.cy.saturating_add_signed(step);
+        self.cx = self.cx.saturating_add_signed(delta);
+        if let Some(sel) = self.selection.as_mut() {
+            sel.anchor.0 = sel.anchor.0.saturating_add_signed(step);
+            sel.anchor.1 = sel.anchor.1.saturating_add_signed(delta);
+        }
+        self.clamp_cursor();
+    }
+
+    // Alt-d: 커서 줄이나 선택한 줄들을 바로 아래에 복제하고 복제본으로 옮겨 감
+    pub(crate) fn duplicate_current(&mut self) {
+        let (start, end) = self.current_lines();
+        let n = self.duplicate_lines(start, end);
+        self.cy += n;
+        if let Some(sel) = self.selection.as_mut() {
+            sel.anchor.0 += n;
+        }
+    }
+
+    fn duplicate_lines(&mut self, start: usize, end: usize) -> usize {
+        let copies: Vec<Row> = self.buffer.rows[start..=end].iter().map(|r| Row::new(r.content.clone())).collect();
+        self.buffer.rows.splice(end + 1..end + 1, copies);
+        self.buffer.touch();
+        end - start + 1
+    }
+
+    // 비주얼 모드면 선택한 줄들, 아니면 커서 줄
+    fn current_lines(&self) -> (usize, usize) {
+        match self.selection_bounds() {
+            Some(((start, _), (end, _))) if self.mode == Mode::Visual => (start, end),
+            _ => (self.cy, self.cy),
+        }
+    }
+
+    // :MoveDown, :MoveUp, :Duplicate (줄 번호는 0부터), 커서가 범위 안이면 따라감
+    pub(crate) fn move_command(&mut self, cmd: &str, start: usize, end: usize) {
+        let inside = (start..=end).contains(&self.cy);
+        match cmd {
+            "Duplicate" => {
+                let n = self.duplicate_lines(start, end);
+                if inside {
+                    self.cy += n;
+                }
+            }
+            _ => {
+                let down = cmd == "MoveDown";
+                if self.move_lines(start, end, down).is_some() && inside {
+                    self.cy = if down { self.cy + 1 } else { self.cy - 1 };
+                }
+            }
+        }
+        self.clamp_cursor();
+    }
+}
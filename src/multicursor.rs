@@ -0,0 +1,105 @@
+// --- Multiple Cursors ---
+// 일반 모드에서 Ctrl-N 을 누를 때마다 커서 아래 낱말이 다음에 나오는 곳에 커서를 하나씩 더 둠
+// (끝에 닿으면 처음부터 찾음, 같은 낱말만 맞춤)
+// 커서가 여럿이면 치는 키 (hjkl, i 와 입력, x, dd 등)가 모든 커서에서 차례로 똑같이 실행되고
+// 앞의 커서에서 줄이나 글자가 늘고 줄면 뒤의 커서 위치도 그만큼 옮김
+// 일반 모드의 Esc, u, Ctrl-R 은 추가 커서를 모두 없앰 (: 명령과 . 등은 주 커서에서만)
+use crate::abbrev::is_word_char;
+use crate::keys::{KeyCode, KeyEvent};
+use crate::{EditorConfig, Mode};
+use std::cmp::Reverse;
+
+impl EditorConfig {
+    // 커서 위치의 낱말 (시작, 끝)
+    fn word_at(&self, line: usize, col: usize) -> Option<(usize, usize)> {
+        let content = &self.buffer.rows[line].content;
+        let col = col.min(content.len());
+        let start = content[..col].char_indices().rev().take_while(|&(_, c)| is_word_char(c)).last().map_or(col, |(i, _)| i);
+        let end = content[col..].char_indices().find(|&(_, c)| !is_word_char(c)).map_or(content.len(), |(i, _)| col + i);
+        (start < end).then_some((start, end))
+    }
+
+    // Ctrl-N: 주 커서 아래 낱말이 마지막 커서 다음에 나오는 곳에 커서를 더함
+    pub(crate) fn add_cursor_at_next_word(&mut self) {
+        let Some((start, end)) = self.word_at(self.cy, self.cx) else {
+            self.status_msg = "No word under cursor".into();
+            return;
+        };
+        let word = self.buffer.rows[self.cy].content[start..end].to_string();
+        if self.cursors.is_empty() {
+            self.cx = start; // 커서들은 모두 낱말의 처음에 둠
+        }
+        let from = self.cursors.last().copied().unwrap_or((self.cy, self.cx));
+        let rows = self.buffer.rows.len();
+        // from 다음부터 버퍼 끝, 다시 처음부터 from 까지 줄마다 찾음
+        let found = (0..=rows).map(|i| (from.0 + i) % rows).enumerate().find_map(|(i, line)| {
+            let content = &self.buffer.rows[line].content;
+            content.match_indices(&word).map(|(col, _)| (line, col)).find(|&(line, col)| {
+                let whole = self.word_at(line, col) == Some((col, col + word.len()));
+                let after = i > 0 || col > from.1;
+                whole && after && (line, col) != (self.cy, self.cx) && !self.cursors.contains(&(line, col))
+            })
+        });
+        match found {
+            Some(pos) => {
+                self.cursors.push(pos);
+                self.status_msg = format!("{} cursors", self.cursors.len() + 1);
+            }
+            None => self.status_msg = format!("No more matches: {}", word),
+        }
+    }
+
+    // 키 하나를 처리, 추가 커서가 있으면 모든 커서에서 실행
+    pub(crate) fn dispatch_multi(&mut self, key: KeyEvent) -> bool {
+        let idle = self.mode == Mode::Normal && self.pending.is_empty() && !self.panel_focused;
+        if idle && [KeyEvent::plain(KeyCode::Esc), KeyEvent::char('u'), KeyEvent::ctrl('r')].contains(&key) {
+            self.cursors.clear();
+        }
+        let primary_only = idle && [KeyEvent::char(':'), KeyEvent::ctrl('n')].contains(&key);
+        if self.cursors.is_empty() || self.panel_focused || self.mode == Mode::Command || primary_only {
+            return self.dispatch_key(key);
+        }
+        let (mode, pending, count, register) = (self.mode, self.pending.clone(), self.count, self.register);
+        // 아래쪽 커서부터 실행해서 앞쪽 편집이 아직 실행하지 않은 커서의 위치를 바꾸지 않게 함
+        let mut todo: Vec<((usize, usize), bool)> = self.cursors.iter().map(|&p| (p, false)).collect();
+        todo.push(((self.cy, self.cx), true));
+        todo.sort_by_key(|&(pos, _)| Reverse(pos));
+        let mut done: Vec<((usize, usize), bool)> = Vec::new();
+        let mut after = (mode, pending.clone(), None, None);
+        let mut running = true;
+        for (pos, primary) in todo {
+            (self.mode, self.pending, self.count, self.register) = (mode, pending.clone(), count, register);
+            (self.cy, self.cx) = pos;
+            self.clamp_cursor();
+            let (lines, len) = (self.buffer.rows.len(), self.buffer.rows[self.cy].content.len());
+            let line = self.cy;
+            running &= self.dispatch_key(key);
+            let line_delta = self.buffer.rows.len() as isize - lines as isize;
+            let col_delta = self.buffer.rows.get(line).map_or(0, |r| r.content.len() as isize - len as isize);
+            for (p, _) in done.iter_mut() {
+                if line_delta != 0 && p.0 > line {
+                    p.0 = p.0.saturating_add_signed(line_delta);
+                } else if line_delta == 0 && p.0 == line && p.1 > pos.1 {
+                    p.1 = p.1.saturating_add_signed(col_delta);
+                }
+            }
+            done.push(((self.cy, self.cx), primary));
+            if primary {
+                after = (self.mode, self.pending.clone(), self.count, self.register);
+            }
+        }
+        (self.mode, self.pending, self.count, self.register) = after;
+        let mut cursors = Vec::new();
+        for (pos, primary) in done.into_iter().rev() {
+            if primary {
+                (self.cy, self.cx) = pos;
+            } else if !cursors.contains(&pos) {
+                cursors.push(pos);
+            }
+        }
+        self.clamp_cursor();
+        cursors.retain(|&p| p != (self.cy, self.cx));
+        self.cursors = cursors;
+        running
+    }
+}
@@ -0,0 +1,88 @@
+// --- Number Increment ---
+// Ctrl-A / Ctrl-X: 커서 위나 뒤에 있는 첫 숫자에 횟수(기본 1)를 더하고 / 뺌
+//   5<C-a>      5 더함
+//   -3          바로 앞의 - 는 음수 부호
+//   0x1f        16진수는 자릿수와 글자 대소문자를 유지 (0x0f -> 0x10, 0xFF -> 0x100)
+// 바꾼 뒤 커서는 숫자의 마지막 글자로
+// 비주얼 모드에서는 선택한 줄마다 선택 안의 첫 숫자를 바꾸고 커서는 선택의 처음으로
+//   g Ctrl-A    줄마다 하나씩 더 더함 (1, 1, 1 → 2, 3, 4)
+use crate::EditorConfig;
+
+// 줄에서 cx 이후로 끝나는 첫 숫자: (시작, 끝, 16진수인지)
+fn find_number(line: &str, cx: usize) -> Option<(usize, usize, bool)> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes[i] == b'0'
+            && matches!(bytes.get(i + 1), Some(b'x' | b'X'))
+            && bytes.get(i + 2).is_some_and(|b| b.is_ascii_hexdigit());
+        let (start, end) = if hex {
+            let digits = bytes[i + 2..].iter().take_while(|b| b.is_ascii_hexdigit()).count();
+            (i, i + 2 + digits)
+        } else if bytes[i].is_ascii_digit() {
+            let digits = bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+            let start = if i > 0 && bytes[i - 1] == b'-' { i - 1 } else { i };
+            (start, i + digits)
+        } else {
+            i += 1;
+            continue;
+        };
+        if end > cx {
+            return Some((start, end, hex));
+        }
+        i = end;
+    }
+    None
+}
+
+// 숫자 글자열에 delta를 더한 글자열
+fn add(number: &str, hex: bool, delta: i64) -> Option<String> {
+    if !hex {
+        let value: i64 = number.parse().ok()?;
+        return Some(value.saturating_add(delta).to_string());
+    }
+    let digits = &number[2..];
+    let value = u64::from_str_radix(digits, 16).ok()?.wrapping_add_signed(delta);
+    let upper = digits.chars().any(|c| c.is_ascii_uppercase());
+    let text = if upper { format!("{:0w$X}", value, w = digits.len()) } else { format!("{:0w$x}", value, w = digits.len()) };
+    Some(format!("{}{}", &number[..2], text))
+}
+
+impl EditorConfig {
+    // Ctrl-A (sign 1) / Ctrl-X (sign -1)
+    pub(crate) fn increment(&mut self, sign: i64) {
+        let delta = sign * self.take_count() as i64;
+        let content = &self.buffer.rows[self.cy].content;
+        let Some((start, end, hex)) = find_number(content, self.cx) else { return };
+        let Some(text) = add(&content[start..end], hex, delta) else { return };
+        self.buffer.rows[self.cy].content.replace_range(start..end, &text);
+        self.cx = start + text.len() - 1;
+        self.buffer.touch();
+    }
+
+    // 비주얼 모드의 Ctrl-A / Ctrl-X, progressive 면 g Ctrl-A / g Ctrl-X
+    pub(crate) fn increment_selection(&mut self, sign: i64, progressive: bool) {
+        let Some(((start_line, start_col), (end_line, _))) = self.selection_bounds() else { return };
+        let step = sign * self.take_count() as i64;
+        let (mut delta, mut changed) = (step, false);
+        for line in start_line..=end_line {
+            let Some((from, to)) = self.selected_columns(line) else { continue };
+            let content = &self.buffer.rows[line].content;
+            let to = to.min(content.len());
+            let Some((start, end, hex)) = content.get(from..to).and_then(|s| find_number(s, 0)) else { continue };
+            let (start, end) = (from + start, from + end);
+            let Some(text) = add(&content[start..end], hex, delta) else { continue };
+            self.buffer.rows[line].content.replace_range(start..end, &text);
+            changed = true;
+            if progressive {
+                delta += step;
+            }
+        }
+        if changed {
+            self.buffer.touch();
+        }
+        self.end_visual();
+        (self.cy, self.cx) = (start_line, start_col);
+        self.clamp_cursor();
+    }
+}
@@ -0,0 +1,294 @@
+// --- Options ---
+// :set 으로 바꾸는 편집기 설정 (viirc에 써 두면 시작할 때마다 적용됨)
+//   set number         켜기          set nonumber      끄기 (줄 번호 열)
+//   set cursorline!    뒤집기        set cursorline?   지금 값 보기
+//   set foldmethod=indent   들여쓰기로 접기 (manual이면 zf로 직접)
+//   set colorcolumn=80,100  값이 있는 설정   set colorcolumn?  (또는 값 없이 이름만) 지금 값 보기
+//   set scrolloff=3           스크롤할 때 커서 위아래로 3줄을 남김 (sidescrolloff 는 긴 줄의 왼쪽과 오른쪽에 칸을)
+//   set makeprg=cargo\ build  값 안의 공백은 \로 (:make가 실행할 명령, errorformat은 출력 형식)
+//   set swapdir=~/.cache/vii  복구 파일을 둘 곳 (backupdir, undodir 도, "." 이면 파일 옆, artifacts.rs)
+// 한 번에 여러 개를 공백으로 나눠 줄 수 있음 (set cul cc=80)
+// number, cursorline, colorcolumn, foldmethod, wrap, displaylines, scrollbar, scrolloff, sidescrolloff 는 창마다 따로 (WindowOptions),
+// tabstop, shiftwidth, softtabstop, expandtab, fileformat, fixendofline, trimtrailing, filetype, readonly 는 버퍼마다 따로
+// (BufferOptions, :set 은 새 버퍼가 물려받을 값도 바꿈), 나머지는 전역
+use crate::{artifacts, lookup, make};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FoldMethod {
+    Manual,
+    Indent,
+}
+
+/// 저장할 때 쓰는 줄바꿈 (읽을 때 첫 줄의 줄바꿈으로 정함)
+#[derive(Clone, Copy, PartialEq)]
+pub enum FileFormat {
+    Unix, // \n
+    Dos,  // \r\n
+}
+
+impl FileFormat {
+    pub fn eol(self) -> &'static str {
+        match self {
+            FileFormat::Unix => "\n",
+            FileFormat::Dos => "\r\n",
+        }
+    }
+}
+
+// 전역 설정
+pub struct Options {
+    pub makeprg: String,
+    pub errorformat: String,
+    pub keywordprg: String,   // K 가 낱말을 찾을 명령 (lookup.rs)
+    pub modeline: bool,       // 파일을 열 때 모드 줄의 버퍼별 설정을 적용
+    pub editorconfig: bool,   // 파일을 열 때 .editorconfig 를 적용
+    pub syntax: bool,         // 파일 종류에 맞춰 글자에 색을 입힘
+    pub smoothscroll: bool,   // 화면이 크게 움직일 때 몇 프레임에 걸쳐 (scrolling.rs)
+    pub zenwidth: usize,      // :Zen 에서 본문의 폭
+    pub messagetime: u64,     // 알림 메시지가 상태 줄에 남아 있는 밀리초 (0 이면 계속)
+    pub swapdir: String,      // 복구 파일을 둘 디렉터리
+    pub backup: bool,         // 저장할 때 덮어쓰기 전의 파일을 backupdir 에 남김
+    pub backupdir: String,
+    pub undofile: bool,       // 저장할 때 실행 취소 기록을 undodir 에 쓰고 열 때 읽음
+    pub undodir: String,
+    pub tags: String,         // Ctrl-] 가 읽을 tags 파일들 (쉼표로 나눔, ./ 는 지금 파일의 디렉터리)
+}
+
+// 창마다 따로 갖는 설정
+#[derive(Clone)]
+pub struct WindowOptions {
+    pub number: bool,            // 왼쪽에 줄 번호 열
+    pub cursorline: bool,        // 커서가 있는 줄에 배경색
+    pub colorcolumn: Vec<usize>, // 배경색을 칠할 열들 (1부터), 줄 길이 안내선
+    pub foldmethod: FoldMethod,
+    pub wrap: bool,              // 긴 줄을 여러 화면 줄로 나눠 보여줌
+    pub displaylines: bool,      // j / k 가 gj / gk 처럼 화면 줄 단위로 움직임
+    pub scrollbar: bool,         // 오른쪽 끝 열에 화면이 버퍼의 어디쯤인지 보여주는 막대
+    pub scrolloff: usize,        // 스크롤할 때 커서 위아래로 남겨 둘 줄 수
+    pub sidescrolloff: usize,    // 가로로 스크롤할 때 커서 왼쪽과 오른쪽에 남겨 둘 칸 수
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Options {
+            makeprg: make::DEFAULT_MAKEPRG.into(),
+            errorformat: make::DEFAULT_ERRORFORMAT.into(),
+            keywordprg: lookup::DEFAULT_KEYWORDPRG.into(),
+            modeline: false,
+            editorconfig: true,
+            syntax: true,
+            smoothscroll: false,
+            zenwidth: 80,
+            messagetime: 4000,
+            swapdir: artifacts::default_dir("swap"),
+            backup: false,
+            backupdir: artifacts::default_dir("backup"),
+            undofile: false,
+            undodir: artifacts::default_dir("undo"),
+            tags: "./tags,tags".into(),
+        }
+    }
+}
+
+// 버퍼마다 따로 갖는 설정
+#[derive(Clone)]
+pub struct BufferOptions {
+    pub tabstop: usize,           // 탭 한 개의 칸 수
+    pub shiftwidth: usize,        // >>, Ctrl-T 등이 한 번에 들여쓰는 칸 수
+    pub expandtab: bool,          // 들여쓰기를 공백으로 (끄면 tabstop 마다 탭)
+    pub softtabstop: isize,       // 입력 모드의 Tab / Backspace 가 멈추는 칸 간격 (0 이면 tabstop, 음수면 shiftwidth, tabs.rs)
+    pub fileformat: FileFormat,
+    pub fixendofline: bool,       // 저장할 때 마지막 줄 뒤에도 줄바꿈
+    pub trimtrailing: bool,       // 저장할 때 줄 끝 공백을 지움
+    pub filetype: Option<String>, // 열 때 판별한 파일 종류 (filetype.rs)
+    pub readonly: bool,           // :w 를 거절함 (:w! 는 켜진 채로 씀)
+}
+
+impl BufferOptions {
+    pub fn new() -> Self {
+        BufferOptions {
+            tabstop: 8,
+            shiftwidth: 4,
+            expandtab: true,
+            softtabstop: 0,
+            fileformat: FileFormat::Unix,
+            fixendofline: false,
+            trimtrailing: false,
+            filetype: None,
+            readonly: false,
+        }
+    }
+}
+
+/// 버퍼마다 따로 갖는 설정인지 (:set 인자에서 no, !, ?, =값 을 떼고 봄)
+pub fn is_buffer_local(arg: &str) -> bool {
+    let name = arg.split('=').next().unwrap_or(arg).trim_end_matches(['!', '?']);
+    matches!(
+        name.strip_prefix("no").unwrap_or(name),
+        "tabstop"
+            | "ts"
+            | "shiftwidth"
+            | "sw"
+            | "softtabstop"
+            | "sts"
+            | "expandtab"
+            | "et"
+            | "fileformat"
+            | "ff"
+            | "fixendofline"
+            | "fixeol"
+            | "trimtrailing"
+            | "filetype"
+            | "ft"
+            | "readonly"
+            | "ro"
+    )
+}
+
+impl WindowOptions {
+    pub fn new() -> Self {
+        WindowOptions {
+            number: false,
+            cursorline: false,
+            colorcolumn: Vec::new(),
+            foldmethod: FoldMethod::Manual,
+            wrap: false,
+            displaylines: false,
+            scrollbar: false,
+            scrolloff: 0,
+            sidescrolloff: 0,
+        }
+    }
+}
+
+/// :set 이 바꾸는 설정들, 전역 설정과 지금 창, 지금 버퍼의 설정을 함께 빌려옴
+pub struct OptionScope<'a> {
+    pub global: &'a mut Options,
+    pub window: &'a mut WindowOptions,
+    pub buffer: &'a mut BufferOptions,
+}
+
+impl OptionScope<'_> {
+    // 켜고 끄는 설정, 줄임말도 받음
+    fn flag(&mut self, name: &str) -> Option<&mut bool> {
+        match name {
+            "number" | "nu" => Some(&mut self.window.number),
+            "cursorline" | "cul" => Some(&mut self.window.cursorline),
+            "wrap" => Some(&mut self.window.wrap),
+            "displaylines" | "dl" => Some(&mut self.window.displaylines),
+            "scrollbar" => Some(&mut self.window.scrollbar),
+            "modeline" | "ml" => Some(&mut self.global.modeline),
+            "editorconfig" | "ec" => Some(&mut self.global.editorconfig),
+            "syntax" | "syn" => Some(&mut self.global.syntax),
+            "smoothscroll" | "sms" => Some(&mut self.global.smoothscroll),
+            "backup" | "bk" => Some(&mut self.global.backup),
+            "undofile" | "udf" => Some(&mut self.global.undofile),
+            "expandtab" | "et" => Some(&mut self.buffer.expandtab),
+            "fixendofline" | "fixeol" => Some(&mut self.buffer.fixendofline),
+            "trimtrailing" => Some(&mut self.buffer.trimtrailing),
+            "readonly" | "ro" => Some(&mut self.buffer.readonly),
+            _ => None,
+        }
+    }
+
+    // 값이 있는 설정의 지금 값
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "colorcolumn" | "cc" => Some(self.window.colorcolumn.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(",")),
+            "foldmethod" | "fdm" => Some(match self.window.foldmethod {
+                FoldMethod::Manual => "manual".into(),
+                FoldMethod::Indent => "indent".into(),
+            }),
+            "makeprg" | "mp" => Some(self.global.makeprg.clone()),
+            "keywordprg" | "kp" => Some(self.global.keywordprg.clone()),
+            "scrolloff" | "so" => Some(self.window.scrolloff.to_string()),
+            "sidescrolloff" | "siso" => Some(self.window.sidescrolloff.to_string()),
+            "messagetime" | "mt" => Some(self.global.messagetime.to_string()),
+            "errorformat" | "efm" => Some(self.global.errorformat.clone()),
+            "swapdir" | "dir" => Some(self.global.swapdir.clone()),
+            "backupdir" | "bdir" => Some(self.global.backupdir.clone()),
+            "undodir" | "udir" => Some(self.global.undodir.clone()),
+            "tags" | "tag" => Some(self.global.tags.clone()),
+            "zenwidth" => Some(self.global.zenwidth.to_string()),
+            "tabstop" | "ts" => Some(self.buffer.tabstop.to_string()),
+            "shiftwidth" | "sw" => Some(self.buffer.shiftwidth.to_string()),
+            "softtabstop" | "sts" => Some(self.buffer.softtabstop.to_string()),
+            "fileformat" | "ff" => Some(match self.buffer.fileformat {
+                FileFormat::Unix => "unix".into(),
+                FileFormat::Dos => "dos".into(),
+            }),
+            "filetype" | "ft" => Some(self.buffer.filetype.clone().unwrap_or_default()),
+            _ => None,
+        }
+    }
+
+    fn set_value(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let invalid = || format!("Invalid value: {}={}", name, value);
+        match name {
+            "colorcolumn" | "cc" => {
+                let columns: Result<Vec<usize>, _> = value.split(',').filter(|c| !c.is_empty()).map(|c| c.parse()).collect();
+                self.window.colorcolumn = columns.ok().filter(|c| !c.contains(&0)).ok_or_else(invalid)?;
+            }
+            "foldmethod" | "fdm" => {
+                self.window.foldmethod = match value {
+                    "manual" => FoldMethod::Manual,
+                    "indent" => FoldMethod::Indent,
+                    _ => return Err(invalid()),
+                }
+            }
+            "makeprg" | "mp" => self.global.makeprg = value.to_string(),
+            "keywordprg" | "kp" => self.global.keywordprg = value.to_string(),
+            "scrolloff" | "so" => self.window.scrolloff = value.parse().map_err(|_| invalid())?,
+            "sidescrolloff" | "siso" => self.window.sidescrolloff = value.parse().map_err(|_| invalid())?,
+            "messagetime" | "mt" => self.global.messagetime = value.parse().map_err(|_| invalid())?,
+            "errorformat" | "efm" => self.global.errorformat = value.to_string(),
+            "swapdir" | "dir" | "backupdir" | "bdir" | "undodir" | "udir" if value.is_empty() => return Err(invalid()),
+            "swapdir" | "dir" => self.global.swapdir = value.to_string(),
+            "backupdir" | "bdir" => self.global.backupdir = value.to_string(),
+            "undodir" | "udir" => self.global.undodir = value.to_string(),
+            "tags" | "tag" => self.global.tags = value.to_string(),
+            "zenwidth" => self.global.zenwidth = value.parse().ok().filter(|&n| n > 0).ok_or_else(invalid)?,
+            "tabstop" | "ts" => self.buffer.tabstop = value.parse().ok().filter(|&n| n > 0).ok_or_else(invalid)?,
+            "shiftwidth" | "sw" => self.buffer.shiftwidth = value.parse().ok().filter(|&n| n > 0).ok_or_else(invalid)?,
+            "softtabstop" | "sts" => self.buffer.softtabstop = value.parse().map_err(|_| invalid())?,
+            "fileformat" | "ff" => {
+                self.buffer.fileformat = match value {
+                    "unix" => FileFormat::Unix,
+                    "dos" => FileFormat::Dos,
+                    _ => return Err(invalid()),
+                }
+            }
+            "filetype" | "ft" => self.buffer.filetype = (!value.is_empty()).then(|| value.to_string()),
+            _ => return Err(format!("Unknown option: {}", name)),
+        }
+        Ok(())
+    }
+
+    /// :set 인자 하나를 적용, 값을 물어본 경우엔 보여줄 문자열을 돌려줌
+    pub fn set(&mut self, arg: &str) -> Result<Option<String>, String> {
+        let unknown = || format!("Unknown option: {}", arg);
+        if let Some((name, value)) = arg.split_once('=') {
+            return self.set_value(name, value).map(|_| None);
+        }
+        let name = arg.strip_suffix('?').unwrap_or(arg);
+        if let Some(value) = self.value(name) {
+            return Ok(Some(format!("{}={}", name, value)));
+        }
+        if let Some(name) = arg.strip_suffix('?') {
+            let value = *self.flag(name).ok_or_else(unknown)?;
+            return Ok(Some(format!("{}{}", if value { "" } else { "no" }, name)));
+        }
+        if let Some(name) = arg.strip_suffix('!') {
+            let flag = self.flag(name).ok_or_else(unknown)?;
+            *flag = !*flag;
+            return Ok(None);
+        }
+        if let Some(flag) = self.flag(arg) {
+            *flag = true;
+            return Ok(None);
+        }
+        let flag = arg.strip_prefix("no").and_then(|name| self.flag(name)).ok_or_else(unknown)?;
+        *flag = false;
+        Ok(None)
+    }
+}
@@ -0,0 +1,71 @@
+// --- Bracketed Paste ---
+// 터미널이 붙여넣은 글을 (\x1b[200~ ... \x1b[201~ 로 감싸서 보냄) 키 하나씩이 아니라 한 번에 받아
+// 버퍼를 한 번만 고치고 화면도 한 번만 그림 (매핑, 약어, 완성 메뉴를 거치지 않음)
+//   입력 모드   커서 자리에 넣고 커서는 붙인 글 뒤로
+//   일반 모드   커서 앞에 넣고 커서는 붙인 글의 마지막 글자로 (u 한 번에 모두 취소)
+//   비주얼 모드 선택한 글을 붙인 글로 바꿈 (줄 단위 선택은 줄들을 통째로), 그 뒤는 일반 모드와 같음
+//   명령 모드   첫 줄을 명령 줄에 덧붙임
+// 붙여넣기를 알리지 않는 터미널에서도 이벤트 루프가 쌓인 키를 다 처리한 뒤에 한 번만 그림
+use crate::{EditorConfig, Mode, Row};
+
+impl EditorConfig {
+    /// 붙여넣은 글 (줄바꿈은 \n) 을 지금 모드에 맞게 넣음
+    pub fn paste_text(&mut self, text: &str) {
+        match self.mode {
+            Mode::Insert => self.insert_pasted(text),
+            Mode::Normal | Mode::Visual if !text.is_empty() => {
+                let mut text = text;
+                // 줄 단위 선택을 바꿀 때 끝의 줄바꿈은 지운 줄의 것으로 봄
+                if self.mode == Mode::Visual && self.remove_selection() {
+                    text = text.strip_suffix('\n').unwrap_or(text);
+                }
+                self.insert_pasted(text);
+                let content = &self.buffer.rows[self.cy].content;
+                self.cx = content[..self.cx].char_indices().next_back().map_or(0, |(i, _)| i);
+                self.commit_undo();
+            }
+            Mode::Command => self.command_buffer.push_str(text.lines().next().unwrap_or("")),
+            _ => {}
+        }
+    }
+
+    // 선택한 글을 지우고 커서를 그 자리로, 줄 단위 선택이었으면 빈 줄 하나를 남기고 true
+    fn remove_selection(&mut self) -> bool {
+        let Some(((start_line, start_col), (end_line, end_col))) = self.selection_bounds() else { return false };
+        let linewise = self.selection.is_some_and(|s| s.linewise);
+        self.end_visual();
+        if linewise {
+            self.buffer.rows.splice(start_line..=end_line, [Row::new(String::new())]);
+            (self.cy, self.cx) = (start_line, 0);
+        } else {
+            let last = &self.buffer.rows[end_line].content;
+            let end = (end_col + last.get(end_col..).and_then(|s| s.chars().next()).map_or(0, char::len_utf8)).min(last.len());
+            let rest = last[end..].to_string();
+            let row = &mut self.buffer.rows[start_line].content;
+            let start = start_col.min(row.len());
+            row.truncate(start);
+            row.push_str(&rest);
+            self.buffer.rows.drain(start_line + 1..=end_line);
+            (self.cy, self.cx) = (start_line, start);
+        }
+        self.buffer.touch();
+        linewise
+    }
+
+    // 커서 자리에 넣고 커서를 붙인 글 바로 뒤로
+    fn insert_pasted(&mut self, text: &str) {
+        let row = &mut self.buffer.rows[self.cy].content;
+        let rest = row.split_off(self.cx);
+        let mut lines = text.split('\n');
+        row.push_str(lines.next().unwrap_or(""));
+        let mut rows: Vec<Row> = lines.map(|l| Row::new(l.to_string())).collect();
+        let last = rows.last_mut().map_or(row, |r| &mut r.content);
+        self.cx = last.len();
+        last.push_str(&rest);
+        let added = rows.len();
+        self.buffer.rows.splice(self.cy + 1..self.cy + 1, rows);
+        self.cy += added;
+        self.buffer.touch();
+        self.changes.record((self.cy, self.cx));
+    }
+}
@@ -0,0 +1,99 @@
+// --- Picker Overlay ---
+// 화면 가운데에 뜨는 목록: 글자를 치면 퍼지 검색으로 좁히고 Enter로 고름.
+// 버퍼 선택(:Buffers, 기본 매핑 <leader>b)과 심볼 목록(:Symbols, symbols.rs)에 씀
+use crate::keys::{KeyCode, KeyEvent, Modifiers};
+use crate::EditorConfig;
+
+// 고른 항목으로 할 일
+pub enum PickerKind {
+    Buffers,
+    Symbols(Vec<(usize, usize)>), // 항목마다의 (줄, 바이트 열)
+}
+
+pub struct Picker {
+    pub kind: PickerKind,
+    pub title: String,
+    pub query: String,
+    items: Vec<(usize, String, String)>, // (id, 검색할 이름, 보여줄 줄)
+    pub matches: Vec<usize>,              // items 안의 위치, 잘 맞는 순서
+    pub selected: usize,
+}
+
+impl Picker {
+    pub fn new(kind: PickerKind, title: &str, items: Vec<(usize, String, String)>, selected: usize) -> Self {
+        let mut picker = Picker { kind, title: title.to_string(), query: String::new(), items, matches: Vec::new(), selected };
+        picker.filter();
+        picker.selected = selected.min(picker.matches.len().saturating_sub(1));
+        picker
+    }
+
+    pub fn line(&self, i: usize) -> &str {
+        &self.items[self.matches[i]].2
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(usize, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, name, _))| fuzzy_score(&self.query, name).map(|score| (score, i)))
+            .collect();
+        scored.sort();
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+
+    fn chosen(&self) -> Option<usize> {
+        self.matches.get(self.selected).map(|&i| self.items[i].0)
+    }
+}
+
+// query의 글자가 순서대로 (대소문자 무시) 나타나면 흩어진 정도를 점수로, 작을수록 잘 맞음
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut chars = text.chars().enumerate();
+    let (mut score, mut last) = (0, None);
+    for q in query.chars() {
+        let (i, _) = chars.by_ref().find(|(_, c)| c.to_lowercase().eq(q.to_lowercase()))?;
+        score += last.map_or(i, |l| i - l - 1);
+        last = Some(i);
+    }
+    Some(score)
+}
+
+impl EditorConfig {
+    pub(crate) fn open_buffer_picker(&mut self) {
+        let items = self.buffer_infos().into_iter().map(|b| (b.number - 1, b.name.clone(), b.display())).collect();
+        self.picker = Some(Picker::new(PickerKind::Buffers, "Buffers", items, self.buffers.current));
+    }
+
+    // 선택 창이 떠 있는 동안의 키 처리
+    pub(crate) fn handle_picker_key(&mut self, key: KeyEvent) {
+        let Some(picker) = self.picker.as_mut() else { return };
+        let last = picker.matches.len().saturating_sub(1);
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => self.picker = None,
+            (KeyCode::Enter, _) => {
+                let chosen = picker.chosen();
+                let Some(picker) = self.picker.take() else { return };
+                match (picker.kind, chosen) {
+                    (PickerKind::Buffers, Some(index)) => self.switch_buffer(index),
+                    (PickerKind::Symbols(positions), Some(index)) => self.jump_to_symbol(positions[index]),
+                    _ => {}
+                }
+            }
+            (KeyCode::Down | KeyCode::Tab, _) | (KeyCode::Char('n'), Modifiers::CTRL) => {
+                picker.selected = (picker.selected + 1).min(last)
+            }
+            (KeyCode::Up, _) | (KeyCode::Char('p'), Modifiers::CTRL) => picker.selected = picker.selected.saturating_sub(1),
+            (KeyCode::Backspace, _) => {
+                picker.query.pop();
+                picker.filter();
+            }
+            (KeyCode::Char(c), Modifiers::NONE | Modifiers::SHIFT) => {
+                picker.query.push(c);
+                picker.filter();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// --- Profiling ---
+// 느린 곳을 찾기 위해 화면 그리기, 키 처리, 입력 지연, 백그라운드 작업(lsp, git, syntax, search)에 걸린 시간을 잼
+//   :profile on / off   재기를 켜고 끔 (vii --timing 은 켠 채로 시작해 끝날 때 결과를 표준 오류에 냄)
+//   :profile            잰 것을 목록 창에 보여줌 (횟수, 평균, 최대, 합계)
+//   :profile clear      잰 것을 지움
+// 입력 지연은 키가 들어온 때부터 그 키를 반영한 화면을 다 그릴 때까지
+use crate::{EditorConfig, PanelKind};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct Stats {
+    count: u32,
+    total: Duration,
+    max: Duration,
+}
+
+pub struct Profiler {
+    enabled: bool,
+    stats: Vec<(&'static str, Stats)>, // 처음 잰 순서대로
+    input_since: Option<Instant>,      // 아직 화면에 반영하지 않은 가장 이른 입력이 온 때
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Profiler { enabled: false, stats: Vec::new(), input_since: None }
+    }
+
+    fn record(&mut self, name: &'static str, elapsed: Duration) {
+        let index = match self.stats.iter().position(|(n, _)| *n == name) {
+            Some(i) => i,
+            None => {
+                self.stats.push((name, Stats::default()));
+                self.stats.len() - 1
+            }
+        };
+        let stats = &mut self.stats[index].1;
+        stats.count += 1;
+        stats.total += elapsed;
+        stats.max = stats.max.max(elapsed);
+    }
+
+    /// 재는 중이면 지금 시각 (stop 에 넘김)
+    pub fn start(&self) -> Option<Instant> {
+        self.enabled.then(Instant::now)
+    }
+
+    pub fn stop(&mut self, name: &'static str, start: Option<Instant>) {
+        if let Some(start) = start {
+            self.record(name, start.elapsed());
+        }
+    }
+
+    // 키나 붙여넣기가 들어옴
+    pub fn input_arrived(&mut self) {
+        if self.enabled && self.input_since.is_none() {
+            self.input_since = Some(Instant::now());
+        }
+    }
+
+    // 화면을 다 그림
+    pub fn frame_drawn(&mut self) {
+        if let Some(since) = self.input_since.take() {
+            self.record("input latency", since.elapsed());
+        }
+    }
+
+    /// 항목마다 한 줄씩
+    pub fn report(&self) -> Vec<String> {
+        let ms = |d: Duration| format!("{:.2}ms", d.as_secs_f64() * 1000.0);
+        self.stats
+            .iter()
+            .map(|(name, s)| {
+                let average = s.total / s.count.max(1);
+                format!("{:<14}{:>7} calls  avg {:>9}  max {:>9}  total {:>10}", name, s.count, ms(average), ms(s.max), ms(s.total))
+            })
+            .collect()
+    }
+}
+
+impl EditorConfig {
+    // :profile [on|off|clear]
+    pub(crate) fn profile_command(&mut self, args: &str) {
+        let profiler = &mut self.profiler;
+        match args {
+            "on" | "start" => {
+                profiler.enabled = true;
+                self.status_msg = "Profiling on".into();
+            }
+            "off" | "stop" => {
+                profiler.enabled = false;
+                profiler.input_since = None;
+                self.status_msg = "Profiling off".into();
+            }
+            "clear" => profiler.stats.clear(),
+            "" if profiler.stats.is_empty() && !profiler.enabled => self.status_msg = "Not profiling (use :profile on)".into(),
+            "" if profiler.stats.is_empty() => self.status_msg = "Nothing measured yet".into(),
+            "" => {
+                let title = if profiler.enabled { "measuring" } else { "stopped" };
+                let lines = profiler.report();
+                self.open_panel(PanelKind::Profile, title.into(), lines, 0);
+            }
+            _ => self.error(format!("Invalid argument: {}", args)),
+        }
+    }
+
+    /// 잰 결과 (vii --timing 이 끝날 때 냄)
+    pub fn profile_report(&self) -> Vec<String> {
+        self.profiler.report()
+    }
+}
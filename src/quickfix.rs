@@ -0,0 +1,82 @@
+// --- Quickfix List ---
+// grep 등 여러 기능이 공유하는 "파일:줄:열" 위치 목록
+
+pub struct QuickfixEntry {
+    pub filename: String,
+    pub line: usize, // 1부터 시작
+    pub col: usize,  // 1부터 시작
+    pub text: String,
+}
+
+impl QuickfixEntry {
+    // "file:line:col:text" 또는 "file:line:text" 형식의 한 줄을 해석
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.splitn(4, ':');
+        let filename = parts.next()?.to_string();
+        let line = parts.next()?.trim().parse().ok()?;
+        let rest: Vec<&str> = parts.collect();
+        let (col, text) = match rest.as_slice() {
+            [col, text] => match col.trim().parse() {
+                Ok(c) => (c, text.to_string()),
+                Err(_) => (1, format!("{}:{}", col, text)),
+            },
+            [text] => (1, text.to_string()),
+            _ => (1, String::new()),
+        };
+        if filename.is_empty() {
+            return None;
+        }
+        Some(QuickfixEntry { filename, line, col, text })
+    }
+
+    pub fn display(&self) -> String {
+        format!("{}:{}:{}: {}", self.filename, self.line, self.col, self.text.trim())
+    }
+}
+
+pub struct QuickfixList {
+    pub title: String,
+    pub entries: Vec<QuickfixEntry>,
+    pub current: usize,
+}
+
+impl QuickfixList {
+    pub fn new() -> Self {
+        QuickfixList { title: String::new(), entries: Vec::new(), current: 0 }
+    }
+
+    pub fn set(&mut self, title: String, entries: Vec<QuickfixEntry>) {
+        self.title = title;
+        self.entries = entries;
+        self.current = 0;
+    }
+
+    // 다음 항목으로 이동, 마지막이면 false
+    pub fn next(&mut self) -> bool {
+        if self.current + 1 < self.entries.len() {
+            self.current += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn prev(&mut self) -> bool {
+        if self.current > 0 && !self.entries.is_empty() {
+            self.current -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // 1부터 시작하는 번호로 선택
+    pub fn select(&mut self, nr: usize) -> bool {
+        if nr >= 1 && nr <= self.entries.len() {
+            self.current = nr - 1;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// --- Ex Command Ranges ---
+// 명령 앞에 붙는 줄 범위: 42, ., $, %, .+1, 3,$-2 같은 표기 (줄 번호는 1부터)
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Range {
+    pub start: usize,
+    pub end: usize,
+}
+
+// 줄 하나를 가리키는 표기 (숫자, ., $ 와 +N/-N 오프셋)
+fn address(s: &str, current: usize, last: usize) -> Option<(usize, &str)> {
+    let (mut line, mut rest) = match s.chars().next()? {
+        '.' => (current as i64, &s[1..]),
+        '$' => (last as i64, &s[1..]),
+        c if c.is_ascii_digit() => {
+            let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            (s[..end].parse().ok()?, &s[end..])
+        }
+        '+' | '-' => (current as i64, s),
+        _ => return None,
+    };
+    while let Some(sign) = rest.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let digits = &rest[1..];
+        let end = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+        let n: i64 = if end == 0 { 1 } else { digits[..end].parse().ok()? };
+        line += if sign == '+' { n } else { -n };
+        rest = &digits[end..];
+    }
+    Some((line.clamp(0, last as i64) as usize, rest))
+}
+
+// 앞에 붙은 범위를 떼어내고 (범위, 나머지 명령) 반환
+pub fn parse(line: &str, current: usize, last: usize) -> (Option<Range>, &str) {
+    if let Some(rest) = line.strip_prefix('%') {
+        return (Some(Range { start: 1, end: last }), rest.trim_start());
+    }
+    let Some((start, rest)) = address(line, current, last) else { return (None, line) };
+    let (end, rest) = match rest.strip_prefix(',').and_then(|r| address(r, current, last)) {
+        Some((end, rest)) => (end, rest),
+        None => (start, rest),
+    };
+    let (start, end) = if start <= end { (start, end) } else { (end, start) };
+    (Some(Range { start: start.max(1), end: end.max(1) }), rest.trim_start())
+}
@@ -0,0 +1,122 @@
+// --- Recovery Files ---
+// 종료 신호(SIGTERM, SIGHUP)를 받으면 저장하지 않은 버퍼마다 내용을 복구 파일에 써 두고 나감
+//   dir/name      → swapdir 의 %dir%name.vii.swp (swapdir 이 "." 이면 dir/.name.vii.swp, artifacts.rs)
+//   vim 의 .name.swp 와 이름이 겹치지 않게 .vii.swp 를 붙이고, 그 자리에 보통 파일이 아닌 것이 있으면 쓰지 않음
+//   이름 없는 버퍼 → swapdir 의 vii-<pid>-<버퍼 번호>.swp ("." 이면 임시 디렉터리)
+// 쓰지 못한 파일은 건너뜀 (터미널이 닫힌 뒤라 알릴 곳이 없음)
+// 파일을 열 때 복구 파일이 파일보다 새로우면 (파일이 없어도) 바뀐 줄들을 목록 창에 미리 보여주고 물음
+//   r  복구 파일의 내용으로 버퍼를 채우고 (수정된 상태, :w 로 저장) 복구 파일을 지움
+//   d  복구 파일과 좌우로 비교 (:diffsplit)
+//   x  복구 파일을 지움
+//   q / Esc  그대로 둠 (다음에 열 때 다시 물음)
+use crate::{EditorConfig, PanelKind, artifacts, diff};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+// 미리 보기에 보여줄 최대 줄 수
+const PREVIEW: usize = 500;
+
+/// 버퍼의 복구 파일 경로
+pub fn recovery_path(swapdir: &str, filename: Option<&str>, number: usize) -> PathBuf {
+    match filename {
+        Some(name) => artifacts::path_in(swapdir, name, ".vii.swp"),
+        None => artifacts::unnamed_in(swapdir, &format!("vii-{}-{}.swp", std::process::id(), number)),
+    }
+}
+
+// 전에 남긴 복구 파일은 새 내용으로 바꾸고, 심볼릭 링크 같은 다른 것이 있으면 건드리지 않음
+fn write_new(path: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_file() => fs::remove_file(path)?,
+        Ok(_) => return Err(io::Error::new(io::ErrorKind::AlreadyExists, "not a recovery file")),
+        Err(_) => {}
+    }
+    OpenOptions::new().write(true).create_new(true).open(path)?.write_all(content)
+}
+
+// 파일에서 복구 파일로 바뀐 줄들 ("   12 - 지운 줄", "   12 + 더한 줄")
+fn preview(file: &[String], recovered: &[String]) -> Vec<String> {
+    let (mut old, mut new) = (0, 0);
+    let mut lines = Vec::new();
+    for edit in diff::diff(file, recovered) {
+        match edit {
+            diff::Edit::Equal => (old, new) = (old + 1, new + 1),
+            diff::Edit::Delete => {
+                lines.push(format!("{:>5} - {}", old + 1, file[old]));
+                old += 1;
+            }
+            diff::Edit::Insert => {
+                lines.push(format!("{:>5} + {}", new + 1, recovered[new]));
+                new += 1;
+            }
+        }
+    }
+    lines.truncate(PREVIEW);
+    lines
+}
+
+impl EditorConfig {
+    // 연 파일보다 새 복구 파일이 있으면 물어봄
+    pub(crate) fn check_recovery(&mut self, filename: &str) {
+        let path = recovery_path(&self.options.swapdir, Some(filename), 0);
+        let Ok(recovered) = fs::metadata(&path).and_then(|m| m.modified()) else { return };
+        if fs::metadata(filename).and_then(|m| m.modified()).is_ok_and(|saved| saved >= recovered) {
+            return;
+        }
+        let Ok(text) = fs::read_to_string(&path) else { return };
+        let file: Vec<String> = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+        let recovered: Vec<String> = text.lines().map(String::from).collect();
+        let mut lines = preview(&file, &recovered);
+        if lines.is_empty() {
+            lines.push("(same text as the file)".into());
+        }
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into());
+        let title = format!("{} is newer: r recover, d diff, x delete, q ignore", name);
+        self.open_panel(PanelKind::Recovery, title, lines, 0);
+        self.status_msg = format!("Found recovery file {}", path.display());
+        self.recovery = Some(path);
+    }
+
+    // 복구 창에서 r / d / x 를 누름, 처리했으면 true
+    pub(crate) fn recovery_key(&mut self, key: char) -> bool {
+        let Some(path) = self.recovery.clone() else { return false };
+        match key {
+            'r' => match fs::read_to_string(&path) {
+                Ok(text) => {
+                    // u 로 파일의 내용으로 돌아갈 수 있게
+                    self.commit_undo();
+                    self.buffer.load(&text);
+                    self.buffer.touch();
+                    self.clamp_cursor();
+                    let _ = fs::remove_file(&path);
+                    self.status_msg = format!("Recovered from {}, :w to keep it", path.display());
+                }
+                Err(e) => self.error(format!("{}: {}", path.display(), e)),
+            },
+            'd' => self.diff_split(&path.to_string_lossy()),
+            'x' => match fs::remove_file(&path) {
+                Ok(()) => self.status_msg = format!("Deleted {}", path.display()),
+                Err(e) => self.error(format!("{}: {}", path.display(), e)),
+            },
+            _ => return false,
+        }
+        self.recovery = None;
+        self.close_panel();
+        true
+    }
+
+    /// 저장하지 않은 버퍼들을 복구 파일에 쓰고 쓴 경로들을 돌려줌
+    pub fn write_recovery(&self) -> Vec<PathBuf> {
+        self.unsaved_buffers()
+            .into_iter()
+            .filter_map(|(number, filename, text)| {
+                let path = recovery_path(&self.options.swapdir, filename.as_deref(), number);
+                write_new(&path, text.as_bytes()).ok().map(|_| path)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,267 @@
+// --- Registers and Marks ---
+// 일반 모드의 복사/삭제/붙여넣기가 쓰는 레지스터와 m{a-z}로 찍는 마크
+//   "x   다음 yy/dd/x/p/P 가 쓸 레지스터를 고름 (a-z, 기본은 이름 없는 레지스터 ")
+//        "+ 와 "* 는 시스템 클립보드 (줄 단위 내용은 끝에 줄바꿈을 붙여 주고받음)
+//   yy / dd / x   줄 복사 / 줄 삭제 / 글자 삭제, 3dd 처럼 횟수를 붙이면 그만큼의 줄이나 글자를 한 번에
+//   D / C         줄 끝까지 삭제 / 삭제하고 입력 모드   S, cc / s  줄 내용 / 글자를 지우고 입력 모드
+//   p / P         커서 뒤 / 앞에 붙여넣기, 3p 처럼 횟수를 붙이면 그만큼 되풀이
+//   ma / 'a / `a  마크 a를 찍음 / 그 줄로 / 그 위치로 이동
+// 복사한 내용은 0번, 삭제한 내용은 1번 레지스터에도 들어가고 이전 삭제는 2~9번으로 밀림
+// :registers 와 :marks 는 내용을 목록 창에 보여줌
+use crate::{EditorConfig, PanelKind, Row, clipboard};
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Register {
+    pub text: String,
+    pub linewise: bool, // 줄 단위면 붙여넣을 때 줄로 들어감
+}
+
+pub struct Registers {
+    regs: BTreeMap<char, Register>,
+    pub(crate) system_clipboard: bool, // false 면 "+ 는 편집기 안에서만 (테스트 하네스)
+}
+
+impl Registers {
+    pub fn new() -> Self {
+        Registers { regs: BTreeMap::new(), system_clipboard: true }
+    }
+
+    pub fn get(&self, name: char) -> Option<&Register> {
+        self.regs.get(&name)
+    }
+
+    // 붙여넣을 내용, 클립보드 레지스터는 클립보드를 먼저 읽음
+    fn read(&self, name: char) -> Option<Register> {
+        if !matches!(name, '+' | '*') {
+            return self.get(name).cloned();
+        }
+        let text = if self.system_clipboard { clipboard::paste() } else { None };
+        match text.filter(|text| !text.is_empty()) {
+            Some(text) => Some(from_clipboard(text)),
+            None => self.get('+').cloned(),
+        }
+    }
+
+    // 시스템 클립보드를 쓰지 않을 때 (테스트 하네스) 클립보드에 text 가 있는 것처럼 "+ 를 채움
+    pub(crate) fn set_clipboard(&mut self, text: &str) {
+        self.regs.insert('+', from_clipboard(text.to_string()));
+    }
+
+    pub fn yank(&mut self, name: Option<char>, reg: Register) {
+        self.store(name, reg.clone());
+        self.regs.insert('0', reg);
+    }
+
+    pub fn delete(&mut self, name: Option<char>, reg: Register) {
+        self.store(name, reg.clone());
+        for n in (1..9).rev() {
+            if let Some(r) = self.regs.remove(&digit(n)) {
+                self.regs.insert(digit(n + 1), r);
+            }
+        }
+        self.regs.insert('1', reg);
+    }
+
+    // 이름 없는 레지스터는 항상 마지막 내용을 가짐
+    fn store(&mut self, name: Option<char>, reg: Register) {
+        match name {
+            Some(name) if name.is_ascii_lowercase() => {
+                self.regs.insert(name, reg.clone());
+            }
+            Some('+' | '*') => {
+                let newline = if reg.linewise { "\n" } else { "" };
+                if self.system_clipboard {
+                    clipboard::copy(&format!("{}{}", reg.text, newline));
+                }
+                self.regs.insert('+', reg.clone());
+            }
+            _ => {}
+        }
+        self.regs.insert('"', reg);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, &Register)> {
+        // " 를 맨 앞에, 나머지는 이름 순
+        let unnamed = self.regs.get_key_value(&'"').into_iter();
+        unnamed.chain(self.regs.iter().filter(|(c, _)| **c != '"')).map(|(c, r)| (*c, r))
+    }
+}
+
+// 클립보드의 글, 줄바꿈으로 끝나면 줄 단위
+fn from_clipboard(text: String) -> Register {
+    match text.strip_suffix('\n') {
+        Some(lines) => Register { text: lines.to_string(), linewise: true },
+        None => Register { text, linewise: false },
+    }
+}
+
+fn digit(n: u32) -> char {
+    char::from_digit(n, 10).unwrap()
+}
+
+impl EditorConfig {
+    // 줄들의 내용을 줄바꿈으로 이음
+    fn joined_lines(&self, start: usize, end: usize) -> String {
+        self.buffer.rows[start..end].iter().map(|r| r.content.as_str()).collect::<Vec<_>>().join("\n")
+    }
+
+    pub(crate) fn yank_line(&mut self) {
+        let (start, end) = self.counted_lines();
+        let text = self.joined_lines(start, end);
+        let name = self.register.take();
+        self.registers.yank(name, Register { text, linewise: true });
+        self.flash(start, end - 1, None);
+    }
+
+    pub(crate) fn delete_line(&mut self) {
+        let (start, end) = self.counted_lines();
+        let name = self.register.take();
+        let text = self.joined_lines(start, end);
+        self.buffer.rows.drain(start..end);
+        if self.buffer.rows.is_empty() {
+            self.buffer.rows.push(Row::new(String::new()));
+        }
+        self.registers.delete(name, Register { text, linewise: true });
+        self.buffer.touch();
+        self.clamp_cursor();
+    }
+
+    // x: 커서부터 횟수만큼의 글자를 지움 (줄 끝을 넘지 않음)
+    pub(crate) fn delete_char_under(&mut self) {
+        let count = self.take_count();
+        let content = &self.buffer.rows[self.cy].content;
+        let start = self.cx.min(content.len());
+        let end = content[start..].char_indices().nth(count).map_or(content.len(), |(i, _)| start + i);
+        if start == end {
+            return;
+        }
+        let name = self.register.take();
+        let text: String = self.buffer.rows[self.cy].content.drain(start..end).collect();
+        self.registers.delete(name, Register { text, linewise: false });
+        self.buffer.touch();
+        self.clamp_cursor();
+    }
+
+    // D / C: 커서부터 줄 끝까지 지움
+    pub(crate) fn delete_to_line_end(&mut self) {
+        let name = self.register.take();
+        let row = &mut self.buffer.rows[self.cy].content;
+        let text = row.split_off(self.cx.min(row.len()));
+        if text.is_empty() {
+            return;
+        }
+        self.registers.delete(name, Register { text, linewise: false });
+        self.buffer.touch();
+    }
+
+    // S / cc: 줄은 남기고 내용만 지움 (레지스터에는 줄 단위로), 횟수가 있으면 나머지 줄은 지움
+    pub(crate) fn clear_line(&mut self) {
+        let (start, end) = self.counted_lines();
+        let name = self.register.take();
+        let text = self.joined_lines(start, end);
+        self.buffer.rows.drain(start + 1..end);
+        self.buffer.rows[start].content.clear();
+        self.registers.delete(name, Register { text, linewise: true });
+        self.cx = 0;
+        self.buffer.touch();
+    }
+
+    pub(crate) fn put(&mut self, after: bool) {
+        let name = self.register.take().unwrap_or('"');
+        let count = self.take_count();
+        let Some(reg) = self.registers.read(name) else {
+            self.error(format!("Nothing in register {}", name));
+            return;
+        };
+        if reg.linewise {
+            let at = if after { self.cy + 1 } else { self.cy };
+            let text = vec![reg.text.as_str(); count].join("\n");
+            let lines: Vec<Row> = text.split('\n').map(|l| Row::new(l.to_string())).collect();
+            self.buffer.rows.splice(at..at, lines);
+            (self.cy, self.cx) = (at, 0);
+        } else {
+            let text = reg.text.repeat(count);
+            let row = &mut self.buffer.rows[self.cy].content;
+            let at = if after && !row.is_empty() { (self.cx + 1).min(row.len()) } else { self.cx };
+            // 여러 줄이면 첫 줄은 커서 자리에, 나머지는 새 줄로 (커서 뒤의 글은 마지막 줄 끝에)
+            let rest = row.split_off(at);
+            let mut lines = text.split('\n');
+            row.push_str(lines.next().unwrap_or(""));
+            let mut rows: Vec<Row> = lines.map(|l| Row::new(l.to_string())).collect();
+            match rows.last_mut() {
+                Some(last) => {
+                    last.content.push_str(&rest);
+                    self.buffer.rows.splice(self.cy + 1..self.cy + 1, rows);
+                    self.cx = at;
+                }
+                None => {
+                    row.push_str(&rest);
+                    self.cx = at + text.len().saturating_sub(1);
+                }
+            }
+        }
+        self.buffer.touch();
+    }
+
+    pub(crate) fn set_mark(&mut self, name: char) {
+        if name.is_ascii_lowercase() {
+            self.marks.insert(name, (self.cy, self.cx));
+        }
+    }
+
+    // 'a 는 줄의 첫 글자로, `a 는 찍은 위치 그대로
+    pub(crate) fn jump_mark(&mut self, name: char, exact: bool) {
+        let Some(&(line, col)) = self.marks.get(&name) else {
+            self.error(format!("Mark not set: {}", name));
+            return;
+        };
+        self.cy = line;
+        self.cx = if exact { col } else { 0 };
+        self.clamp_cursor();
+        if !exact {
+            let content = &self.buffer.rows[self.cy].content;
+            self.cx = content.len() - content.trim_start().len();
+        }
+    }
+
+    pub(crate) fn show_registers(&mut self) {
+        let lines: Vec<String> = self
+            .registers
+            .iter()
+            .map(|(name, reg)| {
+                let kind = if reg.linewise { 'l' } else { 'c' };
+                format!("\"{}  {}  {}", name, kind, reg.text.replace('\n', "^J"))
+            })
+            .collect();
+        if lines.is_empty() {
+            self.status_msg = "No registers".into();
+            return;
+        }
+        self.open_panel(PanelKind::Registers, "name type content".into(), lines, 0);
+    }
+
+    pub(crate) fn show_marks(&mut self) {
+        let lines: Vec<String> = self
+            .marks
+            .iter()
+            .map(|(name, &(line, col))| {
+                let text = self.buffer.rows.get(line).map_or("", |r| r.content.trim());
+                format!("{}  {:>5}  {:>4}  {}", name, line + 1, col, text)
+            })
+            .collect();
+        if lines.is_empty() {
+            self.status_msg = "No marks set".into();
+            return;
+        }
+        self.open_panel(PanelKind::Marks, "mark line col text".into(), lines, 0);
+    }
+
+    // 목록 창의 Enter: 고른 마크로 이동
+    pub(crate) fn jump_to_listed_mark(&mut self, selected: usize) {
+        if let Some(&name) = self.marks.keys().nth(selected) {
+            self.jump_mark(name, true);
+            self.panel_focused = false;
+        }
+    }
+}
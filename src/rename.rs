@@ -0,0 +1,58 @@
+// --- LSP Rename ---
+// :Rename 새이름   커서 아래 이름을 언어 서버에 물어 쓰인 곳 모두에서 바꿈
+// 서버가 돌려준 편집(WorkspaceEdit)을 파일마다 그 버퍼에 적용 (열려 있지 않으면 새 버퍼로 열어서), 저장은 하지 않음
+// 바뀐 버퍼는 u 로 하나씩 되돌릴 수 있음, 기다리는 사이 버퍼를 고쳤으면 응답은 버림
+use crate::json::Json;
+use crate::lsp::{self, Request};
+use crate::{EditorConfig, Row};
+use std::path::Path;
+
+impl EditorConfig {
+    // :Rename 새이름
+    pub(crate) fn lsp_rename(&mut self, new_name: &str) {
+        if new_name.is_empty() || new_name.contains(char::is_whitespace) {
+            self.status_msg = "Usage: :Rename <newname>".into();
+            return;
+        }
+        let params = vec![self.lsp_cursor(), ("newName", new_name.into())];
+        if self.lsp_request(Request::Rename, params) {
+            self.status_msg = "Asking the language server...".into();
+        }
+    }
+
+    // 이름 바꾸기 응답의 편집들을 적용
+    pub(crate) fn apply_rename(&mut self, version: u64, result: &Json) {
+        if version != self.buffer.version {
+            self.error("Buffer changed while renaming, try again");
+            return;
+        }
+        let files = lsp::workspace_edits(result);
+        if files.is_empty() {
+            self.error("Nothing to rename here");
+            return;
+        }
+        if let Some((missing, _)) = files.iter().find(|(f, _)| !Path::new(f).exists() && self.buffer_for_mut(f).is_none()) {
+            self.error(format!("Rename: {} does not exist", missing));
+            return;
+        }
+        let original = self.buffers.current;
+        let mut count = 0;
+        for (filename, edits) in &files {
+            count += edits.len();
+            if self.buffer_for_mut(filename).is_none() {
+                self.edit_file(filename);
+            }
+            let Some((buffer, undo)) = self.buffer_and_undo_for_mut(filename) else { continue };
+            // 방금 연 파일도 u 로 원래 내용으로 돌아갈 수 있게 앞뒤 상태를 남김
+            undo.commit(&buffer.rows, buffer.version);
+            let lines: Vec<String> = buffer.rows.iter().map(|r| r.content.clone()).collect();
+            buffer.rows = lsp::apply_edits(&lines, edits).into_iter().map(Row::new).collect();
+            buffer.touch();
+            undo.commit(&buffer.rows, buffer.version);
+        }
+        self.switch_buffer(original);
+        self.clamp_cursor();
+        let files = files.len();
+        self.status_msg = format!("Renamed: {} changes in {} file{}", count, files, if files == 1 { "" } else { "s" });
+    }
+}
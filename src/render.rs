@@ -0,0 +1,506 @@
+// --- Rendering ---
+// 한 프레임을 ANSI 이스케이프 시퀀스로 만들어 터미널에 내보냄
+use crate::diffview::{DiffKind, DiffView};
+use crate::terminal::Terminal;
+use crate::{EditorBuffer, EditorConfig, Mode, PanelKind, completion, conflict, git, hover, lsp, virtualtext};
+use std::io::{self, Write};
+use std::iter;
+use std::ops::Range;
+use std::path::Path;
+
+// 한 줄에서 col_offset부터 width 칸만큼 잘라냄
+fn visible_part(content: &str, col_offset: usize, width: usize) -> String {
+    content.chars().skip(col_offset).take(width).collect()
+}
+
+// 비교 모드: 왼쪽은 현재 버퍼, 오른쪽은 비교 대상 파일
+fn draw_diff(config: &EditorConfig, view: &DiffView, out: &mut impl Write) -> io::Result<()> {
+    let width = config.pane_width();
+    let side = |buffer: &EditorBuffer, line: Option<usize>, kind: DiffKind| -> String {
+        let Some(line) = line else {
+            return format!("\x1b[90m{}\x1b[m", "-".repeat(width));
+        };
+        let text = visible_part(&buffer.rows[line].content, config.col_offset, width);
+        let color = match kind {
+            DiffKind::Same => "",
+            DiffKind::Changed => "\x1b[44m",
+            DiffKind::Removed => "\x1b[41m",
+            DiffKind::Added => "\x1b[42m",
+        };
+        format!("{}{:width$}\x1b[m", color, text, width = width)
+    };
+
+    for y in 0..config.text_rows() {
+        write!(out, "\x1b[K")?;
+        match view.lines.get(config.row_offset + y) {
+            Some(line) => write!(out,
+                "{}\x1b[90m│\x1b[m{}\r\n",
+                side(&config.buffer, line.left, line.kind),
+                side(&view.other, line.right, line.kind)
+            )?,
+            None => write!(out, "~{:width$}\x1b[90m│\x1b[m~\r\n", "", width = width - 1)?,
+        }
+    }
+    Ok(())
+}
+
+fn draw_screen(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    if let Some(view) = &config.diff {
+        return draw_diff(config, view, out);
+    }
+    let visible_rows = config.text_rows();
+    let gutter = config.gutter_width();
+    let visible_cols = config.text_cols();
+    let diagnostics = config.diagnostics();
+    // wrap 이면 줄 하나가 여러 화면 줄 (segment 는 그 줄의 몇 번째 화면 줄인지)
+    let (mut display_row, mut segment) = (config.row_offset, 0);
+
+    for _ in 0..visible_rows {
+        let file_row_idx = config.folds.line_at(display_row);
+        let (part, last_part) = (segment, segment + 1 >= config.wrapped_rows(file_row_idx));
+        if last_part {
+            (display_row, segment) = (display_row + 1, 0);
+        } else {
+            segment += 1;
+        }
+        write!(out, "\x1b[K")?;
+        // 나뉜 줄의 이어지는 화면 줄은 왼쪽 열들을 비움 (:Zen 이면 모든 줄 앞에 가운데로 미는 빈 칸)
+        if part > 0 || config.zen {
+            write!(out, "{:width$}", "", width = config.left_margin())?;
+        }
+        let first_part = part == 0 && file_row_idx < config.buffer.rows.len();
+
+        if let Some(blame) = &config.blame && first_part && !config.zen {
+            match blame.get(file_row_idx) {
+                Some(line) => write!(out, "\x1b[90m{}\x1b[m", line.display())?,
+                None => write!(out, "{:width$}", "", width = git::BlameLine::WIDTH)?,
+            }
+        }
+
+        if gutter > 0 && first_part {
+            // 그 줄에서 가장 심각한 진단을 표시하고, 없으면 git 변경 표시
+            let severity = diagnostics.iter().filter(|d| d.line == file_row_idx).map(|d| d.severity).min();
+            match (severity, config.git.sign(file_row_idx)) {
+                (Some(s @ lsp::Severity::Error), _) => write!(out, "\x1b[31m{} \x1b[m", s.sign())?,
+                (Some(s @ lsp::Severity::Warning), _) => write!(out, "\x1b[33m{} \x1b[m", s.sign())?,
+                (Some(s), _) => write!(out, "\x1b[36m{} \x1b[m", s.sign())?,
+                (None, Some(g @ git::GitSign::Added)) => write!(out, "\x1b[32m{} \x1b[m", g.sign())?,
+                (None, Some(g @ git::GitSign::Modified)) => write!(out, "\x1b[34m{} \x1b[m", g.sign())?,
+                (None, Some(g)) => write!(out, "\x1b[31m{} \x1b[m", g.sign())?,
+                (None, None) => write!(out, "  ")?,
+            }
+        }
+
+        if config.fold_column_width() > 0 && first_part {
+            write!(out, "\x1b[90m{} \x1b[m", config.folds.sign(file_row_idx))?;
+        }
+
+        let number = config.number_width();
+        if number > 0 && first_part {
+            write!(out, "\x1b[90m{:>width$} \x1b[m", file_row_idx + 1, width = number - 1)?;
+        }
+
+        // 닫힌 접기는 줄 수와 첫 줄을 보여주는 요약 줄 하나로
+        if let Some((start, end)) = config.folds.closed_at(file_row_idx) {
+            let summary = format!("+--{:>3} lines: {}", end - start + 1, config.buffer.rows[start].content.trim());
+            let summary: String = summary.chars().chain(iter::repeat('·')).take(visible_cols).collect();
+            write!(out, "\x1b[36;48;5;235m{}\x1b[m\r\n", summary)?;
+        } else if file_row_idx < config.buffer.rows.len() {
+            let row_content = &config.buffer.rows[file_row_idx].content;
+
+            // 충돌 구간은 줄 전체에 배경색, 그 밖에서는 cursorline이 켜져 있으면 커서 줄에 옅은 배경색
+            let style = match config.conflicts.iter().find(|c| c.contains(file_row_idx)).map(|c| c.region(file_row_idx)) {
+                Some(conflict::Region::Marker) => "\x1b[1;45m",
+                Some(conflict::Region::Ours) => "\x1b[42m",
+                Some(conflict::Region::Base) => "\x1b[100m",
+                Some(conflict::Region::Theirs) => "\x1b[44m",
+                None if config.window.options.cursorline && file_row_idx == config.cy => "\x1b[48;5;236m",
+                None => "",
+            };
+            
+            // col_offset 이후 (wrap 이면 이 화면 줄의 처음부터)의 문자열만 추출, 오프셋이 내용보다 길면 빈 줄
+            // 건너뛸 글자 수를 바이트 위치로 바꿔서 글자 중간에서 자르지 않음
+            let skipped = if part == 0 { config.col_offset } else { part * visible_cols };
+            let offset = row_content.char_indices().nth(skipped).map_or(row_content.len(), |(i, _)| i);
+            let line = row_content.get(offset..).unwrap_or("");
+            let mut line: Vec<char> = line.chars().take(visible_cols + 1).map(control_picture).collect();
+            // 가로로 스크롤해 화면 밖에 가려진 글자가 있으면 첫 칸에 <, 마지막 칸에 >
+            if line.len() > visible_cols {
+                line.truncate(visible_cols); // 화면 폭만큼 자르기
+                if !config.wraps() {
+                    line[visible_cols - 1] = '>';
+                }
+            }
+            if offset > 0 && part == 0 && !row_content.is_empty() {
+                match line.first_mut() {
+                    Some(c) => *c = '<',
+                    None => line.push('<'),
+                }
+            }
+            let line: String = line.into_iter().collect();
+            // 화면에 보이는 colorcolumn들의 화면상 위치
+            let columns: Vec<usize> = config
+                .window
+                .options
+                .colorcolumn
+                .iter()
+                .filter_map(|&c| (c - 1).checked_sub(skipped))
+                .filter(|&c| c < visible_cols)
+                .collect();
+            // 비주얼 모드의 선택, 방금 복사한 부분, 추가 커서들은 반전 (바이트 위치를 화면의 글자 위치로)
+            let screen = |b: usize| {
+                let text = row_content.get(offset..b.min(row_content.len())).unwrap_or("");
+                text.chars().count() + b.saturating_sub(row_content.len())
+            };
+            let extra_cursors = config.cursors.iter().filter(|c| c.0 == file_row_idx).map(|c| screen(c.1)..screen(c.1) + 1);
+            let selected: Vec<Range<usize>> =
+                config.selected_columns(file_row_idx).into_iter().chain(config.flashed_columns(file_row_idx)).map(|(start, end)| screen(start)..screen(end)).chain(extra_cursors).collect();
+            let selected_end = selected.iter().map(|r| r.end).max().unwrap_or(0);
+            // 문법 색 (충돌 구간은 배경색만)
+            let colors: Vec<(Range<usize>, &str)> = match config.conflicts.iter().any(|c| c.contains(file_row_idx)) {
+                true => Vec::new(),
+                false => config.syntax_spans(file_row_idx).iter().map(|(r, token)| (screen(r.start)..screen(r.end), token.color())).collect(),
+            };
+
+            // 줄 뒤의 가상 글은 두 칸 띄우고 남은 폭 안에서만
+            let len = line.chars().count();
+            let room = if last_part { visible_cols.saturating_sub(len + 2) } else { 0 };
+            let (virtual_text, used) = virtualtext::render(&config.virtual_text(file_row_idx), room);
+            if used > 0 {
+                let pad = if style.is_empty() { 0 } else { visible_cols - len - 2 - used };
+                write!(out, "{}{}  {}{}{:pad$}\x1b[m\r\n", style, paint_columns(&line, 0, &columns, &selected, &colors, style), virtual_text, style, "", pad = pad)?;
+                continue;
+            }
+            // 배경색이 있으면 화면 끝까지, 없으면 가장 오른쪽 colorcolumn까지 채움
+            let width = if style.is_empty() { columns.iter().max().map_or(0, |&c| c + 1) } else { visible_cols };
+            write!(out, "{}{}\x1b[m\r\n", style, paint_columns(&line, width.max(selected_end.min(visible_cols)), &columns, &selected, &colors, style))?;
+        } else {
+            write!(out, "{}\r\n", if config.zen { "" } else { "~" })?;
+        }
+    }
+    Ok(())
+}
+
+// 스크롤 막대에서 보이는 부분의 (시작, 길이): total 화면 줄 중 offset 부터 rows 줄이 보일 때
+fn scrollbar_thumb(total: usize, offset: usize, rows: usize) -> (usize, usize) {
+    let total = total.max(rows);
+    let len = (rows * rows).div_ceil(total).clamp(1, rows);
+    // 마지막 줄이 보이면 막대도 끝에 닿게
+    let start = if offset + rows >= total { rows - len } else { (offset * rows / total).min(rows - len) };
+    (start, len)
+}
+
+// set scrollbar: 오른쪽 끝 열에 트랙(│)과 화면에 보이는 부분(┃)
+fn draw_scrollbar(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    if config.right_margin() == 0 {
+        return Ok(());
+    }
+    let rows = config.text_rows();
+    let (start, len) = scrollbar_thumb(config.display_rows(), config.row_offset, rows);
+    for y in 0..rows {
+        let glyph = if (start..start + len).contains(&y) { "\x1b[37m┃" } else { "\x1b[90m│" };
+        write!(out, "\x1b[{};{}H{}\x1b[m", y + 1, config.screen_cols, glyph)?;
+    }
+    Ok(())
+}
+
+// 제어 문자(탭 제외)는 터미널로 그대로 보내지 않고 한 칸짜리 기호(␁, ␛, ␡ 등)로 보여줌
+fn control_picture(c: char) -> char {
+    match c {
+        '\t' => c,
+        '\0'..='\x1f' => char::from_u32(0x2400 + c as u32).unwrap_or('?'),
+        '\x7f' => '␡',
+        _ => c,
+    }
+}
+
+// 줄을 width 칸까지 공백으로 채우고 columns 칸에 colorcolumn 배경색을 입힘 (그 뒤엔 줄의 style로 돌아감),
+// selected 범위의 칸들은 반전, colors 범위의 칸들은 그 글자색
+fn paint_columns(line: &str, width: usize, columns: &[usize], selected: &[Range<usize>], colors: &[(Range<usize>, &str)], style: &str) -> String {
+    let len = line.chars().count().max(width);
+    let mut painted = String::new();
+    let mut reversed = false;
+    let mut color = None;
+    for (i, c) in line.chars().chain(iter::repeat(' ')).take(len).enumerate() {
+        let inside = selected.iter().any(|r| r.contains(&i));
+        if inside != reversed {
+            painted.push_str(if inside { "\x1b[7m" } else { "\x1b[27m" });
+            reversed = inside;
+        }
+        let fg = colors.iter().find(|(r, _)| r.contains(&i)).map(|&(_, fg)| fg);
+        if fg != color {
+            painted.push_str(fg.unwrap_or("\x1b[39m"));
+            color = fg;
+        }
+        if columns.contains(&i) && !inside {
+            painted.push_str(&format!("\x1b[48;5;238m{}\x1b[m{}{}", c, style, color.unwrap_or("")));
+        } else {
+            painted.push(c);
+        }
+    }
+    if reversed {
+        painted.push_str("\x1b[27m");
+    }
+    if color.is_some() {
+        painted.push_str("\x1b[39m");
+    }
+    painted
+}
+
+fn draw_panel(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    let Some(panel) = &config.panel else { return Ok(()) };
+    let width = config.screen_cols as usize;
+    let list_rows = config.panel_height() - 1;
+
+    let kind = match panel.kind {
+        PanelKind::Quickfix => "Quickfix",
+        PanelKind::Registers => "Registers",
+        PanelKind::Marks => "Marks",
+        PanelKind::UndoTree => "Undo Tree",
+        PanelKind::Messages => "Messages",
+        PanelKind::Profile => "Profile",
+        PanelKind::Recovery => "Recovery",
+        PanelKind::Replace => "Replace",
+        PanelKind::Tags => "Tags",
+        PanelKind::Diagnostics => "Diagnostics",
+        PanelKind::Lookup => "Lookup",
+        PanelKind::Help => "Help",
+    };
+    let title = format!("[{}] {} ({} items)", kind, panel.title, panel.lines.len());
+    let title: String = title.chars().take(width).collect();
+    let bold = if config.panel_focused { "\x1b[1m" } else { "" };
+    write!(out, "\x1b[K\x1b[7m{}{:width$}\x1b[m\r\n", bold, title, width = width)?;
+
+    for y in 0..list_rows {
+        write!(out, "\x1b[K")?;
+        if let Some(line) = panel.lines.get(panel.offset + y) {
+            let line: String = line.chars().take(width).collect();
+            if panel.offset + y == panel.selected {
+                write!(out, "\x1b[7m{}\x1b[m", line)?;
+            } else {
+                write!(out, "{}", line)?;
+            }
+        }
+        write!(out, "\r\n")?;
+    }
+    Ok(())
+}
+
+// 커서 아래(공간이 없으면 위)에 완성 후보 메뉴를 그림
+fn draw_completion_menu(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    const MENU_HEIGHT: usize = 8;
+    let Some(comp) = &config.completion else { return Ok(()) };
+    let height = comp.items.len().min(MENU_HEIGHT);
+    let (cursor_y, cursor_x) = config.cursor_screen();
+    let y = if cursor_y + 1 + height <= config.text_rows() { cursor_y + 1 } else { cursor_y.saturating_sub(height) };
+    let x = cursor_x.saturating_sub(config.cx.saturating_sub(comp.start)) + config.left_margin();
+    let max_width = (config.screen_cols as usize).saturating_sub(x);
+    // " 종류 단어  설명"
+    let longest = comp.items.iter().map(|i| i.word.chars().count()).max().unwrap_or(0);
+    let line = |item: &completion::Item| match item.detail.is_empty() {
+        true => format!(" {} {}", item.kind, item.word),
+        false => format!(" {} {:longest$}  {}", item.kind, item.word, item.detail),
+    };
+    let width = (comp.items.iter().map(|i| line(i).chars().count()).max().unwrap_or(0) + 1).min(max_width);
+    let offset = (comp.selected + 1).saturating_sub(height);
+
+    for (i, item) in comp.items.iter().enumerate().skip(offset).take(height) {
+        let text: String = line(item).chars().take(width).collect();
+        let style = if i == comp.selected { "\x1b[7m" } else { "\x1b[100m" };
+        write!(out, "\x1b[{};{}H{}{:width$}\x1b[m", y + i - offset + 1, x + 1, style, text, width = width)?;
+    }
+    Ok(())
+}
+
+// 커서 위(자리가 없으면 아래)에 K 의 설명 창을 그림, 긴 줄은 창 너비에서 접음
+fn draw_hover(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    let Some(lines) = &config.hover else { return Ok(()) };
+    let cols = config.screen_cols as usize;
+    let longest = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let width = (longest + 2).min(cols.saturating_sub(2).min(80)).max(3);
+    let mut rows: Vec<String> = Vec::new();
+    for line in lines {
+        let chars: Vec<char> = line.chars().collect();
+        rows.extend(chars.chunks(width - 2).map(|c| c.iter().collect()));
+        if chars.is_empty() {
+            rows.push(String::new());
+        }
+    }
+    if rows.len() > hover::HOVER_HEIGHT {
+        rows.truncate(hover::HOVER_HEIGHT);
+        rows[hover::HOVER_HEIGHT - 1] = "...".into();
+    }
+    let (cursor_y, cursor_x) = config.cursor_screen();
+    let height = rows.len();
+    let y = if cursor_y >= height || cursor_y + 1 + height > config.text_rows() { cursor_y.saturating_sub(height) } else { cursor_y + 1 };
+    let x = (cursor_x + config.left_margin()).min(cols.saturating_sub(width));
+    for (i, row) in rows.iter().enumerate() {
+        write!(out, "\x1b[{};{}H\x1b[100m {:width$} \x1b[m", y + i + 1, x + 1, row, width = width - 2)?;
+    }
+    Ok(())
+}
+
+// 입력 모드에서 커서 줄 위(첫 줄이면 아래)에 시그니처를 그림, 지금 인자는 굵게 밑줄
+fn draw_signature(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    let Some(signature) = config.signature.as_ref().filter(|_| config.mode == Mode::Insert) else { return Ok(()) };
+    let cols = config.screen_cols as usize;
+    let (cursor_y, cursor_x) = config.cursor_screen();
+    let y = if cursor_y > 0 { cursor_y - 1 } else { cursor_y + 1 };
+    let room = cols.saturating_sub(2);
+    let label: String = signature.label.chars().take(room).collect();
+    let width = label.chars().count() + 2;
+    let x = (cursor_x + config.left_margin()).min(cols.saturating_sub(width));
+    let text = match signature.active.clone().filter(|r| r.end <= label.len()) {
+        Some(r) => format!("{}\x1b[1;4m{}\x1b[22;24m{}", &label[..r.start], &label[r.clone()], &label[r.end..]),
+        None => label,
+    };
+    write!(out, "\x1b[{};{}H\x1b[100m {} \x1b[m", y + 1, x + 1, text)
+}
+
+// 화면 가운데의 선택 창: 첫 줄은 제목과 검색어, 아래로 맞는 항목들
+fn draw_picker(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    const PICKER_HEIGHT: usize = 10;
+    let Some(picker) = &config.picker else { return Ok(()) };
+    let width = (config.screen_cols as usize).saturating_sub(4).min(60);
+    let height = picker.matches.len().clamp(1, PICKER_HEIGHT);
+    let x = (config.screen_cols as usize).saturating_sub(width) / 2;
+    let y = config.text_rows().saturating_sub(height + 1) / 2;
+    let offset = (picker.selected + 1).saturating_sub(height);
+
+    let header: String = format!(" {}> {}", picker.title, picker.query).chars().take(width).collect();
+    write!(out, "\x1b[{};{}H\x1b[7;1m{:width$}\x1b[m", y + 1, x + 1, header, width = width)?;
+    for row in 0..height {
+        let i = offset + row;
+        let text: String = match picker.matches.get(i) {
+            Some(_) => format!(" {}", picker.line(i)).chars().take(width).collect(),
+            None => " (no matches)".into(),
+        };
+        let style = if i == picker.selected && i < picker.matches.len() { "\x1b[7m" } else { "\x1b[100m" };
+        write!(out, "\x1b[{};{}H{}{:width$}\x1b[m", y + row + 2, x + 1, style, text, width = width)?;
+    }
+    Ok(())
+}
+
+// "줄:열"과 화면이 파일의 어디쯤인지 (Top, Bot, All 또는 화면 위에 가려진 줄의 비율)
+fn ruler(config: &EditorConfig) -> String {
+    let total = config.display_rows();
+    let above = config.row_offset;
+    let below = total.saturating_sub(above + config.text_rows());
+    let position = match (above, below) {
+        (0, 0) => "All".to_string(),
+        (0, _) => "Top".to_string(),
+        (_, 0) => "Bot".to_string(),
+        _ => format!("{}%", above * 100 / (above + below)),
+    };
+    format!("{}:{}  {:>3}", config.cy + 1, config.cx + 1, position)
+}
+
+fn draw_status_bar(config: &EditorConfig, out: &mut impl Write) -> io::Result<()> {
+    write!(out, "\x1b[{};1H\x1b[K", config.screen_rows)?;
+    if config.mode == Mode::Command {
+        write!(out, "{}{}", config.prompt, config.command_buffer)?;
+    } else {
+        let mode_str = match config.mode {
+            Mode::Normal => "-- NORMAL --",
+            Mode::Insert => "-- INSERT --",
+            Mode::Visual if config.selection.is_some_and(|s| s.linewise) => "-- VISUAL LINE --",
+            Mode::Visual => "-- VISUAL --",
+            _ => "",
+        };
+        let diagnostics = config.diagnostics();
+        // 보여줄 메시지가 없을 때만 커서 줄의 진단을 보여줌 (여럿이면 커서 앞의 마지막 것), 오류 메시지면 상태 줄을 빨간 바탕으로
+        let on_line: Vec<_> = diagnostics.iter().filter(|d| d.line == config.cy).collect();
+        let cursor = lsp::to_character(&config.buffer.rows[config.cy].content, config.cx);
+        let diagnostic = on_line.iter().rev().find(|d| d.col <= cursor).or(on_line.first()).filter(|_| config.status_msg.is_empty());
+        let (msg, color) = match diagnostic {
+            Some(d) => (format!("{}: {}", d.severity.sign(), d.message.lines().next().unwrap_or("")), "\x1b[7m"),
+            None if config.is_error_message() => (config.status_msg.clone(), "\x1b[41;97m"),
+            None => (config.status_msg.clone(), "\x1b[7m"),
+        };
+        // :Zen 이면 입력 / 비주얼 모드 표시와 메시지만
+        if config.zen {
+            let color = if color == "\x1b[7m" { "" } else { color };
+            let status = if config.mode == Mode::Normal { msg } else { format!("{} {}", mode_str, msg) };
+            write!(out, "{}{:.width$}\x1b[m", color, status, width = config.screen_cols as usize)?;
+            return Ok(());
+        }
+        let mut status = mode_str.to_string();
+        // 브랜치와 파일 종류는 있을 때만
+        if let Some(branch) = &config.branch {
+            status = format!("{} | {}", status, branch);
+        }
+        if let Some(filetype) = &config.buffer_options.filetype {
+            status = format!("{} | {}", status, filetype);
+        }
+        status = format!("{} | {}", status, msg);
+        if let Some(view) = &config.diff {
+            status = format!("DIFF {} | {}", view.other_name, status);
+        }
+        if !diagnostics.is_empty() {
+            let count = |s| diagnostics.iter().filter(|d| d.severity == s).count();
+            status = format!("E:{} W:{} | {}", count(lsp::Severity::Error), count(lsp::Severity::Warning), status);
+        }
+        // 오른쪽 끝에는 입력 중인 매핑과 위치 표시
+        let pending = config.pending_keys();
+        let right = if pending.is_empty() { ruler(config) } else { format!("{}  {}", pending, ruler(config)) };
+        let width = (config.screen_cols as usize).saturating_sub(right.chars().count() + 1);
+        write!(out, "{}{:width$.width$} {}\x1b[m", color, status, right, width = width)?;
+    }
+    Ok(())
+}
+
+// 한 프레임을 모아서 한 번에 내보냄
+pub fn refresh_screen(config: &mut EditorConfig, term: &mut impl Terminal) -> io::Result<()> {
+    config.scroll();
+    config.track_message();
+
+    let mut out = Vec::new();
+    write!(out, "\x1b[?25l\x1b[H")?;
+    // smoothscroll 로 움직이는 중이면 본문만 중간 위치에서 그림
+    let target = config.row_offset;
+    config.row_offset = config.animate_scroll();
+    draw_screen(config, &mut out)?;
+    draw_scrollbar(config, &mut out)?;
+    config.row_offset = target;
+    draw_panel(config, &mut out)?;
+    draw_status_bar(config, &mut out)?;
+    draw_completion_menu(config, &mut out)?;
+    draw_hover(config, &mut out)?;
+    draw_signature(config, &mut out)?;
+    draw_picker(config, &mut out)?;
+
+    // 상대 좌표 계산
+    let (screen_y, screen_x) = match &config.panel {
+        Some(panel) if config.panel_focused && config.mode != Mode::Command => {
+            (config.text_rows() + 1 + panel.selected - panel.offset, 0)
+        }
+        _ => {
+            let (y, x) = config.cursor_screen();
+            (y, x + config.left_margin())
+        }
+    };
+    
+    write!(out, "\x1b[{};{}H", screen_y + 1, screen_x + 1)?;
+    if !config.is_scrolling() {
+        write!(out, "\x1b[?25h")?;
+    }
+
+    // 창 제목은 바뀌었을 때만 OSC 2로 보냄
+    let title = window_title(config);
+    if title != config.title {
+        write!(out, "\x1b]2;{}\x07", title)?;
+        config.title = title;
+    }
+    term.write(&out)
+}
+
+// "파일 이름 [+] — vii", 경로는 빼고 파일 이름만
+fn window_title(config: &EditorConfig) -> String {
+    let name = config
+        .filename
+        .as_deref()
+        .map(|f| Path::new(f).file_name().map_or(f.to_string(), |n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "[No Name]".into());
+    let modified = if config.buffer.dirty { " [+]" } else { "" };
+    format!("{}{} — vii", name, modified)
+}
@@ -0,0 +1,208 @@
+// --- Project-wide Replace ---
+// :greplace/찾을 글/바꿀 글/   :grep 처럼 프로젝트 전체에서 글자 그대로 찾아 바뀔 줄들을 목록 창에 보여줌
+//   :greplace/찾을 글/바꿀 글/ dir   지금 디렉터리 대신 dir 아래에서 찾음
+//   Space  고른 줄을 바꿀지 말지 뒤집음     a  모두 켜거나 모두 끔
+//   Enter  그 줄을 열어 봄 (목록 창은 그대로)
+//   w      켜 둔 줄들을 바꾸고 파일들을 저장  q / Esc  그만둠
+// 줄에서 나온 것은 모두 바꿈, 구분자는 :s 와 같음 (substitute.rs)
+// 저장은 모든 파일을 임시 파일에 다 쓴 뒤에 한꺼번에 바꿔치움 (임시 파일을 쓰다 실패하면 아무 파일도 바꾸지 않고,
+// 바꿔치우다 실패하면 이미 바꾼 파일들을 원래 내용으로 되돌림)
+// 찾은 뒤 파일의 줄이 바뀌었거나 저장하지 않은 버퍼로 열려 있는 파일이 있으면 바꾸지 않음
+// 열려 있는 버퍼는 같은 줄들을 바꿔서 디스크와 맞추고 실행 취소 상태를 남김 (u 로 되돌릴 수 있음)
+use crate::{EditorConfig, PanelKind, Row, grep, substitute};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone)]
+struct Change {
+    filename: String,
+    line: usize, // 1부터
+    old: String,
+    new: String,
+    enabled: bool,
+}
+
+impl Change {
+    fn display(&self) -> String {
+        let mark = if self.enabled { 'x' } else { ' ' };
+        format!("[{}] {}:{}: {}", mark, self.filename, self.line, self.new.trim())
+    }
+}
+
+pub struct ProjectReplace {
+    changes: Vec<Change>,
+}
+
+// 파일 내용에서 바꿀 줄들을 바꿈, 찾은 뒤 줄이 달라졌으면 Err(줄 번호)
+fn apply(content: &str, changes: &[&Change]) -> Result<String, usize> {
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+    for change in changes {
+        let Some(line) = lines.get_mut(change.line - 1) else { return Err(change.line) };
+        let len = line.trim_end_matches(['\n', '\r']).len();
+        if line[..len] != change.old {
+            return Err(change.line);
+        }
+        line.replace_range(..len, &change.new);
+    }
+    Ok(lines.concat())
+}
+
+impl EditorConfig {
+    // :greplace/찾을 글/바꿀 글/ [dir]
+    pub(crate) fn project_replace(&mut self, args: &str) {
+        let Some((pattern, replacement, dir)) = substitute::split(args).filter(|(p, _, _)| !p.is_empty()) else {
+            self.status_msg = "Usage: :greplace/pattern/replacement/ [dir]".into();
+            return;
+        };
+        let dir = match dir.trim() {
+            "" => ".",
+            dir => dir,
+        };
+        let (entries, warning) = match grep::grep(&pattern, dir) {
+            Ok(found) => found,
+            Err(e) => {
+                self.error(format!("Error: {}", e));
+                return;
+            }
+        };
+        // 한 줄에 여러 번 나와도 한 항목으로
+        let mut changes: Vec<Change> = Vec::new();
+        for entry in entries {
+            if changes.last().is_some_and(|c| c.filename == entry.filename && c.line == entry.line) {
+                continue;
+            }
+            let old = entry.text.trim_end_matches('\r').to_string();
+            let mut new = old.clone();
+            if substitute::replace_line(&mut new, &pattern, &replacement, true, false) > 0 {
+                changes.push(Change { filename: entry.filename, line: entry.line, old, new, enabled: true });
+            }
+        }
+        // rg 는 파일들을 나란히 찾으므로 순서를 맞춤
+        changes.sort_by(|a, b| (&a.filename, a.line).cmp(&(&b.filename, b.line)));
+        if changes.is_empty() {
+            match warning {
+                Some(warning) => self.error(format!("Error: {}", warning)),
+                None => self.status_msg = format!("No matches: {}", pattern),
+            }
+            return;
+        }
+        let files = changes.iter().map(|c| &c.filename).collect::<BTreeSet<_>>().len();
+        let lines = changes.iter().map(Change::display).collect();
+        let title = format!("{} -> {}: Space toggle, a all, w apply, q cancel", pattern, replacement);
+        self.status_msg = format!("{} lines in {} files", changes.len(), files);
+        if let Some(warning) = warning {
+            self.status_msg += &format!(" (warning: {})", warning);
+        }
+        self.open_panel(PanelKind::Replace, title, lines, 0);
+        self.project_replace = Some(ProjectReplace { changes });
+    }
+
+    // 바꾸기 창에서 Space / a / w 를 누름, 처리했으면 true
+    pub(crate) fn replace_key(&mut self, key: char) -> bool {
+        let (Some(replace), Some(panel)) = (self.project_replace.as_mut(), self.panel.as_mut()) else { return false };
+        match key {
+            ' ' => {
+                let Some(change) = replace.changes.get_mut(panel.selected) else { return true };
+                change.enabled = !change.enabled;
+                panel.lines[panel.selected] = change.display();
+                if panel.selected + 1 < panel.lines.len() {
+                    panel.selected += 1;
+                }
+            }
+            'a' => {
+                let enable = !replace.changes.iter().all(|c| c.enabled);
+                for change in &mut replace.changes {
+                    change.enabled = enable;
+                }
+                panel.lines = replace.changes.iter().map(Change::display).collect();
+            }
+            'w' => self.apply_project_replace(),
+            _ => return false,
+        }
+        true
+    }
+
+    // 목록 창의 Enter: 그 줄을 보여줌
+    pub(crate) fn show_replace_line(&mut self, selected: usize) {
+        let Some(change) = self.project_replace.as_ref().and_then(|r| r.changes.get(selected)).cloned() else { return };
+        self.edit_file(&change.filename);
+        self.goto_line(change.line);
+    }
+
+    fn apply_project_replace(&mut self) {
+        let Some(replace) = self.project_replace.as_ref() else { return };
+        let changes: Vec<Change> = replace.changes.iter().filter(|c| c.enabled).cloned().collect();
+        if changes.is_empty() {
+            self.error("Nothing selected");
+            return;
+        }
+        let mut files: BTreeMap<&str, Vec<&Change>> = BTreeMap::new();
+        for change in &changes {
+            files.entry(&change.filename).or_default().push(change);
+        }
+        // 모두 바꿀 수 있는지 먼저 확인하고 새 내용을 만듦
+        let mut contents = Vec::new();
+        for (&filename, file_changes) in &files {
+            if self.buffer_for_mut(filename).is_some_and(|b| b.dirty) {
+                self.error(format!("{} has unsaved changes", filename));
+                return;
+            }
+            let content = match fs::read_to_string(filename) {
+                Ok(content) => content,
+                Err(e) => {
+                    self.error(format!("{}: {}", filename, e));
+                    return;
+                }
+            };
+            match apply(&content, file_changes) {
+                Ok(new) => contents.push((filename, content, new)),
+                Err(line) => {
+                    self.error(format!("{}:{} changed since the search, run :greplace again", filename, line));
+                    return;
+                }
+            }
+        }
+        let mut staged = Vec::new();
+        for (filename, _, new) in &contents {
+            match crate::write_staged(Path::new(filename), new.as_bytes()) {
+                Ok(paths) => staged.push(paths),
+                Err(e) => {
+                    for (temp, _) in &staged {
+                        let _ = fs::remove_file(temp);
+                    }
+                    self.error(format!("{}: {}", filename, e));
+                    return;
+                }
+            }
+        }
+        for (i, (temp, path)) in staged.iter().enumerate() {
+            if let Err(e) = fs::rename(temp, path) {
+                for (temp, _) in &staged[i..] {
+                    let _ = fs::remove_file(temp);
+                }
+                for ((_, path), (_, original, _)) in staged[..i].iter().zip(&contents) {
+                    let _ = crate::write_replacing(path, original.as_bytes());
+                }
+                self.error(format!("{}: {}", path.display(), e));
+                return;
+            }
+        }
+        // 열려 있는 버퍼도 디스크와 같게
+        for (&filename, file_changes) in &files {
+            let Some((buffer, undo)) = self.buffer_and_undo_for_mut(filename) else { continue };
+            for change in file_changes {
+                if let Some(row) = buffer.rows.get_mut(change.line - 1).filter(|r| r.content == change.old) {
+                    *row = Row::new(change.new.clone());
+                }
+            }
+            buffer.touch();
+            buffer.dirty = false;
+            undo.commit(&buffer.rows, buffer.version);
+        }
+        self.git.reload();
+        self.project_replace = None;
+        self.close_panel();
+        self.status_msg = format!("Replaced {} lines in {} files", changes.len(), files.len());
+    }
+}
@@ -0,0 +1,256 @@
+// --- Lua Scripting ---
+// init.lua와 plugins/*.lua에서 쓰는 `vii` API
+//
+//   vii.keymap(mode, lhs, fn)     "n"/"i" 모드 키 매핑 (lhs는 "<C-x>" 표기 가능)
+//   vii.create_command(name, fn)  :[range]name args 로 실행되는 명령 (fn(args, line1, line2))
+//   vii.autocmd(event, fn)        :autocmd 와 같은 이벤트 (autocmd.rs), fn(event, 파일 이름 등)
+//   vii.cmd(line)                 ex 명령 실행
+//   vii.get_lines() / vii.get_line(n) / vii.set_line(n, text) / vii.set_lines(first, last, lines)
+//   vii.line_count() / vii.get_cursor() / vii.set_cursor(line, col) / vii.insert(text)
+//   vii.filename() / vii.filetype() / vii.mode() / vii.message(text)
+// 줄과 열 번호는 모두 1부터 시작
+use crate::range::Range;
+use crate::{EditorConfig, Row, config, keys};
+use mlua::{Function, Lua, Result, Table, Value};
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+
+pub struct Scripting {
+    lua: Lua,
+}
+
+impl Scripting {
+    pub fn new() -> Result<Self> {
+        let lua = Lua::new();
+        let vii = lua.create_table()?;
+        vii.set("_keymaps", lua.create_table()?)?;
+        vii.set("_commands", lua.create_table()?)?;
+        vii.set("_autocmds", lua.create_table()?)?;
+
+        // 등록 함수는 에디터 상태가 필요 없으므로 Lua 쪽 테이블에 저장만 함
+        vii.set(
+            "keymap",
+            lua.create_function(|lua, (mode, lhs, f): (String, String, Function)| {
+                let maps: Table = lua.globals().get::<Table>("vii")?.get("_keymaps")?;
+                maps.set(format!("{}:{}", mode, keys::normalize(&lhs)), f)
+            })?,
+        )?;
+        vii.set(
+            "create_command",
+            lua.create_function(|lua, (name, f): (String, Function)| {
+                let cmds: Table = lua.globals().get::<Table>("vii")?.get("_commands")?;
+                cmds.set(name, f)
+            })?,
+        )?;
+        vii.set(
+            "autocmd",
+            lua.create_function(|lua, (event, f): (String, Function)| {
+                let autocmds: Table = lua.globals().get::<Table>("vii")?.get("_autocmds")?;
+                let list: Table = match autocmds.get::<Value>(event.as_str())? {
+                    Value::Table(t) => t,
+                    _ => {
+                        let t = lua.create_table()?;
+                        autocmds.set(event.as_str(), &t)?;
+                        t
+                    }
+                };
+                list.push(f)
+            })?,
+        )?;
+        lua.globals().set("vii", vii)?;
+        Ok(Scripting { lua })
+    }
+
+    fn table(&self, name: &str) -> Result<Table> {
+        self.lua.globals().get::<Table>("vii")?.get(name)
+    }
+
+    // 설정 디렉터리의 init.lua와 plugins/*.lua를 차례로 실행
+    pub fn load_config(&self, editor: &mut EditorConfig) -> Result<()> {
+        let Some(dir) = config::config_dir() else { return Ok(()) };
+        let mut files = vec![dir.join("init.lua")];
+        if let Ok(entries) = fs::read_dir(dir.join("plugins")) {
+            let mut plugins: Vec<_> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "lua"))
+                .collect();
+            plugins.sort();
+            files.extend(plugins);
+        }
+        for file in files.iter().filter(|f| f.is_file()) {
+            self.run_file(editor, file)?;
+        }
+        Ok(())
+    }
+
+    pub fn run_file(&self, editor: &mut EditorConfig, path: &Path) -> Result<()> {
+        let code = fs::read_to_string(path).map_err(mlua::Error::external)?;
+        let chunk = self.lua.load(code).set_name(path.to_string_lossy());
+        self.with_api(editor, || chunk.exec())
+    }
+
+    // 모드에 등록된 키 매핑의 lhs 목록 (<leader>는 그대로)
+    pub fn keymaps(&self, mode: &str) -> Vec<String> {
+        let prefix = format!("{}:", mode);
+        let Ok(maps) = self.table("_keymaps") else { return Vec::new() };
+        maps.pairs::<String, Function>()
+            .filter_map(|pair| pair.ok())
+            .filter_map(|(key, _)| key.strip_prefix(&prefix).map(str::to_string))
+            .collect()
+    }
+
+    pub fn call_keymap(&self, editor: &mut EditorConfig, mode: &str, lhs: &str) -> Result<()> {
+        let f: Function = self.table("_keymaps")?.get(format!("{}:{}", mode, lhs))?;
+        self.with_api(editor, || f.call(()))
+    }
+
+    pub fn has_command(&self, name: &str) -> bool {
+        self.table("_commands").and_then(|t| t.contains_key(name)).unwrap_or(false)
+    }
+
+    // fn(args, line1, line2): 범위가 없으면 두 줄 번호 모두 커서 줄
+    pub fn call_command(&self, editor: &mut EditorConfig, name: &str, args: &str, range: Range) -> Result<()> {
+        let f: Function = self.table("_commands")?.get(name)?;
+        self.with_api(editor, || f.call((args, range.start, range.end)))
+    }
+
+    // :lua 코드 한 줄 실행
+    pub fn run_string(&self, editor: &mut EditorConfig, code: &str) -> Result<()> {
+        let chunk = self.lua.load(code).set_name(":lua");
+        self.with_api(editor, || chunk.exec())
+    }
+
+    // 이벤트에 등록된 autocmd를 등록 순서대로 실행, 함수는 (이벤트, 패턴과 맞춘 대상)을 받음
+    pub fn emit(&self, editor: &mut EditorConfig, event: &str, target: &str) -> Result<()> {
+        let list = match self.table("_autocmds")?.get::<Value>(event)? {
+            Value::Table(t) => t,
+            _ => return Ok(()),
+        };
+        self.with_api(editor, || {
+            for f in list.sequence_values::<Function>() {
+                f?.call::<()>((event, target))?;
+            }
+            Ok(())
+        })
+    }
+
+    // 에디터 상태를 다루는 함수들은 스크립트가 실행되는 동안만 유효
+    fn with_api<R>(&self, editor: &mut EditorConfig, f: impl FnOnce() -> Result<R>) -> Result<R> {
+        let cell = RefCell::new(editor);
+        let cell = &cell;
+        self.lua.scope(|scope| {
+            let vii: Table = self.lua.globals().get("vii")?;
+            vii.set(
+                "get_lines",
+                scope.create_function(move |_, ()| {
+                    let ed = cell.borrow();
+                    Ok(ed.buffer.rows.iter().map(|r| r.content.clone()).collect::<Vec<_>>())
+                })?,
+            )?;
+            vii.set(
+                "get_line",
+                scope.create_function(move |_, n: usize| {
+                    let ed = cell.borrow();
+                    Ok(n.checked_sub(1).and_then(|i| ed.buffer.rows.get(i)).map(|r| r.content.clone()))
+                })?,
+            )?;
+            vii.set(
+                "set_line",
+                scope.create_function(move |_, (n, text): (usize, String)| {
+                    let mut ed = cell.borrow_mut();
+                    let ed = &mut **ed;
+                    let Some(row) = n.checked_sub(1).and_then(|i| ed.buffer.rows.get_mut(i)) else {
+                        return Err(mlua::Error::runtime(format!("line {} out of range", n)));
+                    };
+                    row.content = text;
+                    ed.buffer.touch();
+                    ed.clamp_cursor();
+                    Ok(())
+                })?,
+            )?;
+            vii.set(
+                "set_lines",
+                scope.create_function(move |_, (first, last, lines): (usize, usize, Vec<String>)| {
+                    let mut ed = cell.borrow_mut();
+                    let ed = &mut **ed;
+                    let len = ed.buffer.rows.len();
+                    // first..=last 를 바꿈 (last = first - 1 이면 first 앞에 끼워 넣음)
+                    if first == 0 || first > len + 1 || last + 1 < first || last > len {
+                        return Err(mlua::Error::runtime("invalid line range"));
+                    }
+                    ed.buffer.rows.splice(first - 1..last, lines.into_iter().map(Row::new));
+                    if ed.buffer.rows.is_empty() {
+                        ed.buffer.rows.push(Row::new(String::new()));
+                    }
+                    ed.buffer.touch();
+                    ed.clamp_cursor();
+                    Ok(())
+                })?,
+            )?;
+            vii.set("line_count", scope.create_function(move |_, ()| Ok(cell.borrow().buffer.rows.len()))?)?;
+            vii.set(
+                "get_cursor",
+                scope.create_function(move |_, ()| {
+                    let ed = cell.borrow();
+                    Ok((ed.cy + 1, ed.cx + 1))
+                })?,
+            )?;
+            vii.set(
+                "set_cursor",
+                scope.create_function(move |_, (line, col): (usize, Option<usize>)| {
+                    let mut ed = cell.borrow_mut();
+                    ed.cy = line.saturating_sub(1);
+                    ed.cx = col.unwrap_or(1).saturating_sub(1);
+                    ed.clamp_cursor();
+                    Ok(())
+                })?,
+            )?;
+            vii.set(
+                "insert",
+                scope.create_function(move |_, text: String| {
+                    let mut ed = cell.borrow_mut();
+                    for c in text.chars() {
+                        ed.insert_text_char(c);
+                    }
+                    Ok(())
+                })?,
+            )?;
+            vii.set(
+                "cmd",
+                scope.create_function(move |_, line: String| Ok(cell.borrow_mut().run_command(&line)))?,
+            )?;
+            vii.set(
+                "message",
+                scope.create_function(move |_, text: String| {
+                    cell.borrow_mut().status_msg = text;
+                    Ok(())
+                })?,
+            )?;
+            vii.set(
+                "annotate",
+                scope.create_function(move |_, (line, text): (usize, Option<String>)| {
+                    cell.borrow_mut().annotate(line.saturating_sub(1), text.as_deref().unwrap_or(""));
+                    Ok(())
+                })?,
+            )?;
+            vii.set(
+                "clear_annotations",
+                scope.create_function(move |_, ()| {
+                    cell.borrow_mut().clear_annotations();
+                    Ok(())
+                })?,
+            )?;
+            vii.set("filename", scope.create_function(move |_, ()| Ok(cell.borrow().filename.clone()))?)?;
+            vii.set("filetype", scope.create_function(move |_, ()| Ok(cell.borrow().buffer_options.filetype.clone()))?)?;
+            vii.set(
+                "mode",
+                scope.create_function(move |_, ()| {
+                    Ok(cell.borrow().mode.short_name())
+                })?,
+            )?;
+            f()
+        })
+    }
+}
@@ -0,0 +1,85 @@
+// --- Page Scrolling ---
+// Ctrl-D / Ctrl-U   반 화면 (횟수를 주면 그 줄 수) 아래 / 위로, 화면과 커서가 같은 줄 수만큼 움직임
+// Ctrl-F / Ctrl-B   한 화면에서 두 줄 뺀 만큼 (PageDown / PageUp 도), 횟수만큼 되풀이
+// set smoothscroll  화면이 두 줄 넘게 한 번에 움직이면 (위 키들, G, 찾기 등) 몇 프레임에 걸쳐 미끄러지듯 옮김
+//                   움직이는 동안은 커서를 숨기고, 그 사이 키를 치면 지금 보이는 자리에서 새 위치로 다시 움직임
+use crate::EditorConfig;
+use std::time::{Duration, Instant};
+
+// 한 번 움직이는 데 걸리는 시간 (프레임 여덟 개쯤)
+const DURATION: Duration = Duration::from_millis(130);
+
+struct Animation {
+    from: usize,
+    to: usize,
+    started: Instant,
+}
+
+impl Animation {
+    // 지금 보일 row_offset, 끝났으면 None (처음엔 빠르고 끝에서 느려짐)
+    fn position(&self) -> Option<usize> {
+        let t = self.started.elapsed().as_secs_f64() / DURATION.as_secs_f64();
+        if t >= 1.0 {
+            return None;
+        }
+        let moved = (self.from.abs_diff(self.to) as f64 * (1.0 - (1.0 - t).powi(2))) as usize;
+        Some(if self.to > self.from { self.from + moved } else { self.from - moved })
+    }
+}
+
+/// 마지막으로 그린 본문의 위치와 진행 중인 움직임
+#[derive(Default)]
+pub struct SmoothScroll {
+    buffer: usize, // 그때의 버퍼 (버퍼를 바꾸면 움직이지 않고 바로 보여줌)
+    shown: usize,
+    animation: Option<Animation>,
+}
+
+impl EditorConfig {
+    // Ctrl-D / Ctrl-U
+    pub(crate) fn scroll_half_page(&mut self, down: bool) {
+        let rows = self.count.take().unwrap_or(self.text_rows() / 2).max(1);
+        self.scroll_lines(down, rows);
+    }
+
+    // Ctrl-F / Ctrl-B
+    pub(crate) fn scroll_pages(&mut self, down: bool) {
+        let rows = self.take_count() * self.text_rows().saturating_sub(2).max(1);
+        self.scroll_lines(down, rows);
+    }
+
+    // 커서를 rows 줄 옮기고 (닫힌 접기는 한 줄) 화면도 커서가 실제로 움직인 만큼
+    fn scroll_lines(&mut self, down: bool, rows: usize) {
+        let before = self.cursor_display_row();
+        (0..rows).for_each(|_| self.move_cursor(if down { 'j' } else { 'k' }));
+        let moved = self.cursor_display_row().abs_diff(before);
+        self.row_offset = if down { self.row_offset + moved } else { self.row_offset.saturating_sub(moved) };
+    }
+
+    /// 이번 프레임에 본문을 그릴 row_offset (scroll 로 row_offset 을 정한 뒤에 부름)
+    pub(crate) fn animate_scroll(&mut self) -> usize {
+        let target = self.row_offset;
+        let current = self.buffers.current;
+        let smooth = &mut self.smooth;
+        let shown = match &smooth.animation {
+            Some(animation) => animation.position().unwrap_or(animation.to),
+            None => smooth.shown,
+        };
+        if !self.options.smoothscroll || smooth.buffer != current {
+            smooth.animation = None;
+        } else if smooth.animation.as_ref().is_none_or(|a| a.to != target) {
+            smooth.animation = (shown.abs_diff(target) > 2).then(|| Animation { from: shown, to: target, started: Instant::now() });
+        }
+        let position = smooth.animation.as_ref().and_then(Animation::position);
+        if position.is_none() {
+            smooth.animation = None;
+        }
+        (smooth.buffer, smooth.shown) = (current, position.unwrap_or(target));
+        smooth.shown
+    }
+
+    /// 화면이 움직이는 중인지 (이벤트 루프가 키를 기다리지 않고 다음 프레임을 그림)
+    pub fn is_scrolling(&self) -> bool {
+        self.smooth.animation.is_some()
+    }
+}
@@ -0,0 +1,219 @@
+// --- Search ---
+// 글자 그대로 찾음 (정규식이 아님), 버퍼 끝에서 처음으로 (? 는 처음에서 끝으로) 돌아가며 찾음
+//   /글 Enter   커서 뒤로 찾기        ?글 Enter   커서 앞으로 찾기   (글 없이 Enter 면 마지막 글)
+//   n / N       마지막 찾기를 같은 / 반대 방향으로 되풀이
+//   :[범위]s/글//n   바꾸지 않고 나온 횟수만 셈 (substitute.rs)
+// LARGE 줄이 넘는 버퍼에서는 백그라운드 스레드가 찾고 상태 줄에 진행률을 보여줌
+// 찾는 동안에도 키는 그대로 처리되고, 일반 모드의 Esc 나 Ctrl-C 로 그만둠 (다른 버퍼로 옮겨도 그만둠)
+use crate::EditorConfig;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+// 이보다 줄이 많으면 백그라운드에서 찾음
+const LARGE: usize = 100_000;
+// 몇 줄마다 진행률을 올리고 그만두라는지 확인할지
+const STEP: usize = 4096;
+
+enum Found {
+    At(Option<(usize, usize)>), // 찾은 (줄, 바이트 열)
+    Count(usize, usize),        // (나온 횟수, 줄 수)
+}
+
+struct Running {
+    what: String, // 상태 줄에 보여줄 이름 ("Searching for foo")
+    pattern: String,
+    total: usize,
+    cancel: Arc<AtomicBool>,
+    progress: Arc<AtomicUsize>,
+    shown: usize, // 마지막으로 보여준 진행률 (%)
+    rx: Receiver<Option<Found>>, // 그만뒀으면 None
+}
+
+pub struct Search {
+    last: Option<(String, bool)>, // 마지막으로 찾은 글과 방향 (true 면 앞으로)
+    running: Option<Running>,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Search { last: None, running: None }
+    }
+}
+
+// 그만두라면 None, 못 찾았으면 Some(None)
+fn find(lines: &[String], pattern: &str, from: (usize, usize), forward: bool, cancel: &AtomicBool, progress: &AtomicUsize) -> Option<Option<(usize, usize)>> {
+    let len = lines.len();
+    let (row, col) = from;
+    let line = &lines[row];
+    if forward {
+        let after = col + line.get(col..).and_then(|l| l.chars().next()).map_or(0, char::len_utf8);
+        if let Some(x) = line.get(after..).and_then(|l| l.find(pattern)) {
+            return Some(Some((row, after + x)));
+        }
+    } else if let Some(x) = line.get(..col).and_then(|l| l.rfind(pattern)) {
+        return Some(Some((row, x)));
+    }
+    for n in 1..=len {
+        if n % STEP == 0 {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            progress.store(n, Ordering::Relaxed);
+        }
+        let i = if forward { (row + n) % len } else { (row + len - n) % len };
+        let found = if forward { lines[i].find(pattern) } else { lines[i].rfind(pattern) };
+        if let Some(x) = found {
+            return Some(Some((i, x)));
+        }
+    }
+    Some(None)
+}
+
+// 줄들에서 나온 횟수 (global 이 아니면 줄마다 한 번)와 나온 줄 수, 그만두라면 None
+fn count(lines: &[String], pattern: &str, global: bool, ignore_case: bool, cancel: &AtomicBool, progress: &AtomicUsize) -> Option<(usize, usize)> {
+    let pattern = if ignore_case { pattern.to_ascii_lowercase() } else { pattern.to_string() };
+    let (mut total, mut matched) = (0, 0);
+    for (n, line) in lines.iter().enumerate() {
+        if n % STEP == 0 {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            progress.store(n, Ordering::Relaxed);
+        }
+        let found = match ignore_case {
+            true => line.to_ascii_lowercase().matches(&pattern).count(),
+            false => line.matches(&pattern).count(),
+        };
+        if found > 0 {
+            total += if global { found } else { 1 };
+            matched += 1;
+        }
+    }
+    Some((total, matched))
+}
+
+impl EditorConfig {
+    // / 나 ? 명령 줄에서 Enter
+    pub(crate) fn search_prompt(&mut self, pattern: &str, forward: bool) {
+        let pattern = match (pattern.is_empty(), &self.search.last) {
+            (false, _) => pattern.to_string(),
+            (true, Some((last, _))) => last.clone(),
+            (true, None) => {
+                self.error("No previous search pattern");
+                return;
+            }
+        };
+        self.search.last = Some((pattern.clone(), forward));
+        self.search_for(&pattern, forward);
+    }
+
+    // n / N
+    pub(crate) fn search_next(&mut self, reverse: bool) {
+        let Some((pattern, forward)) = self.search.last.clone() else {
+            self.error("No previous search pattern");
+            return;
+        };
+        self.search_for(&pattern, forward != reverse);
+    }
+
+    fn search_for(&mut self, pattern: &str, forward: bool) {
+        self.cancel_search();
+        let lines: Vec<String> = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+        let from = (self.cy, self.cx);
+        if lines.len() <= LARGE {
+            let found = find(&lines, pattern, from, forward, &AtomicBool::new(false), &AtomicUsize::new(0));
+            self.search_done(pattern, Found::At(found.flatten()));
+            return;
+        }
+        let pattern_owned = pattern.to_string();
+        self.spawn_search(format!("Searching for {}", pattern), pattern, lines.len(), move |cancel, progress| {
+            find(&lines, &pattern_owned, from, forward, cancel, progress).map(Found::At)
+        });
+    }
+
+    // :s/글//n, start..end 줄에서 나온 횟수를 셈
+    pub(crate) fn count_matches(&mut self, pattern: &str, global: bool, ignore_case: bool, start: usize, end: usize) {
+        self.cancel_search();
+        let lines: Vec<String> = self.buffer.rows[start..end].iter().map(|r| r.content.clone()).collect();
+        if lines.len() <= LARGE {
+            if let Some((total, matched)) = count(&lines, pattern, global, ignore_case, &AtomicBool::new(false), &AtomicUsize::new(0)) {
+                self.search_done(pattern, Found::Count(total, matched));
+            }
+            return;
+        }
+        let pattern_owned = pattern.to_string();
+        self.spawn_search(format!("Counting {}", pattern), pattern, lines.len(), move |cancel, progress| {
+            count(&lines, &pattern_owned, global, ignore_case, cancel, progress).map(|(total, matched)| Found::Count(total, matched))
+        });
+    }
+
+    fn spawn_search(&mut self, what: String, pattern: &str, total: usize, job: impl FnOnce(&AtomicBool, &AtomicUsize) -> Option<Found> + Send + 'static) {
+        let (cancel, progress) = (Arc::new(AtomicBool::new(false)), Arc::new(AtomicUsize::new(0)));
+        let (tx, rx) = mpsc::channel();
+        let (job_cancel, job_progress) = (cancel.clone(), progress.clone());
+        thread::spawn(move || {
+            let _ = tx.send(job(&job_cancel, &job_progress));
+        });
+        self.status_msg = format!("{}... 0% (Esc to cancel)", what);
+        let pattern = pattern.to_string();
+        self.search.running = Some(Running { what, pattern, total, cancel, progress, shown: 0, rx });
+    }
+
+    fn search_done(&mut self, pattern: &str, found: Found) {
+        match found {
+            Found::At(Some((line, col))) => {
+                // 찾는 동안 버퍼가 바뀌었을 수 있으므로 범위 안으로
+                (self.cy, self.cx) = (line, col);
+                self.clamp_cursor();
+                while !self.buffer.rows[self.cy].content.is_char_boundary(self.cx) {
+                    self.cx -= 1;
+                }
+                self.status_msg = format!("/{}", pattern);
+            }
+            Found::At(None) => self.error(format!("Pattern not found: {}", pattern)),
+            Found::Count(0, _) => self.error(format!("Pattern not found: {}", pattern)),
+            Found::Count(total, lines) => self.status_msg = format!("{} matches on {} lines", total, lines),
+        }
+    }
+
+    /// 백그라운드에서 찾는 중인지
+    pub fn is_searching(&self) -> bool {
+        self.search.running.is_some()
+    }
+
+    // Esc / Ctrl-C
+    pub(crate) fn cancel_search(&mut self) {
+        let Some(running) = self.search.running.take() else { return };
+        running.cancel.store(true, Ordering::Relaxed);
+        self.status_msg = format!("{}: cancelled", running.what);
+    }
+
+    // 결과가 왔거나 진행률이 바뀌었으면 true
+    pub(crate) fn poll_search(&mut self) -> bool {
+        let Some(running) = self.search.running.as_mut() else { return false };
+        match running.rx.try_recv() {
+            Ok(result) => {
+                let running = self.search.running.take().expect("running search");
+                if let Some(found) = result {
+                    self.search_done(&running.pattern, found);
+                }
+                true
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.search.running = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                let percent = running.progress.load(Ordering::Relaxed) * 100 / running.total.max(1);
+                if percent == running.shown {
+                    return false;
+                }
+                running.shown = percent;
+                self.status_msg = format!("{}... {}% (Esc to cancel)", running.what, percent);
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// --- Headless RPC Server ---
+// vii --server <socket>: 터미널 없이 에디터를 띄우고 유닉스 소켓으로 명령을 받는다.
+// 한 줄에 JSON-RPC 요청 하나, 응답도 한 줄 (GUI 프론트엔드나 원격 조작용)
+//
+//   {"id":1,"method":"open","params":{"path":"a.txt"}}
+//   {"id":2,"method":"keys","params":{"keys":"ihello<Esc>"}}   "<C-x>" 표기 가능
+//   {"id":3,"method":"get_lines"}
+//
+// 메서드: open, keys, command, get_lines, get_cursor, get_mode, get_status, quit
+// 줄과 열 번호는 1부터 시작
+use crate::json::Json;
+use crate::{EditorConfig, keys};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+
+pub fn run(path: &str, editor: &mut EditorConfig) -> io::Result<()> {
+    remove_stale_socket(path)?;
+    let listener = UnixListener::bind(path)?;
+    let bound = fs::symlink_metadata(path)?;
+    let result = serve(&listener, editor);
+    // 그 사이 다른 인스턴스가 같은 자리에 새로 연 소켓이면 남겨 둠
+    if let Ok(meta) = fs::symlink_metadata(path)
+        && (meta.dev(), meta.ino()) == (bound.dev(), bound.ino())
+    {
+        let _ = fs::remove_file(path);
+    }
+    result
+}
+
+// 이전에 비정상 종료하며 남은 소켓만 지움, 살아 있는 소켓이나 다른 파일이면 오류
+fn remove_stale_socket(path: &str) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+        Ok(meta) if meta.file_type().is_socket() && UnixStream::connect(path).is_err() => fs::remove_file(path),
+        Ok(_) => Err(io::Error::new(io::ErrorKind::AddrInUse, format!("{}: address in use", path))),
+    }
+}
+
+// 연결을 하나씩 받아 처리, quit 요청이 오면 끝냄
+fn serve(listener: &UnixListener, editor: &mut EditorConfig) -> io::Result<()> {
+    for stream in listener.incoming() {
+        if !handle_client(stream?, editor)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn handle_client(stream: UnixStream, editor: &mut EditorConfig) -> io::Result<bool> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        editor.poll_background();
+        let (response, running) = match Json::parse(&line) {
+            Some(request) => {
+                let id = request.get("id").cloned().unwrap_or(Json::Null);
+                let method = request.get("method").and_then(|m| m.as_str()).unwrap_or("");
+                let params = request.get("params").cloned().unwrap_or(Json::Null);
+                let (result, running) = dispatch(editor, method, &params);
+                (reply(id, result), running)
+            }
+            None => (reply(Json::Null, Err((-32700, "Parse error".into()))), true),
+        };
+        writeln!(writer, "{}", response)?;
+        if !running {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn reply(id: Json, result: Result<Json, (i64, String)>) -> Json {
+    match result {
+        Ok(value) => Json::obj(vec![("jsonrpc", "2.0".into()), ("id", id), ("result", value)]),
+        Err((code, message)) => Json::obj(vec![
+            ("jsonrpc", "2.0".into()),
+            ("id", id),
+            ("error", Json::obj(vec![("code", code.into()), ("message", message.into())])),
+        ]),
+    }
+}
+
+fn param<'a>(params: &'a Json, name: &str) -> Result<&'a str, (i64, String)> {
+    params
+        .get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| (-32602, format!("missing string parameter: {}", name)))
+}
+
+// 요청 하나를 실행하고 (결과, 계속 실행할지) 반환
+fn dispatch(editor: &mut EditorConfig, method: &str, params: &Json) -> (Result<Json, (i64, String)>, bool) {
+    let result = match method {
+        "open" => param(params, "path").map(|path| {
+            editor.open_file(path);
+            Json::String(editor.status_msg.clone())
+        }),
+        "keys" => match param(params, "keys") {
+            Ok(notation) => {
+                for key in keys::parse(notation) {
+                    if !editor.handle_keypress(key) {
+                        return (Ok(Json::Bool(false)), false);
+                    }
+                }
+                Ok(Json::Bool(true))
+            }
+            Err(e) => Err(e),
+        },
+        "command" => match param(params, "command") {
+            Ok(line) => {
+                let line = line.trim_start_matches(':');
+                if !editor.run_command(line) {
+                    return (Ok(Json::Bool(false)), false);
+                }
+                Ok(Json::Bool(true))
+            }
+            Err(e) => Err(e),
+        },
+        "get_lines" => Ok(Json::Array(editor.buffer.rows.iter().map(|r| r.content.as_str().into()).collect())),
+        "get_cursor" => Ok(Json::Array(vec![(editor.cy as i64 + 1).into(), (editor.cx as i64 + 1).into()])),
+        "get_mode" => Ok(editor.mode.short_name().into()),
+        "get_status" => Ok(Json::obj(vec![
+            ("message", editor.status_msg.as_str().into()),
+            ("filename", editor.filename.as_deref().map_or(Json::Null, Json::from)),
+            ("modified", Json::Bool(editor.buffer.dirty)),
+        ])),
+        "quit" => return (Ok(Json::Null), false),
+        _ => Err((-32601, format!("Method not found: {}", method))),
+    };
+    (result, true)
+}
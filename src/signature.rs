@@ -0,0 +1,79 @@
+// --- LSP Signature Help ---
+// 입력 모드에서 ( 나 , 를 치면 언어 서버에 지금 부르는 함수의 시그니처를 물어 커서 위 한 줄 창에 보여줌
+// 지금 쓰고 있는 인자는 굵게 밑줄로, 바깥 호출을 닫는 ) 를 치거나 입력 모드를 나가면 닫힘
+use crate::json::Json;
+use crate::lsp::{self, Request};
+use crate::{EditorConfig, Mode};
+use std::ops::Range;
+
+/// 보여줄 시그니처와 그 안의 지금 인자 (바이트 범위)
+pub struct Signature {
+    pub label: String,
+    pub active: Option<Range<usize>>,
+}
+
+// SignatureHelp 에서 고른 시그니처
+fn parse(result: &Json) -> Option<Signature> {
+    let signatures = result.get("signatures")?.as_array()?;
+    let index = |key: &str, json: &Json| json.get(key).and_then(|n| n.as_i64()).map(|n| n.max(0) as usize);
+    let signature = signatures.get(index("activeSignature", result).unwrap_or(0)).or(signatures.first())?;
+    let label = signature.get("label")?.as_str()?.to_string();
+    // 시그니처의 activeParameter 가 먼저, 없으면 전체의 것
+    let parameter = index("activeParameter", signature).or(index("activeParameter", result)).unwrap_or(0);
+    let active = signature.get("parameters").and_then(|p| p.as_array()).and_then(|p| p.get(parameter)).and_then(|p| {
+        match p.get("label")? {
+            Json::String(name) => label.find(name.as_str()).map(|start| start..start + name.len()),
+            Json::Array(offsets) => {
+                let (start, end) = (offsets.first()?.as_i64()? as usize, offsets.get(1)?.as_i64()? as usize);
+                Some(lsp::to_byte(&label, start)..lsp::to_byte(&label, end))
+            }
+            _ => None,
+        }
+    });
+    Some(Signature { label, active })
+}
+
+// 커서 앞 (같은 줄) 에 아직 닫히지 않은 ( 가 있는지
+fn inside_call(before: &str) -> bool {
+    let mut depth = 0;
+    for c in before.chars().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth == 0 => return true,
+            '(' => depth -= 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+impl EditorConfig {
+    // 입력 모드에서 ( , ) 를 친 뒤
+    pub(crate) fn signature_trigger(&mut self, c: char) {
+        // f(a, g(b)) 의 안쪽 ) 뒤에는 바깥 f 의 시그니처를 다시 물음
+        if c == ')' && !inside_call(&self.buffer.rows[self.cy].content[..self.cx]) {
+            self.signature = None;
+            return;
+        }
+        if !self.filename.as_ref().is_some_and(|name| self.lsp.has_server(name)) {
+            return;
+        }
+        let context = Json::obj(vec![
+            ("triggerKind", 2.into()),
+            ("triggerCharacter", c.to_string().into()),
+            ("isRetrigger", Json::Bool(self.signature.is_some())),
+        ]);
+        let params = vec![self.lsp_cursor(), ("context", context)];
+        if self.lsp_request(Request::SignatureHelp, params) {
+            self.signature_at = (self.cy, self.cx, self.buffer.version);
+        }
+    }
+
+    // 물은 뒤 커서가 움직였거나 글을 더 고쳤으면 늦게 온 응답은 버림
+    pub(crate) fn show_signature(&mut self, version: u64, result: &Json) {
+        let now = (self.cy, self.cx, self.buffer.version);
+        if self.mode == Mode::Insert && now == self.signature_at && version == self.buffer.version {
+            self.signature = parse(result);
+        }
+    }
+}
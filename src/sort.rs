@@ -0,0 +1,76 @@
+// --- :sort ---
+// :[범위]sort[!] [n][u][i]   범위의 줄들을 정렬 (범위가 없으면 버퍼 전체)
+//   !   거꾸로
+//   n   줄에서 처음 나오는 정수로 비교 (- 부호 포함), 숫자가 없는 줄은 앞에 원래 순서대로
+//   u   같은 줄이 이어지면 첫 줄만 남김 (i와 함께면 대소문자를 무시하고 비교)
+//   i   대소문자를 무시
+use crate::{EditorConfig, Row};
+use std::cmp::Ordering;
+
+struct Flags {
+    reverse: bool,
+    numeric: bool,
+    unique: bool,
+    ignore_case: bool,
+}
+
+fn parse_flags(bang: bool, args: &str) -> Result<Flags, String> {
+    let mut flags = Flags { reverse: bang, numeric: false, unique: false, ignore_case: false };
+    for c in args.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            'n' => flags.numeric = true,
+            'u' => flags.unique = true,
+            'i' => flags.ignore_case = true,
+            _ => return Err(format!("Invalid sort flag: {}", c)),
+        }
+    }
+    Ok(flags)
+}
+
+// 줄에서 처음 나오는 정수
+fn first_number(line: &str) -> Option<i64> {
+    let start = line.find(|c: char| c.is_ascii_digit())?;
+    let end = line[start..].find(|c: char| !c.is_ascii_digit()).map_or(line.len(), |n| start + n);
+    let value: i64 = line[start..end].parse().unwrap_or(i64::MAX);
+    Some(if line[..start].ends_with('-') { -value } else { value })
+}
+
+fn sort_lines(lines: &mut Vec<String>, flags: &Flags) {
+    let text_cmp = |a: &String, b: &String| {
+        if flags.ignore_case { a.to_lowercase().cmp(&b.to_lowercase()) } else { a.cmp(b) }
+    };
+    // 안정 정렬이라 같은 값끼리는 원래 순서를 지킴
+    if flags.numeric {
+        lines.sort_by_key(|l| first_number(l));
+    } else {
+        lines.sort_by(text_cmp);
+    }
+    if flags.reverse {
+        lines.reverse();
+    }
+    if flags.unique {
+        lines.dedup_by(|a, b| if flags.numeric { first_number(a) == first_number(b) } else { text_cmp(a, b) == Ordering::Equal });
+    }
+}
+
+impl EditorConfig {
+    // start..=end 줄 (0부터)을 정렬
+    pub(crate) fn sort(&mut self, start: usize, end: usize, bang: bool, args: &str) {
+        let flags = match parse_flags(bang, args) {
+            Ok(flags) => flags,
+            Err(e) => {
+                self.error(e);
+                return;
+            }
+        };
+        let mut lines: Vec<String> = self.buffer.rows[start..=end].iter().map(|r| r.content.clone()).collect();
+        sort_lines(&mut lines, &flags);
+        let removed = end - start + 1 - lines.len();
+        self.buffer.rows.splice(start..=end, lines.into_iter().map(Row::new));
+        self.buffer.touch();
+        if removed > 0 {
+            self.status_msg = format!("{} duplicate lines removed", removed);
+        }
+        self.clamp_cursor();
+    }
+}
@@ -0,0 +1,25 @@
+// --- Startup Position ---
+// 명령 줄의 +인자는 파일을 모두 연 뒤 첫 파일에서 차례로 적용 (vi 와 같음)
+//   vii +42 a.rs      42번째 줄로 (줄 수보다 크면 마지막 줄)
+//   vii + a.rs        마지막 줄로
+//   vii +/TODO a.rs   처음으로 TODO 가 나오는 곳으로 (글자 그대로 찾음)
+//   vii +'set nu' a.rs  그 밖에는 ex 명령으로 실행
+use crate::EditorConfig;
+
+impl EditorConfig {
+    /// 명령 줄의 +인자 하나 ("+" 를 뗀 것)를 적용
+    pub fn startup_command(&mut self, arg: &str) {
+        let last = self.buffer.rows.len();
+        if arg.is_empty() || arg.chars().all(|c| c.is_ascii_digit()) {
+            self.goto_line(arg.parse().unwrap_or(last));
+        } else if let Some(pattern) = arg.strip_prefix('/') {
+            let found = self.buffer.rows.iter().enumerate().find_map(|(i, r)| r.content.find(pattern).map(|x| (i, x)));
+            match found {
+                Some((line, col)) if !pattern.is_empty() => (self.cy, self.cx) = (line, col),
+                _ => self.error(format!("Pattern not found: {}", pattern)),
+            }
+        } else {
+            self.run_command(arg);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// --- :substitute ---
+// :[범위]s/찾을 글/바꿀 글/[g][i][n]   범위(기본은 커서 줄)의 줄마다 글자 그대로 찾아서 바꿈 (정규식이 아님)
+//   g  줄에서 모두 바꿈 (없으면 줄마다 첫 번째만)   i  영문 대소문자를 무시
+//   n  바꾸지 않고 나온 횟수만 셈 (줄이 많으면 백그라운드에서, search.rs)
+// / 대신 다른 문장 부호도 구분자로 쓸 수 있고, \/ 처럼 앞에 \ 를 붙이면 구분자 글자 자체
+use crate::EditorConfig;
+use crate::range::Range;
+
+// 구분자로 나눈 (찾을 글, 바꿀 글, 플래그)
+pub(crate) fn split(args: &str) -> Option<(String, String, String)> {
+    let mut chars = args.chars();
+    let delim = chars.next().filter(|c| !c.is_alphanumeric() && !c.is_whitespace() && *c != '\\')?;
+    let mut parts = vec![String::new()];
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.clone().next() == Some(delim) => {
+                chars.next();
+                parts.last_mut()?.push(delim);
+            }
+            c if c == delim && parts.len() < 3 => parts.push(String::new()),
+            c => parts.last_mut()?.push(c),
+        }
+    }
+    parts.resize(3, String::new());
+    let flags = parts.pop()?;
+    let replacement = parts.pop()?;
+    Some((parts.pop()?, replacement, flags))
+}
+
+// 줄 하나를 바꾸고 바꾼 횟수를 돌려줌
+pub(crate) fn replace_line(line: &mut String, pattern: &str, replacement: &str, global: bool, ignore_case: bool) -> usize {
+    let haystack = if ignore_case { line.to_ascii_lowercase() } else { line.clone() };
+    let needle = if ignore_case { pattern.to_ascii_lowercase() } else { pattern.to_string() };
+    let mut result = String::new();
+    let mut last = 0;
+    let mut count = 0;
+    for (start, _) in haystack.match_indices(&needle) {
+        result.push_str(&line[last..start]);
+        result.push_str(replacement);
+        last = start + needle.len();
+        count += 1;
+        if !global {
+            break;
+        }
+    }
+    if count > 0 {
+        result.push_str(&line[last..]);
+        *line = result;
+    }
+    count
+}
+
+impl EditorConfig {
+    pub(crate) fn substitute(&mut self, range: Range, args: &str) {
+        let Some((pattern, replacement, flags)) = split(args) else {
+            self.status_msg = "Usage: :[range]s/pattern/replacement/[g][i][n]".into();
+            return;
+        };
+        if pattern.is_empty() {
+            self.error("Empty pattern");
+            return;
+        }
+        if let Some(c) = flags.chars().find(|c| !matches!(c, 'g' | 'i' | 'n')) {
+            self.error(format!("Invalid flag: {}", c));
+            return;
+        }
+        let (global, ignore_case) = (flags.contains('g'), flags.contains('i'));
+        if flags.contains('n') {
+            self.count_matches(&pattern, global, ignore_case, range.start - 1, range.end);
+            return;
+        }
+        let (mut total, mut lines) = (0, 0);
+        for line in range.start - 1..range.end {
+            let count = replace_line(&mut self.buffer.rows[line].content, &pattern, &replacement, global, ignore_case);
+            if count > 0 {
+                total += count;
+                lines += 1;
+                (self.cy, self.cx) = (line, 0);
+            }
+        }
+        if total == 0 {
+            self.error(format!("Pattern not found: {}", pattern));
+            return;
+        }
+        self.buffer.touch();
+        self.status_msg = format!("{} substitutions on {} lines", total, lines);
+    }
+}
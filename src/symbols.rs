@@ -0,0 +1,141 @@
+// --- Document Symbols ---
+// :Symbols   지금 버퍼의 함수, 구조체, 제목 등을 선택 창에 보여줌 (글자를 치면 이름으로 퍼지 검색, Enter 로 그 자리로)
+// 언어 서버가 있으면 textDocument/documentSymbol 로 묻고, 없으면 줄의 첫 낱말로 짐작함
+//   fn / def / func / function, struct, enum, trait, interface, impl, class, mod, type, 마크다운의 # 제목
+// 안에 든 심볼은 들여써서 보여줌
+use crate::json::Json;
+use crate::lsp::{self, Request};
+use crate::picker::{Picker, PickerKind};
+use crate::EditorConfig;
+
+struct Symbol {
+    name: String,
+    kind: &'static str,
+    line: usize,
+    col: usize, // 바이트 열 (서버의 것은 UTF-16 글자 번호를 받은 뒤 바꿈)
+    depth: usize,
+}
+
+// SymbolKind 를 짧은 이름으로
+fn kind_name(kind: i64) -> &'static str {
+    match kind {
+        2..=4 => "mod",
+        5 => "class",
+        6 => "method",
+        7 | 8 => "field",
+        9 => "new",
+        10 => "enum",
+        11 => "trait",
+        12 => "fn",
+        13 => "var",
+        14 => "const",
+        22 => "variant",
+        23 => "struct",
+        26 => "type",
+        _ => "symbol",
+    }
+}
+
+// DocumentSymbol[] (children 은 한 단계 깊게) 또는 SymbolInformation[]
+fn from_lsp(items: &[Json], depth: usize, symbols: &mut Vec<Symbol>) {
+    for item in items {
+        let Some(name) = item.get("name").and_then(|n| n.as_str()) else { continue };
+        let range = item.get("selectionRange").or(item.get("location").and_then(|l| l.get("range")));
+        let Some(start) = range.and_then(|r| r.get("start")) else { continue };
+        let line = start.get("line").and_then(|l| l.as_i64()).unwrap_or(0) as usize;
+        let col = start.get("character").and_then(|c| c.as_i64()).unwrap_or(0) as usize;
+        let kind = kind_name(item.get("kind").and_then(|k| k.as_i64()).unwrap_or(0));
+        symbols.push(Symbol { name: name.to_string(), kind, line, col, depth });
+        if let Some(children) = item.get("children").and_then(|c| c.as_array()) {
+            from_lsp(children, depth + 1, symbols);
+        }
+    }
+}
+
+// 서버 없이 줄의 첫 낱말로 짐작한 심볼들
+fn guess(lines: &[String], filetype: Option<&str>) -> Vec<Symbol> {
+    const MODIFIERS: &[&str] = &["pub", "async", "unsafe", "const", "extern", "export", "default", "static", "abstract"];
+    const TYPES: &[&str] = &["struct", "enum", "trait", "interface", "impl", "class", "mod", "type"];
+    let mut symbols = Vec::new();
+    for (line, content) in lines.iter().enumerate() {
+        if filetype == Some("markdown") {
+            let level = content.chars().take_while(|&c| c == '#').count();
+            if (1..=6).contains(&level) && content[level..].starts_with(' ') {
+                symbols.push(Symbol { name: content[level..].trim().to_string(), kind: "#", line, col: 0, depth: level - 1 });
+            }
+            continue;
+        }
+        let body = content.trim_start();
+        let indent = content.len() - body.len();
+        let mut words = body.split_whitespace().map(|w| w.split('(').next().unwrap_or(w)).skip_while(|w| MODIFIERS.contains(w));
+        let Some(keyword) = words.next() else { continue };
+        let kind = match keyword {
+            "fn" | "def" | "func" | "function" => "fn",
+            _ => match TYPES.iter().find(|&&k| k == keyword) {
+                Some(k) => k,
+                None => continue,
+            },
+        };
+        let rest = &body[body.find(keyword).map_or(0, |i| i + keyword.len())..];
+        // impl 은 "Trait for Type" 처럼 {, where 앞까지 전부
+        let name = match kind {
+            "impl" => rest.split(['{', ';']).next().unwrap_or("").split(" where").next().unwrap_or("").trim().to_string(),
+            _ => rest.trim_start().split(|c: char| !(c.is_alphanumeric() || c == '_')).next().unwrap_or("").to_string(),
+        };
+        if name.is_empty() {
+            continue;
+        }
+        // 이름은 rest 의 앞 공백 바로 뒤에서 읽었음
+        let col = content.len() - rest.trim_start().len();
+        symbols.push(Symbol { name, kind, line, col, depth: indent.div_ceil(4) });
+    }
+    symbols
+}
+
+impl EditorConfig {
+    // :Symbols
+    pub(crate) fn document_symbols(&mut self) {
+        if self.filename.as_ref().is_some_and(|name| self.lsp.has_server(name)) {
+            if self.lsp_request(Request::DocumentSymbol, Vec::new()) {
+                self.status_msg = "Asking the language server...".into();
+            }
+            return;
+        }
+        let lines: Vec<String> = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+        let symbols = guess(&lines, self.buffer_options.filetype.as_deref());
+        self.open_symbol_picker(symbols);
+    }
+
+    pub(crate) fn show_lsp_symbols(&mut self, result: &Json) {
+        let mut symbols = Vec::new();
+        from_lsp(result.as_array().unwrap_or(&[]), 0, &mut symbols);
+        for symbol in &mut symbols {
+            let line = symbol.line.min(self.buffer.rows.len() - 1);
+            symbol.col = lsp::to_byte(&self.buffer.rows[line].content, symbol.col);
+        }
+        symbols.sort_by_key(|s| (s.line, s.depth));
+        self.open_symbol_picker(symbols);
+    }
+
+    fn open_symbol_picker(&mut self, symbols: Vec<Symbol>) {
+        if symbols.is_empty() {
+            self.error("No symbols found");
+            return;
+        }
+        self.status_msg.clear();
+        let items = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i, s.name.clone(), format!("{}{} {}  :{}", "  ".repeat(s.depth), s.kind, s.name, s.line + 1)))
+            .collect();
+        let positions = symbols.iter().map(|s| (s.line, s.col)).collect();
+        self.picker = Some(Picker::new(PickerKind::Symbols(positions), "Symbols", items, 0));
+    }
+
+    // 선택 창의 Enter
+    pub(crate) fn jump_to_symbol(&mut self, (line, col): (usize, usize)) {
+        self.goto_line(line + 1);
+        self.cx = col;
+        self.clamp_cursor();
+    }
+}
@@ -0,0 +1,296 @@
+// --- Syntax Highlighting ---
+// 파일 종류(filetype)에 맞춰 키워드, 문자열, 주석, 숫자에 색을 입힘    set nosyntax 로 끔
+// 색은 백그라운드 스레드가 계산해 몇 줄씩 나눠 보내고, 화면은 도착한 만큼 바로 씀
+// (큰 파일을 열거나 고치는 동안에도 키 입력은 기다리지 않고, 아직 계산하지 않은 줄은 전의 색으로 보임)
+use crate::EditorConfig;
+use std::ops::Range;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+// 한 번에 보내는 줄 수
+const CHUNK: usize = 500;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Token {
+    Keyword,
+    String,
+    Comment,
+    Number,
+}
+
+impl Token {
+    pub fn color(self) -> &'static str {
+        match self {
+            Token::Keyword => "\x1b[35m",
+            Token::String => "\x1b[32m",
+            Token::Comment => "\x1b[90m",
+            Token::Number => "\x1b[36m",
+        }
+    }
+}
+
+// 줄 안의 바이트 범위와 그 색
+pub type Span = (Range<usize>, Token);
+
+// 파일 종류의 문법: 키워드, 줄 주석, 블록 주석
+struct Language {
+    keywords: &'static [&'static str],
+    line_comment: &'static str,
+    block_comment: Option<(&'static str, &'static str)>,
+    char_literals: bool, // ' 로 감싼 것이 글자 하나일 때만 문자열 (rust 의 'a 수명은 빼고)
+}
+
+fn language(filetype: &str) -> Option<Language> {
+    let c_like = |keywords| Language { keywords, line_comment: "//", block_comment: Some(("/*", "*/")), char_literals: true };
+    Some(match filetype {
+        "rust" => Language {
+            keywords: &[
+                "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+                "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+                "trait", "true", "type", "unsafe", "use", "where", "while", "async", "await", "dyn",
+            ],
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            char_literals: true,
+        },
+        "c" | "cpp" => c_like(&[
+            "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum", "extern", "float",
+            "for", "goto", "if", "int", "long", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+            "union", "unsigned", "void", "while", "class", "namespace", "template", "public", "private", "protected", "new",
+            "delete", "true", "false", "nullptr", "bool",
+        ]),
+        "go" => c_like(&[
+            "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough", "for", "func", "go", "goto",
+            "if", "import", "interface", "map", "package", "range", "return", "select", "struct", "switch", "type", "var", "true",
+            "false", "nil",
+        ]),
+        "javascript" | "typescript" => Language {
+            keywords: &[
+                "break", "case", "catch", "class", "const", "continue", "default", "delete", "do", "else", "export", "extends",
+                "false", "finally", "for", "function", "if", "import", "in", "instanceof", "let", "new", "null", "return", "switch",
+                "this", "throw", "true", "try", "typeof", "var", "void", "while", "async", "await", "interface", "type",
+            ],
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            char_literals: false,
+        },
+        "python" => Language {
+            keywords: &[
+                "and", "as", "assert", "break", "class", "continue", "def", "del", "elif", "else", "except", "False", "finally",
+                "for", "from", "global", "if", "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise",
+                "return", "True", "try", "while", "with", "yield",
+            ],
+            line_comment: "#",
+            block_comment: None,
+            char_literals: false,
+        },
+        "lua" => Language {
+            keywords: &[
+                "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in", "local", "nil", "not", "or",
+                "repeat", "return", "then", "true", "until", "while",
+            ],
+            line_comment: "--",
+            block_comment: Some(("--[[", "]]")),
+            char_literals: false,
+        },
+        "sh" => Language {
+            keywords: &[
+                "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done", "case", "esac", "in", "function", "return",
+                "local", "export",
+            ],
+            line_comment: "#",
+            block_comment: None,
+            char_literals: false,
+        },
+        "toml" | "yaml" | "make" | "dockerfile" => Language { keywords: &[], line_comment: "#", block_comment: None, char_literals: false },
+        _ => return None,
+    })
+}
+
+// 한 줄의 색들, in_comment 는 블록 주석 안에서 시작하는지 (줄 끝에서의 값으로 바꿈)
+fn highlight_line(lang: &Language, line: &str, in_comment: &mut bool) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < line.len() {
+        let rest = &line[i..];
+        if *in_comment {
+            let close = lang.block_comment.map_or("", |(_, close)| close);
+            let end = match rest.find(close) {
+                Some(e) => {
+                    *in_comment = false;
+                    i + e + close.len()
+                }
+                None => line.len(),
+            };
+            spans.push((i..end, Token::Comment));
+            i = end;
+            continue;
+        }
+        if let Some((open, _)) = lang.block_comment
+            && rest.starts_with(open)
+        {
+            *in_comment = true;
+            spans.push((i..i + open.len(), Token::Comment));
+            i += open.len();
+            continue;
+        }
+        if !lang.line_comment.is_empty() && rest.starts_with(lang.line_comment) {
+            spans.push((i..line.len(), Token::Comment));
+            break;
+        }
+        let c = rest.chars().next().unwrap_or(' ');
+        if c == '"' || c == '`' || (c == '\'' && (!lang.char_literals || char_literal(rest))) {
+            let mut end = i + 1;
+            while end < line.len() && bytes[end] != c as u8 {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(line.len());
+            spans.push((i..end, Token::String));
+            i = end;
+            continue;
+        }
+        if c.is_alphanumeric() || c == '_' {
+            let len = rest.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(rest.len());
+            let word = &rest[..len];
+            if c.is_ascii_digit() {
+                spans.push((i..i + len, Token::Number));
+            } else if lang.keywords.contains(&word) {
+                spans.push((i..i + len, Token::Keyword));
+            }
+            i += len;
+            continue;
+        }
+        i += c.len_utf8();
+    }
+    // 블록 주석이 여러 조각으로 나뉘었으면 하나로 합침
+    spans.dedup_by(|b, a| {
+        let merge = a.1 == Token::Comment && b.1 == Token::Comment && a.0.end == b.0.start;
+        if merge {
+            a.0.end = b.0.end;
+        }
+        merge
+    });
+    spans
+}
+
+// 'x', '\n', '\'' 처럼 글자 하나를 감쌌는지
+fn char_literal(rest: &str) -> bool {
+    let mut chars = rest.chars().skip(1);
+    match chars.next() {
+        Some('\\') => rest.get(3..).and_then(|r| r.find('\'')).is_some_and(|e| e < 10),
+        Some(_) => chars.next() == Some('\''),
+        None => false,
+    }
+}
+
+struct Job {
+    ticket: u64,
+    filetype: String,
+    lines: Vec<String>,
+}
+
+// 버퍼의 색을 백그라운드 스레드에서 계산해 CHUNK 줄씩 받는다
+pub(crate) struct Highlighter {
+    tx: Sender<Job>,
+    rx: Receiver<(u64, usize, Vec<Vec<Span>>)>, // (요청 번호, 첫 줄, 줄들의 색)
+    lines: Vec<Vec<Span>>,
+    ticket: u64,
+    requested: Option<(u64, Option<String>)>, // 마지막으로 요청한 버퍼 버전과 파일 종류
+}
+
+impl Highlighter {
+    pub fn new() -> Self {
+        let (tx, job_rx) = mpsc::channel::<Job>();
+        let (result_tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut next = None;
+            loop {
+                let Some(job) = next.take().or_else(|| job_rx.recv().ok()) else { return };
+                // 블록 주석은 줄을 넘어 이어지므로 처음부터 차례로
+                let Job { ticket, filetype, lines } = job;
+                let Some(lang) = language(&filetype) else {
+                    if result_tx.send((ticket, 0, vec![Vec::new(); lines.len()])).is_err() {
+                        return;
+                    }
+                    continue;
+                };
+                let mut in_comment = false;
+                for (n, chunk) in lines.chunks(CHUNK).enumerate() {
+                    // 더 새 요청이 오면 지금 것은 버리고 가장 최근 것부터
+                    if let Some(newer) = job_rx.try_iter().last() {
+                        next = Some(newer);
+                        break;
+                    }
+                    let spans = chunk.iter().map(|line| highlight_line(&lang, line, &mut in_comment)).collect();
+                    if result_tx.send((ticket, n * CHUNK, spans)).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Highlighter { tx, rx, lines: Vec::new(), ticket: 0, requested: None }
+    }
+
+    // 다른 버퍼로 바꿨을 때
+    pub fn reset(&mut self) {
+        self.lines.clear();
+        self.requested = None;
+    }
+
+    pub fn needs_update(&self, version: u64, filetype: Option<&str>) -> bool {
+        self.requested.as_ref().is_none_or(|(v, f)| *v != version || f.as_deref() != filetype)
+    }
+
+    pub fn request(&mut self, version: u64, filetype: Option<&str>, lines: Vec<String>) {
+        self.requested = Some((version, filetype.map(String::from)));
+        self.ticket += 1;
+        let Some(filetype) = filetype else {
+            self.lines.clear();
+            return;
+        };
+        // 아직 받지 못한 줄은 전의 색을 쓰되 줄 수는 맞춤
+        self.lines.resize(lines.len(), Vec::new());
+        let _ = self.tx.send(Job { ticket: self.ticket, filetype: filetype.to_string(), lines });
+    }
+
+    // 도착한 색을 반영하고, 받은 것이 있으면 true
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((ticket, start, spans)) = self.rx.try_recv() {
+            if ticket == self.ticket {
+                let end = (start + spans.len()).min(self.lines.len());
+                for (slot, spans) in self.lines[start.min(end)..end].iter_mut().zip(spans) {
+                    *slot = spans;
+                }
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn line(&self, line: usize) -> &[Span] {
+        self.lines.get(line).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl EditorConfig {
+    // 버퍼가 바뀌었으면 다시 계산을 맡기고 도착한 색을 반영, 받은 것이 있으면 true
+    pub(crate) fn update_highlight(&mut self) -> bool {
+        let filetype = self.buffer_options.filetype.as_deref().filter(|_| self.options.syntax);
+        if self.syntax.needs_update(self.buffer.version, filetype) {
+            let lines = self.buffer.rows.iter().map(|r| r.content.clone()).collect();
+            let had_colors = !self.syntax.lines.is_empty();
+            self.syntax.request(self.buffer.version, filetype, lines);
+            if filetype.is_none() {
+                return had_colors;
+            }
+        }
+        self.syntax.poll()
+    }
+
+    /// 줄의 색들 (바이트 범위, 아직 계산하지 않았거나 색이 없으면 빈 것)
+    pub fn syntax_spans(&self, line: usize) -> &[Span] {
+        self.syntax.line(line)
+    }
+}
@@ -0,0 +1,98 @@
+// --- Tabs ---
+// 입력 모드의 Tab 과 Backspace 가 들여쓰기 설정을 따름 (>>, <<, Ctrl-T, Ctrl-D 는 shiftwidth, lib.rs)
+//   expandtab           Tab 이 다음 칸 경계까지 공백을 넣음
+//   softtabstop=4       칸 경계를 tabstop 대신 4 칸마다로 (음수면 shiftwidth 를 씀),
+//                       noexpandtab 이면 커서 앞의 공백을 탭과 남는 공백으로 다시 씀
+//                       Backspace 는 커서 앞의 공백을 이전 칸 경계까지 한 번에 지움
+// 파일 종류마다 다르게 하려면 autocmd FileType python set sts=4 처럼
+//
+// :[범위]retab [새 tabstop]   범위 (없으면 버퍼 전체) 줄들의 들여쓰기를 같은 폭의 공백으로 (expandtab)
+//                             또는 탭과 남는 공백으로 (noexpandtab) 다시 씀, 새 tabstop 을 주면 폭은 그대로 두고 tabstop 을 바꿈
+use crate::EditorConfig;
+use crate::options::BufferOptions;
+
+// 탭을 tabstop 칸으로 센 화면 폭
+fn width(text: &str, tabstop: usize) -> usize {
+    text.chars().fold(0, |w, c| if c == '\t' { (w / tabstop + 1) * tabstop } else { w + 1 })
+}
+
+impl EditorConfig {
+    // Tab 이나 Backspace 가 멈출 칸 간격, softtabstop 이 꺼져 있으면 None
+    fn soft_stop(&self) -> Option<usize> {
+        let BufferOptions { softtabstop, shiftwidth, .. } = self.buffer_options;
+        match softtabstop {
+            0 => None,
+            n if n < 0 => Some(shiftwidth),
+            n => Some(n as usize),
+        }
+    }
+
+    // 입력 모드의 Tab
+    pub(crate) fn insert_tab(&mut self) {
+        let BufferOptions { tabstop, expandtab, .. } = self.buffer_options;
+        let stop = match (self.soft_stop(), expandtab) {
+            (None, false) => return self.insert_char('\t'),
+            (stop, _) => stop.unwrap_or(tabstop),
+        };
+        let line = &self.buffer.rows[self.cy].content;
+        // noexpandtab 이면 커서 앞의 공백부터 다시 씀
+        let start = if expandtab { self.cx } else { line[..self.cx].trim_end_matches([' ', '\t']).len() };
+        let mut col = width(&line[..start], tabstop);
+        let target = (width(&line[..self.cx], tabstop) / stop + 1) * stop;
+        let mut fill = String::new();
+        while !expandtab && (col / tabstop + 1) * tabstop <= target {
+            fill.push('\t');
+            col = (col / tabstop + 1) * tabstop;
+        }
+        fill.push_str(&" ".repeat(target - col));
+        self.buffer.rows[self.cy].content.replace_range(start..self.cx, &fill);
+        self.cx = start + fill.len();
+        self.buffer.touch();
+    }
+
+    // 입력 모드의 Backspace
+    pub(crate) fn backspace(&mut self) {
+        let Some(stop) = self.soft_stop() else { return self.delete_char() };
+        let line = &self.buffer.rows[self.cy].content[..self.cx];
+        let col = width(line, self.buffer_options.tabstop);
+        let spaces = line.len() - line.trim_end_matches(' ').len();
+        // 지울 공백이 두 칸 이상일 때만 이전 칸 경계까지
+        let remove = spaces.min(col - col.saturating_sub(1) / stop * stop);
+        if remove < 2 {
+            return self.delete_char();
+        }
+        self.delete_before(self.cx - remove);
+    }
+
+    // :retab, start..=end 줄 (0부터)
+    pub(crate) fn retab(&mut self, start: usize, end: usize, args: &str) {
+        let old = self.buffer_options.tabstop;
+        let tabstop = match args {
+            "" => old,
+            _ => match args.parse() {
+                Ok(n) if n > 0 => n,
+                _ => return self.error(format!("Invalid tabstop: {}", args)),
+            },
+        };
+        let expandtab = self.buffer_options.expandtab;
+        let end = end.min(self.buffer.rows.len() - 1);
+        let mut changed = false;
+        for row in &mut self.buffer.rows[start..=end] {
+            let len = row.content.len() - row.content.trim_start_matches([' ', '\t']).len();
+            let indent = width(&row.content[..len], old);
+            let prefix = match expandtab {
+                true => " ".repeat(indent),
+                false => "\t".repeat(indent / tabstop) + &" ".repeat(indent % tabstop),
+            };
+            if prefix != row.content[..len] {
+                row.content.replace_range(..len, &prefix);
+                changed = true;
+            }
+        }
+        self.buffer_options.tabstop = tabstop;
+        if changed {
+            self.buffer.touch();
+            self.clamp_cursor();
+        }
+    }
+}
@@ -0,0 +1,204 @@
+// --- Tags ---
+// ctags 가 만든 tags 파일로 이름의 정의를 찾아 감 (tags 설정의 파일들을 차례로, ./ 로 시작하면 지금 파일의 디렉터리에서)
+//   Ctrl-]          커서 아래 낱말의 정의로 (여러 개면 첫 번째)
+//   Ctrl-T          태그 스택에서 하나를 꺼내 뛰기 전의 자리로 돌아감
+//   :tag 이름       이름의 정의로
+//   :tselect 이름   같은 이름의 정의들을 목록 창에 보여주고 Enter 로 고름 (이름이 없으면 마지막 태그)
+// tags 파일 한 줄: 이름<Tab>파일<Tab>주소;"<Tab>종류 ... (주소는 줄 번호나 /^찾을 줄$/, 파일은 tags 파일의 디렉터리 기준)
+// 찾을 줄로 된 주소는 파일이 바뀌어 그 줄이 없으면 파일의 처음으로
+use crate::abbrev::is_word_char;
+use crate::{EditorConfig, PanelKind};
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone)]
+enum Address {
+    Line(usize),     // 1부터
+    Pattern(String), // ^ 와 $ 를 뗀 줄 내용
+}
+
+#[derive(Clone)]
+struct Tag {
+    file: String,
+    address: Address,
+    kind: String,
+}
+
+pub struct TagStack {
+    stack: Vec<(String, Option<String>, (usize, usize))>, // (태그 이름, 뛰기 전의 파일, 커서)
+    matches: Vec<Tag>,                                    // :tselect 목록 창의 항목들
+    last: Option<String>,                                 // 마지막으로 찾은 이름
+}
+
+impl TagStack {
+    pub fn new() -> Self {
+        TagStack { stack: Vec::new(), matches: Vec::new(), last: None }
+    }
+}
+
+// ex 주소 /^...$/ 나 ?^...$? 에서 찾을 줄 내용
+fn parse_pattern(address: &str) -> Option<String> {
+    let delim = address.chars().next().filter(|&c| c == '/' || c == '?')?;
+    let body = address[1..].strip_suffix(delim).unwrap_or(&address[1..]);
+    let body = body.strip_prefix('^').unwrap_or(body);
+    let body = body.strip_suffix('$').unwrap_or(body);
+    let mut text = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.extend(chars.next()),
+            c => text.push(c),
+        }
+    }
+    Some(text)
+}
+
+// tags 파일 한 줄, 이름이 name 이 아니면 None
+fn parse_line(line: &str, name: &str, dir: &Path) -> Option<Tag> {
+    let mut fields = line.splitn(3, '\t');
+    if fields.next()? != name {
+        return None;
+    }
+    let file = fields.next()?;
+    let rest = fields.next()?;
+    let (address, extra) = rest.split_once(";\"").unwrap_or((rest, ""));
+    let address = match address.trim().parse() {
+        Ok(line) => Address::Line(line),
+        Err(_) => Address::Pattern(parse_pattern(address.trim())?),
+    };
+    let kind = extra.split('\t').map(|f| f.strip_prefix("kind:").unwrap_or(f)).find(|f| !f.is_empty() && !f.contains(':'));
+    let file = if Path::new(file).is_absolute() || dir == Path::new("") { file.to_string() } else { dir.join(file).to_string_lossy().into() };
+    Some(Tag { file, address, kind: kind.unwrap_or("").to_string() })
+}
+
+impl EditorConfig {
+    // tags 설정의 파일들에서 name 의 정의들
+    fn find_tags(&self, name: &str) -> Vec<Tag> {
+        let here = self.filename.as_deref().and_then(|f| Path::new(f).parent()).unwrap_or(Path::new(""));
+        let mut found = Vec::new();
+        for entry in self.options.tags.split(',').filter(|e| !e.is_empty()) {
+            let path = match entry.strip_prefix("./") {
+                Some(rest) => here.join(rest),
+                None => Path::new(entry).to_path_buf(),
+            };
+            let Ok(text) = fs::read_to_string(&path) else { continue };
+            let dir = path.parent().unwrap_or(Path::new(""));
+            found.extend(text.lines().filter(|l| !l.starts_with("!_TAG_")).filter_map(|l| parse_line(l, name, dir)));
+        }
+        found
+    }
+
+    /// 커서 아래 낱말 (Ctrl-], K)
+    pub(crate) fn identifier_under_cursor(&self) -> Option<String> {
+        let line = &self.buffer.rows[self.cy].content;
+        let col = self.cx.min(line.len());
+        let start = line[..col].char_indices().rev().take_while(|&(_, c)| is_word_char(c)).last().map_or(col, |(i, _)| i);
+        let end = line[col..].char_indices().find(|&(_, c)| !is_word_char(c)).map_or(line.len(), |(i, _)| col + i);
+        (start < end).then(|| line[start..end].to_string())
+    }
+
+    // Ctrl-]
+    pub(crate) fn tag_under_cursor(&mut self) {
+        match self.identifier_under_cursor() {
+            Some(name) => self.tag(&name),
+            None => self.error("No identifier under cursor"),
+        }
+    }
+
+    // :tag 이름
+    pub(crate) fn tag(&mut self, name: &str) {
+        if name.is_empty() {
+            self.status_msg = "Usage: :tag <name>".into();
+            return;
+        }
+        let tags = self.find_tags(name);
+        self.tags.last = Some(name.to_string());
+        let Some(first) = tags.first().cloned() else {
+            self.error(format!("Tag not found: {}", name));
+            return;
+        };
+        if self.jump_to_tag(name, &first) && tags.len() > 1 {
+            self.status_msg = format!("tag 1 of {} (:tselect {} to choose)", tags.len(), name);
+        }
+    }
+
+    // :tselect [이름]
+    pub(crate) fn tselect(&mut self, name: &str) {
+        let Some(name) = (if name.is_empty() { self.tags.last.clone() } else { Some(name.to_string()) }) else {
+            self.error("No previous tag");
+            return;
+        };
+        let tags = self.find_tags(&name);
+        self.tags.last = Some(name.clone());
+        if tags.is_empty() {
+            self.error(format!("Tag not found: {}", name));
+            return;
+        }
+        let lines = tags
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| {
+                let address = match &tag.address {
+                    Address::Line(line) => format!("line {}", line),
+                    Address::Pattern(text) => text.trim().to_string(),
+                };
+                format!("{:>3} {:<2} {}  {}", i + 1, tag.kind, tag.file, address)
+            })
+            .collect();
+        self.tags.matches = tags;
+        self.open_panel(PanelKind::Tags, name, lines, 0);
+    }
+
+    // 목록 창의 Enter
+    pub(crate) fn jump_to_listed_tag(&mut self, selected: usize) {
+        let Some(tag) = self.tags.matches.get(selected).cloned() else { return };
+        let Some(panel) = self.panel.as_ref() else { return };
+        let name = panel.title.clone();
+        if self.jump_to_tag(&name, &tag) {
+            self.close_panel();
+        }
+    }
+
+    // 지금 자리를 스택에 넣고 정의로 감
+    fn jump_to_tag(&mut self, name: &str, tag: &Tag) -> bool {
+        let from = (name.to_string(), self.filename.clone(), (self.cy, self.cx));
+        if !self.jump_to(&tag.file, 1, 1) {
+            return false;
+        }
+        self.tags.stack.push(from);
+        let line = match &tag.address {
+            Address::Line(line) => Some(*line),
+            // 줄이 길면 ctags 가 앞부분만 적으므로 같은 줄이 없으면 그렇게 시작하는 줄
+            Address::Pattern(text) => {
+                let rows = &self.buffer.rows;
+                rows.iter().position(|r| r.content == *text).or_else(|| rows.iter().position(|r| r.content.starts_with(text.as_str()))).map(|i| i + 1)
+            }
+        };
+        match line {
+            Some(line) => self.goto_line(line),
+            None => self.status_msg = format!("{}: definition moved, showing the start of {}", name, tag.file),
+        }
+        true
+    }
+
+    // Ctrl-T
+    pub(crate) fn pop_tag(&mut self) {
+        let Some((name, filename, (line, col))) = self.tags.stack.pop() else {
+            self.error("Tag stack is empty");
+            return;
+        };
+        let moved = match &filename {
+            Some(filename) => self.jump_to(filename, line + 1, col + 1),
+            None => {
+                (self.cy, self.cx) = (line, col);
+                self.clamp_cursor();
+                true
+            }
+        };
+        if !moved {
+            self.tags.stack.push((name, filename, (line, col)));
+            return;
+        }
+        self.status_msg = format!("Back from {} ({} left on the tag stack)", name, self.tags.stack.len());
+    }
+}
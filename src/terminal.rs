@@ -0,0 +1,524 @@
+// --- Terminal Backends ---
+// 에디터는 Terminal 트레잇만 보고 키를 읽고 화면을 그린다.
+//   AnsiTerminal    실제 터미널 (termios 로우 모드, 표준 입력/출력)
+//   MemoryTerminal  정해진 키를 돌려주고 출력을 가상 화면에 그리는 테스트용 구현
+// 로우 모드에 들어갈 때 SIGTERM / SIGHUP 처리기를 걸어 두고, 신호가 오면 read_event 가 Input::Signal 을 돌려줌
+// (처리기는 표시만 남기고, 복구 파일을 쓰고 로우 모드를 푸는 일은 이벤트 루프와 Drop 이 함)
+// 표준 입력을 파이프로 받은 경우 (vii -) AnsiTerminal::tty 로 /dev/tty 에서 키를 읽음
+// 편집 화면은 대체 화면에 그려서 끝나면 원래 화면이 돌아옴
+// install_panic_hook: 패닉하면 메시지를 찍기 전에 로우 모드와 대체 화면을 먼저 되돌림
+use crate::keys::{self, KeyEvent};
+use libc::{ECHO, ICANON, STDIN_FILENO, STDOUT_FILENO, c_int, TCSAFLUSH, TIOCGWINSZ, ioctl, tcgetattr, tcsetattr, termios, winsize};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Write};
+use std::fmt;
+use std::mem;
+use std::os::fd::AsRawFd;
+use std::panic;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+pub enum Input {
+    Key(KeyEvent),
+    Timeout, // 기다리는 동안 입력이 없음
+    Eof,     // 더 읽을 입력이 없음
+    Signal(Signal),
+    Paste(String), // 붙여넣은 글 (bracketed paste, 줄바꿈은 \n)
+}
+
+/// 편집기를 끝내는 신호
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signal {
+    Term, // kill 등으로 끝내라는 요청
+    Hup,  // 터미널이 닫힘 (SSH 연결이 끊김 등)
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Signal::Term => "SIGTERM",
+            Signal::Hup => "SIGHUP",
+        })
+    }
+}
+
+// 처리기가 받은 신호 번호, 0 이면 없음
+static RECEIVED: AtomicI32 = AtomicI32::new(0);
+
+extern "C" fn on_signal(signal: libc::c_int) {
+    RECEIVED.store(signal, Ordering::SeqCst);
+}
+
+// SA_RESTART 없이 걸어서 기다리던 poll 이 바로 깨어나게 함
+fn install_signal_handlers() {
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+        for signal in [libc::SIGTERM, libc::SIGHUP] {
+            libc::sigaction(signal, &action, std::ptr::null_mut());
+        }
+    }
+}
+
+// 로우 모드를 풀 때 되돌릴 (입력 fd, 원래 termios, kitty 프로토콜을 켰는지), 패닉 훅이 씀
+static RAW_STATE: Mutex<Option<(c_int, termios, bool)>> = Mutex::new(None);
+
+// 로우 모드에서 나갈 때 보낼 시퀀스: kitty 프로토콜 끄기, 창 제목 되돌리기, 붙여넣기 알림 끄기, 커서 보이기, 대체 화면에서 나가기
+fn leave_sequence(kitty: bool) -> String {
+    format!("{}\x1b[23;0t\x1b[?2004l\x1b[?25h\x1b[?1049l", if kitty { "\x1b[<u" } else { "" })
+}
+
+/// 패닉 메시지를 찍기 전에 터미널을 되돌리는 훅을 검 (로우 모드가 아니면 아무것도 하지 않음)
+pub fn install_panic_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Ok(mut state) = RAW_STATE.try_lock()
+            && let Some((input, orig, kitty)) = state.take()
+        {
+            let _ = io::stdout().write_all(leave_sequence(kitty).as_bytes());
+            let _ = io::stdout().flush();
+            unsafe { tcsetattr(input, TCSAFLUSH, &orig) };
+        }
+        default(info);
+    }));
+}
+
+fn take_signal() -> Option<Signal> {
+    match RECEIVED.swap(0, Ordering::SeqCst) {
+        libc::SIGTERM => Some(Signal::Term),
+        libc::SIGHUP => Some(Signal::Hup),
+        _ => None,
+    }
+}
+
+pub trait Terminal {
+    /// 한 글자씩, 입력을 화면에 되풀이하지 않고 읽도록 전환
+    fn enable_raw_mode(&mut self) -> io::Result<()>;
+    /// 로우 모드에 들어가기 전 상태로 되돌림
+    fn disable_raw_mode(&mut self) -> io::Result<()>;
+    /// (열, 줄)
+    fn size(&self) -> (u16, u16);
+    /// 출력(ANSI 이스케이프 포함)을 내보내고 바로 반영
+    fn write(&mut self, data: &[u8]) -> io::Result<()>;
+    /// 최대 timeout_ms 동안 키 하나를 기다림 (이스케이프 시퀀스는 키 하나로 해석)
+    fn read_event(&mut self, timeout_ms: i32) -> io::Result<Input>;
+}
+
+pub struct AnsiTerminal {
+    _tty: Option<File>, // input 이 /dev/tty 이면 닫히지 않도록 가지고 있음
+    input: c_int,      // 키를 읽는 fd
+    orig_termios: Option<termios>,
+    pending: Vec<u8>, // 읽었지만 아직 키로 바꾸지 않은 바이트
+    eof: bool,
+    kitty: bool, // kitty 키보드 프로토콜을 켰는지 (끌 때 되돌림)
+}
+
+// ESC 뒤의 바이트를 기다리는 시간: 이 안에 오면 Alt 조합이나 이스케이프 시퀀스, 아니면 Esc 키
+const ESC_TIMEOUT_MS: i32 = 25;
+// 터미널 응답을 기다리는 시간 (10번까지)
+const KITTY_QUERY_MS: i32 = 50;
+// 붙여넣은 글을 감싸는 시퀀스와, 시작을 받은 뒤 나머지를 기다리는 시간
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+const PASTE_WAIT_MS: i32 = 100;
+// kitty 프로토콜 플래그: 1 모호한 키 구분 (Ctrl-I와 Tab, Shift-Enter 등), 2 뗀 이벤트도 보고
+const KITTY_FLAGS: u8 = 1 | 2;
+
+// 버퍼에서 "CSI ? ... u"(kitty 지원)와 "CSI ? ... c"(장치 속성) 응답을 빼냄,
+// 사용자가 그 사이에 누른 키는 그대로 남김. (kitty 응답이 있었는지, 장치 속성 응답이 왔는지)
+fn take_replies(pending: &mut Vec<u8>) -> (bool, bool) {
+    let (mut kitty, mut done) = (false, false);
+    let mut i = 0;
+    while let Some(start) = pending[i..].windows(3).position(|w| w == b"\x1b[?").map(|p| p + i) {
+        let Some(end) = pending[start + 3..].iter().position(|b| (0x40..=0x7e).contains(b)).map(|p| p + start + 3) else {
+            break;
+        };
+        match pending[end] {
+            b'u' => kitty = true,
+            b'c' => done = true,
+            _ => {
+                i = end + 1;
+                continue;
+            }
+        }
+        pending.drain(start..=end);
+        i = start;
+    }
+    (kitty, done)
+}
+
+impl AnsiTerminal {
+    pub fn new() -> Self {
+        AnsiTerminal { _tty: None, input: STDIN_FILENO, orig_termios: None, pending: Vec::new(), eof: false, kitty: false }
+    }
+
+    /// 표준 입력 대신 제어 터미널(/dev/tty)에서 키를 읽음
+    pub fn tty() -> io::Result<Self> {
+        let tty = File::open("/dev/tty")?;
+        let mut term = AnsiTerminal::new();
+        term.input = tty.as_raw_fd();
+        term._tty = Some(tty);
+        Ok(term)
+    }
+
+    // 쌓인 바이트에서 키 하나를 꺼냄. 시퀀스가 잘려 있으면 ESC_TIMEOUT_MS 동안 나머지를 기다리고,
+    // 그래도 안 오면 온 만큼만 해석 (ESC 하나면 Esc, 바로 뒤에 글자가 왔으면 Alt 조합)
+    fn next_key(&mut self) -> io::Result<Option<KeyEvent>> {
+        while !self.pending.is_empty() {
+            if let Some((key, len)) = keys::decode(&self.pending) {
+                self.pending.drain(..len);
+                match key {
+                    Some(key) => return Ok(Some(key)),
+                    None => continue, // 키를 뗀 이벤트, 늦게 온 터미널 응답 등은 버림
+                }
+            }
+            if !self.fill(ESC_TIMEOUT_MS)? {
+                let (key, len) = keys::decode_partial(&self.pending);
+                self.pending.drain(..len);
+                return Ok(Some(key));
+            }
+        }
+        Ok(None)
+    }
+
+    // 붙여넣기 시작 시퀀스 뒤의 글을 끝 시퀀스까지 모음 (끝이 오지 않으면 받은 데까지)
+    fn read_paste(&mut self) -> io::Result<String> {
+        self.pending.drain(..PASTE_START.len());
+        let end = loop {
+            if let Some(end) = self.pending.windows(PASTE_END.len()).position(|w| w == PASTE_END) {
+                break end;
+            }
+            if !self.fill(PASTE_WAIT_MS)? {
+                break self.pending.len();
+            }
+        };
+        let text: Vec<u8> = self.pending.drain(..end).collect();
+        self.pending.drain(..PASTE_END.len().min(self.pending.len()));
+        Ok(String::from_utf8_lossy(&text).replace("\r\n", "\n").replace('\r', "\n"))
+    }
+
+    // kitty 키보드 프로토콜 지원 여부를 물어봄: "CSI ? u"에 답하는 터미널만 지원.
+    // 뒤에 보내는 장치 속성 요청(CSI c)에는 모든 터미널이 답하므로 그 답이 오면 그만 기다림
+    fn query_kitty(&mut self) -> io::Result<bool> {
+        self.write(b"\x1b[?u\x1b[c")?;
+        let mut supported = false;
+        for _ in 0..10 {
+            if !self.fill(KITTY_QUERY_MS)? {
+                continue;
+            }
+            let (kitty, done) = take_replies(&mut self.pending);
+            supported |= kitty;
+            if done {
+                break;
+            }
+        }
+        Ok(supported)
+    }
+
+    // 최대 timeout_ms 동안 기다렸다가 들어온 바이트를 pending에 덧붙임, 읽은 게 없으면 false
+    fn fill(&mut self, timeout_ms: i32) -> io::Result<bool> {
+        let mut fds = libc::pollfd { fd: self.input, events: libc::POLLIN, revents: 0 };
+        if unsafe { libc::poll(&mut fds, 1, timeout_ms) } <= 0 {
+            return Ok(false);
+        }
+        // std의 버퍼를 거치면 poll이 남은 입력을 보지 못하므로 fd에서 직접 읽음
+        // (이스케이프 시퀀스는 보통 한 번에 도착하므로 있는 만큼 읽음)
+        let mut buf = [0u8; 64];
+        match unsafe { libc::read(self.input, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) } {
+            n if n > 0 => {
+                self.pending.extend_from_slice(&buf[..n as usize]);
+                Ok(true)
+            }
+            0 => {
+                self.eof = true;
+                Ok(false)
+            }
+            _ => match io::Error::last_os_error() {
+                e if e.kind() == io::ErrorKind::Interrupted => Ok(false),
+                e => Err(e),
+            },
+        }
+    }
+}
+
+impl Default for AnsiTerminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Terminal for AnsiTerminal {
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        unsafe {
+            let mut raw: termios = mem::zeroed();
+            if tcgetattr(self.input, &mut raw) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            let orig_termios = raw;
+            raw.c_lflag &= !(ECHO | ICANON);
+            if tcsetattr(self.input, TCSAFLUSH, &raw) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            self.orig_termios = Some(orig_termios);
+        }
+        install_signal_handlers();
+        // 지원하는 터미널에서만 켜고, 아니면 지금까지처럼 바이트를 해석
+        if self.query_kitty()? {
+            self.write(format!("\x1b[>{}u", KITTY_FLAGS).as_bytes())?;
+            self.kitty = true;
+        }
+        if let (Ok(mut state), Some(orig)) = (RAW_STATE.lock(), self.orig_termios) {
+            *state = Some((self.input, orig, self.kitty));
+        }
+        // 지금의 창 제목을 터미널의 제목 스택에 넣어 두고 끝날 때 되돌림, 대체 화면으로, 붙여넣기를 감싸서 받음
+        self.write(b"\x1b[22;0t\x1b[?1049h\x1b[?2004h")
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        if let Ok(mut state) = RAW_STATE.lock() {
+            *state = None;
+        }
+        if self.orig_termios.is_some() {
+            self.write(leave_sequence(self.kitty).as_bytes())?;
+            self.kitty = false;
+        }
+        if let Some(orig) = self.orig_termios.take() && unsafe { tcsetattr(self.input, TCSAFLUSH, &orig) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> (u16, u16) {
+        unsafe {
+            let mut ws: winsize = mem::zeroed();
+            if ioctl(STDOUT_FILENO, TIOCGWINSZ, &mut ws) == -1 || ws.ws_col == 0 {
+                return (80, 24);
+            }
+            (ws.ws_col, ws.ws_row)
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(data)?;
+        stdout.flush()
+    }
+
+    fn read_event(&mut self, timeout_ms: i32) -> io::Result<Input> {
+        if let Some(signal) = take_signal() {
+            return Ok(Input::Signal(signal));
+        }
+        if self.pending.is_empty() && !self.fill(timeout_ms)? {
+            if let Some(signal) = take_signal() {
+                return Ok(Input::Signal(signal));
+            }
+            return Ok(if self.eof { Input::Eof } else { Input::Timeout });
+        }
+        if self.pending.starts_with(PASTE_START) {
+            return self.read_paste().map(Input::Paste);
+        }
+        Ok(self.next_key()?.map_or(Input::Timeout, Input::Key))
+    }
+}
+
+impl Drop for AnsiTerminal {
+    fn drop(&mut self) {
+        let _ = self.disable_raw_mode();
+    }
+}
+
+// 에디터가 쓰는 만큼의 ANSI 시퀀스(커서 이동, 지우기, 반전)만 해석하는 가상 터미널
+pub struct MemoryTerminal {
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<char>>,
+    inverted: Vec<Vec<bool>>, // 반전 (SGR 7) 으로 그린 칸
+    reverse: bool,            // 지금 반전으로 쓰는 중인지
+    input: VecDeque<Input>,
+    paused: bool,           // 바로 앞에 키를 돌려줌 (다음 읽기는 사람이 멈춘 것처럼 Timeout)
+    signal: Option<Signal>, // 키를 다 쓴 뒤 돌려줄 신호
+    pub cursor: (usize, usize), // (줄, 열), 0부터 시작
+    pub raw: bool,
+    pub title: String, // OSC 0/2로 받은 창 제목
+}
+
+impl MemoryTerminal {
+    pub fn new(cols: u16, rows: u16) -> Self {
+        let (cols, rows) = (cols as usize, rows as usize);
+        MemoryTerminal {
+            cols,
+            rows,
+            cells: vec![vec![' '; cols]; rows],
+            inverted: vec![vec![false; cols]; rows],
+            reverse: false,
+            input: VecDeque::new(),
+            paused: false,
+            signal: None,
+            cursor: (0, 0),
+            raw: false,
+            title: String::new(),
+        }
+    }
+
+    // 다음 read_event에서 돌려줄 키들, 다 쓰면 Eof
+    pub fn push_keys(&mut self, keys: &[KeyEvent]) {
+        self.input.extend(keys.iter().map(|&k| Input::Key(k)));
+    }
+
+    // 다음 read_event 에서 붙여넣은 글로 돌려줌
+    pub fn push_paste(&mut self, text: &str) {
+        self.input.push_back(Input::Paste(text.to_string()));
+    }
+
+    // 넣어 둔 키를 다 쓴 뒤 신호를 받은 것처럼 함
+    pub fn push_signal(&mut self, signal: Signal) {
+        self.signal = Some(signal);
+    }
+
+    // 한 줄의 내용 (오른쪽 공백 제거)
+    pub fn line(&self, y: usize) -> String {
+        self.cells[y].iter().collect::<String>().trim_end().to_string()
+    }
+
+    pub fn text(&self) -> String {
+        (0..self.rows).map(|y| self.line(y)).collect::<Vec<_>>().join("\n")
+    }
+
+    // 한 줄에서 반전으로 그린 글자들만
+    pub fn inverted(&self, y: usize) -> String {
+        self.cells[y].iter().zip(&self.inverted[y]).filter(|&(_, &inverted)| inverted).map(|(&c, _)| c).collect()
+    }
+
+    fn put(&mut self, c: char) {
+        let (y, x) = self.cursor;
+        if y < self.rows && x < self.cols {
+            self.cells[y][x] = c;
+            self.inverted[y][x] = self.reverse;
+        }
+        self.cursor.1 += 1;
+    }
+
+    fn clear_line(&mut self) {
+        let (y, x) = self.cursor;
+        if y < self.rows {
+            for cell in self.cells[y].iter_mut().skip(x) {
+                *cell = ' ';
+            }
+            for inverted in self.inverted[y].iter_mut().skip(x) {
+                *inverted = false;
+            }
+        }
+    }
+
+    // 글자 속성은 반전만 따라감 (색은 무시)
+    fn sgr(&mut self, params: &str) {
+        let mut codes = params.split(';');
+        while let Some(code) = codes.next() {
+            match code {
+                "" | "0" | "27" => self.reverse = false,
+                "7" => self.reverse = true,
+                // 38;5;n, 48;2;r;g;b 같은 색의 인자는 건너뜀
+                "38" | "48" => {
+                    let skip = if codes.next() == Some("2") { 3 } else { 1 };
+                    codes.by_ref().take(skip).for_each(drop);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn csi(&mut self, params: &str, cmd: char) {
+        let nums: Vec<usize> = params.split(';').map(|n| n.parse().unwrap_or(1)).collect();
+        match cmd {
+            'H' => {
+                let row = nums.first().copied().unwrap_or(1).max(1);
+                let col = nums.get(1).copied().unwrap_or(1).max(1);
+                self.cursor = (row - 1, col - 1);
+            }
+            'K' => self.clear_line(),
+            'J' if params == "2" => {
+                self.cells = vec![vec![' '; self.cols]; self.rows];
+                self.inverted = vec![vec![false; self.cols]; self.rows];
+            }
+            'm' => self.sgr(params),
+            _ => {} // 커서 표시(?25h/l) 등은 무시
+        }
+    }
+}
+
+impl Terminal for MemoryTerminal {
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        self.raw = true;
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        self.raw = false;
+        Ok(())
+    }
+
+    fn size(&self) -> (u16, u16) {
+        (self.cols as u16, self.rows as u16)
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        let text = String::from_utf8_lossy(data);
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    match chars.next() {
+                        Some('[') => {}
+                        // OSC: BEL이나 ESC \ 까지
+                        Some(']') => {
+                            let mut osc = String::new();
+                            for c in chars.by_ref() {
+                                match c {
+                                    '\x07' => break,
+                                    '\x1b' => {
+                                        chars.next();
+                                        break;
+                                    }
+                                    c => osc.push(c),
+                                }
+                            }
+                            if let Some(title) = osc.strip_prefix("0;").or_else(|| osc.strip_prefix("2;")) {
+                                self.title = title.to_string();
+                            }
+                            continue;
+                        }
+                        _ => continue,
+                    }
+                    let mut params = String::new();
+                    for c in chars.by_ref() {
+                        if c.is_ascii_alphabetic() {
+                            self.csi(params.trim_start_matches('?'), c);
+                            break;
+                        }
+                        params.push(c);
+                    }
+                }
+                '\r' => self.cursor.1 = 0,
+                '\n' => self.cursor.0 += 1,
+                c => self.put(c),
+            }
+        }
+        Ok(())
+    }
+
+    // 키는 한 번에 하나씩 친 것처럼 돌려줘서 키마다 화면을 그리게 함
+    fn read_event(&mut self, _timeout_ms: i32) -> io::Result<Input> {
+        if mem::take(&mut self.paused) {
+            return Ok(Input::Timeout);
+        }
+        match self.input.pop_front() {
+            Some(input) => {
+                self.paused = true;
+                Ok(input)
+            }
+            None => Ok(self.signal.take().map_or(Input::Eof, Input::Signal)),
+        }
+    }
+}
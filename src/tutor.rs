@@ -0,0 +1,21 @@
+// --- Tutor ---
+// :Tutor, vii --tutor   연습 문제가 든 튜토리얼 (tutor.txt) 을 새 버퍼로 엶
+//   이동, 지우기, 되돌리기, 입력과 바꾸기, 복사, 찾기와 바꾸기, ex 명령 순서
+//   파일 이름이 없는 버퍼라 마음대로 고쳐도 되고, 다시 열 때마다 처음 상태로 새로 만듦
+use crate::{EditorBuffer, EditorConfig};
+
+const TEXT: &str = include_str!("tutor.txt");
+
+impl EditorConfig {
+    // :Tutor
+    pub(crate) fn open_tutor(&mut self) {
+        // 고치지 않은 튜토리얼 버퍼는 이름이 없으니 그 자리를 새로 채움
+        self.new_buffer();
+        self.buffer = EditorBuffer::new();
+        self.buffer.load(TEXT);
+        self.reset_buffer_state();
+        self.status_msg = "vii tutor: j moves down, :q quits".into();
+        self.emit("BufNewFile");
+        self.emit("BufEnter");
+    }
+}
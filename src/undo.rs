@@ -0,0 +1,310 @@
+// --- Undo Tree ---
+// 버퍼의 상태들을 트리로 기억해서, 되돌린 뒤 새로 고쳐도 이전 가지가 사라지지 않음
+//   u / Ctrl-R   부모 상태로 되돌림 / 마지막으로 있던 자식 상태로 다시 실행
+//   g- / g+      가지와 상관없이 만들어진 순서로 이전 / 다음 상태
+//   :undotree    모든 상태를 목록 창에 보여줌 (가지는 들여써서), Enter로 그 상태로 이동
+//   :earlier 2m / :later 30s   지금 상태가 만들어진 때보다 그만큼 이전 / 이후의 상태로 (s, m, h, d)
+//   :earlier 3 / :later 3      g- / g+ 를 세 번
+// 일반 모드로 돌아올 때마다 (ihello<Esc>, dd, :sort 등) 버퍼가 바뀌었으면 상태 하나를 만듦
+// 상태마다 버퍼 전체를 저장함 (0번은 처음 내용)
+// set undofile 이면 저장할 때 트리를 undodir 에 써 두고, 다음에 열 때 파일 내용이 지금 상태와 같으면 이어 씀
+use crate::{EditorConfig, PanelKind, Row, artifacts};
+use std::fs;
+use std::time::{Duration, Instant};
+
+struct State {
+    parent: Option<usize>,
+    last_child: Option<usize>, // 다시 실행할 자식 (마지막으로 있던 가지)
+    children: Vec<usize>,
+    lines: Vec<String>,
+    time: Instant,
+}
+
+pub struct UndoTree {
+    states: Vec<State>, // 만들어진 순서 (번호 = 순서)
+    current: usize,
+    version: u64, // current 상태의 버퍼 버전
+}
+
+// :earlier / :later 의 인자
+enum Amount {
+    Steps(usize),
+    Time(Duration),
+}
+
+fn parse_amount(arg: &str) -> Result<Amount, String> {
+    let arg = if arg.is_empty() { "1" } else { arg };
+    let split = arg.find(|c: char| !c.is_ascii_digit()).unwrap_or(arg.len());
+    let invalid = || format!("Invalid argument: {}", arg);
+    let n: u64 = arg[..split].parse().map_err(|_| invalid())?;
+    let seconds = match &arg[split..] {
+        "" => return Ok(Amount::Steps(n as usize)),
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    Ok(Amount::Time(Duration::from_secs(n.saturating_mul(seconds))))
+}
+
+impl UndoTree {
+    pub fn new() -> Self {
+        UndoTree { states: Vec::new(), current: 0, version: 0 }
+    }
+
+    // 버퍼가 마지막 상태와 다르면 새 상태를 만듦 (처음이면 0번 상태)
+    pub(crate) fn commit(&mut self, rows: &[Row], version: u64) -> bool {
+        if !self.states.is_empty() && version == self.version {
+            return false;
+        }
+        let parent = (!self.states.is_empty()).then_some(self.current);
+        let index = self.states.len();
+        let lines = rows.iter().map(|r| r.content.clone()).collect();
+        self.states.push(State { parent, last_child: None, children: Vec::new(), lines, time: Instant::now() });
+        if let Some(parent) = parent {
+            self.states[parent].children.push(index);
+            self.states[parent].last_child = Some(index);
+        }
+        self.current = index;
+        self.version = version;
+        true
+    }
+
+    // 트리를 깊이 우선으로 (새 가지 먼저) 훑은 (번호, 들여쓰기 단계) 목록
+    fn walk(&self) -> Vec<(usize, usize)> {
+        let mut order = Vec::new();
+        let mut stack = vec![(0, 0)];
+        while let Some((index, depth)) = stack.pop() {
+            order.push((index, depth));
+            let children = &self.states[index].children;
+            // 가장 새 가지는 같은 단계로 이어지고 나머지는 한 단계 들여씀 (스택이라 거꾸로 넣음)
+            for (i, &child) in children.iter().enumerate() {
+                stack.push((child, if i + 1 == children.len() { depth } else { depth + 1 }));
+            }
+        }
+        order
+    }
+
+    // 실행 취소 기록 파일의 내용: 머리 줄, 지금 상태, 상태마다 "state 부모 다시실행할자식 몇초전 줄수" 와 그 줄들 (없으면 -)
+    fn to_text(&self) -> String {
+        let index = |i: Option<usize>| i.map_or("-".to_string(), |i| i.to_string());
+        let mut text = format!("vii-undo 1\ncurrent {}\n", self.current);
+        for state in &self.states {
+            let ago = state.time.elapsed().as_secs();
+            text.push_str(&format!("state {} {} {} {}\n", index(state.parent), index(state.last_child), ago, state.lines.len()));
+            for line in &state.lines {
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    // to_text 의 반대, 형식이 맞지 않으면 None
+    fn from_text(text: &str) -> Option<UndoTree> {
+        let mut lines = text.lines();
+        if lines.next()? != "vii-undo 1" {
+            return None;
+        }
+        let current: usize = lines.next()?.strip_prefix("current ")?.parse().ok()?;
+        let index = |s: &str| -> Option<Option<usize>> { if s == "-" { Some(None) } else { s.parse().ok().map(Some) } };
+        let mut states: Vec<State> = Vec::new();
+        while let Some(header) = lines.next() {
+            let fields: Vec<&str> = header.strip_prefix("state ")?.split(' ').collect();
+            let [parent, last_child, ago, count] = fields[..] else { return None };
+            let (parent, last_child) = (index(parent)?, index(last_child)?);
+            // 부모는 언제나 먼저 만들어진 상태
+            if parent.is_some_and(|p| p >= states.len()) {
+                return None;
+            }
+            let ago = Duration::from_secs(ago.parse().ok()?);
+            let count: usize = count.parse().ok()?;
+            let lines = (0..count).map(|_| lines.next().map(String::from)).collect::<Option<Vec<_>>>()?;
+            let time = Instant::now().checked_sub(ago).unwrap_or_else(Instant::now);
+            let number = states.len();
+            if let Some(parent) = parent {
+                states[parent].children.push(number);
+            }
+            states.push(State { parent, last_child, children: Vec::new(), lines, time });
+        }
+        let len = states.len();
+        if current >= len || states.iter().any(|s| s.last_child.is_some_and(|c| c >= len)) {
+            return None;
+        }
+        Some(UndoTree { states, current, version: 0 })
+    }
+
+    // 부모와 처음 달라진 줄 번호 (1부터)
+    fn changed_line(&self, index: usize) -> Option<usize> {
+        let state = &self.states[index];
+        let parent = &self.states[state.parent?].lines;
+        let same = state.lines.iter().zip(parent).take_while(|(a, b)| a == b).count();
+        Some(same + 1)
+    }
+}
+
+impl EditorConfig {
+    // 일반 모드로 돌아왔을 때 부름
+    pub(crate) fn commit_undo(&mut self) {
+        if self.undo.commit(&self.buffer.rows, self.buffer.version) {
+            self.refresh_undo_panel();
+        }
+    }
+
+    // 저장한 뒤: undofile 이면 실행 취소 기록을 씀
+    pub(crate) fn write_undo_file(&mut self, filename: &str) {
+        if !self.options.undofile {
+            return;
+        }
+        self.commit_undo();
+        let path = artifacts::path_in(&self.options.undodir, filename, ".undo");
+        if let Err(e) = artifacts::write(&path, self.undo.to_text().as_bytes()) {
+            self.error(format!("{}: {}", path.display(), e));
+        }
+    }
+
+    // 파일을 연 뒤: undofile 이고 기록의 지금 상태가 파일 내용과 같으면 기록을 이어 씀
+    pub(crate) fn read_undo_file(&mut self, filename: &str) {
+        if !self.options.undofile {
+            return;
+        }
+        let path = artifacts::path_in(&self.options.undodir, filename, ".undo");
+        let Some(mut tree) = fs::read_to_string(&path).ok().and_then(|text| UndoTree::from_text(&text)) else { return };
+        if !tree.states[tree.current].lines.iter().eq(self.buffer.rows.iter().map(|r| &r.content)) {
+            return;
+        }
+        tree.version = self.buffer.version;
+        self.undo = tree;
+    }
+
+    // 상태의 내용으로 바꾸고 커서는 처음 달라진 곳으로
+    fn restore_undo(&mut self, index: usize) {
+        let lines = &self.undo.states[index].lines;
+        let rows = &self.buffer.rows;
+        let line = lines.iter().zip(rows).take_while(|(a, b)| **a == b.content).count();
+        let col = match (lines.get(line), rows.get(line)) {
+            (Some(a), Some(b)) => a.char_indices().zip(b.content.chars()).find(|((_, x), y)| x != y).map_or(a.len().min(b.content.len()), |((i, _), _)| i),
+            _ => 0,
+        };
+        self.buffer.rows = lines.iter().map(|l| Row::new(l.clone())).collect();
+        self.buffer.touch();
+        self.undo.current = index;
+        self.undo.version = self.buffer.version;
+        self.change = None; // 되돌리기는 . 으로 되풀이하지 않음
+        (self.cy, self.cx) = (line, col);
+        self.clamp_cursor();
+        self.refresh_undo_panel();
+    }
+
+    // u
+    pub(crate) fn undo(&mut self) {
+        self.commit_undo();
+        let current = self.undo.current;
+        let Some(parent) = self.undo.states.get(current).and_then(|s| s.parent) else {
+            self.status_msg = "Already at oldest change".into();
+            return;
+        };
+        self.undo.states[parent].last_child = Some(current);
+        self.restore_undo(parent);
+    }
+
+    // Ctrl-R
+    pub(crate) fn redo(&mut self) {
+        self.commit_undo();
+        let Some(child) = self.undo.states.get(self.undo.current).and_then(|s| s.last_child) else {
+            self.status_msg = "Already at newest change".into();
+            return;
+        };
+        self.restore_undo(child);
+    }
+
+    // g- / g+
+    pub(crate) fn undo_chronological(&mut self, forward: bool) {
+        self.commit_undo();
+        let current = self.undo.current;
+        let target = if forward { current + 1 } else { current.wrapping_sub(1) };
+        if target >= self.undo.states.len() {
+            self.status_msg = if forward { "Already at newest change".into() } else { "Already at oldest change".into() };
+            return;
+        }
+        self.restore_undo(target);
+    }
+
+    // :earlier / :later
+    pub(crate) fn undo_by_amount(&mut self, args: &str, forward: bool) {
+        let amount = match parse_amount(args.trim()) {
+            Ok(amount) => amount,
+            Err(e) => {
+                self.error(e);
+                return;
+            }
+        };
+        self.commit_undo();
+        let current = self.undo.current;
+        let last = self.undo.states.len() - 1;
+        let target = match amount {
+            Amount::Steps(n) if forward => current.saturating_add(n).min(last),
+            Amount::Steps(n) => current.saturating_sub(n),
+            Amount::Time(d) => {
+                let now = self.undo.states[current].time;
+                // 만들어진 순서가 곧 시간 순서라 기준 시각 이전의 마지막 상태를 고름
+                match if forward { now.checked_add(d) } else { now.checked_sub(d) } {
+                    Some(when) => {
+                        let candidates = if forward { &self.undo.states[..] } else { &self.undo.states[..=current] };
+                        candidates.iter().rposition(|s| s.time <= when).unwrap_or(0)
+                    }
+                    None if forward => last,
+                    None => 0,
+                }
+            }
+        };
+        if target != current {
+            self.restore_undo(target);
+        }
+    }
+
+    fn undo_panel_lines(&self) -> (Vec<String>, usize) {
+        let order = self.undo.walk();
+        let lines = order
+            .iter()
+            .map(|&(index, depth)| {
+                let state = &self.undo.states[index];
+                let mark = if index == self.undo.current { '>' } else { ' ' };
+                let what = match self.undo.changed_line(index) {
+                    Some(line) => format!("line {}", line),
+                    None => "original".into(),
+                };
+                let ago = state.time.elapsed().as_secs();
+                format!("{} {}{:<3}  {:>4}s ago  {}", mark, "  ".repeat(depth), index, ago, what)
+            })
+            .collect();
+        let selected = order.iter().position(|&(index, _)| index == self.undo.current).unwrap_or(0);
+        (lines, selected)
+    }
+
+    // :undotree
+    pub(crate) fn open_undo_panel(&mut self) {
+        self.commit_undo();
+        let (lines, selected) = self.undo_panel_lines();
+        self.open_panel(PanelKind::UndoTree, "state time change".into(), lines, selected);
+    }
+
+    fn refresh_undo_panel(&mut self) {
+        if self.panel.as_ref().is_none_or(|p| p.kind != PanelKind::UndoTree) {
+            return;
+        }
+        let (lines, selected) = self.undo_panel_lines();
+        if let Some(panel) = self.panel.as_mut() {
+            panel.lines = lines;
+            panel.selected = selected;
+        }
+    }
+
+    // 목록 창의 Enter: 고른 상태로 이동
+    pub(crate) fn jump_to_listed_undo(&mut self, selected: usize) {
+        let Some(&(index, _)) = self.undo.walk().get(selected) else { return };
+        self.restore_undo(index);
+        self.panel_focused = false;
+    }
+}
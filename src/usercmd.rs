@@ -0,0 +1,95 @@
+// --- User-Defined Commands ---
+// :command[!] Name 대체명령  (viirc에 써 두면 시작할 때마다 정의됨)
+// 대체 명령은 |로 여러 개를 이을 수 있고 실행 전에 아래 표기를 바꿔 넣음
+//   <args>    명령 뒤에 준 인자 그대로
+//   <q-args>  인자를 따옴표로 감싼 문자열 (Lua 코드에 넘길 때)
+//   <line1>, <line2>  범위의 첫 줄과 끝 줄 (범위가 없으면 커서 줄)
+//   <lt>      '<' 문자
+use crate::json::Json;
+use crate::range::Range;
+
+pub struct UserCommand {
+    pub name: String,
+    pub replacement: String,
+}
+
+pub struct UserCommands {
+    commands: Vec<UserCommand>,
+}
+
+impl UserCommands {
+    pub fn new() -> Self {
+        UserCommands { commands: Vec::new() }
+    }
+
+    // 이미 있는 이름은 bang(!)이 있을 때만 덮어씀
+    pub fn define(&mut self, name: &str, replacement: &str, bang: bool) -> Result<(), String> {
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("Invalid command name: {} (must start with an uppercase letter)", name));
+        }
+        match self.commands.iter_mut().find(|c| c.name == name) {
+            Some(_) if !bang => Err(format!("Command already exists: {} (add ! to replace)", name)),
+            Some(cmd) => {
+                cmd.replacement = replacement.to_string();
+                Ok(())
+            }
+            None => {
+                self.commands.push(UserCommand { name: name.to_string(), replacement: replacement.to_string() });
+                Ok(())
+            }
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.commands.len();
+        self.commands.retain(|c| c.name != name);
+        self.commands.len() != len
+    }
+
+    pub fn get(&self, name: &str) -> Option<&UserCommand> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UserCommand> {
+        self.commands.iter()
+    }
+}
+
+impl UserCommand {
+    // 인자와 범위를 채워 넣은 실행할 명령 목록 (|는 인자를 넣기 전에 나눔)
+    pub fn expand(&self, args: &str, range: Range) -> Vec<String> {
+        self.replacement
+            .split('|')
+            .map(|part| substitute(part.trim(), args, range))
+            .filter(|c| !c.is_empty())
+            .collect()
+    }
+}
+
+fn substitute(text: &str, args: &str, range: Range) -> String {
+    let vars = [
+        ("<args>", args.to_string()),
+        ("<q-args>", Json::from(args).to_string()),
+        ("<line1>", range.start.to_string()),
+        ("<line2>", range.end.to_string()),
+        ("<lt>", "<".to_string()),
+    ];
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match vars.iter().find(|(key, _)| rest.starts_with(key)) {
+            Some((key, value)) => {
+                out.push_str(value);
+                rest = &rest[key.len()..];
+            }
+            None => {
+                out.push('<');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
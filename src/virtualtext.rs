@@ -0,0 +1,73 @@
+// --- Virtual Text ---
+// 줄 내용 뒤에 덧붙여 보여주는 글 (버퍼에는 들어가지 않고 저장되지도 않음)
+//   LSP 진단 메시지          심각도 색으로
+//   annotate로 붙인 주석      스크립트의 vii.annotate(줄, 글) 또는 라이브러리 API
+// 줄 내용과 두 칸 띄우고, 화면 폭을 넘으면 잘라서 끝에 …를 붙임
+use crate::{EditorConfig, lsp};
+
+pub struct VirtualText {
+    pub text: String,
+    pub style: &'static str, // ANSI 색
+}
+
+impl EditorConfig {
+    /// 줄(0부터) 뒤에 주석을 붙임, 빈 글이면 지움
+    pub fn annotate(&mut self, line: usize, text: &str) {
+        if text.is_empty() {
+            self.annotations.remove(&line);
+        } else {
+            self.annotations.insert(line, text.to_string());
+        }
+    }
+
+    pub fn clear_annotations(&mut self) {
+        self.annotations.clear();
+    }
+
+    // 줄 뒤에 붙일 글들 (진단이 먼저)
+    pub(crate) fn virtual_text(&self, line: usize) -> Vec<VirtualText> {
+        let mut texts: Vec<VirtualText> = self
+            .diagnostics_iter()
+            .filter(|d| d.line == line)
+            .min_by_key(|d| (d.severity, d.col))
+            .map(|d| VirtualText {
+                text: format!("{} {}", d.severity.sign(), d.message.lines().next().unwrap_or("")),
+                style: match d.severity {
+                    lsp::Severity::Error => "\x1b[31m",
+                    lsp::Severity::Warning => "\x1b[33m",
+                    _ => "\x1b[36m",
+                },
+            })
+            .into_iter()
+            .collect();
+        if let Some(text) = self.annotations.get(&line) {
+            texts.push(VirtualText { text: text.clone(), style: "\x1b[90m" });
+        }
+        texts
+    }
+}
+
+/// 글들을 두 칸씩 띄워 이어 붙이되 room 칸을 넘으면 잘라서 …로 끝냄, 반환값은 (ANSI 포함 문자열, 차지한 칸 수)
+pub fn render(texts: &[VirtualText], room: usize) -> (String, usize) {
+    let mut out = String::new();
+    let mut used = 0;
+    for vt in texts {
+        let gap = if used == 0 { 0 } else { 2 };
+        let left = room.saturating_sub(used + gap);
+        if left == 0 {
+            break;
+        }
+        let len = vt.text.chars().count();
+        let shown: String = if len > left {
+            vt.text.chars().take(left - 1).chain(Some('…')).collect()
+        } else {
+            vt.text.clone()
+        };
+        out.push_str(&format!("{:gap$}{}{}\x1b[m", "", vt.style, shown, gap = gap));
+        used += gap + len.min(left);
+        if len > left {
+            break;
+        }
+    }
+    (out, used)
+}
@@ -0,0 +1,135 @@
+// --- Visual Mode ---
+// v 는 글자 단위, V 는 줄 단위로 선택을 시작하고 hjkl로 넓힘 (선택은 시작점과 커서 사이)
+//   > / <       선택한 줄들을 들여쓰기 / 내어쓰기 (빈 줄은 들여쓰지 않음)
+//   gc          선택한 줄들을 주석으로, 모두 주석이면 주석 해제
+//   u / U / ~   소문자로 / 대문자로 / 대소문자 뒤집기
+//   =           선택한 줄들을 언어 서버로 모양 맞춤 (formatting.rs)
+//   Ctrl-A / Ctrl-X, g Ctrl-A / g Ctrl-X   줄마다 숫자를 늘리고 줄임 (number.rs)
+//   zf          선택한 줄들을 접음 (fold.rs)
+//   Esc         선택을 끝냄, v / V 는 같은 종류면 끝내고 다르면 종류를 바꿈
+//   gv          일반 모드에서 마지막 선택을 다시 선택
+use crate::case::CaseOp;
+use crate::{EditorConfig, Mode, filetype};
+
+#[derive(Clone, Copy)]
+pub struct Selection {
+    pub(crate) anchor: (usize, usize), // 선택을 시작한 (줄, 열)
+    pub(crate) linewise: bool,
+}
+
+impl EditorConfig {
+    pub(crate) fn start_visual(&mut self, linewise: bool) {
+        self.selection = Some(Selection { anchor: (self.cy, self.cx), linewise });
+        self.mode = Mode::Visual;
+    }
+
+    // 선택 중에 v / V
+    pub(crate) fn switch_visual(&mut self, linewise: bool) {
+        match self.selection.as_mut() {
+            Some(sel) if sel.linewise != linewise => sel.linewise = linewise,
+            _ => self.end_visual(),
+        }
+    }
+
+    pub(crate) fn end_visual(&mut self) {
+        if let Some(sel) = self.selection.take() {
+            self.last_selection = Some((sel, (self.cy, self.cx)));
+        }
+        self.mode = Mode::Normal;
+    }
+
+    // gv
+    pub(crate) fn reselect(&mut self) {
+        let Some((mut sel, cursor)) = self.last_selection else {
+            self.status_msg = "No previous selection".into();
+            return;
+        };
+        let last = self.buffer.rows.len() - 1;
+        sel.anchor.0 = sel.anchor.0.min(last);
+        (self.cy, self.cx) = cursor;
+        self.clamp_cursor();
+        self.selection = Some(sel);
+        self.mode = Mode::Visual;
+    }
+
+    // 선택 중이면 ((시작 줄, 열), (끝 줄, 열)), 끝 글자도 선택에 들어감
+    pub(crate) fn selection_bounds(&self) -> Option<((usize, usize), (usize, usize))> {
+        let sel = self.selection.filter(|_| self.mode == Mode::Visual)?;
+        let cursor = (self.cy, self.cx);
+        Some(if sel.anchor <= cursor { (sel.anchor, cursor) } else { (cursor, sel.anchor) })
+    }
+
+    // 줄에서 선택된 바이트 범위 [시작, 끝), 줄 끝을 넘을 수 있음 (빈 줄도 한 칸 보이게)
+    pub(crate) fn selected_columns(&self, line: usize) -> Option<(usize, usize)> {
+        let ((start_line, start_col), (end_line, end_col)) = self.selection_bounds()?;
+        if line < start_line || line > end_line {
+            return None;
+        }
+        let content = &self.buffer.rows.get(line)?.content;
+        if self.selection.is_some_and(|s| s.linewise) {
+            return Some((0, content.len() + 1));
+        }
+        let start = if line == start_line { start_col } else { 0 };
+        let end = if line == end_line {
+            end_col + content.get(end_col..).and_then(|s| s.chars().next()).map_or(1, char::len_utf8)
+        } else {
+            content.len() + 1
+        };
+        Some((start, end))
+    }
+
+    // 선택에 대해 >, <, c (gc), u, U, ~ 를 실행하고 일반 모드로
+    pub(crate) fn visual_operator(&mut self, op: char) {
+        let Some(((start_line, start_col), (end_line, _))) = self.selection_bounds() else { return };
+        let linewise = self.selection.is_some_and(|s| s.linewise);
+        match op {
+            '>' | '<' => {
+                for line in start_line..=end_line {
+                    if op == '<' || !self.buffer.rows[line].content.is_empty() {
+                        self.shift_row(line, op == '>');
+                    }
+                }
+            }
+            'c' => self.toggle_comment(start_line, end_line),
+            '=' => self.lsp_format_lines(start_line, end_line + 1),
+            _ => {
+                let Some(case) = CaseOp::from_key(op) else { return };
+                for line in start_line..=end_line {
+                    let (start, end) = self.selected_columns(line).unwrap_or((0, 0));
+                    let len = self.buffer.rows[line].content.len();
+                    self.change_case(case, line, start.min(len), end.min(len));
+                }
+            }
+        }
+        self.end_visual();
+        // 커서는 선택의 처음으로 (줄 단위 명령은 첫 글자)
+        self.cy = start_line;
+        self.cx = if linewise || matches!(op, '>' | '<' | '=' | 'c') {
+            let content = &self.buffer.rows[start_line].content;
+            content.len() - content.trim_start().len()
+        } else {
+            start_col
+        };
+        self.clamp_cursor();
+    }
+
+    // 빈 줄이 아닌 줄들이 모두 주석이면 주석 기호를 지우고, 아니면 가장 얕은 들여쓰기 위치에 붙임
+    fn toggle_comment(&mut self, start: usize, end: usize) {
+        let prefix = filetype::comment_prefix(self.buffer_options.filetype.as_deref());
+        let rows = &mut self.buffer.rows[start..=end];
+        let lines = || rows.iter().map(|r| r.content.as_str()).filter(|l| !l.trim().is_empty());
+        let commented = lines().all(|l| l.trim_start().starts_with(prefix));
+        let indent = lines().map(|l| l.len() - l.trim_start().len()).min().unwrap_or(0);
+        for row in rows.iter_mut().filter(|r| !r.content.trim().is_empty()) {
+            let content = &mut row.content;
+            if commented {
+                let at = content.len() - content.trim_start().len();
+                let len = prefix.len() + usize::from(content[at + prefix.len()..].starts_with(' '));
+                content.replace_range(at..at + len, "");
+            } else {
+                content.insert_str(indent, &format!("{} ", prefix));
+            }
+        }
+        self.buffer.touch();
+    }
+}
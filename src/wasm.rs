@@ -0,0 +1,217 @@
+// --- WebAssembly Plugins ---
+// plugins/*.wasm (또는 .wat)을 wasmtime으로 실행한다. WASI를 연결하지 않으므로 플러그인은
+// 파일 시스템이나 네트워크에 접근할 수 없고, 아래 "vii" 모듈의 함수만 쓸 수 있다.
+//
+// 가져올 수 있는 함수 (모듈 "vii", 줄/열 번호는 1부터, 음수 반환은 실패):
+//   line_count() -> i32
+//   get_line(line, ptr, cap) -> i32      줄 내용을 ptr에 최대 cap 바이트 복사하고 전체 길이 반환
+//   set_line(line, ptr, len) -> i32
+//   insert_line(line, ptr, len) -> i32   line 위치에 새 줄을 끼워 넣음
+//   delete_line(line) -> i32
+//   cursor_line() -> i32, cursor_col() -> i32, set_cursor(line, col)
+//   message(ptr, len)
+//   register_command(ptr, len) -> i32    :이름 명령을 등록하고 번호를 반환
+// 플러그인이 내보내야 하는 것: memory, alloc(len) -> ptr, 그리고 선택적으로 init(), on_command(id, ptr, len)
+use std::fs;
+use std::path::Path;
+use wasmtime::{Caller, Config, Engine, Error, Extern, Instance, Linker, Memory, Module, Store};
+
+// 한 번 호출할 때 쓸 수 있는 연산량 (무한 루프 방지)
+const FUEL_PER_CALL: u64 = 100_000_000;
+
+// 플러그인이 볼 수 있는 에디터 상태의 사본, 호출이 끝나면 에디터에 다시 반영
+pub struct Snapshot {
+    pub lines: Vec<String>,
+    pub cy: usize,
+    pub cx: usize,
+    pub message: Option<String>,
+    pub modified: bool,
+}
+
+struct HostState {
+    view: Snapshot,
+    commands: Vec<String>, // 명령 번호 → 이름 (init 과 on_command 안에서 등록한 것 모두)
+}
+
+struct Plugin {
+    store: Store<HostState>,
+    instance: Instance,
+}
+
+impl Plugin {
+    fn command_id(&self, name: &str) -> Option<usize> {
+        self.store.data().commands.iter().position(|c| c == name)
+    }
+}
+
+pub struct WasmPlugins {
+    plugins: Vec<Plugin>,
+}
+
+fn memory(caller: &mut Caller<'_, HostState>) -> Option<Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(mem)) => Some(mem),
+        _ => None,
+    }
+}
+
+fn read_str(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Option<String> {
+    let mem = memory(caller)?;
+    let mut buf = vec![0; usize::try_from(len).ok()?];
+    mem.read(&*caller, usize::try_from(ptr).ok()?, &mut buf).ok()?;
+    String::from_utf8(buf).ok()
+}
+
+// 1부터 시작하는 줄 번호를 인덱스로 (end까지 허용)
+fn line_index(line: i32, end: usize) -> Option<usize> {
+    let i = usize::try_from(line).ok()?.checked_sub(1)?;
+    (i < end).then_some(i)
+}
+
+fn link(engine: &Engine) -> wasmtime::Result<Linker<HostState>> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap("vii", "line_count", |caller: Caller<'_, HostState>| caller.data().view.lines.len() as i32)?;
+    linker.func_wrap("vii", "get_line", |mut caller: Caller<'_, HostState>, line: i32, ptr: i32, cap: i32| -> i32 {
+        let lines = &caller.data().view.lines;
+        let Some(text) = line_index(line, lines.len()).map(|i| lines[i].clone()) else { return -1 };
+        let n = text.len().min(cap.max(0) as usize);
+        let Some(mem) = memory(&mut caller) else { return -1 };
+        if mem.write(&mut caller, ptr.max(0) as usize, &text.as_bytes()[..n]).is_err() {
+            return -1;
+        }
+        text.len() as i32
+    })?;
+    linker.func_wrap("vii", "set_line", |mut caller: Caller<'_, HostState>, line: i32, ptr: i32, len: i32| -> i32 {
+        let Some(text) = read_str(&mut caller, ptr, len) else { return -1 };
+        let view = &mut caller.data_mut().view;
+        let Some(i) = line_index(line, view.lines.len()) else { return -1 };
+        view.lines[i] = text;
+        view.modified = true;
+        0
+    })?;
+    linker.func_wrap("vii", "insert_line", |mut caller: Caller<'_, HostState>, line: i32, ptr: i32, len: i32| -> i32 {
+        let Some(text) = read_str(&mut caller, ptr, len) else { return -1 };
+        let view = &mut caller.data_mut().view;
+        let Some(i) = line_index(line, view.lines.len() + 1) else { return -1 };
+        view.lines.insert(i, text);
+        view.modified = true;
+        0
+    })?;
+    linker.func_wrap("vii", "delete_line", |mut caller: Caller<'_, HostState>, line: i32| -> i32 {
+        let view = &mut caller.data_mut().view;
+        let Some(i) = line_index(line, view.lines.len()) else { return -1 };
+        view.lines.remove(i);
+        if view.lines.is_empty() {
+            view.lines.push(String::new());
+        }
+        view.modified = true;
+        0
+    })?;
+    linker.func_wrap("vii", "cursor_line", |caller: Caller<'_, HostState>| caller.data().view.cy as i32 + 1)?;
+    linker.func_wrap("vii", "cursor_col", |caller: Caller<'_, HostState>| caller.data().view.cx as i32 + 1)?;
+    linker.func_wrap("vii", "set_cursor", |mut caller: Caller<'_, HostState>, line: i32, col: i32| {
+        let view = &mut caller.data_mut().view;
+        view.cy = (line.max(1) - 1) as usize;
+        view.cx = (col.max(1) - 1) as usize;
+    })?;
+    linker.func_wrap("vii", "message", |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+        if let Some(text) = read_str(&mut caller, ptr, len) {
+            caller.data_mut().view.message = Some(text);
+        }
+    })?;
+    linker.func_wrap("vii", "register_command", |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+        let Some(name) = read_str(&mut caller, ptr, len) else { return -1 };
+        let commands = &mut caller.data_mut().commands;
+        commands.push(name);
+        commands.len() as i32 - 1
+    })?;
+    Ok(linker)
+}
+
+impl WasmPlugins {
+    // 디렉터리의 플러그인을 모두 읽고 init()을 실행, 실패한 플러그인의 오류 메시지도 함께 돌려줌
+    pub fn load(dir: &Path, view: &mut Snapshot) -> (Self, Vec<String>) {
+        let mut plugins = WasmPlugins { plugins: Vec::new() };
+        let mut errors = Vec::new();
+        let Ok(entries) = fs::read_dir(dir) else { return (plugins, errors) };
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "wasm" || ext == "wat"))
+            .collect();
+        if paths.is_empty() {
+            return (plugins, errors);
+        }
+        paths.sort();
+
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = match Engine::new(&config) {
+            Ok(engine) => engine,
+            Err(e) => return (plugins, vec![e.to_string()]),
+        };
+        let linker = match link(&engine) {
+            Ok(linker) => linker,
+            Err(e) => return (plugins, vec![e.to_string()]),
+        };
+        for path in paths {
+            match Self::instantiate(&engine, &linker, &path, view) {
+                Ok(plugin) => plugins.plugins.push(plugin),
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+        (plugins, errors)
+    }
+
+    fn instantiate(engine: &Engine, linker: &Linker<HostState>, path: &Path, view: &mut Snapshot) -> wasmtime::Result<Plugin> {
+        let module = Module::from_file(engine, path)?;
+        let state = HostState { view: take_view(view), commands: Vec::new() };
+        let mut store = Store::new(engine, state);
+        store.set_fuel(FUEL_PER_CALL)?;
+        let instance = linker.instantiate(&mut store, &module)?;
+        let result = match instance.get_typed_func::<(), ()>(&mut store, "init") {
+            Ok(init) => init.call(&mut store, ()),
+            Err(_) => Ok(()),
+        };
+        *view = take_view(&mut store.data_mut().view);
+        result?;
+        Ok(Plugin { store, instance })
+    }
+
+    pub fn has_command(&self, name: &str) -> bool {
+        self.plugins.iter().any(|p| p.command_id(name).is_some())
+    }
+
+    pub fn run_command(&mut self, name: &str, args: &str, view: &mut Snapshot) -> wasmtime::Result<()> {
+        let Some((plugin, id)) = self.plugins.iter_mut().find_map(|p| p.command_id(name).map(|id| (p, id as i32))) else {
+            return Err(Error::msg(format!("unknown command {}", name)));
+        };
+        let store = &mut plugin.store;
+        store.set_fuel(FUEL_PER_CALL)?;
+        store.data_mut().view = take_view(view);
+
+        let result = (|| {
+            let alloc = plugin.instance.get_typed_func::<i32, i32>(&mut *store, "alloc")?;
+            let on_command = plugin.instance.get_typed_func::<(i32, i32, i32), ()>(&mut *store, "on_command")?;
+            let memory = plugin
+                .instance
+                .get_memory(&mut *store, "memory")
+                .ok_or_else(|| Error::msg("plugin does not export memory"))?;
+            let ptr = alloc.call(&mut *store, args.len() as i32)?;
+            memory.write(&mut *store, ptr as usize, args.as_bytes())?;
+            on_command.call(&mut *store, (id, ptr, args.len() as i32))
+        })();
+        *view = take_view(&mut store.data_mut().view);
+        result
+    }
+}
+
+fn take_view(view: &mut Snapshot) -> Snapshot {
+    Snapshot {
+        lines: std::mem::take(&mut view.lines),
+        cy: view.cy,
+        cx: view.cx,
+        message: view.message.take(),
+        modified: std::mem::take(&mut view.modified),
+    }
+}
@@ -0,0 +1,16 @@
+// --- Windows ---
+// 창마다 따로 갖는 설정, 지금은 창이 하나라 EditorConfig 가 Window 하나를 가짐
+// 창별 설정(number, cursorline, colorcolumn, foldmethod, wrap, displaylines, scrollbar, scrolloff, sidescrolloff)은
+// :set 으로 바꾸면 지금 창에만 적용되고 전역 설정(makeprg 등)은 Options 에 남음
+// 커서와 스크롤 위치는 아직 창마다 나뉘지 않고 EditorConfig 필드(cx, cy, row_offset, col_offset)에 있음
+use crate::options::WindowOptions;
+
+pub struct Window {
+    pub options: WindowOptions,
+}
+
+impl Window {
+    pub fn new() -> Self {
+        Window { options: WindowOptions::new() }
+    }
+}
@@ -0,0 +1,91 @@
+// --- Word Motions ---
+// 낱말은 글자/숫자/_ 가 이어진 것이거나 공백이 아닌 다른 글자가 이어진 것
+//   w          다음 낱말의 처음으로 (줄 끝이면 다음 줄의 첫 낱말), 3w 처럼 횟수만큼
+//   dw / yw    커서부터 다음 낱말 앞까지 지움 / 복사 (줄 끝을 넘지 않음), 2dw 와 d2w 는 두 낱말
+//   cw         커서부터 낱말 끝까지 지우고 입력 모드 (뒤의 공백은 남김)
+use crate::EditorConfig;
+use crate::abbrev::is_word_char;
+use crate::registers::Register;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Blank,
+    Word,
+    Punct,
+}
+
+fn class(c: char) -> Class {
+    if c.is_whitespace() {
+        Class::Blank
+    } else if is_word_char(c) {
+        Class::Word
+    } else {
+        Class::Punct
+    }
+}
+
+// from부터 keep에 맞는 글자들을 건너뛴 위치
+fn skip(line: &str, from: usize, keep: impl Fn(Class) -> bool) -> usize {
+    line[from..].find(|c| !keep(class(c))).map_or(line.len(), |i| from + i)
+}
+
+/// col의 낱말과 그 뒤의 공백을 건너뛴 위치 (줄 끝이면 줄 길이)
+pub fn next_word_start(line: &str, col: usize) -> usize {
+    let col = col.min(line.len());
+    let end = match line[col..].chars().next().map(class) {
+        Some(k) if k != Class::Blank => skip(line, col, |c| c == k),
+        _ => col,
+    };
+    skip(line, end, |c| c == Class::Blank)
+}
+
+/// col 뒤의 공백을 건너뛴 다음 낱말이 끝나는 위치 (그 낱말 바로 뒤)
+pub fn word_end(line: &str, col: usize) -> usize {
+    let start = skip(line, col.min(line.len()), |c| c == Class::Blank);
+    match line[start..].chars().next().map(class) {
+        Some(k) => skip(line, start, |c| c == k),
+        None => start,
+    }
+}
+
+impl EditorConfig {
+    pub(crate) fn word_forward(&mut self) {
+        for _ in 0..self.take_count() {
+            let line = &self.buffer.rows[self.cy].content;
+            let next = next_word_start(line, self.cx);
+            if next < line.len() {
+                self.cx = next;
+            } else if self.cy + 1 < self.buffer.rows.len() {
+                self.cy += 1;
+                self.cx = skip(&self.buffer.rows[self.cy].content, 0, |c| c == Class::Blank);
+            } else {
+                self.cx = line.len();
+                break;
+            }
+        }
+    }
+
+    // dw / yw / cw
+    pub(crate) fn word_operator(&mut self, op: &str) {
+        let count = self.take_count();
+        let line = &self.buffer.rows[self.cy].content;
+        let start = self.cx.min(line.len());
+        let end = (0..count).fold(start, |end, _| if op == "c" { word_end(line, end) } else { next_word_start(line, end) });
+        let name = self.register.take();
+        if end > start {
+            let reg = Register { text: line[start..end].to_string(), linewise: false };
+            if op == "y" {
+                self.registers.yank(name, reg);
+                self.flash(self.cy, self.cy, Some((start, end)));
+                return;
+            }
+            self.buffer.rows[self.cy].content.replace_range(start..end, "");
+            self.registers.delete(name, reg);
+            self.buffer.touch();
+        }
+        match op {
+            "c" => self.start_insert(),
+            _ => self.clamp_cursor(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// --- Soft Wrap and Line Start ---
+// set wrap 이면 화면 폭보다 긴 줄을 여러 화면 줄에 나눠 보여줌 (가로 스크롤은 하지 않음, 비교 모드에서는 끔)
+// row_offset 은 그대로 화면 맨 위에 오는 (닫힌 접기를 한 줄로 센) 줄이고, 그 줄은 처음부터 보임
+//   0 / Home   줄의 첫 공백 아닌 글자로, 이미 거기 있으면 0열로 (누를 때마다 오감)
+//   ^          줄의 첫 공백 아닌 글자로
+//   Home       wrap 으로 나뉜 줄의 두 번째 화면 줄부터는 먼저 그 화면 줄의 처음으로 (입력 모드에서도)
+//   gj / gk    줄 대신 화면 줄 단위로 아래 / 위로 (wrap 이 꺼져 있으면 j / k 와 같음)
+//   set displaylines   j / k 도 gj / gk 처럼 움직임
+use crate::EditorConfig;
+
+impl EditorConfig {
+    pub(crate) fn wraps(&self) -> bool {
+        self.window.options.wrap && self.diff.is_none()
+    }
+
+    // 커서 앞의 글자 수 (화면 열)
+    pub(crate) fn cursor_col(&self) -> usize {
+        let content = &self.buffer.rows[self.cy].content;
+        content.get(..self.cx).map_or(content.len(), |s| s.chars().count())
+    }
+
+    /// 줄이 차지하는 화면 줄 수, 커서가 줄 끝 뒤의 새 화면 줄에 있으면 그 줄도 셈
+    pub(crate) fn wrapped_rows(&self, line: usize) -> usize {
+        if !self.wraps() || line >= self.buffer.rows.len() || self.folds.closed_at(line).is_some() {
+            return 1;
+        }
+        let width = self.text_cols();
+        let rows = self.buffer.rows[line].content.chars().count().div_ceil(width).max(1);
+        if line == self.cy { rows.max(self.cursor_col() / width + 1) } else { rows }
+    }
+
+    // 화면 줄 from..to (닫힌 접기는 한 줄) 가 차지하는 화면 줄 수
+    fn rows_between(&self, from: usize, to: usize) -> usize {
+        (from..to).map(|row| self.wrapped_rows(self.folds.line_at(row))).sum()
+    }
+
+    /// 편집 영역 안에서 커서의 (줄, 열)
+    pub(crate) fn cursor_screen(&self) -> (usize, usize) {
+        let row = self.cursor_display_row();
+        if !self.wraps() {
+            return (row - self.row_offset, self.cursor_col().saturating_sub(self.col_offset));
+        }
+        let width = self.text_cols();
+        let col = self.cursor_col();
+        (self.rows_between(self.row_offset, row) + col / width, col % width)
+    }
+
+    // wrap 일 때 커서가 있는 화면 줄과 그 아래 below 줄이 보이도록 row_offset 을 내림
+    pub(crate) fn scroll_wrapped(&mut self, visible_rows: usize, below: usize) {
+        let row = self.cursor_display_row();
+        let segment = self.cursor_col() / self.text_cols();
+        let after = self.rows_between(row + 1, row + 1 + below);
+        while self.row_offset < row && self.rows_between(self.row_offset, row) + segment + after >= visible_rows {
+            self.row_offset += 1;
+        }
+        self.col_offset = 0;
+    }
+
+    // gj / gk, 화면 줄 안의 열을 지킴
+    pub(crate) fn display_move(&mut self, down: bool) {
+        if !self.wraps() {
+            self.move_cursor(if down { 'j' } else { 'k' });
+            return;
+        }
+        let width = self.text_cols();
+        let col = self.cursor_col();
+        let (segment, x) = (col / width, col % width);
+        let rows = |content: &str| content.chars().count().div_ceil(width).max(1);
+        let target = if down && segment + 1 < rows(&self.buffer.rows[self.cy].content) {
+            (segment + 1) * width + x
+        } else if !down && segment > 0 {
+            (segment - 1) * width + x
+        } else {
+            let line = self.cy;
+            self.move_cursor(if down { 'j' } else { 'k' });
+            if self.cy == line {
+                return;
+            }
+            if down { x } else { (rows(&self.buffer.rows[self.cy].content) - 1) * width + x }
+        };
+        let content = &self.buffer.rows[self.cy].content;
+        self.cx = content.char_indices().nth(target).map_or(content.len(), |(i, _)| i);
+    }
+
+    // ^
+    pub(crate) fn first_non_blank(&mut self) {
+        let content = &self.buffer.rows[self.cy].content;
+        self.cx = content.len() - content.trim_start().len();
+    }
+
+    // 0 / Home, display 이면 wrap 으로 나뉜 화면 줄의 처음을 먼저
+    pub(crate) fn smart_home(&mut self, display: bool) {
+        let content = &self.buffer.rows[self.cy].content;
+        if display && self.wraps() {
+            let start = self.cursor_col() / self.text_cols() * self.text_cols();
+            let at = content.char_indices().nth(start).map_or(content.len(), |(i, _)| i);
+            if start > 0 && self.cx != at {
+                self.cx = at;
+                return;
+            }
+        }
+        let first = content.len() - content.trim_start().len();
+        self.cx = if self.cx == first { 0 } else { first };
+    }
+}
@@ -0,0 +1,18 @@
+// --- Zen Mode ---
+// :Zen   글쓰기에 맞춘 화면을 켜거나 끔
+//   본문을 zenwidth 칸 폭으로 화면 가운데에 두고 줄 번호, 표시 열, 접기 열, blame 열, 스크롤 막대, 버퍼 끝의 ~ 를 숨김
+//   상태 줄에는 입력 / 비주얼 모드 표시와 메시지만 (바탕색 없이)
+// 창 설정은 건드리지 않으므로 끄면 그대로 돌아옴 (긴 줄을 나눠 보려면 set wrap)
+use crate::EditorConfig;
+
+impl EditorConfig {
+    // :Zen
+    pub(crate) fn toggle_zen(&mut self) {
+        self.zen = !self.zen;
+    }
+
+    // 본문 왼쪽의 빈 칸 수 (본문이 화면 가운데에 오게)
+    pub(crate) fn zen_padding(&self) -> usize {
+        (self.screen_cols as usize).saturating_sub(self.text_cols()) / 2
+    }
+}
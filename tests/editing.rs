@@ -0,0 +1,1404 @@
+// 키 입력을 흘려 넣어 편집 동작과 화면을 검사하는 통합 테스트
+use vii::cli::{self, Action};
+use vii::harness::Harness;
+use vii::terminal::Signal;
+use vii::{EditorConfig, Mode, batch};
+
+#[test]
+fn insert_text_and_newline() {
+    let mut h = Harness::new(20, 5);
+    h.keys("ihello<CR>world<Esc>");
+    assert_eq!(h.lines(), ["hello", "world"]);
+    assert_eq!(h.cursor(), (1, 5));
+    assert!(h.editor.mode == Mode::Normal);
+    assert!(h.editor.buffer.dirty);
+}
+
+#[test]
+fn backspace_joins_lines() {
+    let mut h = Harness::with_lines(20, 5, &["ab", "cd"]);
+    h.keys("ji<BS>");
+    assert_eq!(h.lines(), ["abcd"]);
+    assert_eq!(h.cursor(), (0, 2));
+}
+
+#[test]
+fn motions_stay_inside_the_buffer() {
+    let mut h = Harness::with_lines(20, 5, &["long line", "ab"]);
+    h.keys("llllllj");
+    assert_eq!(h.cursor(), (1, 2));
+    h.keys("jjjkkkhhhh");
+    assert_eq!(h.cursor(), (0, 0));
+}
+
+#[test]
+fn screen_shows_buffer_and_status_bar() {
+    let mut h = Harness::with_lines(30, 4, &["first", "second"]);
+    h.keys("j");
+    assert_eq!(h.screen.line(0), "first");
+    assert_eq!(h.screen.line(1), "second");
+    assert_eq!(h.screen.line(2), "~");
+    assert!(h.screen.line(3).starts_with("-- NORMAL --"));
+    assert!(h.screen.line(3).ends_with("2:1  All"));
+    assert_eq!(h.screen.cursor, (1, 0));
+}
+
+#[test]
+fn vertical_scroll_follows_cursor() {
+    let lines: Vec<String> = (1..=10).map(|n| format!("line {}", n)).collect();
+    let lines: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+    let mut h = Harness::with_lines(20, 4, &lines);
+    h.keys("jjjjj");
+    assert_eq!(h.editor.row_offset, 3);
+    assert_eq!(h.screen.line(0), "line 4");
+    assert!(h.screen.line(3).ends_with("6:1  42%"));
+    assert_eq!(h.screen.line(2), "line 6");
+    assert_eq!(h.screen.cursor, (2, 0));
+}
+
+#[test]
+fn command_line_is_drawn_while_typing() {
+    let mut h = Harness::new(20, 3);
+    h.keys(":grep");
+    assert_eq!(h.screen.line(2), ":grep");
+    h.keys("<Esc>");
+    assert!(h.editor.mode == Mode::Normal);
+}
+
+#[test]
+fn quit_stops_the_loop_and_clears_the_screen() {
+    let mut h = Harness::with_lines(20, 3, &["text"]);
+    h.keys(":q<CR>ihello");
+    assert_eq!(h.lines(), ["text"]);
+    assert_eq!(h.screen.text().trim(), "");
+}
+
+#[test]
+fn user_command_runs_its_replacement() {
+    let mut h = Harness::new(40, 3);
+    h.keys(":command Quit q<CR>:Quit<CR>ihello");
+    assert_eq!(h.lines(), [""]);
+}
+
+#[test]
+fn insert_mode_ctrl_w_and_ctrl_u_delete_backwards() {
+    let mut h = Harness::with_lines(40, 10, &["    foo.bar baz"]);
+    h.editor.cx = 15;
+    h.keys("i<C-w>");
+    assert_eq!(h.lines(), vec!["    foo.bar "]);
+    h.keys("<C-w>");
+    assert_eq!(h.lines(), vec!["    foo."]);
+    h.keys("<C-w><C-w>");
+    assert_eq!(h.lines(), vec!["    "]);
+    h.keys("xy<C-u>");
+    assert_eq!(h.lines(), vec!["    "]);
+    h.keys("<C-u>");
+    assert_eq!(h.lines(), vec![""]);
+}
+
+#[test]
+fn insert_mode_ctrl_t_and_ctrl_d_shift_the_line() {
+    let mut h = Harness::with_lines(40, 10, &["x"]);
+    h.keys("i<C-t><C-t>");
+    assert_eq!(h.lines(), vec!["        x"]);
+    assert_eq!(h.cursor(), (0, 8));
+    h.keys("<C-d>");
+    assert_eq!(h.lines(), vec!["    x"]);
+    assert_eq!(h.cursor(), (0, 4));
+}
+
+#[test]
+fn yank_delete_and_put_use_registers() {
+    let mut h = Harness::with_lines(60, 12, &["one", "two", "three"]);
+    h.keys("\"ayyjddp");
+    assert_eq!(h.lines(), vec!["one", "three", "two"]);
+    h.keys("\"aP");
+    assert_eq!(h.lines(), vec!["one", "three", "one", "two"]);
+    h.keys(":registers<CR>");
+    let screen = h.screen.text();
+    assert!(screen.contains("\"\"  l  two"));
+    assert!(screen.contains("\"a  l  one"));
+    assert!(screen.contains("\"1  l  two"));
+}
+
+#[test]
+fn marks_remember_positions() {
+    let mut h = Harness::with_lines(60, 12, &["  alpha", "beta", "gamma"]);
+    h.keys("llmajjmb`a");
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys("'b");
+    assert_eq!(h.cursor(), (2, 0));
+    h.keys("j'a");
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys(":marks<CR>");
+    assert!(h.screen.text().contains("b      3     2  gamma"));
+}
+
+#[test]
+fn buffers_keep_their_contents_and_picker_switches() {
+    let mut h = Harness::new(60, 16);
+    h.keys(":e /nonexistent/alpha.txt<CR>iaaa<Esc>:e /nonexistent/beta.txt<CR>ibbb<Esc>");
+    assert_eq!(h.lines(), vec!["bbb"]);
+    h.keys(":bp<CR>");
+    assert_eq!(h.lines(), vec!["aaa"]);
+    assert_eq!(h.cursor(), (0, 3));
+    h.keys("\\b");
+    assert!(h.screen.text().contains("Buffers>"));
+    assert!(h.screen.text().contains("beta.txt [+]  1 lines"));
+    h.keys("bet<CR>");
+    assert_eq!(h.lines(), vec!["bbb"]);
+    assert_eq!(h.editor.filename.as_deref(), Some("/nonexistent/beta.txt"));
+}
+
+#[test]
+fn window_title_follows_file_and_modified_state() {
+    let mut h = Harness::new(40, 5);
+    h.keys(":e /nonexistent/dir/notes.txt<CR>");
+    assert_eq!(h.screen.title, "notes.txt — vii");
+    h.keys("ix<Esc>");
+    assert_eq!(h.screen.title, "notes.txt [+] — vii");
+    assert_eq!(h.screen.line(0), "x");
+}
+
+#[test]
+fn closed_folds_show_one_summary_line() {
+    let lines: Vec<String> = (1..=6).map(|n| format!("l{}", n)).collect();
+    let lines: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+    let mut h = Harness::with_lines(30, 8, &lines);
+    h.keys("jzfjj");
+    assert_eq!(h.screen.line(0), "  l1");
+    assert!(h.screen.line(1).starts_with("+ +--  2 lines: l2··"));
+    assert_eq!(h.screen.line(2), "  l4");
+    assert_eq!(h.cursor(), (3, 0));
+    h.keys("kzo");
+    assert_eq!(h.screen.line(1), "- l2");
+    assert_eq!(h.screen.line(2), "│ l3");
+    h.keys(":2,5fold<CR>");
+    assert!(h.screen.line(1).starts_with("+ +--  4 lines: l2"));
+    assert_eq!(h.screen.line(2), "  l6");
+    h.keys("zE");
+    assert_eq!(h.screen.line(1), "l2");
+    h.keys("Vjjzf");
+    assert!(h.screen.line(1).starts_with("+ +--  3 lines: l2"));
+    assert!(h.screen.text().contains("-- NORMAL --"));
+}
+
+#[test]
+fn indent_folds_follow_the_indentation() {
+    let mut h = Harness::with_lines(60, 10, &["fn a() {", "    if x {", "        y();", "    }", "}", "fn b() {}"]);
+    h.keys(":set foldmethod=indent<CR>");
+    assert!(h.screen.line(1).starts_with("+ +--  3 lines: if x {"));
+    assert_eq!(h.screen.line(2), "  }");
+    h.keys("jzo");
+    assert_eq!(h.screen.line(1), "-     if x {");
+    assert!(h.screen.line(2).starts_with("+ +--  1 lines: y();"));
+    // 위에 줄을 넣어도 열고 닫은 상태가 그대로
+    h.keys("ki<CR><Esc>");
+    assert_eq!(h.screen.line(2), "-     if x {");
+    assert!(h.screen.line(3).starts_with("+ +--  1 lines: y();"));
+    h.keys("zfj");
+    assert!(h.screen.line(9).contains("Cannot create a fold"));
+}
+
+#[test]
+fn annotations_are_drawn_after_the_line_and_truncated() {
+    let mut h = Harness::with_lines(20, 4, &["let x = 1;", "a longer line"]);
+    h.editor.annotate(0, "i32");
+    h.editor.annotate(1, "too long to fit");
+    h.keys("");
+    assert_eq!(h.screen.line(0), "let x = 1;  i32");
+    assert_eq!(h.screen.line(1), "a longer line  too …");
+    assert_eq!(h.lines(), ["let x = 1;", "a longer line"]);
+    h.editor.annotate(0, "");
+    h.keys("");
+    assert_eq!(h.screen.line(0), "let x = 1;");
+}
+
+#[test]
+fn make_parses_errors_into_quickfix_and_jumps() {
+    let path = std::env::temp_dir().join(format!("vii_make_{}.rs", std::process::id()));
+    std::fs::write(&path, "fn main() {\n    let x = ;\n}\n").unwrap();
+    let path = path.to_str().unwrap().to_string();
+    let mut h = Harness::new(80, 6);
+    h.keys(":set makeprg=printf\\ '%s\\n'<CR>");
+    h.keys(&format!(":make 'Compiling' '{}:2:13: expected expression' 'warning: unused'<CR>", path));
+    assert_eq!(h.editor.filename.as_deref(), Some(path.as_str()));
+    assert_eq!(h.cursor(), (1, 12));
+    // 메시지가 먼저, 메시지가 사라지면 찾은 오류가 그 줄의 진단으로 보임
+    assert!(h.screen.line(5).contains("E:1 W:0 | -- NORMAL -- | rust | (1 of 1) expected expression"));
+    h.editor.run_command("set mt=1");
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    h.editor.poll_background();
+    h.keys("");
+    assert!(h.screen.line(5).contains("E:1 W:0 | -- NORMAL -- | rust | E: expected expression"));
+    h.editor.run_command("set mt=4000");
+    // 파일 이름이 없는 형식에 맞는 줄은 위치가 아님
+    h.keys(":set efm=E\\ %l\\ %m<CR>:make 'E 3 closing brace'<CR>");
+    assert_eq!(h.cursor(), (1, 12));
+    assert!(h.screen.line(5).contains("no errors"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn bracket_d_walks_diagnostics_and_the_panel_lists_them() {
+    let path = std::env::temp_dir().join(format!("vii_diagnostics_{}.rs", std::process::id()));
+    std::fs::write(&path, "fn main() {\n    let x = ;\n    let y = 2;\n}\n").unwrap();
+    let path = path.to_str().unwrap().to_string();
+    let mut h = Harness::new(100, 10);
+    h.keys(":set makeprg=printf\\ '%s\\n'<CR>");
+    h.keys(&format!(":make '{p}:3:9: warning: unused variable' '{p}:2:13: expected expression' 'other.rs:1:1: elsewhere'<CR>", p = path));
+    assert_eq!(h.cursor(), (2, 8));
+    h.keys("gg]d");
+    assert_eq!(h.cursor(), (1, 12));
+    assert!(h.screen.line(9).contains("E: expected expression"));
+    h.keys("]d");
+    assert_eq!(h.cursor(), (2, 8));
+    assert!(h.screen.line(9).contains("W: warning: unused variable"));
+    // 끝에서는 처음으로
+    h.keys("]d");
+    assert_eq!(h.cursor(), (1, 12));
+    h.keys("[d");
+    assert_eq!(h.cursor(), (2, 8));
+    h.keys(":Diagnostics<CR>");
+    let text = h.screen.text();
+    assert!(text.contains(&format!("[Diagnostics] {} (2 items)", path)));
+    assert!(text.contains("   2:13  E  expected expression"));
+    assert!(text.contains("   3:9   W  warning: unused variable"));
+    h.keys("<CR>");
+    assert_eq!(h.cursor(), (1, 12));
+    h.keys(":Diagnostics<CR>");
+    assert!(!h.screen.text().contains("[Diagnostics]"));
+    h.keys(":e /nonexistent/none.rs<CR>]d");
+    assert!(h.screen.line(9).contains("No diagnostics"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn make_columns_are_bytes_even_after_wide_characters() {
+    let path = std::env::temp_dir().join(format!("vii_make_utf8_{}.rs", std::process::id()));
+    std::fs::write(&path, "fn main() {\n    let é = ;\n}\n").unwrap();
+    let path = path.to_str().unwrap().to_string();
+    let mut h = Harness::new(100, 10);
+    h.keys(":set makeprg=printf\\ '%s\n'<CR>");
+    h.keys(&format!(":make '{}:2:14: expected expression'<CR>", path));
+    assert_eq!(h.cursor(), (1, 13));
+    h.keys("gg]d");
+    assert_eq!(h.cursor(), (1, 13));
+    h.keys(":Diagnostics<CR>");
+    assert!(h.screen.text().contains("   2:13  E  expected expression"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn abbreviations_expand_after_a_non_word_character() {
+    let mut h = Harness::new(60, 5);
+    h.keys(":iabbrev teh the<CR>:iab -ft=rust sopl println!()<CR>");
+    h.keys("iteh cat, tehx teh<Esc>");
+    assert_eq!(h.lines(), ["the cat, tehx the"]);
+    h.keys(":e /nonexistent/main.rs<CR>isopl;<CR>teh.<Esc>");
+    assert_eq!(h.lines(), ["println!();", "the."]);
+    h.keys(":iunabbrev teh<CR>i teh <Esc>");
+    assert_eq!(h.lines(), ["println!();", "the. teh "]);
+}
+
+#[test]
+fn ctrl_v_inserts_keys_literally_and_by_code() {
+    let mut h = Harness::new(40, 4);
+    h.keys("i<C-v><C-a><C-v><Esc><C-v>u00e9<C-v>x41<C-v>065<C-v>u3b!<Esc>");
+    assert_eq!(h.lines(), ["\u{1}\u{1b}éAA;!"]);
+    assert_eq!(h.screen.line(0), "␁␛éAA;!");
+    assert!(h.editor.mode == Mode::Normal);
+}
+
+#[test]
+fn change_shorthands_feed_registers_and_repeat_with_dot() {
+    let mut h = Harness::with_lines(60, 12, &["hello world", "one", "two", "three", "four"]);
+    h.keys("lllllD");
+    assert_eq!(h.lines()[0], "hello");
+    h.keys("jSuno<Esc>j.");
+    assert_eq!(h.lines()[1..3], ["uno", "uno"]);
+    h.keys("jccdrei<Esc>");
+    assert_eq!(h.lines()[3], "drei");
+    h.keys("jhhhhsF<Esc>");
+    assert_eq!(h.lines()[4], "Four");
+    h.keys("kCx<Esc>");
+    assert_eq!(h.lines()[3], "dx");
+    h.keys(":registers<CR>");
+    let screen = h.screen.text();
+    assert!(screen.contains("\"\"  c  rei"), "{}", screen);
+    assert!(screen.contains("\"3  l  three"), "{}", screen);
+}
+
+#[test]
+fn case_operators_change_case_over_motions_and_lines() {
+    let mut h = Harness::with_lines(60, 12, &["Hello World", "abc", "DEF"]);
+    h.keys("~");
+    assert_eq!(h.lines()[0], "hello World");
+    assert_eq!(h.cursor(), (0, 1));
+    h.keys("gU$");
+    assert_eq!(h.lines()[0], "hELLO WORLD");
+    h.keys("jgUU");
+    assert_eq!(h.lines()[1], "ABC");
+    h.keys("jg~g~");
+    assert_eq!(h.lines()[2], "def");
+    h.keys("guk");
+    assert_eq!(h.lines()[1..], ["abc", "def"]);
+    assert_eq!(h.cursor().0, 1);
+    h.keys("kguujgUUj.");
+    assert_eq!(h.lines(), vec!["hello world", "ABC", "DEF"]);
+}
+
+#[test]
+fn ctrl_a_and_ctrl_x_change_the_next_number() {
+    let mut h = Harness::with_lines(60, 12, &["x = 9;", "y = -2", "0x0f 0xFF", "v1.10"]);
+    h.keys("<C-a>");
+    assert_eq!(h.lines()[0], "x = 10;");
+    assert_eq!(h.cursor(), (0, 5));
+    h.keys("j5<C-a>");
+    assert_eq!(h.lines()[1], "y = 3");
+    h.keys("j<C-a>");
+    assert_eq!(h.lines()[2], "0x0f 0x100");
+    h.keys("hhhhhhhhh<C-a>");
+    assert_eq!(h.lines()[2], "0x10 0x100");
+    h.keys("j12<C-x>");
+    assert_eq!(h.lines()[3], "v1.-2");
+    h.keys(".");
+    assert_eq!(h.lines()[3], "v1.-14");
+}
+
+#[test]
+fn visual_ctrl_a_changes_numbers_in_the_selection() {
+    let mut h = Harness::with_lines(60, 12, &["a 0", "b 0", "c 0", "d 1 7"]);
+    h.keys("Vjj<C-a>");
+    assert_eq!(h.lines(), ["a 1", "b 1", "c 1", "d 1 7"]);
+    assert_eq!(h.cursor(), (0, 0));
+    h.keys("Vjjg<C-a>");
+    assert_eq!(h.lines(), ["a 2", "b 3", "c 4", "d 1 7"]);
+    // 글자 단위 선택은 선택 안의 숫자만 (마지막 줄은 "d " 까지)
+    h.keys("jlvjjg<C-x>");
+    assert_eq!(h.lines(), ["a 2", "b 2", "c 2", "d 1 7"]);
+    assert_eq!(h.cursor(), (1, 1));
+    assert!(h.screen.text().contains("-- NORMAL --"));
+    h.keys("u");
+    assert_eq!(h.lines(), ["a 2", "b 3", "c 4", "d 1 7"]);
+}
+
+#[test]
+fn change_list_and_gi_return_to_earlier_edits() {
+    let mut h = Harness::with_lines(60, 12, &["one", "two", "three", "four"]);
+    h.keys("x");
+    h.keys("jjllix<Esc>");
+    h.keys("j");
+    assert_eq!(h.cursor(), (3, 3));
+    h.keys("g;");
+    assert_eq!(h.cursor(), (2, 3));
+    h.keys("g;");
+    assert_eq!(h.cursor(), (0, 0));
+    h.keys("g;");
+    assert!(h.screen.text().contains("At start of change list"));
+    h.keys("g,");
+    assert_eq!(h.cursor(), (2, 3));
+    h.keys("kgiy<Esc>");
+    assert_eq!(h.lines()[2], "thxyree");
+}
+
+#[test]
+fn visual_selection_shifts_comments_and_changes_case() {
+    let mut h = Harness::with_lines(60, 12, &["let a = 1;", "", "let b = 2;"]);
+    h.keys("Vjj");
+    assert!(h.screen.text().contains("-- VISUAL LINE --"));
+    h.keys(">");
+    assert_eq!(h.lines(), vec!["    let a = 1;", "", "    let b = 2;"]);
+    assert_eq!(h.cursor(), (0, 4));
+    h.keys("gvgc");
+    assert_eq!(h.lines(), vec!["    # let a = 1;", "", "    # let b = 2;"]);
+    h.keys("gv<");
+    h.keys("gvgc");
+    assert_eq!(h.lines(), vec!["let a = 1;", "", "let b = 2;"]);
+    h.keys("vllU");
+    assert_eq!(h.lines()[0], "LET a = 1;");
+    h.keys("jjvk~");
+    assert_eq!(h.lines(), vec!["LET a = 1;", "", "Let b = 2;"]);
+    h.keys("vl<Esc>");
+    assert!(h.screen.text().contains("-- NORMAL --"));
+}
+
+#[test]
+fn put_takes_a_count_and_the_clipboard_register() {
+    let mut h = Harness::with_lines(60, 12, &["one", "two"]);
+    h.keys("yy2p");
+    assert_eq!(h.lines(), vec!["one", "one", "one", "two"]);
+    h.keys("jj\"+yykkk3\"+P");
+    assert_eq!(h.lines(), vec!["two", "two", "two", "one", "one", "one", "two"]);
+    h.keys("x3p");
+    assert_eq!(h.lines()[0], "wttto");
+    assert_eq!(h.cursor(), (0, 3));
+    h.keys("\"c2p");
+    assert!(h.screen.text().contains("Nothing in register c"));
+    // 줄바꿈으로 끝나지 않는 여러 줄 클립보드는 글자 단위로, 줄을 나눠서 넣음
+    let mut h = Harness::with_lines(60, 12, &["xy"]);
+    h.set_clipboard("a\nb");
+    h.keys("\"+p");
+    assert_eq!(h.lines(), vec!["xa", "by"]);
+    assert_eq!(h.cursor(), (0, 1));
+    h.keys("u");
+    assert_eq!(h.lines(), vec!["xy"]);
+}
+
+#[test]
+fn moved_lines_follow_block_indentation_and_duplicate() {
+    let mut h = Harness::with_lines(60, 12, &["fn a() {", "    x();", "}", "y();"]);
+    h.keys("j<M-j>");
+    assert_eq!(h.lines(), vec!["fn a() {", "}", "x();", "y();"]);
+    assert_eq!(h.cursor(), (2, 0));
+    h.keys("j<M-k><M-k>");
+    assert_eq!(h.lines(), vec!["fn a() {", "    y();", "}", "x();"]);
+    assert_eq!(h.cursor(), (1, 4));
+    h.keys("<M-d>");
+    assert_eq!(h.lines(), vec!["fn a() {", "    y();", "    y();", "}", "x();"]);
+    assert_eq!(h.cursor().0, 2);
+    h.keys("Vk<M-d><Esc>");
+    assert_eq!(h.lines().len(), 7);
+    assert_eq!(h.cursor().0, 3);
+    h.keys(":1MoveDown<CR>");
+    assert_eq!(h.lines()[..2], ["    y();", "fn a() {"]);
+    h.keys(":6,7Duplicate<CR>");
+    assert_eq!(h.lines()[5..], ["}", "x();", "}", "x();"]);
+    // 탭 들여쓰기는 tabstop 칸으로 재고 noexpandtab 이면 탭으로 다시 씀
+    let mut h = Harness::with_lines(60, 12, &["x();", "\tif (a) {", "\t}"]);
+    h.keys(":set noet ts=8 sw=8<CR><M-j>");
+    assert_eq!(h.lines(), vec!["\tif (a) {", "\t\tx();", "\t}"]);
+    assert_eq!(h.cursor(), (1, 2));
+    h.keys("<M-k>:set sw=4<CR><M-j>");
+    assert_eq!(h.lines()[1], "\t    x();");
+    h.keys("<M-k>:set et<CR><M-j>");
+    assert_eq!(h.lines()[1], "            x();");
+}
+
+#[test]
+fn sort_orders_a_range_with_flags() {
+    let mut h = Harness::with_lines(60, 12, &["b", "A", "c", "a", "B"]);
+    h.keys(":sort<CR>");
+    assert_eq!(h.lines(), vec!["A", "B", "a", "b", "c"]);
+    h.keys(":sort! iu<CR>");
+    assert_eq!(h.lines(), vec!["c", "b", "a"]);
+    assert!(h.screen.text().contains("2 duplicate lines removed"));
+    let mut h = Harness::with_lines(60, 12, &["head", "x10", "none", "x-3", "x2", "tail"]);
+    h.keys(":2,5sort n<CR>");
+    assert_eq!(h.lines(), vec!["head", "none", "x-3", "x2", "x10", "tail"]);
+    h.keys(":sort q<CR>");
+    assert!(h.screen.text().contains("Invalid sort flag: q"));
+}
+
+#[test]
+fn undo_tree_keeps_branches_and_walks_chronologically() {
+    let mut h = Harness::with_lines(60, 12, &["base"]);
+    h.keys("ione <Esc>");
+    h.keys("itwo <Esc>");
+    assert_eq!(h.lines(), vec!["one two base"]);
+    h.keys("u");
+    assert_eq!(h.lines(), vec!["one base"]);
+    h.keys("<C-r>");
+    assert_eq!(h.lines(), vec!["one two base"]);
+    h.keys("uuithree <Esc>");
+    assert_eq!(h.lines(), vec!["three base"]);
+    h.keys("g-");
+    assert_eq!(h.lines(), vec!["one two base"]);
+    h.keys("g-g-");
+    assert_eq!(h.lines(), vec!["base"]);
+    h.keys("u");
+    assert!(h.screen.text().contains("Already at oldest change"));
+    h.keys("g+g+g+");
+    assert_eq!(h.lines(), vec!["three base"]);
+    h.keys(":undotree<CR>");
+    let screen = h.screen.text();
+    assert!(screen.contains("[Undo Tree] state time change (4 items)"), "{}", screen);
+    assert!(screen.contains("> 3 "), "{}", screen);
+    h.keys("jj<CR>");
+    assert_eq!(h.lines(), vec!["one two base"]);
+}
+
+#[test]
+fn earlier_and_later_move_by_steps_or_time() {
+    let mut h = Harness::with_lines(60, 12, &["x"]);
+    h.keys("ia<Esc>ib<Esc>ic<Esc>");
+    assert_eq!(h.lines(), vec!["abcx"]);
+    h.keys(":earlier 2<CR>");
+    assert_eq!(h.lines(), vec!["ax"]);
+    h.keys(":later<CR>");
+    assert_eq!(h.lines(), vec!["abx"]);
+    h.keys(":earlier 1h<CR>");
+    assert_eq!(h.lines(), vec!["x"]);
+    h.keys(":later 10m<CR>");
+    assert_eq!(h.lines(), vec!["abcx"]);
+    h.keys(":earlier 5y<CR>");
+    assert!(h.screen.text().contains("Invalid argument: 5y"));
+}
+
+#[test]
+fn ctrl_n_adds_cursors_that_edit_together() {
+    let mut h = Harness::with_lines(60, 12, &["foo bar foo", "x foo", "foobar"]);
+    h.keys("l<C-n><C-n>");
+    assert!(h.screen.text().contains("3 cursors"));
+    assert_eq!(h.cursor(), (0, 0));
+    h.keys("<C-n>");
+    assert!(h.screen.text().contains("No more matches: foo"));
+    h.keys("iX<Esc>x");
+    assert_eq!(h.lines(), vec!["Xoo bar Xoo", "x Xoo", "foobar"]);
+    h.keys("<Esc>x");
+    assert_eq!(h.lines(), vec!["Xo bar Xoo", "x Xoo", "foobar"]);
+}
+
+#[test]
+fn substitute_and_bufdo_argdo_run_in_every_buffer() {
+    let mut h = Harness::with_lines(80, 8, &["foo Foo foo", "foo"]);
+    h.keys(":s/foo/bar/<CR>");
+    assert_eq!(h.lines(), vec!["bar Foo foo", "foo"]);
+    h.keys(":%s/foo/baz/gi<CR>");
+    assert_eq!(h.lines(), vec!["bar baz baz", "baz"]);
+    assert!(h.screen.text().contains("3 substitutions on 2 lines"));
+    h.keys(":s#a/#x#<CR>");
+    assert!(h.screen.text().contains("Pattern not found: a/"));
+
+    h.keys(":e /nonexistent/one.txt<CR>ia a<Esc>:e /nonexistent/two.txt<CR>ib<Esc>");
+    h.keys(":bufdo %s/a/c/g<CR>");
+    assert!(h.screen.text().contains("bufdo: 3 lines changed in 2 of 3 buffers"));
+    assert_eq!(h.editor.filename.as_deref(), Some("/nonexistent/two.txt"));
+    h.keys(":b 2<CR>");
+    assert_eq!(h.lines(), vec!["c c"]);
+
+    let dir = std::env::temp_dir();
+    let files: Vec<String> = ["one", "two"]
+        .iter()
+        .map(|name| {
+            let path = dir.join(format!("vii_argdo_{}_{}.txt", name, std::process::id()));
+            std::fs::write(&path, format!("{}\n", name)).unwrap();
+            path.to_str().unwrap().to_string()
+        })
+        .collect();
+    let mut h = Harness::new(80, 6);
+    h.editor.set_arglist(&files);
+    h.keys(":argdo s/o/0/g<CR>");
+    assert!(h.screen.text().contains("argdo: 2 lines changed in 2 of 2 files"));
+    assert_eq!(h.lines(), vec!["0ne"]);
+    h.keys(":b 2<CR>");
+    assert_eq!(h.lines(), vec!["tw0"]);
+    for file in &files {
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
+#[test]
+fn argument_list_moves_between_files() {
+    let mut h = Harness::new(100, 6);
+    h.keys(":next<CR>");
+    assert!(h.screen.text().contains("Argument list is empty"));
+    let files: Vec<String> = ["a", "b", "c"].iter().map(|f| format!("/nonexistent/{}.txt", f)).collect();
+    h.editor.set_arglist(&files);
+    h.keys(":args<CR>");
+    assert!(h.screen.text().contains("[/nonexistent/a.txt] /nonexistent/b.txt /nonexistent/c.txt"));
+    h.keys("ione<Esc>:n<CR>");
+    assert_eq!(h.editor.filename.as_deref(), Some("/nonexistent/b.txt"));
+    assert!(h.screen.text().contains("\"/nonexistent/b.txt\" (2 of 3)"));
+    h.keys(":last<CR>:next<CR>");
+    assert!(h.screen.text().contains("Cannot go beyond last file"));
+    h.keys(":first<CR>");
+    assert_eq!(h.lines(), vec!["one"]);
+    h.keys(":prev<CR>");
+    assert!(h.screen.text().contains("Cannot go before first file"));
+    h.keys(":args /nonexistent/x.txt /nonexistent/y.txt<CR>:N<CR>:n<CR>:ar<CR>");
+    assert!(h.screen.text().contains("/nonexistent/x.txt [/nonexistent/y.txt]"));
+}
+
+#[test]
+fn counts_apply_to_operators_and_edits() {
+    let mut h = Harness::with_lines(60, 12, &["one", "two", "three", "four", "five"]);
+    h.keys("3ddp");
+    assert_eq!(h.lines(), vec!["four", "one", "two", "three", "five"]);
+    h.keys("k2>>");
+    assert_eq!(h.lines(), vec!["    four", "    one", "two", "three", "five"]);
+    assert_eq!(h.cursor(), (0, 4));
+    h.keys("j4<<");
+    assert_eq!(h.lines(), vec!["    four", "one", "two", "three", "five"]);
+    h.keys("10x");
+    assert_eq!(h.lines()[1], "");
+    h.keys("j2yyjjj");
+    h.keys("5p");
+    assert_eq!(h.lines().len(), 15);
+
+    let mut h = Harness::with_lines(60, 12, &["alpha beta.gamma delta", "x"]);
+    h.keys("2dw");
+    assert_eq!(h.lines()[0], ".gamma delta");
+    h.keys("d2w");
+    assert_eq!(h.lines()[0], "delta");
+    h.keys("u2d2w");
+    assert_eq!(h.lines()[0], "");
+    h.keys("uwcwfoo<Esc>");
+    assert_eq!(h.lines()[0], ".foo delta");
+    h.keys("2w");
+    assert_eq!(h.cursor(), (1, 0));
+    h.keys("k.");
+    assert_eq!(h.lines()[0], "foofoo delta");
+}
+
+#[test]
+fn at_colon_repeats_the_last_command_line() {
+    let mut h = Harness::with_lines(60, 12, &["a1", "a2", "a3", "a4", "a5"]);
+    h.keys("@:");
+    assert!(h.screen.text().contains("No previous command line"));
+    h.keys(":.,+1s/a/b/<CR>");
+    assert_eq!(h.lines(), vec!["b1", "b2", "a3", "a4", "a5"]);
+    h.keys("j@:");
+    assert_eq!(h.lines(), vec!["b1", "b2", "b3", "b4", "a5"]);
+    h.keys("@@");
+    assert_eq!(h.lines(), vec!["b1", "b2", "b3", "b4", "b5"]);
+    h.keys(":MoveUp<CR>2@:");
+    assert_eq!(h.lines(), vec!["b1", "b5", "b2", "b3", "b4"]);
+    h.keys("@x");
+    assert!(h.screen.text().contains("Invalid register name: x"));
+}
+
+#[test]
+fn autocommands_run_on_events() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("vii_autocmd_{}.txt", std::process::id()));
+    std::fs::write(&path, "one  \ntwo\t\n").unwrap();
+    let mut h = Harness::new(100, 5);
+    h.keys(":autocmd BufWritePre *.txt trim<CR>:autocmd ModeChanged *:i set cul<CR>");
+    h.keys(":autocmd BufReadPost,BufNewFile *.md set sw=2<CR>:autocmd<CR>");
+    assert!(h.screen.line(4).contains("BufWritePre *.txt trim | ModeChanged *:i set cul | BufReadPost *.md"));
+    h.keys(&format!(":e {}<CR>:set cul?<CR>", path.display()));
+    assert!(h.screen.line(4).contains("| nocul"));
+    h.keys("i<Esc>:set cul?<CR>");
+    assert!(h.screen.line(4).contains("| cul"));
+    h.keys(":w<CR>");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\ntwo");
+    h.keys(":e /nonexistent/notes.md<CR>:set sw?<CR>");
+    assert!(h.screen.line(4).contains("sw=2"));
+    h.keys(":set sw=4<CR>:autocmd! BufNewFile<CR>:e /nonexistent/more.md<CR>:set sw?<CR>");
+    assert!(h.screen.line(4).contains("sw=4"));
+    h.keys(":autocmd Nope * w<CR>");
+    assert!(h.screen.line(4).contains("No such event: Nope"));
+    h.keys(":autocmd!<CR>:autocmd<CR>");
+    assert!(h.screen.line(4).contains("No autocommands"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn long_lines_show_markers_for_hidden_text() {
+    let mut h = Harness::with_lines(10, 5, &["0123456789abc", "short", ""]);
+    h.keys("<Esc>");
+    assert_eq!(h.screen.line(0), "012345678>");
+    assert_eq!(h.screen.line(1), "short");
+    h.keys(&"l".repeat(12));
+    assert_eq!(h.screen.line(0), "<456789abc");
+    assert_eq!(h.screen.line(1), "<t");
+    assert_eq!(h.screen.line(2), "");
+    h.keys(":set wrap<CR>");
+    assert_eq!(h.screen.line(0), "0123456789");
+    assert_eq!(h.screen.line(1), "abc");
+}
+
+#[test]
+fn messages_lists_earlier_status_messages() {
+    let mut h = Harness::with_lines(60, 16, &["a", "b"]);
+    h.keys(":messages clear<CR>:messages<CR>");
+    assert!(h.screen.line(15).contains("No messages"));
+    h.keys(":set sw?<CR>:nosuch<CR>:set ts?<CR>:messages<CR>");
+    let text = h.screen.text();
+    assert!(text.contains("[Messages]"));
+    assert!(text.contains("No messages\nsw=4\nE Unknown: nosuch\nts=8"));
+    h.keys("q:messages clear<CR>:messages<CR>");
+    assert!(h.screen.line(15).contains("No messages"));
+}
+
+#[test]
+fn failed_reads_and_writes_show_errors() {
+    let dir = std::env::temp_dir().join(format!("vii_errors_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut h = Harness::with_lines(80, 5, &["a"]);
+    h.keys(":w<CR>");
+    assert!(h.screen.line(4).contains("No file name"));
+    h.keys(&format!(":e {}<CR>", dir.display()));
+    assert!(h.screen.line(4).contains(&format!("{}: Is a directory", dir.display())));
+    h.keys("ix<Esc>:wq<CR>iy<Esc>");
+    assert_eq!(h.lines(), ["xy"]);
+    assert!(std::fs::metadata(&dir).unwrap().is_dir());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+    h.keys(":e /nonexistent/dir/file<CR>iz<Esc>:w<CR>");
+    assert!(h.screen.line(4).contains("/nonexistent/dir/file: No such file or directory"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn write_after_a_failed_read_leaves_the_file_alone() {
+    let path = std::env::temp_dir().join(format!("vii_unreadable_{}.txt", std::process::id()));
+    std::fs::write(&path, b"caf\xe9\n").unwrap();
+    let mut h = Harness::new(100, 5);
+    h.keys(&format!(":e {}<CR>", path.display()));
+    assert!(h.screen.line(4).contains("valid UTF-8"));
+    h.keys("ix<Esc>:w<CR>");
+    assert!(h.screen.line(4).contains("readonly option is set"));
+    assert_eq!(std::fs::read(&path).unwrap(), b"caf\xe9\n");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn termination_signal_writes_recovery_files() {
+    let dir = std::env::temp_dir().join(format!("vii_signal_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+    std::fs::write(&a, "one").unwrap();
+    std::fs::write(&b, "two").unwrap();
+    let mut h = Harness::new(80, 5);
+    let swapdir = dir.join("swap");
+    h.keys(&format!(":set swapdir={}<CR>:e {}<CR>ix<Esc>:e {}<CR>", swapdir.display(), a.display(), b.display()));
+    h.screen.push_signal(Signal::Term);
+    let err = vii::run(&mut h.editor, &mut h.screen).unwrap_err();
+    let swap = swapdir.join(format!("{}.vii.swp", a.display().to_string().replace('/', "%")));
+    assert_eq!(err.to_string(), format!("caught SIGTERM, unsaved changes written to {}", swap.display()));
+    assert_eq!(std::fs::read_to_string(&swap).unwrap(), "xone");
+    // 복구 파일은 바뀐 버퍼의 것 하나, 나머지는 연 두 파일의 잠금 파일
+    let names: Vec<String> = std::fs::read_dir(&swapdir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    assert_eq!(names.iter().filter(|n| n.ends_with(".vii.swp")).count(), 1);
+    assert_eq!(names.iter().filter(|n| n.ends_with(".vii-lock")).count(), 2);
+    assert_eq!(std::fs::read_to_string(&a).unwrap(), "one");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn backups_and_undo_files_go_to_their_directories() {
+    let dir = std::env::temp_dir().join(format!("vii_artifacts_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("a.txt");
+    std::fs::write(&file, "one\n").unwrap();
+    let mangled = file.display().to_string().replace('/', "%");
+    let (backupdir, undodir) = (dir.join("backup"), dir.join("state/undo"));
+    let set = format!(":set backup bdir={} undofile udir={}<CR>", backupdir.display(), undodir.display());
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!("{}:set dir?<CR>", set));
+    assert!(h.screen.text().contains("vii/swap"));
+    h.keys(&format!(":e {}<CR>dditwo<Esc>:w<CR>:s/two/three/<CR>:w<CR>", file.display()));
+    assert_eq!(std::fs::read_to_string(backupdir.join(format!("{}~", mangled))).unwrap(), "two");
+    assert!(undodir.join(format!("{}.undo", mangled)).exists());
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!("{}:e {}<CR>u", set, file.display()));
+    assert_eq!(h.lines(), ["two"]);
+    h.keys("uu");
+    assert_eq!(h.lines(), ["one"]);
+    h.keys("<C-r>");
+    assert_eq!(h.lines(), [""]);
+    // 파일이 밖에서 바뀌었으면 기록을 쓰지 않음
+    std::fs::write(&file, "changed\n").unwrap();
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!("{}:e {}<CR>u", set, file.display()));
+    assert_eq!(h.lines(), ["changed"]);
+    assert!(h.screen.text().contains("Already at oldest change"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn greplace_lists_matches_and_applies_the_checked_ones() {
+    let dir = std::env::temp_dir().join(format!("vii_greplace_{}", std::process::id()));
+    let mark = format!("MARK{}", std::process::id());
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.txt"), format!("one {m}\ntwo\n{m} {m}\n", m = mark)).unwrap();
+    std::fs::write(dir.join("b.txt"), format!("{}\n", mark)).unwrap();
+    let mut h = Harness::new(100, 12);
+    h.keys(&format!(":e {}<CR>:e {}<CR>", dir.join("a.txt").display(), dir.join("b.txt").display()));
+    h.keys(&format!(":greplace/{}/done/ {}<CR>", mark, dir.display()));
+    let text = h.screen.text();
+    assert!(text.contains(&format!("[Replace] {} -> done: Space toggle, a all, w apply, q cancel (3 items)", mark)));
+    assert!(text.contains(&format!("[x] {}:1: one done", dir.join("a.txt").display())));
+    assert!(text.contains(&format!("[x] {}:3: done done", dir.join("a.txt").display())));
+    h.keys(" ");
+    assert!(h.screen.text().contains(&format!("[ ] {}:1: one done", dir.join("a.txt").display())));
+    h.keys("w");
+    assert!(h.screen.text().contains("Replaced 2 lines in 2 files"));
+    assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), format!("one {}\ntwo\ndone done\n", mark));
+    assert_eq!(std::fs::read_to_string(dir.join("b.txt")).unwrap(), "done\n");
+    assert_eq!(h.lines(), ["done"]);
+    assert!(!h.editor.buffer.dirty);
+    // 숨은 버퍼도 바뀌고 u 로 되돌릴 수 있음
+    h.keys(&format!(":e {}<CR>", dir.join("a.txt").display()));
+    assert_eq!(h.lines(), ["one ".to_string() + &mark, "two".into(), "done done".into()]);
+    h.keys("u");
+    assert_eq!(h.lines()[2], format!("{m} {m}", m = mark));
+    // 저장하지 않은 버퍼가 있으면 바꾸지 않음
+    h.keys(&format!(":e {}<CR>ix<Esc>:greplace/{}/x/ {}<CR>w", dir.join("a.txt").display(), mark, dir.display()));
+    assert!(h.screen.text().contains(&format!("{} has unsaved changes", dir.join("a.txt").display())));
+    assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), format!("one {}\ntwo\ndone done\n", mark));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn ctrl_bracket_jumps_to_tags_and_ctrl_t_comes_back() {
+    let dir = std::env::temp_dir().join(format!("vii_tags_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("main.rs"), "fn main() {\n    helper();\n}\n").unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "// lib\n\nfn helper() {\n}\n").unwrap();
+    std::fs::write(dir.join("src/other.rs"), "a\nb\nfn helper() {}\n").unwrap();
+    let tags = "!_TAG_FILE_FORMAT\t2\nhelper\tsrc/lib.rs\t/^fn helper() {$/;\"\tf\nhelper\tsrc/other.rs\t3;\"\tf\n";
+    std::fs::write(dir.join("tags"), tags).unwrap();
+    let mut h = Harness::new(80, 12);
+    h.keys(&format!(":e {}<CR>j^<C-]>", dir.join("main.rs").display()));
+    assert_eq!(h.lines(), ["// lib", "", "fn helper() {", "}"]);
+    assert_eq!(h.cursor(), (2, 0));
+    assert!(h.screen.line(11).contains("tag 1 of 2 (:tselect helper to choose)"));
+    h.keys("<C-t>");
+    assert_eq!(h.lines()[1], "    helper();");
+    assert_eq!(h.cursor(), (1, 4));
+    h.keys(":tselect helper<CR>");
+    assert!(h.screen.text().contains("[Tags] helper (2 items)"));
+    assert!(h.screen.text().contains(&format!("  2 f  {}  line 3", dir.join("src/other.rs").display())));
+    h.keys("j<CR>");
+    assert_eq!(h.lines(), ["a", "b", "fn helper() {}"]);
+    assert_eq!(h.cursor().0, 2);
+    h.keys("<C-t><C-t>");
+    assert!(h.screen.line(11).contains("Tag stack is empty"));
+    h.keys(":tag nothing<CR>");
+    assert!(h.screen.line(11).contains("Tag not found: nothing"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn text_from_stdin_can_be_written_to_a_file() {
+    let path = std::env::temp_dir().join(format!("vii_stdin_{}.txt", std::process::id()));
+    let mut h = Harness::new(80, 5);
+    h.editor.load_text("#!/bin/sh\necho hi\n");
+    h.keys(":filetype<CR>");
+    assert_eq!(h.lines(), ["#!/bin/sh", "echo hi"]);
+    assert!(h.screen.line(4).contains("filetype=sh"));
+    h.keys(&format!(":w {}<CR>", path.display()));
+    assert!(h.screen.line(4).contains(&format!("Saved to {}", path.display())));
+    let copy = path.with_extension("bak");
+    h.keys(&format!("x:w {}<CR>", copy.display()));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "#!/bin/sh\necho hi");
+    assert_eq!(std::fs::read_to_string(&copy).unwrap(), "!/bin/sh\necho hi");
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&copy).unwrap();
+}
+
+#[test]
+fn batch_mode_runs_ex_commands_without_a_screen() {
+    let dir = std::env::temp_dir().join(format!("vii_batch_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (file, script) = (dir.join("a.txt"), dir.join("fix.vii"));
+    std::fs::write(&file, "foo 1\nfoo 2\n").unwrap();
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let name = file.display().to_string();
+    let errors = batch::run(&args(&["-c", "%s/foo/bar/g", "+nosuch", "+wq", &name]), &mut EditorConfig::new()).unwrap();
+    assert_eq!(errors, ["nosuch: Unknown: nosuch"]);
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "bar 1\nbar 2");
+    std::fs::write(&script, "\" 줄을 뒤집음\n:%sort!\n\nw\n").unwrap();
+    let errors = batch::run(&args(&["-S", &script.display().to_string(), &name]), &mut EditorConfig::new()).unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "bar 2\nbar 1");
+    assert!(batch::run(&args(&["-c"]), &mut EditorConfig::new()).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn every_command_line_file_gets_a_buffer() {
+    let dir = std::env::temp_dir().join(format!("vii_files_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let files: Vec<String> = ["a", "b", "c"].iter().map(|n| dir.join(n).display().to_string()).collect();
+    for (file, text) in files.iter().zip(["one", "two", "three"]) {
+        std::fs::write(file, text).unwrap();
+    }
+    let mut h = Harness::new(100, 8);
+    h.editor.set_arglist(&files);
+    h.keys("<Esc>");
+    assert_eq!(h.lines(), ["one"]);
+    assert!(h.screen.line(7).contains("3 files to edit"));
+    h.keys(":b 3<CR>");
+    assert_eq!(h.lines(), ["three"]);
+    h.keys(":b 1<CR>");
+    h.keys(":bn<CR>");
+    assert_eq!(h.lines(), ["two"]);
+    h.keys(":next<CR>");
+    assert_eq!(h.lines(), ["two"]);
+    assert!(h.screen.line(7).contains("(2 of 3)"));
+    h.keys(":last<CR>");
+    assert_eq!(h.lines(), ["three"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn plus_arguments_position_the_cursor_at_startup() {
+    let mut h = Harness::with_lines(60, 5, &["one", "  two", "three TODO", "four"]);
+    h.editor.startup_command("2");
+    assert_eq!(h.cursor(), (1, 2));
+    h.editor.startup_command("99");
+    assert_eq!(h.cursor(), (3, 0));
+    h.editor.startup_command("/TODO");
+    assert_eq!(h.cursor(), (2, 6));
+    h.editor.startup_command("");
+    assert_eq!(h.cursor(), (3, 0));
+    h.editor.startup_command("/nowhere");
+    h.keys("<Esc>");
+    assert!(h.screen.line(4).contains("Pattern not found: nowhere"));
+    h.editor.startup_command("set sw=2");
+    h.keys(":set sw?<CR>");
+    assert!(h.screen.line(4).contains("sw=2"));
+}
+
+#[test]
+fn command_line_arguments_are_parsed_into_an_action() {
+    let parse = |list: &[&str]| cli::parse(&list.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+    let Ok(Action::Edit(edit)) = parse(&["-R", "a.rs", "+42", "-c", "set nu", "--clean", "--", "-b.rs"]) else { panic!() };
+    assert_eq!(edit.files, ["a.rs", "-b.rs"]);
+    assert_eq!(edit.commands, ["42", "set nu"]);
+    assert!(edit.readonly && edit.clean && !edit.stdin);
+    let Ok(Action::Edit(edit)) = parse(&["-d", "x", "y", "--timing"]) else { panic!() };
+    assert_eq!(edit.diff, Some(("x".into(), "y".into())));
+    assert!(edit.timing);
+    assert_eq!(parse(&["a", "--help"]), Ok(Action::Help));
+    assert_eq!(parse(&["--version"]), Ok(Action::Version));
+    assert_eq!(parse(&["-es", "-c", "wq"]), Ok(Action::Batch(vec!["-c".into(), "wq".into()])));
+    assert_eq!(parse(&["--server", "s.sock"]), Ok(Action::Server("s.sock".into())));
+    assert_eq!(parse(&["-x"]), Err("Unknown option: -x".into()));
+    assert_eq!(parse(&["-d", "x"]), Err("-d needs two files".into()));
+    assert_eq!(parse(&["-", "a"]), Err("- cannot be combined with files".into()));
+    let Ok(Action::Edit(edit)) = parse(&["--tutor"]) else { panic!() };
+    assert!(edit.tutor && edit.files.is_empty());
+    assert_eq!(parse(&["--tutor", "a"]), Err("--tutor cannot be combined with files".into()));
+}
+
+#[test]
+fn readonly_buffers_are_only_written_with_a_bang() {
+    let path = std::env::temp_dir().join(format!("vii_readonly_{}.txt", std::process::id()));
+    std::fs::write(&path, "a").unwrap();
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":set ro<CR>:e {}<CR>xib<Esc>:w<CR>", path.display()));
+    assert!(h.screen.line(4).contains("readonly option is set (add ! to override)"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "a");
+    h.keys(":wq<CR>:w!<CR>:set ro?<CR>");
+    assert!(h.screen.line(4).contains("| ro "));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "b");
+    // 한 번 쓴 뒤에도 ! 없는 :w 는 거절
+    h.keys("xic<Esc>:w<CR>");
+    assert!(h.screen.line(4).contains("readonly option is set (add ! to override)"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "b");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn pasted_text_is_inserted_in_one_edit() {
+    let mut h = Harness::with_lines(60, 8, &["ab"]);
+    h.keys(":inoremap x y<CR>li");
+    h.screen.push_paste("x1\nx2\nx3");
+    h.keys("<Esc>");
+    assert_eq!(h.lines(), ["ax1", "x2", "x3b"]);
+    assert_eq!(h.screen.line(2), "x3b");
+    h.keys("u");
+    assert_eq!(h.lines(), ["ab"]);
+    assert_eq!(h.cursor(), (0, 1));
+    h.screen.push_paste("[]");
+    h.keys("<Esc>");
+    assert_eq!(h.lines(), ["a[]b"]);
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys("u");
+    assert_eq!(h.lines(), ["ab"]);
+    // 비주얼 모드에서는 선택을 바꿈
+    let mut v = Harness::with_lines(60, 8, &["one two", "three", "four"]);
+    v.keys("wvjh");
+    v.screen.push_paste("X\nY");
+    v.keys("");
+    assert_eq!(v.lines(), ["one X", "Ye", "four"]);
+    assert_eq!(v.cursor(), (1, 0));
+    v.keys("u");
+    assert_eq!(v.lines(), ["one two", "three", "four"]);
+    v.keys("jVj");
+    v.screen.push_paste("new\n");
+    v.keys("");
+    assert_eq!(v.lines(), ["one two", "new"]);
+    assert!(matches!(v.editor.mode, vii::Mode::Normal));
+    h.keys(":");
+    h.screen.push_paste("set sw=3\nignored");
+    h.keys("<CR>:set sw?<CR>");
+    assert!(h.screen.line(7).contains("sw=3"));
+}
+
+#[test]
+fn slash_and_question_mark_search_and_n_repeats() {
+    let mut h = Harness::with_lines(80, 5, &["foo bar", "baz foo", "foo"]);
+    h.keys("/foo<CR>");
+    assert_eq!(h.cursor(), (1, 4));
+    h.keys("n");
+    assert_eq!(h.cursor(), (2, 0));
+    h.keys("n");
+    assert_eq!(h.cursor(), (0, 0));
+    h.keys("N");
+    assert_eq!(h.cursor(), (2, 0));
+    h.keys("?ba<CR>");
+    assert_eq!(h.cursor(), (1, 0));
+    h.keys("n");
+    assert_eq!(h.cursor(), (0, 4));
+    h.keys("/nope<CR>");
+    assert!(h.screen.line(4).contains("Pattern not found: nope"));
+    h.keys(":%s/foo//n<CR>");
+    assert!(h.screen.line(4).contains("3 matches on 3 lines"));
+    assert_eq!(h.lines()[0], "foo bar");
+}
+
+#[test]
+fn searches_in_large_buffers_run_in_the_background() {
+    let mut lines = vec!["x"; 150_000];
+    lines[120_000] = "needle";
+    let mut h = Harness::with_lines(80, 5, &lines);
+    h.keys("/needle<CR>");
+    assert!(h.editor.is_searching());
+    assert_eq!(h.cursor(), (0, 0));
+    h.keys("<Esc>");
+    assert!(!h.editor.is_searching());
+    assert!(h.screen.line(4).contains("cancelled"));
+    let wait = |h: &mut Harness| {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while h.editor.is_searching() && std::time::Instant::now() < deadline {
+            h.editor.poll_background();
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    };
+    h.keys("n");
+    wait(&mut h);
+    assert_eq!(h.cursor(), (120_000, 0));
+    h.keys(":%s/x//n<CR>");
+    wait(&mut h);
+    h.keys("<Esc>");
+    assert!(h.screen.line(4).contains("149999 matches on 149999 lines"));
+    // 다른 버퍼로 옮기면 그만둠
+    let other = std::env::temp_dir().join(format!("vii_search_other_{}.txt", std::process::id()));
+    h.keys(&format!("gg/needle<CR>:e {}<CR>", other.display()));
+    assert!(!h.editor.is_searching());
+    h.editor.poll_background();
+    assert_eq!(h.cursor(), (0, 0));
+    assert!(!other.exists());
+}
+
+#[test]
+fn profile_records_render_key_and_latency_timings() {
+    let mut h = Harness::with_lines(100, 8, &["a", "b"]);
+    h.keys(":profile<CR>");
+    assert!(h.screen.line(7).contains("Not profiling (use :profile on)"));
+    h.keys(":profile on<CR>jk");
+    let report = h.editor.profile_report();
+    for name in ["render", "keys", "input latency"] {
+        assert!(report.iter().any(|l| l.starts_with(name) && l.contains(" calls  avg ")), "{}", name);
+    }
+    h.keys(":profile<CR>");
+    assert!(h.screen.text().contains("[Profile] measuring"));
+    h.keys("q:profile off<CR>:profile clear<CR>:profile<CR>");
+    assert!(h.screen.line(7).contains("Not profiling"));
+}
+
+#[test]
+fn newer_recovery_files_are_offered_when_opening() {
+    let dir = std::env::temp_dir().join(format!("vii_recover_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (file, swap) = (dir.join("a.txt"), dir.join(".a.txt.vii.swp"));
+    let age = |path: &std::path::Path, secs: u64| {
+        let time = std::time::SystemTime::now() - std::time::Duration::from_secs(secs);
+        std::fs::File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+    };
+    std::fs::write(&file, "one\ntwo\n").unwrap();
+    std::fs::write(&swap, "one\n2").unwrap();
+    age(&file, 60);
+    // vii a.txt 로 시작할 때처럼 키를 거치지 않고 엶
+    let mut h = Harness::new(100, 12);
+    h.keys(":set dir=.<CR>");
+    h.editor.open_file(&file.to_string_lossy());
+    h.keys("");
+    let text = h.screen.text();
+    assert!(text.contains("[Recovery] .a.txt.vii.swp is newer: r recover, d diff, x delete, q ignore"));
+    assert!(text.contains("    2 - two") && text.contains("    2 + 2"));
+    h.keys("r");
+    assert_eq!(h.lines(), ["one", "2"]);
+    assert!(!swap.exists());
+    assert!(h.editor.buffer.dirty);
+    h.keys("u");
+    assert_eq!(h.lines(), ["one", "two"]);
+    h.keys("<C-r>");
+    assert_eq!(h.lines(), ["one", "2"]);
+    std::fs::write(&swap, "x").unwrap();
+    age(&file, 60);
+    let mut h = Harness::new(100, 12);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>d", file.display()));
+    assert!(h.screen.line(11).contains("Diff with"));
+    assert!(swap.exists());
+    let mut h = Harness::new(100, 12);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>x", file.display()));
+    assert!(!swap.exists());
+    assert_eq!(h.lines(), ["one", "two"]);
+    std::fs::write(&swap, "x").unwrap();
+    age(&swap, 120);
+    let mut h = Harness::new(100, 12);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>", file.display()));
+    assert!(!h.screen.text().contains("[Recovery]"));
+    // vim 의 스왑 파일은 복구 파일로 보지 않음
+    let vim_swap = dir.join(".a.txt.swp");
+    std::fs::write(&vim_swap, "vim").unwrap();
+    let mut h = Harness::new(100, 12);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>", file.display()));
+    assert!(!h.screen.text().contains("[Recovery]"));
+    assert_eq!(std::fs::read_to_string(&vim_swap).unwrap(), "vim");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn saving_keeps_the_file_mode_and_writes_through_symlinks() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("vii_attrs_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (script, link) = (dir.join("run.sh"), dir.join("link.sh"));
+    std::fs::write(&script, "echo hi\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o750)).unwrap();
+    std::os::unix::fs::symlink("run.sh", &link).unwrap();
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>i# <Esc>:w<CR>", link.display()));
+    assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+    assert_eq!(std::fs::read_to_string(&script).unwrap(), "# echo hi");
+    assert_eq!(std::fs::metadata(&script).unwrap().permissions().mode() & 0o777, 0o750);
+    // run.sh, link.sh 와 링크가 가리키는 파일의 잠금 파일 .run.sh.vii-lock 만 남음
+    assert!(dir.join(".run.sh.vii-lock").exists());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn lock_files_warn_about_files_open_in_another_vii() {
+    let dir = std::env::temp_dir().join(format!("vii_lock_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (file, lock) = (dir.join("a.txt"), dir.join(".a.txt.vii-lock"));
+    std::fs::write(&file, "text").unwrap();
+    let mut h = Harness::new(100, 5);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>", file.display()));
+    assert_eq!(std::fs::read_to_string(&lock).unwrap().trim(), std::process::id().to_string());
+    drop(h);
+    assert!(!lock.exists());
+    // 잠금 파일은 복구 파일처럼 swapdir 에 전체 경로로 이름을 지어 둠
+    let swap = dir.join("swap");
+    let mut h = Harness::new(100, 5);
+    h.keys(&format!(":set dir={}<CR>:e {}<CR>", swap.display(), file.display()));
+    let named = swap.join(format!("{}.vii-lock", std::fs::canonicalize(&file).unwrap().display().to_string().replace('/', "%")));
+    assert_eq!(std::fs::read_to_string(&named).unwrap().trim(), std::process::id().to_string());
+    assert!(!lock.exists());
+    drop(h);
+    assert!(!named.exists());
+    // 살아 있는 다른 프로세스 (init) 가 잡고 있으면 경고하고 잠금 파일은 그대로
+    std::fs::write(&lock, "1\n").unwrap();
+    let mut h = Harness::new(100, 5);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>", file.display()));
+    assert!(h.screen.line(4).contains("a.txt is being edited by another vii (pid 1)"));
+    drop(h);
+    assert_eq!(std::fs::read_to_string(&lock).unwrap(), "1\n");
+    // 죽은 프로세스가 남긴 것은 새로 씀
+    std::fs::write(&lock, "999999999\n").unwrap();
+    let mut h = Harness::new(100, 5);
+    h.keys(&format!(":set dir=.<CR>:e {}<CR>", file.display()));
+    assert!(!h.screen.line(4).contains("being edited"));
+    assert_eq!(std::fs::read_to_string(&lock).unwrap().trim(), std::process::id().to_string());
+    drop(h);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn symbols_picker_guesses_an_outline_without_a_server() {
+    let lines = ["pub struct Point {", "    x: u32,", "}", "", "impl Point {", "    pub fn norm(&self) -> u32 {", "        self.x", "    }", "}", "fn main() {}", "fn f() {}"];
+    let mut h = Harness::with_lines(60, 16, &lines);
+    h.keys(":Symbols<CR>");
+    let text = h.screen.text();
+    assert!(text.contains("Symbols>"));
+    assert!(text.contains("struct Point  :1"));
+    assert!(text.contains("impl Point  :5"));
+    assert!(text.contains("  fn norm  :6"));
+    assert!(text.contains("fn main  :10"));
+    h.keys("nrm<CR>");
+    assert_eq!(h.cursor(), (5, 11));
+    h.keys(":Symbols<CR>zzz<CR>");
+    assert_eq!(h.cursor(), (5, 11));
+    // 이름이 키워드 안에도 들어 있을 때 (fn 의 f)
+    h.keys(":Symbols<CR>f<CR>");
+    assert_eq!(h.cursor(), (10, 3));
+}
+
+#[test]
+fn ctrl_d_and_ctrl_f_scroll_the_screen_with_the_cursor() {
+    let lines: Vec<String> = (1..=40).map(|i| format!("line {}", i)).collect();
+    let lines: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+    let mut h = Harness::with_lines(40, 12, &lines);
+    h.keys("<C-d>");
+    assert_eq!(h.cursor(), (5, 0));
+    assert_eq!(h.screen.line(0), "line 6");
+    h.keys("<C-f>");
+    assert_eq!(h.cursor(), (14, 0));
+    assert_eq!(h.screen.line(0), "line 15");
+    h.keys("<C-b><C-u>");
+    assert_eq!(h.cursor(), (0, 0));
+    assert_eq!(h.screen.line(0), "line 1");
+    h.keys("3<C-d>");
+    assert_eq!(h.cursor(), (3, 0));
+    assert_eq!(h.screen.line(0), "line 4");
+    h.keys("G<C-d>");
+    assert_eq!(h.cursor(), (39, 0));
+    assert_eq!(h.screen.line(10), "line 40");
+}
+
+#[test]
+fn smoothscroll_moves_the_screen_over_several_frames() {
+    let lines: Vec<String> = (1..=40).map(|i| format!("line {}", i)).collect();
+    let lines: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+    let mut h = Harness::with_lines(40, 12, &lines);
+    h.keys(":set smoothscroll<CR><C-f>");
+    assert_eq!(h.cursor(), (9, 0));
+    assert_eq!(h.screen.line(0), "line 1");
+    assert!(h.editor.is_scrolling());
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    h.keys("");
+    assert_eq!(h.screen.line(0), "line 10");
+    assert!(!h.editor.is_scrolling());
+    h.keys("j");
+    assert_eq!(h.screen.line(0), "line 10");
+}
+
+#[test]
+fn zen_centers_the_text_and_hides_the_columns() {
+    let mut h = Harness::with_lines(40, 6, &["hello world", "a line that is longer than twenty columns"]);
+    h.keys(":set number zenwidth=20<CR>:Zen<CR>");
+    assert_eq!(h.screen.line(0), format!("{}hello world", " ".repeat(10)));
+    assert_eq!(h.screen.line(1), format!("{}a line that is long>", " ".repeat(10)));
+    assert_eq!(h.screen.line(2), "");
+    assert!(!h.screen.line(5).contains("NORMAL"));
+    h.keys("jl");
+    assert_eq!(h.screen.cursor, (1, 11));
+    h.keys("i");
+    assert!(h.screen.line(5).starts_with("-- INSERT --"));
+    h.keys("<Esc>:Zen<CR>");
+    assert_eq!(h.screen.line(0), "  1 hello world");
+    assert_eq!(h.screen.line(2), "~");
+    assert!(h.screen.line(5).contains("-- NORMAL --"));
+}
+
+#[test]
+fn insert_tab_follows_expandtab_and_softtabstop() {
+    let mut h = Harness::with_lines(40, 5, &["ab"]);
+    h.keys("Sab<Tab>x<Esc>");
+    assert_eq!(h.lines(), vec!["ab      x"]);
+    h.keys(":set sts=4<CR>S<Tab><Tab>x<BS><BS>y<Esc>");
+    assert_eq!(h.lines(), vec!["    y"]);
+    h.keys(":set noet<CR>S<Tab>a<Esc>");
+    assert_eq!(h.lines(), vec!["    a"]);
+    h.keys("S<Tab><Tab><Tab>b<Esc>");
+    assert_eq!(h.lines(), vec!["\t    b"]);
+    h.keys(":set sts=-1 sw=2<CR>S<Tab><Tab><Tab><Tab>c<Esc>");
+    assert_eq!(h.lines(), vec!["\tc"]);
+    h.keys(":set sts=0<CR>S<Tab>d<Esc>");
+    assert_eq!(h.lines(), vec!["\td"]);
+}
+
+#[test]
+fn retab_rewrites_indentation_for_tabstop_and_expandtab() {
+    let mut h = Harness::with_lines(60, 5, &["\tone", "  \t two", "three\tx", "        four"]);
+    h.keys(":retab<CR>");
+    assert_eq!(h.lines(), vec!["        one", "         two", "three\tx", "        four"]);
+    h.keys(":set noet<CR>:2,$retab 4<CR>");
+    assert_eq!(h.lines(), vec!["        one", "\t\t two", "three\tx", "\t\tfour"]);
+    h.keys(":set ts?<CR>");
+    assert!(h.screen.line(4).contains("ts=4"));
+    h.keys(":retab x<CR>");
+    assert!(h.screen.line(4).contains("Invalid tabstop: x"));
+}
+
+#[test]
+fn yank_flashes_until_the_timer_runs_out() {
+    let mut h = Harness::with_lines(40, 5, &["one two", "three"]);
+    assert!(!h.editor.is_animating());
+    h.keys("yw");
+    assert!(h.editor.is_animating());
+    assert_eq!(h.screen.inverted(0), "one ");
+    assert_eq!(h.screen.inverted(1), "");
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    h.keys("");
+    assert!(!h.editor.is_animating());
+    assert_eq!(h.screen.inverted(0), "");
+    // 줄 전체는 줄 끝 한 칸까지
+    h.keys("jyy");
+    assert!(h.editor.is_animating());
+    assert_eq!(h.screen.inverted(1), "three ");
+    h.keys("P");
+    assert_eq!(h.lines(), vec!["one two", "three", "three"]);
+}
+
+#[test]
+fn command_prefixes_and_aliases_resolve_to_builtins() {
+    let mut h = Harness::with_lines(60, 5, &["abc"]);
+    h.keys("x:und<CR>");
+    assert_eq!(h.lines(), ["abc"]);
+    h.keys(":c<CR>");
+    assert!(h.screen.line(4).contains("Ambiguous command: c (cnext"));
+    h.keys(":ca U undo<CR>:ca<CR>");
+    assert!(h.screen.line(4).contains("| U undo "));
+    h.keys("x:U<CR>");
+    assert_eq!(h.lines(), ["abc"]);
+    h.keys(":cuna U<CR>:U<CR>");
+    assert!(h.screen.line(4).contains("Unknown: U"));
+    h.keys(":qu<CR>ihello");
+    assert_eq!(h.lines(), ["abc"]);
+    // ! 는 이름에 붙은 채로 풀림 (:q! → quit!)
+    let mut h = Harness::with_lines(60, 5, &["abc"]);
+    assert!(!h.editor.run_command("q!"));
+    assert!(h.editor.run_command("cabbrev Q q!"));
+    assert!(!h.editor.run_command("Q"));
+    assert!(!h.editor.status_msg.contains("Unknown"));
+}
+
+#[test]
+fn k_shows_keywordprg_output_in_a_panel() {
+    let mut h = Harness::with_lines(60, 10, &["see printf here"]);
+    h.keys(":set kp=echo<CR>wK");
+    let screen = h.screen.text();
+    assert!(screen.contains("[Lookup] echo printf (1 items)"));
+    assert!(screen.contains("\nprintf"));
+    h.keys("q3K");
+    assert!(h.screen.text().contains("\n3 printf"));
+    h.keys("q:set kp=false<CR>K");
+    assert!(h.screen.line(9).contains("Nothing found: false printf"));
+}
+
+#[test]
+fn help_opens_at_topics_and_follows_links() {
+    let mut h = Harness::new(80, 24);
+    // 도움말 창의 제목 다음 줄
+    let top = |h: &Harness| {
+        let title = (0..24).find(|&y| h.screen.line(y).starts_with("[Help] help.txt")).expect("help window");
+        h.screen.line(title + 1)
+    };
+    h.keys(":h wri<CR>");
+    assert!(top(&h).starts_with(":w[rite][!] [file]"));
+    h.keys(":help nu<CR>");
+    assert!(top(&h).starts_with("'number' 'nu'"));
+    h.keys(":help contents<CR>j<CR>");
+    assert!(top(&h).starts_with("Normal mode"));
+    h.keys("<C-t>");
+    assert!(top(&h).starts_with("    |normal|"));
+    h.keys(":help nosuch<CR>");
+    assert!(h.screen.line(23).contains("Sorry, no help for nosuch"));
+}
+
+#[test]
+fn tutor_opens_a_fresh_editable_buffer_each_time() {
+    let mut h = Harness::with_lines(80, 10, &["my notes"]);
+    h.keys(":autocmd BufNewFile * set sw=3<CR>x:Tutor<CR>");
+    assert!(h.lines()[1].contains("Welcome to the vii tutor"));
+    assert_eq!(h.editor.filename, None);
+    // 새 파일처럼 BufNewFile 이 불림
+    h.keys(":set sw?<CR>");
+    assert!(h.screen.line(9).contains("sw=3"));
+    h.keys("dd:Tutor<CR>");
+    assert!(h.lines()[1].contains("Welcome to the vii tutor"));
+    // 고친 튜토리얼은 그대로 남아 있음
+    h.keys(":b 2<CR>");
+    assert!(h.lines()[0].contains("Welcome to the vii tutor"));
+    h.keys(":b 1<CR>");
+    assert_eq!(h.lines(), ["y notes"]);
+}
@@ -0,0 +1,180 @@
+use vii::harness::Harness;
+use vii::keys::{self, KeyCode, KeyEvent, Modifiers};
+
+#[test]
+fn decode_control_bytes_and_escape_sequences() {
+    assert_eq!(keys::decode(b"\x17"), Some((Some(KeyEvent::ctrl('w')), 1)));
+    assert_eq!(keys::decode(b"\r"), Some((Some(KeyEvent::plain(KeyCode::Enter)), 1)));
+    assert_eq!(keys::decode(b"\x1b[A"), Some((Some(KeyEvent::plain(KeyCode::Up)), 3)));
+    assert_eq!(keys::decode(b"\x1b[1;5C"), Some((Some(KeyEvent::new(KeyCode::Right, Modifiers::CTRL)), 6)));
+    assert_eq!(keys::decode(b"\x1b[15~"), Some((Some(KeyEvent::plain(KeyCode::F(5))), 5)));
+    assert_eq!(keys::decode("한".as_bytes()), Some((Some(KeyEvent::char('한')), 3)));
+    // 끝이 잘린 시퀀스는 다음 바이트를 기다림
+    assert_eq!(keys::decode(b"\x1b[1;5"), None);
+}
+
+#[test]
+fn decode_kitty_keyboard_protocol() {
+    // Ctrl-I와 Tab, Shift-Enter와 Enter를 구분
+    assert_eq!(keys::decode(b"\x1b[105;5u"), Some((Some(KeyEvent::ctrl('i')), 8)));
+    assert_eq!(keys::decode(b"\x1b[13;2u"), Some((Some(KeyEvent::new(KeyCode::Enter, Modifiers::SHIFT)), 7)));
+    assert_eq!(keys::decode(b"\x1b[27u"), Some((Some(KeyEvent::plain(KeyCode::Esc)), 5)));
+    assert_eq!(keys::decode(b"\x1b[1;5:2A"), Some((Some(KeyEvent::new(KeyCode::Up, Modifiers::CTRL)), 8)));
+    // 뗀 이벤트와 터미널 응답은 키가 아님
+    assert_eq!(keys::decode(b"\x1b[106;1:3u"), Some((None, 10)));
+    assert_eq!(keys::decode(b"\x1b[?1u"), Some((None, 5)));
+}
+
+#[test]
+fn escape_prefix_means_alt_unless_nothing_follows() {
+    assert_eq!(keys::decode(b"\x1bj"), Some((Some(KeyEvent::alt('j')), 2)));
+    assert_eq!(keys::decode(b"\x1b\x1b"), Some((Some(KeyEvent::plain(KeyCode::Esc)), 1)));
+    // ESC 하나는 뒤따를 바이트를 기다렸다가, 안 오면 Esc
+    assert_eq!(keys::decode(b"\x1b"), None);
+    assert_eq!(keys::decode_partial(b"\x1b"), (KeyEvent::plain(KeyCode::Esc), 1));
+    assert_eq!(keys::decode_partial(b"\x1b["), (KeyEvent::alt('['), 2));
+}
+
+#[test]
+fn notation_round_trips() {
+    for notation in ["a", "<C-w>", "<M-x>", "<lt>", "<Space>", "<CR>", "<S-Tab>", "<C-S-Up>", "<F12>"] {
+        assert_eq!(keys::normalize(notation), notation);
+    }
+    assert_eq!(keys::normalize("<c-W><esc>"), "<C-w><Esc>");
+}
+
+#[test]
+fn arrow_keys_move_like_hjkl() {
+    let mut h = Harness::with_lines(40, 10, &["abc", "def"]);
+    h.keys("<Right><Down>");
+    assert_eq!(h.cursor(), (1, 1));
+    h.keys("i<Left>X");
+    assert_eq!(h.lines(), vec!["abc", "Xdef"]);
+}
+
+#[test]
+fn alt_j_and_alt_k_move_the_line() {
+    let mut h = Harness::with_lines(40, 10, &["one", "two", "three"]);
+    h.keys("<M-j>");
+    assert_eq!(h.lines(), vec!["two", "one", "three"]);
+    h.keys("<M-j><M-j><M-k>");
+    assert_eq!(h.lines(), vec!["two", "one", "three"]);
+    assert_eq!(h.cursor(), (1, 0));
+}
+
+#[test]
+fn leader_mappings_wait_for_the_whole_sequence() {
+    let mut h = Harness::with_lines(40, 10, &["one", "two"]);
+    h.editor.run_command("leader <Space>");
+    h.editor.run_command("nnoremap <leader>dd jix<Esc>");
+    h.keys("<Space>d");
+    // 앞부분만 눌렀을 때는 아무것도 하지 않고 상태 줄에 보여줌
+    assert_eq!(h.lines(), vec!["one", "two"]);
+    assert!(h.screen.line(9).contains("<Space>d"));
+    h.keys("d");
+    assert_eq!(h.lines(), vec!["one", "xtwo"]);
+    // 맞지 않는 키가 오면 눌렀던 키를 그대로 처리
+    h.keys("<Space>k");
+    assert_eq!(h.cursor(), (0, 1));
+}
+
+#[test]
+fn home_toggles_between_indent_and_column_zero() {
+    let mut h = Harness::with_lines(40, 10, &["    let x = 1;", "y"]);
+    h.keys("llllll0");
+    assert_eq!(h.cursor(), (0, 4));
+    h.keys("0");
+    assert_eq!(h.cursor(), (0, 0));
+    h.keys("<Home>");
+    assert_eq!(h.cursor(), (0, 4));
+    h.keys("0^");
+    assert_eq!(h.cursor(), (0, 4));
+    h.keys("ia<Home>b<Esc>");
+    assert_eq!(h.lines()[0], "    balet x = 1;");
+}
+
+#[test]
+fn wrap_splits_long_lines_and_home_starts_the_screen_row() {
+    let mut h = Harness::with_lines(10, 5, &["  abcdefghijklmnop", "x"]);
+    h.keys(":set wrap<CR>");
+    assert_eq!(h.screen.line(0), "  abcdefgh");
+    assert_eq!(h.screen.line(1), "ijklmnop");
+    assert_eq!(h.screen.line(2), "x");
+    h.keys(&"l".repeat(13));
+    assert_eq!(h.screen.cursor, (1, 3));
+    h.keys("<Home>");
+    assert_eq!(h.cursor(), (0, 10));
+    h.keys("<Home>");
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys(":set nowrap<CR>");
+    assert_eq!(h.screen.line(1), "x");
+}
+
+#[test]
+fn gj_and_gk_move_by_screen_rows_when_wrapping() {
+    let mut h = Harness::with_lines(10, 6, &["abcdefghijklmnopqrstuvw", "xyz"]);
+    h.keys("gj");
+    assert_eq!(h.cursor(), (1, 0));
+    h.keys(":set wrap<CR>kllgj");
+    assert_eq!(h.cursor(), (0, 12));
+    assert_eq!(h.screen.cursor, (1, 2));
+    h.keys("gjgj");
+    assert_eq!(h.cursor(), (1, 2));
+    h.keys("gk");
+    assert_eq!(h.cursor(), (0, 22));
+    h.keys("2gk");
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys(":set dl<CR>jj");
+    assert_eq!(h.cursor(), (0, 22));
+    h.keys("vgk");
+    assert_eq!(h.cursor(), (0, 12));
+}
+
+#[test]
+fn line_numbers_jump_with_colon_and_g() {
+    let lines: Vec<String> = (1..=30).map(|i| format!("  line {}", i)).collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let mut h = Harness::with_lines(40, 6, &lines);
+    h.keys(":20<CR>");
+    assert_eq!(h.cursor(), (19, 2));
+    assert_eq!(h.screen.line(4), "  line 20");
+    h.keys(":0<CR>");
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys(":$-1<CR>");
+    assert_eq!(h.cursor(), (28, 2));
+    h.keys("gg");
+    assert_eq!(h.cursor(), (0, 2));
+    h.keys("G");
+    assert_eq!(h.cursor(), (29, 2));
+    h.keys("12G");
+    assert_eq!(h.cursor(), (11, 2));
+    h.keys("5gg");
+    assert_eq!(h.cursor(), (4, 2));
+    assert_eq!(h.screen.line(0), "  line 5");
+    h.keys("99G");
+    assert_eq!(h.cursor(), (29, 2));
+}
+
+#[test]
+fn ctrl_g_shows_file_info() {
+    let mut h = Harness::with_lines(100, 5, &["a", "b", "c", "d"]);
+    h.keys("j<C-g>");
+    assert!(h.screen.line(4).contains("\"[No Name]\" 4 lines --50%--"));
+    h.keys("x:set ro<CR>G<C-g>");
+    assert!(h.screen.line(4).contains("\"[No Name]\" [Modified] [readonly] 4 lines --100%--"));
+}
+
+#[test]
+fn ga_shows_the_code_of_the_character_under_the_cursor() {
+    let mut h = Harness::with_lines(80, 5, &["aé\t한", ""]);
+    h.keys("ga");
+    assert!(h.screen.line(4).contains("<a> 97, Hex 0061, Oct 141, UTF-8 61"));
+    h.keys("lga");
+    assert!(h.screen.line(4).contains("<é> 233, Hex 00e9, Oct 351, UTF-8 c3 a9"));
+    h.keys("lga");
+    assert!(h.screen.line(4).contains("<^I> 9, Hex 0009, Oct 11, UTF-8 09"));
+    h.keys("lga");
+    assert!(h.screen.line(4).contains("<한> 54620, Hex d55c, Oct 152534, UTF-8 ed 95 9c"));
+    h.keys("jga");
+    assert!(h.screen.line(4).contains("NUL"));
+}
@@ -0,0 +1,295 @@
+// 가짜 언어 서버로 해 보는 LSP 기능들의 통합 테스트
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use vii::harness::Harness;
+
+// 요청마다 dir/<메서드의 / 를 _ 로 바꾼 이름>.json 을 결과로 돌려주는 (없으면 null) 서버, 받은 메시지는 dir/log 에 남김
+const SERVER: &str = r#"dir=$(dirname "$0")
+while :; do
+  len=
+  while IFS= read -r line; do
+    line=$(printf '%s' "$line" | tr -d '\r')
+    [ -z "$line" ] && break
+    case "$line" in Content-Length:*) len=${line#Content-Length: } ;; esac
+  done
+  [ -z "$len" ] && exit 0
+  body=$(dd bs=1 count="$len" 2>/dev/null)
+  printf '%s\n' "$body" >> "$dir/log"
+  request=$(printf '%s' "$body" | sed -n 's/^{"jsonrpc":"2.0","id":\([0-9]*\),"method":"\([^"]*\)".*/\1 \2/p')
+  [ -z "$request" ] && continue
+  id=${request%% *}
+  file="$dir/$(printf '%s' "${request#* }" | tr / _).json"
+  result=null
+  [ "${request#* }" = initialize ] && result='{"capabilities":{}}'
+  [ -f "$file" ] && result=$(cat "$file")
+  reply="{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":$result}"
+  printf 'Content-Length: %d\r\n\r\n%s' "$(printf '%s' "$reply" | wc -c)" "$reply"
+done
+"#;
+
+// 테스트마다 새 디렉터리에 서버를 두고 rust 파일에 쓰도록 등록하는 명령 줄을 돌려줌
+fn fake_server(name: &str) -> (PathBuf, String) {
+    let dir = std::env::temp_dir().join(format!("vii_lsp_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("server.sh"), SERVER).unwrap();
+    let command = format!(":lsp rust sh {}<CR>", dir.join("server.sh").display());
+    (dir, command)
+}
+
+fn respond(dir: &Path, method: &str, result: &str) {
+    std::fs::write(dir.join(format!("{}.json", method.replace('/', "_"))), result).unwrap();
+}
+
+fn uri(path: &Path) -> String {
+    format!("file://{}", std::fs::canonicalize(path).unwrap().display())
+}
+
+// 응답이 올 때까지 백그라운드 일을 돌리며 화면을 다시 그림
+fn wait_until(h: &mut Harness, done: impl Fn(&Harness) -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        h.editor.poll_background();
+        h.keys("");
+        if done(h) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(2));
+    }
+}
+
+#[test]
+fn gd_gr_and_gd_upper_ask_the_server_for_locations() {
+    let (dir, lsp) = fake_server("goto");
+    let (main, lib) = (dir.join("main.rs"), dir.join("lib.rs"));
+    std::fs::write(&main, "fn main() {\n    helper();\n    helper();\n}\n").unwrap();
+    std::fs::write(&lib, "\nfn helper() {}\n").unwrap();
+    let range = |line: usize, character: usize| {
+        format!(r#"{{"start":{{"line":{},"character":{}}},"end":{{"line":{},"character":{}}}}}"#, line, character, line, character + 6)
+    };
+    respond(&dir, "textDocument/definition", &format!(r#"{{"uri":"{}","range":{}}}"#, uri(&lib), range(1, 3)));
+    let references = format!(r#"[{{"uri":"{u}","range":{}}},{{"uri":"{u}","range":{}}}]"#, range(1, 4), range(2, 4), u = uri(&main));
+    respond(&dir, "textDocument/references", &references);
+    let mut h = Harness::new(80, 12);
+    h.keys(&format!("{}:e {}<CR>j^gd", lsp, main.display()));
+    wait_until(&mut h, |h| h.lines().len() == 2);
+    assert_eq!(h.lines(), ["", "fn helper() {}"]);
+    assert_eq!(h.cursor(), (1, 3));
+    h.keys(":b 1<CR>");
+    assert_eq!(h.lines()[1], "    helper();");
+    h.keys("gr");
+    wait_until(&mut h, |h| h.screen.text().contains("[Quickfix]"));
+    let text = h.screen.text();
+    assert!(text.contains("[Quickfix] lsp references (2 items)"));
+    assert!(text.contains(&format!("{}:3:5: helper();", main.display())));
+    h.keys("<Esc>gD");
+    wait_until(&mut h, |h| h.screen.text().contains("No declaration found"));
+    assert!(h.screen.line(11).contains("No declaration found"));
+    let log = std::fs::read_to_string(dir.join("log")).unwrap();
+    assert!(log.contains(r#""method":"textDocument/definition","params":{"textDocument":{"uri":"#));
+    assert!(log.contains(r#""position":{"line":1,"character":4}"#));
+    assert!(log.contains(r#""context":{"includeDeclaration":true}"#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn k_shows_hover_markdown_in_a_window_above_the_cursor() {
+    let (dir, lsp) = fake_server("hover");
+    let main = dir.join("main.rs");
+    std::fs::write(&main, "fn main() {\n\n\n\n\n    helper();\n}\n").unwrap();
+    let markdown = r#"```rust\nfn helper() -> u32\n```\n\n---\n# Helper\nReturns **the** `answer`, see \\[docs\\]."#;
+    respond(&dir, "textDocument/hover", &format!(r#"{{"contents":{{"kind":"markdown","value":"{}"}}}}"#, markdown));
+    let mut h = Harness::new(60, 12);
+    h.keys(&format!("{}:e {}<CR>5jwK", lsp, main.display()));
+    wait_until(&mut h, |h| h.screen.text().contains("fn helper"));
+    assert!(h.screen.line(0).contains("fn m fn helper() -> u32"));
+    assert!(h.screen.line(2).contains(" ---"));
+    assert!(h.screen.line(3).contains("     Helper"));
+    assert!(h.screen.line(4).contains(" Returns the answer, see [docs]."));
+    assert_eq!(h.screen.line(5), "    helper();");
+    h.keys("l");
+    assert!(!h.screen.text().contains("fn helper"));
+    respond(&dir, "textDocument/hover", "null");
+    h.keys("K");
+    wait_until(&mut h, |h| h.screen.text().contains("No hover information"));
+    assert!(h.screen.line(11).contains("No hover information"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rename_applies_the_workspace_edit_to_every_file() {
+    let (dir, lsp) = fake_server("rename");
+    let (main, lib) = (dir.join("main.rs"), dir.join("lib.rs"));
+    std::fs::write(&main, "fn main() {\n    helper(); helper();\n}\n").unwrap();
+    std::fs::write(&lib, "fn helper() {}\n").unwrap();
+    let edit = |line: usize, character: usize| {
+        format!(r#"{{"range":{{"start":{{"line":{l},"character":{c}}},"end":{{"line":{l},"character":{}}}}},"newText":"assist"}}"#, character + 6, l = line, c = character)
+    };
+    let changes = format!(r#"{{"changes":{{"{}":[{},{}],"{}":[{}]}}}}"#, uri(&main), edit(1, 4), edit(1, 14), uri(&lib), edit(0, 3));
+    respond(&dir, "textDocument/rename", &changes);
+    let mut h = Harness::new(160, 12);
+    h.keys(&format!("{}:e {}<CR>j^:Rename assist<CR>", lsp, main.display()));
+    wait_until(&mut h, |h| h.screen.text().contains("Renamed"));
+    assert!(h.screen.line(11).contains("Renamed: 3 changes in 2 files"));
+    assert_eq!(h.lines(), ["fn main() {", "    assist(); assist();", "}"]);
+    assert_eq!(h.cursor(), (1, 4));
+    h.keys(":ls<CR>");
+    assert!(h.screen.line(11).contains("lib.rs [+]"));
+    h.keys(":b 2<CR>");
+    assert_eq!(h.lines(), ["fn assist() {}"]);
+    h.keys("u");
+    assert_eq!(h.lines(), ["fn helper() {}"]);
+    h.keys(":b 1<CR>u");
+    assert_eq!(h.lines()[1], "    helper(); helper();");
+    let log = std::fs::read_to_string(dir.join("log")).unwrap();
+    assert!(log.contains(r#""position":{"line":1,"character":4},"newName":"assist""#));
+    // 응답을 기다리는 사이 고친 버퍼에는 적용하지 않음
+    h.keys("gg:Rename assist<CR>x");
+    wait_until(&mut h, |h| h.screen.text().contains("Buffer changed while renaming"));
+    assert_eq!(h.lines(), ["n main() {", "    helper(); helper();", "}"]);
+    respond(&dir, "textDocument/rename", "null");
+    h.keys(":Rename other<CR>");
+    wait_until(&mut h, |h| h.screen.text().contains("Nothing to rename"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn format_and_equal_apply_the_servers_text_edits() {
+    let (dir, lsp) = fake_server("format");
+    let main = dir.join("main.rs");
+    std::fs::write(&main, "fn main() {\nlet x=1;\n}\n").unwrap();
+    let edit = r#"[{"range":{"start":{"line":1,"character":0},"end":{"line":1,"character":8}},"newText":"    let x = 1;"}]"#;
+    respond(&dir, "textDocument/formatting", edit);
+    respond(&dir, "textDocument/rangeFormatting", edit);
+    let mut h = Harness::new(80, 12);
+    h.keys(&format!("{}:e {}<CR>:Format<CR>", lsp, main.display()));
+    wait_until(&mut h, |h| h.screen.text().contains("Formatted"));
+    assert_eq!(h.lines(), ["fn main() {", "    let x = 1;", "}"]);
+    assert!(h.screen.line(11).contains("Formatted, 1 line changed"));
+    h.keys("u");
+    assert_eq!(h.lines()[1], "let x=1;");
+    h.keys("ggj==");
+    wait_until(&mut h, |h| h.screen.text().contains("Formatted"));
+    assert_eq!(h.lines()[1], "    let x = 1;");
+    let log = std::fs::read_to_string(dir.join("log")).unwrap();
+    assert!(log.contains(r#""options":{"tabSize":4,"insertSpaces":true}"#));
+    assert!(log.contains(r#""range":{"start":{"line":1,"character":0},"end":{"line":1,"character":8}}"#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn ctrl_n_merges_server_completions_and_applies_their_extra_edits() {
+    let (dir, lsp) = fake_server("completion");
+    let main = dir.join("main.rs");
+    std::fs::write(&main, "fn main() {\n\n}\n").unwrap();
+    let items = r#"{"isIncomplete":false,"items":[
+        {"label":"main","kind":3},
+        {"label":"hello","kind":6,"detail":"&str"},
+        {"label":"helper","kind":3,"detail":"fn() -> u32","sortText":"0"}]}"#;
+    respond(&dir, "textDocument/completion", &items.replace('\n', ""));
+    let import = r#"{"label":"helper","additionalTextEdits":[{"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":0}},"newText":"use lib::helper;\n"}]}"#;
+    respond(&dir, "completionItem/resolve", &import.replace('\n', "\\n"));
+    let mut h = Harness::new(80, 12);
+    h.keys(&format!("{}:e {}<CR>ji    he<C-n>", lsp, main.display()));
+    wait_until(&mut h, |h| h.screen.text().contains("hello"));
+    // 서버의 후보만 prefix 에 맞고, sortText 순서로 첫 후보가 들어감
+    assert_eq!(h.lines()[1], "    helper");
+    assert!(h.screen.line(2).contains(" f helper  fn() -> u32"));
+    assert!(h.screen.line(3).contains(" v hello   &str"));
+    assert!(!h.screen.text().contains(" f main"));
+    h.keys("<C-n><C-p><CR>");
+    assert_eq!(h.lines()[1], "    helper");
+    wait_until(&mut h, |h| h.lines().len() == 4);
+    assert_eq!(h.lines(), ["use lib::helper;", "fn main() {", "    helper", "}"]);
+    assert_eq!(h.cursor(), (2, 10));
+    // 확정한 뒤 더 입력했으면 늦게 온 추가 편집은 버림
+    h.keys(" he<C-n>");
+    wait_until(&mut h, |h| h.screen.text().contains("hello"));
+    h.keys("<CR>;");
+    wait_until(&mut h, |h| h.screen.text().contains("Buffer changed, extra edits skipped"));
+    assert_eq!(h.lines(), ["use lib::helper;", "fn main() {", "    helper helper;", "}"]);
+    let log = std::fs::read_to_string(dir.join("log")).unwrap();
+    assert!(log.contains(r#""method":"textDocument/completion","params":{"textDocument":{"uri":"#));
+    assert!(log.contains(r#""position":{"line":1,"character":6}"#));
+    assert!(log.contains(r#""method":"completionItem/resolve","params":{"label":"helper","kind":3"#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn typing_a_paren_or_comma_shows_the_signature_above_the_cursor() {
+    let (dir, lsp) = fake_server("signature");
+    let main = dir.join("main.rs");
+    std::fs::write(&main, "fn main() {\n\n}\n").unwrap();
+    let help = |active: usize| {
+        format!(
+            r#"{{"signatures":[{{"label":"fn add(a: u32, b: u32) -> u32","parameters":[{{"label":[7,13]}},{{"label":"b: u32"}}]}}],"activeSignature":0,"activeParameter":{}}}"#,
+            active
+        )
+    };
+    respond(&dir, "textDocument/signatureHelp", &help(0));
+    let mut h = Harness::new(60, 12);
+    h.keys(&format!("{}:e {}<CR>ji    add(", lsp, main.display()));
+    wait_until(&mut h, |h| h.screen.text().contains("fn add"));
+    assert!(h.screen.line(0).contains("fn main( fn add(a: u32, b: u32) -> u32"));
+    respond(&dir, "textDocument/signatureHelp", &help(1));
+    h.keys("1,");
+    let log = dir.join("log");
+    wait_until(&mut h, |_| std::fs::read_to_string(&log).unwrap().contains(r#""isRetrigger":true"#));
+    assert!(h.screen.line(0).contains("fn add(a: u32, b: u32) -> u32"));
+    // 안쪽 호출을 닫는 ) 뒤에는 바깥 add 의 시그니처를 다시 물음
+    h.keys(" f(2)");
+    wait_until(&mut h, |_| std::fs::read_to_string(&log).unwrap().contains(r#""triggerCharacter":")""#));
+    wait_until(&mut h, |h| h.screen.text().contains("fn add"));
+    assert!(h.screen.line(0).contains("fn add(a: u32, b: u32) -> u32"));
+    h.keys(")");
+    assert!(!h.screen.text().contains("fn add"));
+    assert_eq!(h.lines()[1], "    add(1, f(2))");
+    let log = std::fs::read_to_string(dir.join("log")).unwrap();
+    assert!(log.contains(r#""position":{"line":1,"character":8},"context":{"triggerKind":2,"triggerCharacter":"(","isRetrigger":false}"#));
+    assert!(log.contains(r#""triggerCharacter":",","isRetrigger":true"#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn symbols_lists_the_servers_document_symbols() {
+    let (dir, lsp) = fake_server("symbols");
+    let main = dir.join("main.rs");
+    std::fs::write(&main, "struct Wide;\nimpl Wide {\n    fn 넓이() {}\n}\n").unwrap();
+    let range = |line: usize, character: usize| {
+        format!(r#"{{"start":{{"line":{line},"character":{character}}},"end":{{"line":{line},"character":{character}}}}}"#)
+    };
+    let symbols = format!(
+        r#"[{{"name":"Wide","kind":23,"range":{},"selectionRange":{}}},{{"name":"impl Wide","kind":19,"range":{},"selectionRange":{},"children":[{{"name":"넓이","kind":6,"range":{},"selectionRange":{}}}]}}]"#,
+        range(0, 0),
+        range(0, 7),
+        range(1, 0),
+        range(1, 5),
+        range(2, 4),
+        range(2, 7)
+    );
+    respond(&dir, "textDocument/documentSymbol", &symbols);
+    let mut h = Harness::new(60, 12);
+    h.keys(&format!("{}:e {}<CR>:Symbols<CR>", lsp, main.display()));
+    wait_until(&mut h, |h| h.screen.text().contains("Symbols>"));
+    assert!(h.screen.text().contains("struct Wide  :1"));
+    assert!(h.screen.text().contains("symbol impl Wide  :2"));
+    assert!(h.screen.text().contains("  method 넓이  :3"));
+    h.keys("넓<CR>");
+    assert_eq!(h.cursor(), (2, 7));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn new_files_are_opened_with_an_absolute_uri() {
+    let (dir, lsp) = fake_server("newfile");
+    let name = format!("vii_lsp_new_{}.rs", std::process::id());
+    let mut h = Harness::new(80, 12);
+    h.keys(&format!("{}:e {}<CR>", lsp, name));
+    let log = dir.join("log");
+    wait_until(&mut h, |_| std::fs::read_to_string(&log).is_ok_and(|l| l.contains("didOpen")));
+    // 아직 없는 파일도 지금 디렉터리를 붙인 절대 경로로 보냄
+    let expected = format!(r#""uri":"{}/{}""#, uri(&std::env::current_dir().unwrap()), name);
+    assert!(std::fs::read_to_string(&log).unwrap().contains(&expected));
+    assert!(!Path::new(&name).exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,229 @@
+// :set 으로 바꾸는 설정들의 통합 테스트
+use vii::harness::Harness;
+
+#[test]
+fn set_toggles_and_reports_cursorline() {
+    let mut h = Harness::with_lines(60, 5, &["one", "two"]);
+    h.keys(":set cursorline?<CR>");
+    assert!(h.screen.line(4).contains("nocursorline"));
+    h.keys(":set cul<CR>:set cursorline?<CR>");
+    assert!(h.screen.line(4).contains("| cursorline"));
+    h.keys(":set cul!<CR>:set cul?<CR>");
+    assert!(h.screen.line(4).contains("nocul"));
+    h.keys(":set cursorlin<CR>");
+    assert!(h.screen.line(4).contains("Unknown option: cursorlin"));
+}
+
+#[test]
+fn colorcolumn_takes_a_list_of_columns() {
+    let mut h = Harness::with_lines(60, 5, &["fn main() {}"]);
+    h.keys(":set cc=80,100<CR>:set cc<CR>");
+    assert!(h.screen.line(4).contains("cc=80,100"));
+    h.keys(":set colorcolumn=4<CR>");
+    assert_eq!(h.screen.line(0), "fn main() {}");
+    h.keys(":set cc=0<CR>");
+    assert!(h.screen.line(4).contains("Invalid value: cc=0"));
+    h.keys(":set cc=<CR>:set colorcolumn?<CR>");
+    assert!(h.screen.line(4).contains("colorcolumn="));
+}
+
+#[test]
+fn number_shows_a_line_number_column_per_window() {
+    let mut h = Harness::with_lines(60, 5, &["one", "two"]);
+    h.keys(":set nu<CR>");
+    assert_eq!(h.screen.line(0), "  1 one");
+    assert_eq!(h.screen.line(1), "  2 two");
+    h.keys("jl");
+    assert_eq!(h.cursor(), (1, 1));
+    assert_eq!(h.screen.cursor, (1, 5));
+    h.keys(":set number?<CR>");
+    assert!(h.screen.line(4).contains("number"));
+    h.keys(":set nonumber<CR>");
+    assert_eq!(h.screen.line(0), "one");
+}
+
+#[test]
+fn modelines_set_buffer_options_when_enabled() {
+    let dir = std::env::temp_dir();
+    let path = |name: &str| dir.join(format!("vii_modeline_{}_{}.rs", name, std::process::id()));
+    let (a, b) = (path("a"), path("b"));
+    std::fs::write(&a, "fn main() {}\n// vim: set ts=4 sw=2 mp=rm:\n").unwrap();
+    std::fs::write(&b, "# vi:ts=3:sw=6\nx\n").unwrap();
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":e {}<CR>:set sw?<CR>", a.display()));
+    assert!(h.screen.line(4).contains("sw=4"));
+    h.keys(":set modeline<CR>:set sw=3<CR>");
+    h.keys(&format!(":e {}<CR>", b.display()));
+    h.keys(":set ts? sw?<CR>");
+    assert!(h.screen.line(4).contains("ts=3  sw=6"));
+    h.keys(":b 1<CR>:set sw?<CR>");
+    assert!(h.screen.line(4).contains("sw=3"));
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":set ml<CR>:e {}<CR>", a.display()));
+    assert!(h.screen.line(4).contains("Modeline: option not allowed: mp=rm"));
+    h.keys(">>:set makeprg? ts?<CR>");
+    assert_eq!(h.lines()[0], "  fn main() {}");
+    assert!(h.screen.line(4).contains("makeprg=make  ts=4"));
+    std::fs::remove_file(&a).unwrap();
+    std::fs::remove_file(&b).unwrap();
+}
+
+#[test]
+fn filetype_comes_from_name_shebang_content_and_rules() {
+    let dir = std::env::temp_dir();
+    let path = |name: &str| dir.join(format!("vii_ft_{}_{}", std::process::id(), name));
+    let script = path("script");
+    std::fs::write(&script, "#!/usr/bin/env python3\nprint(1)\n").unwrap();
+    let page = path("page");
+    std::fs::write(&page, "<!DOCTYPE html>\n<p>\n").unwrap();
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":e {}<CR>:filetype<CR>", script.display()));
+    assert!(h.screen.line(4).contains("| python |"));
+    assert!(h.screen.line(4).contains("filetype=python"));
+    h.keys(&format!(":e {}<CR>:set ft?<CR>", page.display()));
+    assert!(h.screen.line(4).contains("ft=html"));
+    h.keys(":filetype vii_ft_* lua<CR>:set ft?<CR>");
+    assert!(h.screen.line(4).contains("ft=lua"));
+    h.keys(":e /nonexistent/Makefile<CR>:filetype<CR>");
+    assert!(h.screen.line(4).contains("filetype=make"));
+    h.keys(":set ft=rust<CR>iabc<Esc>Vgc");
+    assert_eq!(h.lines()[0], "// abc");
+    h.keys(":e /nonexistent/notes<CR>:filetype<CR>");
+    assert!(h.screen.line(4).contains("No filetype"));
+    std::fs::remove_file(&script).unwrap();
+    std::fs::remove_file(&page).unwrap();
+}
+
+#[test]
+fn editorconfig_sets_buffer_options_from_parent_directories() {
+    let root = std::env::temp_dir().join(format!("vii_editorconfig_{}", std::process::id()));
+    let sub = root.join("src");
+    std::fs::create_dir_all(&sub).unwrap();
+    std::fs::write(root.join(".editorconfig"), "root = true\n\n[*]\nindent_style = tab\ntab_width = 4\n\n[*.{md,txt}]\nindent_style = space\nindent_size = 2\n").unwrap();
+    std::fs::write(sub.join(".editorconfig"), "[*.txt]\nend_of_line = crlf\ntrim_trailing_whitespace = true\ninsert_final_newline = true\n").unwrap();
+    let (code, text) = (sub.join("main.c"), sub.join("notes.txt"));
+    std::fs::write(&code, "int x;\n").unwrap();
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":e {}<CR>:set et? ts? sw? ff?<CR>", code.display()));
+    assert!(h.screen.line(4).contains("noet  ts=4  sw=4  ff=unix"));
+    h.keys(":set sw=6<CR>>>");
+    assert_eq!(h.lines()[0], "\t  int x;");
+    h.keys("<<");
+    assert_eq!(h.lines()[0], "int x;");
+    h.keys(&format!(":e {}<CR>:set et? sw? ff?<CR>", text.display()));
+    assert!(h.screen.line(4).contains("et  sw=2  ff=dos"));
+    h.keys("ione  <CR>two <Esc>:w<CR>");
+    assert_eq!(std::fs::read_to_string(&text).unwrap(), "one\r\ntwo\r\n");
+    let mut h = Harness::new(80, 5);
+    h.keys(&format!(":set noeditorconfig<CR>:e {}<CR>:set et? sw?<CR>", code.display()));
+    assert!(h.screen.line(4).contains("et  sw=4"));
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn scrolloff_keeps_lines_around_the_cursor() {
+    let lines: Vec<String> = (1..=20).map(|i| i.to_string()).collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let mut h = Harness::with_lines(60, 6, &lines);
+    h.keys(":set so=2<CR>jj");
+    assert_eq!(h.screen.line(0), "1");
+    h.keys("j");
+    assert_eq!(h.screen.line(0), "2");
+    h.keys(&"j".repeat(16));
+    assert_eq!(h.screen.line(4), "20");
+    assert_eq!(h.screen.cursor, (4, 0));
+    h.keys("kkk");
+    assert_eq!(h.screen.line(0), "15");
+    h.keys(":set scrolloff=9<CR>");
+    assert_eq!(h.screen.cursor, (2, 0));
+    h.keys(":set so=x<CR>");
+    assert!(h.screen.line(5).contains("Invalid value: so=x"));
+}
+
+#[test]
+fn sidescrolloff_keeps_columns_around_the_cursor() {
+    let mut h = Harness::with_lines(20, 5, &["abcdefghijklmnopqrstuvwxyz0123456789"]);
+    h.keys(":set siso=3<CR>");
+    h.keys(&"l".repeat(16));
+    assert_eq!(h.screen.line(0), "abcdefghijklmnopqrs>");
+    h.keys("l");
+    assert_eq!(h.screen.line(0), "<cdefghijklmnopqrst>");
+    assert_eq!(h.screen.cursor, (0, 16));
+    h.keys(&"l".repeat(30));
+    assert_eq!(h.screen.cursor, (0, 19));
+    h.keys(&"h".repeat(14));
+    assert_eq!(h.screen.cursor, (0, 5));
+    h.keys("hhh");
+    assert_eq!(h.screen.cursor, (0, 3));
+
+    // 여러 바이트 글자도 글자 단위로 밀림
+    let mut h = Harness::with_lines(20, 5, &["가나다라마바사아자차카타파하거너더러머버서어저처커터퍼허고노도로모보소오조초"]);
+    h.keys(":set siso=3<CR>");
+    h.keys(&"l".repeat(17));
+    assert_eq!(h.screen.line(0), "<다라마바사아자차카타파하거너더러머버>");
+    assert_eq!(h.screen.cursor, (0, 16));
+    h.keys(&"l".repeat(30));
+    assert_eq!(h.screen.line(0), "<서어저처커터퍼허고노도로모보소오조초");
+    assert_eq!(h.screen.cursor, (0, 19));
+}
+
+#[test]
+fn messages_fade_after_messagetime_but_errors_stay() {
+    let mut h = Harness::with_lines(60, 5, &["a"]);
+    h.keys(":set mt=1<CR>:set mt?<CR>");
+    assert!(h.screen.line(4).contains("mt=1"));
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert!(h.editor.poll_background());
+    h.keys("<Esc>");
+    assert!(!h.screen.line(4).contains("mt=1"));
+    h.keys(":nosuch<CR>");
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert!(!h.editor.poll_background());
+    h.keys("<Esc>");
+    assert!(h.screen.line(4).contains("Unknown: nosuch"));
+}
+
+#[test]
+fn syntax_colors_arrive_from_the_background_worker() {
+    use vii::syntax::Token;
+    let mut lines = vec!["fn main() { let s = \"hi\"; } // 42"; 2000];
+    lines.push("/* open");
+    lines.push("still */ 7");
+    let mut h = Harness::with_lines(80, 5, &lines);
+    h.keys(":set ft=rust<CR>");
+    let token = |h: &Harness, line: usize, col: usize| {
+        h.editor.syntax_spans(line).iter().find(|(r, _)| r.contains(&col)).map(|(_, t)| *t)
+    };
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while token(&h, 2001, 0).is_none() && std::time::Instant::now() < deadline {
+        h.editor.poll_background();
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    assert_eq!(token(&h, 0, 0), Some(Token::Keyword));
+    assert_eq!(token(&h, 0, 3), None);
+    assert_eq!(token(&h, 0, 20), Some(Token::String));
+    assert_eq!(token(&h, 0, 29), Some(Token::Comment));
+    assert_eq!(token(&h, 2001, 0), Some(Token::Comment));
+    assert_eq!(token(&h, 2001, 9), Some(Token::Number));
+    h.keys(":set nosyntax<CR>");
+    h.editor.poll_background();
+    assert!(h.editor.syntax_spans(0).is_empty());
+}
+
+#[test]
+fn scrollbar_shows_the_viewport_in_the_right_column() {
+    let lines: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+    let mut lines: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+    lines[0] = "a line longer than the window";
+    let mut h = Harness::with_lines(20, 6, &lines);
+    h.keys(":set scrollbar<CR>");
+    let bar = |h: &Harness| (0..5).map(|y| h.screen.line(y).chars().nth(19).unwrap_or(' ')).collect::<String>();
+    assert_eq!(h.screen.line(0), "a line longer than>┃");
+    assert_eq!(bar(&h), "┃┃│││");
+    h.keys("10G");
+    assert_eq!(bar(&h), "│┃┃││");
+    h.keys("G");
+    assert_eq!(bar(&h), "│││┃┃");
+    h.keys(":set noscrollbar<CR>gg");
+    assert_eq!(h.screen.line(0), "a line longer than >");
+}
@@ -0,0 +1,45 @@
+// WebAssembly 플러그인의 통합 테스트 (cargo test --features wasm)
+#![cfg(feature = "wasm")]
+use vii::harness::Harness;
+
+// init 에서 :First 를, :First 를 실행할 때 :Second 를 등록하고 받은 번호로 명령을 가림
+const PLUGIN: &str = r#"(module
+  (import "vii" "register_command" (func $register (param i32 i32) (result i32)))
+  (import "vii" "message" (func $message (param i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "First")
+  (data (i32.const 8) "Second")
+  (data (i32.const 16) "unknown")
+  (global $first (mut i32) (i32.const -1))
+  (global $second (mut i32) (i32.const -1))
+  (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+  (func (export "init") (global.set $first (call $register (i32.const 0) (i32.const 5))))
+  (func (export "on_command") (param $id i32) (param i32 i32)
+    (if (i32.eq (local.get $id) (global.get $first))
+      (then
+        (global.set $second (call $register (i32.const 8) (i32.const 6)))
+        (call $message (i32.const 0) (i32.const 5))
+        (return)))
+    (if (i32.eq (local.get $id) (global.get $second))
+      (then
+        (call $message (i32.const 8) (i32.const 6))
+        (return)))
+    (call $message (i32.const 16) (i32.const 7))))
+"#;
+
+#[test]
+fn commands_registered_during_a_command_get_their_own_id() {
+    let dir = std::env::temp_dir().join(format!("vii_wasm_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("vii").join("plugins")).unwrap();
+    std::fs::write(dir.join("vii").join("plugins").join("register.wat"), PLUGIN).unwrap();
+    // 이 파일의 테스트는 하나뿐이라 환경 변수를 바꿔도 다른 스레드와 겹치지 않음
+    unsafe { std::env::set_var("XDG_CONFIG_HOME", &dir) };
+    let mut h = Harness::new(60, 5);
+    vii::load_user_config(&mut h.editor);
+    h.keys(":First<CR>");
+    assert!(h.screen.line(4).contains("First"));
+    h.keys(":Second<CR>");
+    assert!(h.screen.line(4).contains("Second"));
+    assert!(!h.screen.line(4).contains("unknown"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}